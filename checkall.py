@@ -1,7 +1,8 @@
 #!/usr/bin/python3
 
-# This script is used to verify that all combinations of enabled / disabled features
-# compile, produce no warnings, and pass tests.
+# This script is used to verify that all combinations of enabled / disabled core features
+# compile, produce no warnings, and pass tests. Optional integrations are checked one at a time,
+# since including them in the powerset would make the number of combinations explode.
 
 import os
 import subprocess as sp
@@ -13,9 +14,10 @@ def powerset(elements):
     return powerset(elements[1:]) + [[elements[0]] + x for x in powerset(elements[1:])]
 
 
-def check(package: str, feature_list: list[str]):
-    allcombos = powerset(feature_list)
+def check(package: str, core: list[str], optional: list[str] = []):
+    allcombos = powerset(core)
     allcombos.remove([])
+    allcombos += [[feature] for feature in optional]
 
     features = [["--all-features"], ["--no-default-features"]]
     features += [["--no-default-features", "--features", ",".join(features)] for features in allcombos]
@@ -45,10 +47,23 @@ def check(package: str, feature_list: list[str]):
 def main():
     os.environ["QUICKCHECK_TESTS"] = "100000"
 
-    check("dxr", ["derive", "multicall", "i8", "nil"])
+    check(
+        "dxr",
+        ["chrono", "derive", "multicall", "i8", "unsigned", "nil"],
+        ["serde-bridge", "arbitrary", "fuzz", "json", "time", "indexmap", "url", "uuid"],
+    )
     check("dxr_derive", [])
-    check("dxr_client", ["default", "multicall", "reqwest", "default-tls", "native-tls", "rustls-tls"])
-    check("dxr_server", ["default", "multicall", "axum"])
+    check(
+        "dxr_client",
+        ["default", "multicall", "reqwest", "scgi"],
+        ["chrono", "rtorrent", "serde-bridge", "hyper-client", "ureq", "tokio", "async-io", "default-tls", "native-tls", "rustls-tls"],
+    )
+    check(
+        "dxr_server",
+        ["default", "multicall", "axum"],
+        ["chrono", "gateway", "logging", "testing", "timeout", "uploads"],
+    )
+    check("dxr_cli", [])
     check("dxr_tests", [])
 
 
@@ -0,0 +1,130 @@
+//! benchmarks for serializing and deserializing XML-RPC values
+//!
+//! The benchmarks use a response that contains an array of structs, which is a typical shape for
+//! the return value of XML-RPC methods that return lists of records.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dxr::{
+    deserialize_xml, serialize_xml, Array, Member, MethodResponse, MethodResponseRef, SerializeOptions, Struct, Value,
+    WriteXml,
+};
+
+fn record(index: i32) -> Value {
+    Value::structure(Struct::new(vec![
+        Member::new(String::from("id"), Value::i4(index)),
+        Member::new(String::from("name"), Value::string(format!("record #{index}"))),
+        Member::new(
+            String::from("description"),
+            Value::string(String::from("<escaped> & \"quoted\"")),
+        ),
+        Member::new(String::from("active"), Value::boolean(index % 2 == 0)),
+        Member::new(String::from("score"), Value::double(f64::from(index) / 3.0)),
+        Member::new(String::from("data"), Value::base64(index.to_be_bytes().repeat(8))),
+    ]))
+}
+
+// typed equivalent of the records for deserializing them with the serde bridge
+#[cfg(feature = "serde-bridge")]
+#[allow(dead_code)]
+#[derive(serde::Deserialize)]
+struct Record {
+    id: i32,
+    name: String,
+    description: String,
+    active: bool,
+    score: f64,
+}
+
+fn response(length: i32) -> MethodResponse {
+    MethodResponse::new(Value::array(Array::new((0..length).map(record).collect())))
+}
+
+const LENGTHS: [i32; 3] = [1, 100, 10_000];
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+
+    for length in LENGTHS {
+        let response = response(length);
+        let size = serialize_xml(&response).unwrap().len();
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("serde", length), &response, |b, response| {
+            b.iter(|| serialize_xml(black_box(response)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("write_xml", length), &response, |b, response| {
+            b.iter(|| {
+                let mut buf = String::with_capacity(size);
+                black_box(response)
+                    .write_xml(&mut buf, SerializeOptions::default())
+                    .unwrap();
+                buf
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize");
+
+    for length in LENGTHS {
+        let xml = serialize_xml(&response(length)).unwrap();
+        group.throughput(Throughput::Bytes(xml.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("owned", length), xml.as_str(), |b, xml| {
+            b.iter(|| deserialize_xml::<MethodResponse>(black_box(xml)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("borrowed", length), xml.as_str(), |b, xml| {
+            b.iter(|| deserialize_xml::<MethodResponseRef>(black_box(xml)).unwrap())
+        });
+
+        #[cfg(feature = "serde-bridge")]
+        group.bench_with_input(BenchmarkId::new("from_value", length), xml.as_str(), |b, xml| {
+            b.iter(|| {
+                let response = deserialize_xml::<MethodResponse>(black_box(xml)).unwrap();
+                dxr::from_value::<Vec<Record>>(&response.inner()).unwrap()
+            })
+        });
+
+        #[cfg(feature = "serde-bridge")]
+        group.bench_with_input(BenchmarkId::new("from_response_xml", length), xml.as_str(), |b, xml| {
+            b.iter(|| dxr::from_response_xml::<Vec<Record>>(black_box(xml)).unwrap().unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn round_trip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_trip");
+
+    for length in LENGTHS {
+        let response = response(length);
+
+        group.bench_with_input(BenchmarkId::new("serde", length), &response, |b, response| {
+            b.iter(|| {
+                let xml = serialize_xml(black_box(response)).unwrap();
+                deserialize_xml::<MethodResponse>(&xml).unwrap()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("write_xml", length), &response, |b, response| {
+            b.iter(|| {
+                let mut xml = String::new();
+                black_box(response)
+                    .write_xml(&mut xml, SerializeOptions::default())
+                    .unwrap();
+                deserialize_xml::<MethodResponse>(&xml).unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, serialize, deserialize, round_trip);
+criterion_main!(benches);
@@ -1,4 +1,7 @@
 #![allow(clippy::unwrap_used)]
 
+#[cfg(feature = "arbitrary")]
+mod round_trip;
 mod types;
 mod values;
+mod writer;
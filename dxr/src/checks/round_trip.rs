@@ -0,0 +1,64 @@
+use arbitrary::{Arbitrary, Unstructured};
+use quickcheck::TestResult;
+use quickcheck_macros::quickcheck;
+
+use crate::values::{MethodCall, MethodResponse, Value, ValueRef};
+use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string, SerializeOptions, WriteXml};
+
+fn arbitrary_value(bytes: &[u8]) -> Option<Value> {
+    Value::arbitrary(&mut Unstructured::new(bytes)).ok()
+}
+
+#[quickcheck]
+fn value_round_trip(bytes: Vec<u8>) -> TestResult {
+    let Some(value) = arbitrary_value(&bytes) else {
+        return TestResult::discard();
+    };
+
+    let xml = to_string(&value).unwrap();
+    TestResult::from_bool(value == from_str::<Value>(&xml).unwrap())
+}
+
+#[quickcheck]
+fn value_ref_round_trip(bytes: Vec<u8>) -> TestResult {
+    let Some(value) = arbitrary_value(&bytes) else {
+        return TestResult::discard();
+    };
+
+    let xml = to_string(&value).unwrap();
+    TestResult::from_bool(value == from_str::<ValueRef>(&xml).unwrap().into_owned())
+}
+
+#[quickcheck]
+fn writer_round_trip(bytes: Vec<u8>) -> TestResult {
+    let Some(value) = arbitrary_value(&bytes) else {
+        return TestResult::discard();
+    };
+
+    let mut xml = String::new();
+    value.write_xml(&mut xml, SerializeOptions::default()).unwrap();
+    TestResult::from_bool(value == from_str::<Value>(&xml).unwrap())
+}
+
+#[quickcheck]
+fn method_call_round_trip(bytes: Vec<u8>) -> TestResult {
+    let mut u = Unstructured::new(&bytes);
+    let Ok(params) = Vec::<Value>::arbitrary(&mut u) else {
+        return TestResult::discard();
+    };
+
+    let call = MethodCall::new(String::from("method"), params);
+    let xml = to_string(&call).unwrap();
+    TestResult::from_bool(call == from_str::<MethodCall>(&xml).unwrap())
+}
+
+#[quickcheck]
+fn method_response_round_trip(bytes: Vec<u8>) -> TestResult {
+    let Some(value) = arbitrary_value(&bytes) else {
+        return TestResult::discard();
+    };
+
+    let response = MethodResponse::new(value);
+    let xml = to_string(&response).unwrap();
+    TestResult::from_bool(response == from_str::<MethodResponse>(&xml).unwrap())
+}
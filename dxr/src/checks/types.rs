@@ -2,7 +2,7 @@ use quickcheck::TestResult;
 use quickcheck_macros::quickcheck;
 
 use crate::values::Type;
-use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string};
+use crate::xml::{deserialize_xml as from_str, is_forbidden, serialize_xml as to_string};
 
 #[quickcheck]
 fn to_from_i4(int: i32) -> bool {
@@ -49,28 +49,42 @@ fn from_to_boolean(boolean: bool) -> bool {
 }
 
 #[quickcheck]
-fn to_from_string(string: String) -> bool {
+fn to_from_string(string: String) -> TestResult {
+    // NUL and other control characters cannot be represented in XML documents
+    if string.chars().any(is_forbidden) {
+        return TestResult::discard();
+    }
+
     // This creates a new <string> type on a code path that does no XML escaping,
     // so the string needs to be trimmed and XML-escaped first.
     let string = quick_xml::escape::escape(string.trim()).to_string();
     let value = Type::String(string);
 
-    value == from_str::<Type>(&to_string(&value).unwrap()).unwrap()
+    TestResult::from_bool(value == from_str::<Type>(&to_string(&value).unwrap()).unwrap())
 }
 
 #[quickcheck]
-fn from_to_string(string: String) -> bool {
+fn from_to_string(string: String) -> TestResult {
+    // carriage returns are serialized as character references, and other control characters are
+    // rejected
+    if string
+        .chars()
+        .any(|c| c.is_ascii_control() && c != '\t' && c != '\n' && c != '\x7f')
+    {
+        return TestResult::discard();
+    }
+
     // This creates a new <string> type on a code path that does no XML escaping,
     // so the string needs to be trimmed and XML-escaped first.
     let string = quick_xml::escape::escape(string.trim()).to_string();
     let value = format!("<string>{string}</string>");
 
-    value == to_string(&from_str::<Type>(&value).unwrap()).unwrap()
+    TestResult::from_bool(value == to_string(&from_str::<Type>(&value).unwrap()).unwrap())
 }
 
 #[quickcheck]
 fn to_from_double(double: f64) -> TestResult {
-    if double.is_nan() {
+    if !double.is_finite() {
         return TestResult::discard();
     }
 
@@ -81,7 +95,7 @@ fn to_from_double(double: f64) -> TestResult {
 
 #[quickcheck]
 fn from_to_double(double: f64) -> TestResult {
-    if double.is_nan() {
+    if !double.is_finite() {
         return TestResult::discard();
     }
 
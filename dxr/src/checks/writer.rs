@@ -0,0 +1,120 @@
+use quickcheck::{Arbitrary, Gen};
+use quickcheck_macros::quickcheck;
+
+use crate::values::datetime::DateTime;
+use crate::values::{Array, FaultResponse, Member, MethodCall, MethodResponse, Struct, Value};
+use crate::xml::{
+    is_forbidden, serialize_xml_with as to_string_with, DateTimeFormat, NonFinitePolicy, SerializeOptions,
+    StringFormat, WriteXml,
+};
+use crate::Fault;
+
+// control characters that cannot be represented in XML are removed from arbitrary strings
+fn strip(string: &str) -> String {
+    string.chars().filter(|c| !is_forbidden(*c)).collect()
+}
+
+fn text(g: &mut Gen) -> String {
+    strip(&String::arbitrary(g))
+}
+
+// wrapper for generating arbitrary (nested) XML-RPC values
+#[derive(Clone, Debug)]
+struct AnyValue(Value);
+
+fn any_value(g: &mut Gen, depth: usize) -> Value {
+    let choices = if depth == 0 { 8 } else { 10 };
+
+    match u8::arbitrary(g) % choices {
+        0 => Value::i4(i32::arbitrary(g)),
+        #[cfg(feature = "i8")]
+        1 => Value::i8(i64::arbitrary(g)),
+        #[cfg(not(feature = "i8"))]
+        1 => Value::i4(i32::arbitrary(g)),
+        2 => Value::boolean(bool::arbitrary(g)),
+        3 => Value::string(text(g)),
+        4 => Value::double(f64::arbitrary(g)),
+        5 => Value::from_datetime(DateTime::from_unix_timestamp(i64::from(u32::arbitrary(g))).unwrap()),
+        6 => Value::base64(Vec::arbitrary(g)),
+        #[cfg(feature = "nil")]
+        7 => Value::nil(),
+        #[cfg(not(feature = "nil"))]
+        7 => Value::string(String::new()),
+        8 => {
+            let members = (0..usize::arbitrary(g) % 4)
+                .map(|_| Member::new(text(g), any_value(g, depth - 1)))
+                .collect();
+            Value::structure(Struct::new(members))
+        },
+        _ => {
+            let values = (0..usize::arbitrary(g) % 4).map(|_| any_value(g, depth - 1)).collect();
+            Value::array(Array::new(values))
+        },
+    }
+}
+
+impl Arbitrary for AnyValue {
+    fn arbitrary(g: &mut Gen) -> Self {
+        AnyValue(any_value(g, 3))
+    }
+}
+
+fn options(g: &mut Gen) -> SerializeOptions {
+    let policy = *g
+        .choose(&[NonFinitePolicy::Error, NonFinitePolicy::AsString, NonFinitePolicy::Skip])
+        .unwrap();
+    let format = DateTimeFormat::custom(g.choose(&["%Y%m%dT%H:%M:%S", "%Y-%m-%dT%H:%M:%S&<>"]).unwrap()).unwrap();
+    let cdata = Option::<u8>::arbitrary(g).map(usize::from);
+
+    SerializeOptions::default()
+        .strings(
+            *g.choose(&[StringFormat::Typed, StringFormat::Untyped, StringFormat::Bare])
+                .unwrap(),
+        )
+        .apache_extensions(bool::arbitrary(g))
+        .non_finite_doubles(policy)
+        .datetime(format)
+        .cdata_strings(cdata)
+}
+
+#[derive(Clone, Debug)]
+struct AnyOptions(SerializeOptions);
+
+impl Arbitrary for AnyOptions {
+    fn arbitrary(g: &mut Gen) -> Self {
+        AnyOptions(options(g))
+    }
+}
+
+fn fast<T: WriteXml>(value: &T, options: SerializeOptions) -> Result<String, String> {
+    let mut buf = String::new();
+    value.write_xml(&mut buf, options).map_err(|error| error.to_string())?;
+    Ok(buf)
+}
+
+fn generic<T: serde::Serialize>(value: &T, options: SerializeOptions) -> Result<String, String> {
+    to_string_with(value, options).map_err(|error| error.to_string())
+}
+
+#[quickcheck]
+fn write_value(value: AnyValue, options: AnyOptions) -> bool {
+    fast(&value.0, options.0) == generic(&value.0, options.0)
+}
+
+#[quickcheck]
+fn write_method_call(name: String, params: Vec<AnyValue>, options: AnyOptions) -> bool {
+    let call = MethodCall::new(strip(&name), params.into_iter().map(|value| value.0).collect());
+    fast(&call, options.0) == generic(&call, options.0)
+}
+
+#[quickcheck]
+fn write_method_response(value: AnyValue, options: AnyOptions) -> bool {
+    let response = MethodResponse::new(value.0);
+    fast(&response, options.0) == generic(&response, options.0)
+}
+
+#[quickcheck]
+fn write_fault_response(code: i32, string: String, options: AnyOptions) -> bool {
+    let response = FaultResponse::from(Fault::new(code, strip(&string)));
+    fast(&response, options.0) == generic(&response, options.0)
+}
@@ -1,48 +1,228 @@
 use std::borrow::Cow;
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
 
 use thiserror::Error;
 
 use crate::fault::Fault;
 
-#[derive(Debug, Error, PartialEq)]
+#[derive(Debug, Error)]
 /// Error type representing conversion errors between XML-RPC values and Rust values.
+///
+/// All variants can carry additional information about where the error occurred (see
+/// [`DxrError::context`]), which is included in the error message. Errors for invalid data also
+/// keep the underlying error (i.e. of the XML parser), which is returned by
+/// [`Error::source`](std::error::Error::source).
 pub enum DxrError {
     /// Error variant for XML parser errors.
-    #[error("Failed to parse XML data: {}", .error)]
+    #[error("{}Failed to parse XML data: {}", prefix(.context), .error)]
     InvalidData {
         /// description of the parsing error
         error: String,
+        /// method call, location of the value, or position in the XML document
+        context: Option<Box<ErrorContext>>,
+        /// underlying error that caused this error (if there is one)
+        #[source]
+        source: Option<Box<dyn StdError + Send + Sync>>,
     },
     /// Error variant for a missing struct field.
-    #[error("Struct '{}' missing field: {}", .name, .field)]
+    #[error("{}Struct '{}' missing field: {}", prefix(.context), .name, .field)]
     MissingField {
         /// name of the struct that has a missing field
         name: Cow<'static, str>,
         /// name of the missing struct field
         field: Cow<'static, str>,
+        /// method call, location of the value, or position in the XML document
+        context: Option<Box<ErrorContext>>,
     },
-    #[error("Parameter mismatch: got {} values, expected {}", .argument, .expected)]
+    #[error("{}Parameter mismatch: got {} values, expected {}", prefix(.context), .argument, .expected)]
     /// Error variant for mismatch with an expected number of values.
     ParameterMismatch {
         /// number of received values
         argument: usize,
         /// number of expected values
         expected: usize,
+        /// method call, location of the value, or position in the XML document
+        context: Option<Box<ErrorContext>>,
     },
     /// Error variant for mismatch with an expected value type.
-    #[error("Type mismatch: got {}, expected {}", .argument, .expected)]
+    #[error("{}Type mismatch: got {}, expected {}", prefix(.context), .argument, .expected)]
     WrongType {
         /// mismatched input type
         argument: Cow<'static, str>,
         /// expected input type
         expected: Cow<'static, str>,
+        /// method call, location of the value, or position in the XML document
+        context: Option<Box<ErrorContext>>,
     },
 }
 
+// custom PartialEq impl: underlying errors cannot be compared (but their description is included
+// in the description of invalid data)
+impl PartialEq for DxrError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                DxrError::InvalidData { error, context, .. },
+                DxrError::InvalidData {
+                    error: other_error,
+                    context: other_context,
+                    ..
+                },
+            ) => error == other_error && context == other_context,
+            (
+                DxrError::MissingField { name, field, context },
+                DxrError::MissingField {
+                    name: other_name,
+                    field: other_field,
+                    context: other_context,
+                },
+            ) => name == other_name && field == other_field && context == other_context,
+            (
+                DxrError::ParameterMismatch {
+                    argument,
+                    expected,
+                    context,
+                },
+                DxrError::ParameterMismatch {
+                    argument: other_argument,
+                    expected: other_expected,
+                    context: other_context,
+                },
+            ) => argument == other_argument && expected == other_expected && context == other_context,
+            (
+                DxrError::WrongType {
+                    argument,
+                    expected,
+                    context,
+                },
+                DxrError::WrongType {
+                    argument: other_argument,
+                    expected: other_expected,
+                    context: other_context,
+                },
+            ) => argument == other_argument && expected == other_expected && context == other_context,
+            _ => false,
+        }
+    }
+}
+
+// error messages are prefixed with the context of the error, if there is any
+fn prefix(context: &Option<Box<ErrorContext>>) -> String {
+    match context {
+        Some(context) => format!("{context}: "),
+        None => String::new(),
+    }
+}
+
+/// # Location of an error
+///
+/// This type describes where a [`DxrError`] occurred: the name of the method that was called, the
+/// path of the value that could not be converted (i.e. `result[3].files[0].size`, where struct
+/// member names are separated by dots and array indices are enclosed in brackets), and the byte
+/// offset in the XML document for parser errors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorContext {
+    method: Option<String>,
+    path: Vec<PathSegment>,
+    offset: Option<usize>,
+}
+
+/// # Segment of the path of a value inside nested structs and arrays
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    /// struct member with the given name
+    Member(String),
+    /// array element with the given index
+    Index(usize),
+}
+
+impl ErrorContext {
+    /// getter for the name of the method that was called
+    pub fn method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+
+    /// getter for the segments of the path of the value that could not be converted
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// getter for the byte offset in the XML document at which parsing failed
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// method for formatting the path of the value that could not be converted
+    pub fn path_string(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Member(name) if path.is_empty() => path.push_str(name),
+                PathSegment::Member(name) => {
+                    path.push('.');
+                    path.push_str(name);
+                },
+                PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+            }
+        }
+        path
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Error")?;
+        if let Some(method) = &self.method {
+            write!(f, " in method call '{method}'")?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path_string())?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at byte offset {offset}")?;
+        }
+        Ok(())
+    }
+}
+
 impl DxrError {
+    /// Construct a [`DxrError`] for an XML document that could not be parsed.
+    ///
+    /// If the document is not well-formed, the byte offset at which parsing failed is included
+    /// (instead of the contents of the whole document). The parser error is kept as the source of
+    /// the returned error.
+    pub fn invalid_xml(xml: &str, error: impl StdError + Send + Sync + 'static) -> DxrError {
+        DxrError::invalid_data_from(error).in_document(xml)
+    }
+
     /// Construct a [`DxrError`] for invalid input data.
     pub fn invalid_data(error: String) -> DxrError {
-        DxrError::InvalidData { error }
+        DxrError::InvalidData {
+            error,
+            context: None,
+            source: None,
+        }
+    }
+
+    /// Construct a [`DxrError`] for invalid input data from the error that caused it.
+    ///
+    /// The description of the underlying error is used as the description of the invalid data,
+    /// and the error itself is kept as the source of the returned error.
+    pub fn invalid_data_from(error: impl StdError + Send + Sync + 'static) -> DxrError {
+        DxrError::InvalidData {
+            error: error.to_string(),
+            context: None,
+            source: Some(Box::new(error)),
+        }
+    }
+
+    // adds the byte offset of the first syntax error if the document is not well-formed
+    pub(crate) fn in_document(self, xml: &str) -> DxrError {
+        match crate::xml::syntax_error_offset(xml) {
+            Some(offset) => self.at_offset(offset),
+            None => self,
+        }
     }
 
     /// Check if a given [`DxrError`] was raised for invalid data.
@@ -54,7 +234,7 @@ impl DxrError {
     ///
     /// The returned string describes the XML (de)serialization issue.
     pub fn as_invalid_data(&self) -> Option<&str> {
-        if let DxrError::InvalidData { error } = self {
+        if let DxrError::InvalidData { error, .. } = self {
             Some(error)
         } else {
             None
@@ -66,6 +246,7 @@ impl DxrError {
         DxrError::MissingField {
             name: Cow::Borrowed(name),
             field: Cow::Borrowed(field),
+            context: None,
         }
     }
 
@@ -78,7 +259,7 @@ impl DxrError {
     ///
     /// The returned value is a tuple of (struct name, missing field name).
     pub fn as_missing_field(&self) -> Option<(&str, &str)> {
-        if let DxrError::MissingField { name, field } = self {
+        if let DxrError::MissingField { name, field, .. } = self {
             Some((name, field))
         } else {
             None
@@ -87,7 +268,11 @@ impl DxrError {
 
     /// Construct a [`DxrError`] for a parameter number mismatch.
     pub fn parameter_mismatch(argument: usize, expected: usize) -> DxrError {
-        DxrError::ParameterMismatch { argument, expected }
+        DxrError::ParameterMismatch {
+            argument,
+            expected,
+            context: None,
+        }
     }
 
     /// Check if a given [`DxrError`] was raised for unexpected number of return values.
@@ -99,7 +284,7 @@ impl DxrError {
     ///
     /// The returned value is a tuple of the numbers of (received arguments, expected arguments).
     pub fn as_parameter_mismatch(&self) -> Option<(usize, usize)> {
-        if let DxrError::ParameterMismatch { argument, expected } = self {
+        if let DxrError::ParameterMismatch { argument, expected, .. } = self {
             Some((*argument, *expected))
         } else {
             None
@@ -111,6 +296,7 @@ impl DxrError {
         DxrError::WrongType {
             argument: Cow::Borrowed(argument),
             expected: Cow::Borrowed(expected),
+            context: None,
         }
     }
 
@@ -123,21 +309,64 @@ impl DxrError {
     ///
     /// The returned value is a tuple of the names of (received type, expected type).
     pub fn as_wrong_type(&self) -> Option<(&str, &str)> {
-        if let DxrError::WrongType { argument, expected } = self {
+        if let DxrError::WrongType { argument, expected, .. } = self {
             Some((argument, expected))
         } else {
             None
         }
     }
+
+    /// Return the additional information about where the error occurred (if there is any).
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            DxrError::InvalidData { context, .. }
+            | DxrError::MissingField { context, .. }
+            | DxrError::ParameterMismatch { context, .. }
+            | DxrError::WrongType { context, .. } => context.as_deref(),
+        }
+    }
+
+    /// Add the name of the struct member that contained the value that could not be converted.
+    ///
+    /// This is prepended to the path of any existing context, so it needs to be called from the
+    /// innermost to the outermost value.
+    pub fn in_member(self, name: &str) -> DxrError {
+        self.with_context(|context| context.path.insert(0, PathSegment::Member(name.to_owned())))
+    }
+
+    /// Add the index of the array element that could not be converted.
+    ///
+    /// This is prepended to the path of any existing context, so it needs to be called from the
+    /// innermost to the outermost value.
+    pub fn in_index(self, index: usize) -> DxrError {
+        self.with_context(|context| context.path.insert(0, PathSegment::Index(index)))
+    }
+
+    /// Add the name of the method that was called when the error occurred.
+    pub fn in_method(self, name: &str) -> DxrError {
+        self.with_context(|context| context.method = Some(name.to_owned()))
+    }
+
+    /// Add the byte offset in the XML document at which the error occurred.
+    pub fn at_offset(self, offset: usize) -> DxrError {
+        self.with_context(|context| context.offset = Some(offset))
+    }
+
+    // context is merged into the existing context of the error (if there is any)
+    fn with_context(mut self, f: impl FnOnce(&mut ErrorContext)) -> DxrError {
+        let context = match &mut self {
+            DxrError::InvalidData { context, .. }
+            | DxrError::MissingField { context, .. }
+            | DxrError::ParameterMismatch { context, .. }
+            | DxrError::WrongType { context, .. } => context,
+        };
+        f(context.get_or_insert_with(Default::default));
+        self
+    }
 }
 
 impl From<DxrError> for Fault {
     fn from(error: DxrError) -> Self {
-        match error {
-            DxrError::InvalidData { .. } => Fault::new(400, error.to_string()),
-            DxrError::MissingField { .. } => Fault::new(400, error.to_string()),
-            DxrError::ParameterMismatch { .. } => Fault::new(400, error.to_string()),
-            DxrError::WrongType { .. } => Fault::new(400, error.to_string()),
-        }
+        Fault::invalid_params(error.to_string())
     }
 }
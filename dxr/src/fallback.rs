@@ -0,0 +1,29 @@
+// imports for intra-doc links
+#[cfg(doc)]
+use crate::{TryFromValue, TryToValue};
+
+/// # Integer values with string fallback
+///
+/// Integer types that do not fit into the range of `<i4>` (or `<i8>`, if the `i8` feature is
+/// enabled) values cannot be converted into XML-RPC values without losing information, so
+/// [`TryToValue`] returns an error for them instead of silently truncating them.
+///
+/// Wrapping an integer in a [`StringFallback`] opts into converting values that are out of range
+/// into `<string>` values that contain the decimal representation of the integer instead, which is
+/// how some XML-RPC implementations transmit large unsigned integers (i.e. byte counts).
+/// The [`TryFromValue`] implementation accepts `<i4>`, `<i8>`, and `<string>` values:
+///
+/// ```
+/// use dxr::{StringFallback, TryFromValue, TryToValue, Value};
+///
+/// let value = StringFallback(u64::MAX).try_to_value().unwrap();
+/// assert_eq!(value, Value::string(u64::MAX.to_string()));
+///
+/// let StringFallback(int): StringFallback<u64> = TryFromValue::try_from_value(&value).unwrap();
+/// assert_eq!(int, u64::MAX);
+///
+/// let value = StringFallback(42u64).try_to_value().unwrap();
+/// assert_eq!(value, Value::i4(42));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StringFallback<T>(pub T);
@@ -1,13 +1,67 @@
+//! XML-RPC server faults and well-known fault codes
+//!
+//! There are no fault codes in the XML-RPC specification itself, but many implementations use the
+//! codes from the (de-facto standard) ["Specification for Fault Code Interoperability"][spec],
+//! which are provided as constants in this module.
+//!
+//! [spec]: http://xmlrpc-epi.sourceforge.net/specs/rfc.fault_codes.php
+
 use thiserror::Error;
 
 use crate::error::DxrError;
 use crate::traits::TryFromValue;
 use crate::values::FaultResponse;
 
+/// parse error: the request is not well-formed XML
+pub const PARSE_ERROR: i32 = -32700;
+/// parse error: the request uses an unsupported encoding
+pub const UNSUPPORTED_ENCODING: i32 = -32701;
+/// parse error: the request contains invalid characters for its encoding
+pub const INVALID_CHARACTER: i32 = -32702;
+/// server error: the request is not a valid XML-RPC method call
+pub const INVALID_REQUEST: i32 = -32600;
+/// server error: the requested method was not found
+pub const METHOD_NOT_FOUND: i32 = -32601;
+/// server error: the method parameters are invalid
+pub const INVALID_PARAMS: i32 = -32602;
+/// server error: internal XML-RPC error
+pub const INTERNAL_ERROR: i32 = -32603;
+/// application error (the start of the range of codes that are reserved for application errors)
+pub const APPLICATION_ERROR: i32 = -32500;
+/// system error (the start of the range of codes that are reserved for system errors)
+pub const SYSTEM_ERROR: i32 = -32400;
+/// transport error (the start of the range of codes that are reserved for transport errors)
+pub const TRANSPORT_ERROR: i32 = -32300;
+
+/// category of a [`Fault`], based on the well-known fault codes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FaultCategory {
+    /// the request could not be parsed (`-32799` to `-32700`)
+    Parse,
+    /// the request is not a valid XML-RPC method call (`-32600`, and `-32699` to `-32604`)
+    InvalidRequest,
+    /// the requested method was not found (`-32601`)
+    MethodNotFound,
+    /// the method parameters are invalid (`-32602`)
+    InvalidParams,
+    /// internal XML-RPC error (`-32603`)
+    Internal,
+    /// application error (`-32599` to `-32500`)
+    Application,
+    /// system error (`-32499` to `-32400`)
+    System,
+    /// transport error (`-32399` to `-32300`)
+    Transport,
+    /// fault code that is specific to the server application (all other codes)
+    Other,
+}
+
 /// XML-RPC server fault (consisting of a numeric error code and a message)
 ///
 /// *Note*: There are no standardized numeric error codes, and they will likely be
-/// specific to the server application.
+/// specific to the server application. However, the codes of faults that are returned by
+/// `dxr_server` itself follow the conventions of the well-known fault codes in this module.
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[error("Server Fault {}: {}", .code, .string)]
 pub struct Fault {
@@ -21,6 +75,31 @@ impl Fault {
         Fault { code, string }
     }
 
+    /// Construct a new [`Fault`] for requests that could not be parsed.
+    pub fn parse_error(string: String) -> Fault {
+        Fault::new(PARSE_ERROR, string)
+    }
+
+    /// Construct a new [`Fault`] for requests that are not valid XML-RPC method calls.
+    pub fn invalid_request(string: String) -> Fault {
+        Fault::new(INVALID_REQUEST, string)
+    }
+
+    /// Construct a new [`Fault`] for calls of unknown methods.
+    pub fn method_not_found(name: &str) -> Fault {
+        Fault::new(METHOD_NOT_FOUND, format!("Unknown method: {name}"))
+    }
+
+    /// Construct a new [`Fault`] for calls with invalid method parameters.
+    pub fn invalid_params(string: String) -> Fault {
+        Fault::new(INVALID_PARAMS, string)
+    }
+
+    /// Construct a new [`Fault`] for internal server errors.
+    pub fn internal_error(string: String) -> Fault {
+        Fault::new(INTERNAL_ERROR, string)
+    }
+
     /// Retrieve the numeric error code from the [`Fault`].
     pub fn code(&self) -> i32 {
         self.code
@@ -30,6 +109,21 @@ impl Fault {
     pub fn string(&self) -> &str {
         self.string.as_str()
     }
+
+    /// Determine the category of the [`Fault`] based on its numeric error code.
+    pub fn category(&self) -> FaultCategory {
+        match self.code {
+            -32799..=-32700 => FaultCategory::Parse,
+            METHOD_NOT_FOUND => FaultCategory::MethodNotFound,
+            INVALID_PARAMS => FaultCategory::InvalidParams,
+            INTERNAL_ERROR => FaultCategory::Internal,
+            -32699..=-32600 => FaultCategory::InvalidRequest,
+            -32599..=-32500 => FaultCategory::Application,
+            -32499..=-32400 => FaultCategory::System,
+            -32399..=-32300 => FaultCategory::Transport,
+            _ => FaultCategory::Other,
+        }
+    }
 }
 
 impl TryFrom<FaultResponse> for Fault {
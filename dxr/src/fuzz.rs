@@ -0,0 +1,67 @@
+//! # Entry points for fuzzing
+//!
+//! The functions in this module take arbitrary bytes as input and run them through the same code
+//! paths that are used for handling untrusted XML-RPC documents, i.e. method calls that are
+//! received by servers, and method responses that are received by clients. Invalid input results in
+//! an error, and a panic indicates a bug (for example, if the owned [`Value`] and borrowed
+//! [`ValueRef`] representations of a parsed value do not agree).
+//!
+//! The `fuzz` directory in the repository contains targets for [`cargo-fuzz`] that are based on
+//! these functions.
+//!
+//! [`cargo-fuzz`]: https://github.com/rust-fuzz/cargo-fuzz
+
+use std::str;
+
+use crate::error::DxrError;
+use crate::fault::Fault;
+use crate::values::{FaultResponse, MethodCall, MethodResponse, Value, ValueRef};
+use crate::xml::deserialize_xml;
+
+fn to_str(data: &[u8]) -> Result<&str, DxrError> {
+    str::from_utf8(data).map_err(DxrError::invalid_data_from)
+}
+
+fn deserialize<'a, T: serde::Deserialize<'a>>(string: &'a str) -> Result<T, DxrError> {
+    deserialize_xml(string).map_err(DxrError::invalid_data_from)
+}
+
+/// function for parsing an XML-RPC method call (like an XML-RPC server does)
+///
+/// Input that is not valid UTF-8 is rejected with an error. The default [`DeserializeOptions`](crate::DeserializeOptions)
+/// (including the limits for nesting depth and document size) are used.
+pub fn parse_call(data: &[u8]) -> Result<MethodCall, DxrError> {
+    deserialize(to_str(data)?)
+}
+
+/// function for parsing an XML-RPC method response (like an XML-RPC client does)
+///
+/// Fault responses are checked first, and valid faults are returned as the inner error. Input that
+/// is not valid UTF-8 is rejected with an error. The default [`DeserializeOptions`](crate::DeserializeOptions) (including the
+/// limits for nesting depth and document size) are used.
+pub fn parse_response(data: &[u8]) -> Result<Result<Value, Fault>, DxrError> {
+    let string = to_str(data)?;
+
+    if let Ok(fault) = deserialize::<FaultResponse>(string) {
+        return Fault::try_from(fault).map(Err);
+    }
+
+    deserialize::<MethodResponse>(string).map(|response| Ok(response.inner()))
+}
+
+/// function for parsing a single XML-RPC value, both as an owned [`Value`] and as a borrowed
+/// [`ValueRef`]
+///
+/// Both representations must agree on whether the input is valid, and on the parsed value.
+pub fn parse_value(data: &[u8]) -> Result<Value, DxrError> {
+    let string = to_str(data)?;
+
+    let owned = deserialize::<Value>(string);
+    let borrowed = deserialize::<ValueRef>(string).map(ValueRef::into_owned);
+
+    match (owned, borrowed) {
+        (Ok(owned), Ok(borrowed)) if owned == borrowed => Ok(owned),
+        (Err(error), Err(_)) => Err(error),
+        (owned, borrowed) => panic!("owned and borrowed values disagree: {owned:?} / {borrowed:?}"),
+    }
+}
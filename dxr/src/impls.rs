@@ -4,4 +4,23 @@ mod to_value;
 mod from_params;
 mod to_params;
 
-mod utils;
+mod integers;
+
+mod std_types;
+
+mod tuples;
+
+#[cfg(feature = "time")]
+pub(crate) mod time_support;
+
+#[cfg(feature = "indexmap")]
+mod indexmap_support;
+
+#[cfg(feature = "json")]
+mod json_support;
+
+#[cfg(feature = "url")]
+mod url_support;
+
+#[cfg(feature = "uuid")]
+mod uuid_support;
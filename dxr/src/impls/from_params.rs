@@ -1,13 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::str::FromStr;
 
+#[cfg(feature = "chrono")]
 use chrono::NaiveDateTime;
 
 use crate::error::DxrError;
 use crate::traits::{TryFromParams, TryFromValue};
 use crate::values::Value;
 
-use super::utils::*;
-
 // for simple values, just call the impls for singletons / one-tuples
 
 impl TryFromParams for Value {
@@ -24,14 +25,6 @@ impl TryFromParams for i32 {
     }
 }
 
-#[cfg(feature = "i8")]
-impl TryFromParams for i64 {
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        let (value,): (Self,) = TryFromParams::try_from_params(values)?;
-        Ok(value)
-    }
-}
-
 impl TryFromParams for bool {
     fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
         let (value,): (Self,) = TryFromParams::try_from_params(values)?;
@@ -53,6 +46,7 @@ impl TryFromParams for f64 {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl TryFromParams for NaiveDateTime {
     fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
         let (value,): (Self,) = TryFromParams::try_from_params(values)?;
@@ -93,7 +87,11 @@ where
     T: TryFromValue,
 {
     fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values.iter().map(T::try_from_value).collect()
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| T::try_from_value(value).map_err(|error| error.in_index(index)))
+            .collect()
     }
 }
 
@@ -108,8 +106,9 @@ impl TryFromParams for () {
 
 // treat maps as a single value of a struct
 
-impl<T> TryFromParams for HashMap<String, T>
+impl<K, T> TryFromParams for HashMap<K, T>
 where
+    K: FromStr + Eq + Hash,
     T: TryFromValue,
 {
     fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
@@ -118,106 +117,13 @@ where
     }
 }
 
-// treat tuples as collections of values of different types
-
-impl<T> TryFromParams for (T,)
+impl<K, T> TryFromParams for BTreeMap<K, T>
 where
+    K: FromStr + Ord,
     T: TryFromValue,
 {
     fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_1(values)
-    }
-}
-
-impl<A, B> TryFromParams for (A, B)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_2(values)
-    }
-}
-
-impl<A, B, C> TryFromParams for (A, B, C)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_3(values)
-    }
-}
-
-impl<A, B, C, D> TryFromParams for (A, B, C, D)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_4(values)
-    }
-}
-
-impl<A, B, C, D, E> TryFromParams for (A, B, C, D, E)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_5(values)
-    }
-}
-
-impl<A, B, C, D, E, F> TryFromParams for (A, B, C, D, E, F)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_6(values)
-    }
-}
-
-impl<A, B, C, D, E, F, G> TryFromParams for (A, B, C, D, E, F, G)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-    G: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_7(values)
-    }
-}
-
-impl<A, B, C, D, E, F, G, H> TryFromParams for (A, B, C, D, E, F, G, H)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-    G: TryFromValue,
-    H: TryFromValue,
-{
-    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
-        values_to_tuple_8(values)
+        let (value,): (Self,) = TryFromParams::try_from_params(values)?;
+        Ok(value)
     }
 }
-
-// if needed, implementations for more arguments can be implemented
@@ -1,16 +1,17 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
 use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::Arc;
 
+#[cfg(feature = "chrono")]
 use chrono::NaiveDateTime;
 
 use crate::error::DxrError;
 use crate::traits::TryFromValue;
 use crate::values::{Type, Value};
 
-use super::utils::*;
-
 impl TryFromValue for Value {
     fn try_from_value(value: &Value) -> Result<Value, DxrError> {
         Ok(value.clone())
@@ -26,16 +27,6 @@ impl TryFromValue for i32 {
     }
 }
 
-#[cfg(feature = "i8")]
-impl TryFromValue for i64 {
-    fn try_from_value(value: &Value) -> Result<i64, DxrError> {
-        match value.inner() {
-            Type::Long(long) => Ok(*long),
-            t => Err(DxrError::wrong_type(t.name(), "i8")),
-        }
-    }
-}
-
 impl TryFromValue for bool {
     fn try_from_value(value: &Value) -> Result<bool, DxrError> {
         match value.inner() {
@@ -63,10 +54,13 @@ impl TryFromValue for f64 {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl TryFromValue for NaiveDateTime {
     fn try_from_value(value: &Value) -> Result<NaiveDateTime, DxrError> {
         match value.inner() {
-            Type::DateTime(date) => Ok(*date),
+            Type::DateTime(date) => date
+                .to_chrono()
+                .ok_or_else(|| DxrError::invalid_data(format!("Date out of range: {date}"))),
             t => Err(DxrError::wrong_type(t.name(), "dateTime.iso8861")),
         }
     }
@@ -76,6 +70,8 @@ impl TryFromValue for Vec<u8> {
     fn try_from_value(value: &Value) -> Result<Vec<u8>, DxrError> {
         match value.inner() {
             Type::Base64(bytes) => Ok(bytes.clone()),
+            #[cfg(feature = "unsigned")]
+            Type::Array { data } => super::integers::bytes_from_array(data.inner()),
             t => Err(DxrError::wrong_type(t.name(), "base64")),
         }
     }
@@ -147,7 +143,11 @@ where
             t => Err(DxrError::wrong_type(t.name(), "array")),
         };
 
-        values?.iter().map(T::try_from_value).collect()
+        values?
+            .iter()
+            .enumerate()
+            .map(|(index, value)| T::try_from_value(value).map_err(|error| error.in_index(index)))
+            .collect()
     }
 }
 
@@ -163,7 +163,8 @@ where
 
         let mapped: Vec<T> = values
             .iter()
-            .map(T::try_from_value)
+            .enumerate()
+            .map(|(index, value)| T::try_from_value(value).map_err(|error| error.in_index(index)))
             .collect::<Result<Vec<T>, DxrError>>()?;
         let len = mapped.len();
 
@@ -171,26 +172,53 @@ where
     }
 }
 
-impl<T> TryFromValue for HashMap<String, T>
+// struct member names are converted into map keys with their FromStr implementation
+pub(super) fn map_from_value<K, T, M>(value: &Value) -> Result<M, DxrError>
 where
+    K: FromStr,
+    T: TryFromValue,
+    M: FromIterator<(K, T)>,
+{
+    let values = match value.inner() {
+        Type::Struct { members } => Ok(members),
+        t => Err(DxrError::wrong_type(t.name(), "struct")),
+    };
+
+    values?
+        .iter()
+        .map(|v| {
+            let name = v.name().parse().map_err(|_| {
+                DxrError::invalid_data(format!(
+                    "Struct member name cannot be converted into map key of type {}: {}",
+                    std::any::type_name::<K>(),
+                    v.name()
+                ))
+            })?;
+            match T::try_from_value(v.inner()) {
+                Ok(value) => Ok((name, value)),
+                Err(error) => Err(error.in_member(v.name())),
+            }
+        })
+        .collect()
+}
+
+impl<K, T> TryFromValue for HashMap<K, T>
+where
+    K: FromStr + Eq + Hash,
     T: TryFromValue,
 {
-    fn try_from_value(value: &Value) -> Result<HashMap<String, T>, DxrError> {
-        let values = match value.inner() {
-            Type::Struct { members } => Ok(members),
-            t => Err(DxrError::wrong_type(t.name(), "struct")),
-        };
+    fn try_from_value(value: &Value) -> Result<HashMap<K, T>, DxrError> {
+        map_from_value(value)
+    }
+}
 
-        values?
-            .iter()
-            .map(|v| {
-                let name = v.name().to_string();
-                match T::try_from_value(v.inner()) {
-                    Ok(value) => Ok((name, value)),
-                    Err(error) => Err(error),
-                }
-            })
-            .collect()
+impl<K, T> TryFromValue for BTreeMap<K, T>
+where
+    K: FromStr + Ord,
+    T: TryFromValue,
+{
+    fn try_from_value(value: &Value) -> Result<BTreeMap<K, T>, DxrError> {
+        map_from_value(value)
     }
 }
 
@@ -213,145 +241,3 @@ impl TryFromValue for () {
         }
     }
 }
-
-impl<T> TryFromValue for (T,)
-where
-    T: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_1(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B> TryFromValue for (A, B)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_2(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C> TryFromValue for (A, B, C)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_3(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C, D> TryFromValue for (A, B, C, D)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_4(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C, D, E> TryFromValue for (A, B, C, D, E)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_5(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C, D, E, F> TryFromValue for (A, B, C, D, E, F)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_6(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C, D, E, F, G> TryFromValue for (A, B, C, D, E, F, G)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-    G: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_7(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-impl<A, B, C, D, E, F, G, H> TryFromValue for (A, B, C, D, E, F, G, H)
-where
-    A: TryFromValue,
-    B: TryFromValue,
-    C: TryFromValue,
-    D: TryFromValue,
-    E: TryFromValue,
-    F: TryFromValue,
-    G: TryFromValue,
-    H: TryFromValue,
-{
-    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
-        if let Type::Array { data } = value.inner() {
-            let values = data.inner();
-            values_to_tuple_8(values)
-        } else {
-            Err(DxrError::wrong_type(value.inner().name(), "array"))
-        }
-    }
-}
-
-// if needed, implementations for more arguments can be implemented
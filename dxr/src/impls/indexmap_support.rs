@@ -0,0 +1,58 @@
+//! conversions between XML-RPC `<struct>` values and maps from [`indexmap`]
+//!
+//! Unlike conversions for [`HashMap`](std::collections::HashMap), the insertion order of map
+//! entries is preserved, both when converting maps into XML-RPC structs and when converting
+//! XML-RPC structs into maps.
+
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+
+use super::from_value::map_from_value;
+use super::to_value::map_to_value;
+use crate::error::DxrError;
+use crate::traits::{TryFromParams, TryFromValue, TryToParams, TryToValue};
+use crate::values::{Struct, Value};
+
+impl<K, T> TryToValue for IndexMap<K, T>
+where
+    K: Display,
+    T: TryToValue,
+{
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        Ok(Value::structure(Struct::new(map_to_value(self.iter())?)))
+    }
+}
+
+impl<K, T> TryFromValue for IndexMap<K, T>
+where
+    K: FromStr + Eq + Hash,
+    T: TryFromValue,
+{
+    fn try_from_value(value: &Value) -> Result<IndexMap<K, T>, DxrError> {
+        map_from_value(value)
+    }
+}
+
+impl<K, T> TryToParams for IndexMap<K, T>
+where
+    K: Display,
+    T: TryToValue,
+{
+    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+        Ok(vec![self.try_to_value()?])
+    }
+}
+
+impl<K, T> TryFromParams for IndexMap<K, T>
+where
+    K: FromStr + Eq + Hash,
+    T: TryFromValue,
+{
+    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+        let (value,): (Self,) = TryFromParams::try_from_params(values)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,165 @@
+//! conversions for integer types other than [`i32`]
+//!
+//! Integer values are converted into `<i4>` values if they fit into the range of [`i32`], and
+//! into `<i8>` values (if the `i8` feature is enabled) if they fit into the range of [`i64`].
+//! Values that do not fit into either type result in an error instead of being truncated. When
+//! converting XML-RPC values into integers, `<i4>` and `<i8>` values are accepted, as long as
+//! their value can be represented by the target type.
+//!
+//! As the only exception, [`i64`] values are always converted into `<i8>` values if the `i8`
+//! feature is enabled.
+//!
+//! With the `unsigned` feature, the same rules apply to [`u16`] values. Single [`u8`] values are
+//! not supported, since [`Vec<u8>`] (and other byte sequences) are always converted into
+//! `<base64>` values. Instead, the `unsigned` feature also allows converting `<array>` values
+//! that only contain integers in the range of [`u8`] into [`Vec<u8>`].
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::DxrError;
+use crate::fallback::StringFallback;
+use crate::traits::{TryFromParams, TryFromValue, TryToParams, TryToValue};
+use crate::values::{Type, Value};
+
+fn out_of_range(value: impl Display, target: &str) -> DxrError {
+    DxrError::invalid_data(format!("Integer value out of range for {target}: {value}"))
+}
+
+fn to_integer<T>(value: T, source: &str) -> Result<Value, DxrError>
+where
+    T: Copy + Display,
+    i32: TryFrom<T>,
+    i64: TryFrom<T>,
+{
+    if let Ok(int) = i32::try_from(value) {
+        return Ok(Value::i4(int));
+    }
+
+    #[cfg(feature = "i8")]
+    if let Ok(long) = i64::try_from(value) {
+        return Ok(Value::i8(long));
+    }
+
+    Err(out_of_range(value, source))
+}
+
+fn from_integer<T>(value: &Value, target: &str) -> Result<T, DxrError>
+where
+    T: TryFrom<i32> + TryFrom<i64>,
+{
+    match value.inner() {
+        Type::Integer(int) => T::try_from(*int).map_err(|_| out_of_range(int, target)),
+        #[cfg(feature = "i8")]
+        Type::Long(long) => T::try_from(*long).map_err(|_| out_of_range(long, target)),
+        t => Err(DxrError::wrong_type(t.name(), "i4")),
+    }
+}
+
+macro_rules! integer_impls {
+    ($($t:ty)*) => {
+        $(
+            impl TryToValue for $t {
+                fn try_to_value(&self) -> Result<Value, DxrError> {
+                    to_integer(*self, stringify!($t))
+                }
+            }
+
+            impl TryFromValue for $t {
+                fn try_from_value(value: &Value) -> Result<$t, DxrError> {
+                    from_integer(value, stringify!($t))
+                }
+            }
+
+            impl TryToParams for $t {
+                fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+                    Ok(vec![self.try_to_value()?])
+                }
+            }
+
+            impl TryFromParams for $t {
+                fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+                    let (value,): (Self,) = TryFromParams::try_from_params(values)?;
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+integer_impls!(u32 u64 usize);
+
+#[cfg(feature = "unsigned")]
+integer_impls!(u16);
+
+// fallback for byte sequences that are transmitted as arrays of integers instead of base64
+#[cfg(feature = "unsigned")]
+pub(crate) fn bytes_from_array(values: &[Value]) -> Result<Vec<u8>, DxrError> {
+    values.iter().map(|value| from_integer(value, "u8")).collect()
+}
+
+impl TryToValue for i64 {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        #[cfg(feature = "i8")]
+        return Ok(Value::i8(*self));
+
+        #[cfg(not(feature = "i8"))]
+        return to_integer(*self, "i64");
+    }
+}
+
+impl TryFromValue for i64 {
+    fn try_from_value(value: &Value) -> Result<i64, DxrError> {
+        match value.inner() {
+            Type::Integer(int) => Ok(i64::from(*int)),
+            #[cfg(feature = "i8")]
+            Type::Long(long) => Ok(*long),
+            #[cfg(feature = "i8")]
+            t => Err(DxrError::wrong_type(t.name(), "i8")),
+            #[cfg(not(feature = "i8"))]
+            t => Err(DxrError::wrong_type(t.name(), "i4")),
+        }
+    }
+}
+
+impl TryToParams for i64 {
+    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+        Ok(vec![self.try_to_value()?])
+    }
+}
+
+impl TryFromParams for i64 {
+    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+        let (value,): (Self,) = TryFromParams::try_from_params(values)?;
+        Ok(value)
+    }
+}
+
+impl<T> TryToValue for StringFallback<T>
+where
+    T: Copy + Display,
+    i32: TryFrom<T>,
+    i64: TryFrom<T>,
+{
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        match to_integer(self.0, "") {
+            Ok(value) => Ok(value),
+            Err(_) => Ok(Value::string(self.0.to_string())),
+        }
+    }
+}
+
+impl<T> TryFromValue for StringFallback<T>
+where
+    T: FromStr + TryFrom<i32> + TryFrom<i64>,
+{
+    fn try_from_value(value: &Value) -> Result<StringFallback<T>, DxrError> {
+        match value.inner() {
+            Type::String(string) => string
+                .parse()
+                .map(StringFallback)
+                .map_err(|_| DxrError::invalid_data(format!("Invalid integer value: {string}"))),
+            _ => from_integer(value, std::any::type_name::<T>()).map(StringFallback),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! conversions between XML-RPC values and JSON values from [`serde_json`]
+//!
+//! XML-RPC values are converted into JSON values as follows:
+//!
+//! - `<i4>` and `<i8>` values are converted into integer numbers,
+//! - `<double>` values are converted into floating-point numbers (NaN and infinite values, which
+//!   cannot be represented in JSON, are converted into the strings `NaN`, `inf`, and `-inf`),
+//! - `<dateTime.iso8601>` values are converted into RFC 3339 strings (in UTC),
+//! - `<base64>` values are converted into base64-encoded strings,
+//! - `<nil/>` values are converted into `null`,
+//! - `<struct>` values are converted into objects, and `<array>` values into arrays.
+//!
+//! Since JSON has no equivalent of date & time or binary data, the type of these values is lost,
+//! and JSON strings are always converted into `<string>` values. JSON numbers are converted into
+//! `<i4>` (or `<i8>`) values if they are integers, and into `<double>` values otherwise. JSON
+//! `null` values can only be converted if the `nil` feature is enabled.
+
+use serde_json::{Map, Number, Value as Json};
+
+use crate::error::DxrError;
+use crate::traits::{TryFromParams, TryFromValue, TryToParams, TryToValue};
+use crate::values::{ser_de, Array, Member, Struct, Type, Value};
+
+// RFC 3339 format for date & time values (which are assumed to be in UTC)
+const RFC3339_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.fZ";
+
+impl TryToValue for Json {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        match self {
+            #[cfg(feature = "nil")]
+            Json::Null => Ok(Value::nil()),
+            #[cfg(not(feature = "nil"))]
+            Json::Null => Err(DxrError::invalid_data(String::from(
+                "JSON null values cannot be converted without nil support",
+            ))),
+            Json::Bool(boolean) => Ok(Value::boolean(*boolean)),
+            Json::Number(number) => number_to_value(number),
+            Json::String(string) => Ok(Value::string(string.clone())),
+            Json::Array(values) => Ok(Value::array(Array::new(
+                values.iter().map(TryToValue::try_to_value).collect::<Result<_, _>>()?,
+            ))),
+            Json::Object(members) => Ok(Value::structure(Struct::new(
+                members
+                    .iter()
+                    .map(|(k, v)| v.try_to_value().map(|v| Member::new(k.clone(), v)))
+                    .collect::<Result<_, _>>()?,
+            ))),
+        }
+    }
+}
+
+fn number_to_value(number: &Number) -> Result<Value, DxrError> {
+    // integers are converted into <i4> values if possible, and into <i8> values otherwise
+    if let Some(long) = number.as_i64() {
+        return match i32::try_from(long) {
+            Ok(int) => Ok(Value::i4(int)),
+            Err(_) => long.try_to_value(),
+        };
+    }
+
+    if let Some(unsigned) = number.as_u64() {
+        return unsigned.try_to_value();
+    }
+
+    match number.as_f64() {
+        Some(double) => Ok(Value::double(double)),
+        None => Err(DxrError::invalid_data(format!("Unsupported JSON number: {number}"))),
+    }
+}
+
+impl TryFromValue for Json {
+    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
+        Ok(match value.inner() {
+            Type::Integer(int) => Json::from(*int),
+            #[cfg(feature = "i8")]
+            Type::Long(long) => Json::from(*long),
+            Type::Boolean(boolean) => Json::from(*boolean),
+            Type::String(string) => Json::from(string.as_str()),
+            Type::Double(double) => match Number::from_f64(*double) {
+                Some(number) => Json::Number(number),
+                None => Json::from(ser_de::double::to_string(*double)),
+            },
+            Type::DateTime(datetime) => Json::from(
+                datetime
+                    .format(RFC3339_FORMAT)
+                    .expect("Failed to format date & time value."),
+            ),
+            Type::Base64(bytes) => Json::from(crate::base64::encode(bytes)),
+            Type::Struct { members } => Json::Object(
+                members
+                    .iter()
+                    .map(|member| Ok((String::from(member.name()), Json::try_from_value(member.inner())?)))
+                    .collect::<Result<Map<String, Json>, DxrError>>()?,
+            ),
+            Type::Array { data } => Json::Array(
+                data.inner()
+                    .iter()
+                    .map(Json::try_from_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            #[cfg(feature = "nil")]
+            Type::Nil => Json::Null,
+            Type::Custom(custom) => Json::from(custom.text()),
+        })
+    }
+}
+
+// JSON arrays are treated as lists of parameters, other JSON values as a single parameter
+impl TryToParams for Json {
+    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+        match self {
+            Json::Array(values) => values.iter().map(TryToValue::try_to_value).collect(),
+            value => Ok(vec![value.try_to_value()?]),
+        }
+    }
+}
+
+// lists of parameters are converted into JSON arrays
+impl TryFromParams for Json {
+    fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+        Ok(Json::Array(
+            values.iter().map(Json::try_from_value).collect::<Result<_, _>>()?,
+        ))
+    }
+}
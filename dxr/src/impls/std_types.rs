@@ -0,0 +1,114 @@
+//! conversions for types from the standard library that have a natural XML-RPC representation
+//!
+//! Paths, IP addresses, and socket addresses are represented as `<string>` values. Durations are
+//! represented as a number of seconds, either as an `<i4>` value (for whole seconds that fit into
+//! the range of [`i32`]), or as a `<double>` value otherwise.
+
+use std::fmt::Display;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error::DxrError;
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::{Type, Value};
+
+pub(super) fn to_string_value(value: &impl Display) -> Result<Value, DxrError> {
+    Ok(Value::string(value.to_string()))
+}
+
+pub(super) fn from_string_value<T: FromStr>(value: &Value, target: &str) -> Result<T, DxrError> {
+    match value.inner() {
+        Type::String(string) => string
+            .parse()
+            .map_err(|_| DxrError::invalid_data(format!("Invalid {target}: {string}"))),
+        t => Err(DxrError::wrong_type(t.name(), "string")),
+    }
+}
+
+// implement conversions for types that are represented by their Display / FromStr implementations
+macro_rules! string_impls {
+    ($($t:ty => $target:literal),* $(,)?) => {
+        $(
+            impl TryToValue for $t {
+                fn try_to_value(&self) -> Result<Value, DxrError> {
+                    to_string_value(self)
+                }
+            }
+
+            impl TryFromValue for $t {
+                fn try_from_value(value: &Value) -> Result<$t, DxrError> {
+                    from_string_value(value, $target)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(any(feature = "url", feature = "uuid"))]
+pub(super) use string_impls;
+
+string_impls!(
+    IpAddr => "IP address",
+    Ipv4Addr => "IPv4 address",
+    Ipv6Addr => "IPv6 address",
+    SocketAddr => "socket address",
+    SocketAddrV4 => "IPv4 socket address",
+    SocketAddrV6 => "IPv6 socket address",
+);
+
+impl TryToValue for &Path {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        match self.to_str() {
+            Some(path) => Ok(Value::string(String::from(path))),
+            None => Err(DxrError::invalid_data(format!(
+                "Path is not valid UTF-8: {}",
+                self.display()
+            ))),
+        }
+    }
+}
+
+impl TryToValue for PathBuf {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        TryToValue::try_to_value(&self.as_path())
+    }
+}
+
+impl TryFromValue for PathBuf {
+    fn try_from_value(value: &Value) -> Result<PathBuf, DxrError> {
+        match value.inner() {
+            Type::String(string) => Ok(PathBuf::from(string)),
+            t => Err(DxrError::wrong_type(t.name(), "string")),
+        }
+    }
+}
+
+impl TryToValue for Duration {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        if self.subsec_nanos() == 0 {
+            if let Ok(secs) = i32::try_from(self.as_secs()) {
+                return Ok(Value::i4(secs));
+            }
+        }
+
+        Ok(Value::double(self.as_secs_f64()))
+    }
+}
+
+impl TryFromValue for Duration {
+    fn try_from_value(value: &Value) -> Result<Duration, DxrError> {
+        let invalid = |secs: &dyn Display| DxrError::invalid_data(format!("Invalid duration (in seconds): {secs}"));
+
+        match value.inner() {
+            Type::Integer(int) => u64::try_from(*int).map(Duration::from_secs).map_err(|_| invalid(int)),
+            #[cfg(feature = "i8")]
+            Type::Long(long) => u64::try_from(*long).map(Duration::from_secs).map_err(|_| invalid(long)),
+            // Duration::from_secs_f64 panics for negative, non-finite, and overflowing values
+            Type::Double(double) if *double >= 0.0 && *double < u64::MAX as f64 => Ok(Duration::from_secs_f64(*double)),
+            Type::Double(double) => Err(invalid(double)),
+            t => Err(DxrError::wrong_type(t.name(), "double")),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! conversions between XML-RPC `dateTime.iso8601` values and date & time types from [`time`]
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::error::DxrError;
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::{Type, Value};
+
+impl TryToValue for PrimitiveDateTime {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        Ok(Value::primitive_datetime(*self))
+    }
+}
+
+impl TryToValue for OffsetDateTime {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        Ok(Value::offset_datetime(*self))
+    }
+}
+
+impl TryFromValue for PrimitiveDateTime {
+    fn try_from_value(value: &Value) -> Result<PrimitiveDateTime, DxrError> {
+        match value.inner() {
+            Type::DateTime(date) => date.to_time(),
+            t => Err(DxrError::wrong_type(t.name(), "dateTime.iso8861")),
+        }
+    }
+}
+
+impl TryFromValue for OffsetDateTime {
+    fn try_from_value(value: &Value) -> Result<OffsetDateTime, DxrError> {
+        Ok(PrimitiveDateTime::try_from_value(value)?.assume_utc())
+    }
+}
@@ -1,13 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 
+#[cfg(feature = "chrono")]
 use chrono::NaiveDateTime;
 
 use crate::error::DxrError;
 use crate::traits::{TryToParams, TryToValue};
 use crate::values::Value;
 
-use super::utils::*;
-
 // for simple values, use TryToValue to convert them
 
 impl TryToParams for Value {
@@ -28,13 +28,6 @@ impl TryToParams for i32 {
     }
 }
 
-#[cfg(feature = "i8")]
-impl TryToParams for i64 {
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        Ok(vec![self.try_to_value()?])
-    }
-}
-
 impl TryToParams for bool {
     fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
         Ok(vec![self.try_to_value()?])
@@ -59,6 +52,7 @@ impl TryToParams for f64 {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl TryToParams for NaiveDateTime {
     fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
         Ok(vec![self.try_to_value()?])
@@ -134,8 +128,9 @@ where
 
 // treat maps as a single value of a struct
 
-impl<T> TryToParams for HashMap<String, T>
+impl<K, T> TryToParams for HashMap<K, T>
 where
+    K: Display,
     T: TryToValue,
 {
     fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
@@ -143,8 +138,9 @@ where
     }
 }
 
-impl<T> TryToParams for HashMap<&str, T>
+impl<K, T> TryToParams for BTreeMap<K, T>
 where
+    K: Display,
     T: TryToValue,
 {
     fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
@@ -159,105 +155,3 @@ impl TryToParams for () {
         Ok(Vec::new())
     }
 }
-
-impl<T> TryToParams for (T,)
-where
-    T: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_1(self)
-    }
-}
-
-impl<A, B> TryToParams for (A, B)
-where
-    A: TryToValue,
-    B: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_2(self)
-    }
-}
-
-impl<A, B, C> TryToParams for (A, B, C)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_3(self)
-    }
-}
-
-impl<A, B, C, D> TryToParams for (A, B, C, D)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_4(self)
-    }
-}
-
-impl<A, B, C, D, E> TryToParams for (A, B, C, D, E)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_5(self)
-    }
-}
-
-impl<A, B, C, D, E, F> TryToParams for (A, B, C, D, E, F)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_6(self)
-    }
-}
-
-impl<A, B, C, D, E, F, G> TryToParams for (A, B, C, D, E, F, G)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-    G: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_7(self)
-    }
-}
-
-impl<A, B, C, D, E, F, G, H> TryToParams for (A, B, C, D, E, F, G, H)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-    G: TryToValue,
-    H: TryToValue,
-{
-    fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
-        tuple_to_values_8(self)
-    }
-}
-
-// if needed, implementations for more arguments can be implemented
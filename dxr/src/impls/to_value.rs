@@ -1,16 +1,16 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 use std::rc::Rc;
 use std::sync::Arc;
 
+#[cfg(feature = "chrono")]
 use chrono::NaiveDateTime;
 
 use crate::error::DxrError;
 use crate::traits::TryToValue;
 use crate::values::{Array, Member, Struct, Value};
 
-use super::utils::*;
-
 impl<T> TryToValue for &T
 where
     T: TryToValue,
@@ -32,13 +32,6 @@ impl TryToValue for i32 {
     }
 }
 
-#[cfg(feature = "i8")]
-impl TryToValue for i64 {
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::i8(*self))
-    }
-}
-
 impl TryToValue for bool {
     fn try_to_value(&self) -> Result<Value, DxrError> {
         Ok(Value::boolean(*self))
@@ -63,6 +56,7 @@ impl TryToValue for f64 {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl TryToValue for NaiveDateTime {
     fn try_to_value(&self) -> Result<Value, DxrError> {
         Ok(Value::datetime(*self))
@@ -181,132 +175,49 @@ where
     }
 }
 
-impl<T> TryToValue for HashMap<String, T>
+// map keys are converted into struct member names with their Display implementation
+pub(super) fn map_to_value<'a, K, T>(map: impl Iterator<Item = (&'a K, &'a T)>) -> Result<Vec<Member>, DxrError>
 where
-    T: TryToValue,
+    K: Display + 'a,
+    T: TryToValue + 'a,
 {
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        let members = self
-            .iter()
-            .map(|(k, v)| T::try_to_value(v).map(|v| Member::new(k.to_owned(), v)))
-            .collect::<Result<Vec<Member>, DxrError>>();
-
-        Ok(Value::structure(Struct::new(members?)))
-    }
+    map.map(|(k, v)| T::try_to_value(v).map(|v| Member::new(k.to_string(), v)))
+        .collect()
 }
 
-impl<T> TryToValue for HashMap<&str, T>
+impl<K, T> TryToValue for HashMap<K, T>
 where
+    K: Display,
     T: TryToValue,
 {
     fn try_to_value(&self) -> Result<Value, DxrError> {
-        let members = self
-            .iter()
-            .map(|(k, v)| T::try_to_value(v).map(|v| Member::new((*k).to_owned(), v)))
-            .collect::<Result<Vec<Member>, DxrError>>();
+        let mut members = map_to_value(self.iter())?;
 
-        Ok(Value::structure(Struct::new(members?)))
-    }
-}
+        // the iteration order of hash maps is random: sort members by name for deterministic output
+        members.sort_by(|a, b| a.name().cmp(b.name()));
 
-impl<T> TryToValue for (T,)
-where
-    T: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_1(self)?)))
-    }
-}
-
-impl<A, B> TryToValue for (A, B)
-where
-    A: TryToValue,
-    B: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_2(self)?)))
+        Ok(Value::structure(Struct::new(members)))
     }
 }
 
-impl<A, B, C> TryToValue for (A, B, C)
+impl<K, T> TryToValue for BTreeMap<K, T>
 where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_3(self)?)))
-    }
-}
-
-impl<A, B, C, D> TryToValue for (A, B, C, D)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_4(self)?)))
-    }
-}
-
-impl<A, B, C, D, E> TryToValue for (A, B, C, D, E)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
+    K: Display,
+    T: TryToValue,
 {
     fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_5(self)?)))
+        Ok(Value::structure(Struct::new(map_to_value(self.iter())?)))
     }
 }
 
-impl<A, B, C, D, E, F> TryToValue for (A, B, C, D, E, F)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_6(self)?)))
-    }
-}
+// the unit type represents an empty return value (i.e. for methods that do not return anything)
 
-impl<A, B, C, D, E, F, G> TryToValue for (A, B, C, D, E, F, G)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-    G: TryToValue,
-{
+impl TryToValue for () {
     fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_7(self)?)))
-    }
-}
+        #[cfg(feature = "nil")]
+        return Ok(Value::nil());
 
-impl<A, B, C, D, E, F, G, H> TryToValue for (A, B, C, D, E, F, G, H)
-where
-    A: TryToValue,
-    B: TryToValue,
-    C: TryToValue,
-    D: TryToValue,
-    E: TryToValue,
-    F: TryToValue,
-    G: TryToValue,
-    H: TryToValue,
-{
-    fn try_to_value(&self) -> Result<Value, DxrError> {
-        Ok(Value::array(Array::new(tuple_to_values_8(self)?)))
+        #[cfg(not(feature = "nil"))]
+        return Ok(Value::array(Array::new(Vec::new())));
     }
 }
-
-// if needed, implementations for more arguments can be implemented
@@ -0,0 +1,132 @@
+//! implementations for tuples with up to sixteen members, and for tuples with a variadic tail
+
+use crate::error::DxrError;
+use crate::traits::{TryFromParams, TryFromValue, TryToParams, TryToValue};
+use crate::values::{Array, Type, Value};
+use crate::variadic::Variadic;
+
+// Tuples are treated as collections of values of different types:
+// - as values, they are converted from and to values of type "array"
+// - as parameters, they are converted from and to lists of method call parameters
+macro_rules! tuple_impls {
+    ($len:tt => $($idx:tt $t:ident)+) => {
+        impl<$($t),+> TryToValue for ($($t,)+)
+        where
+            $($t: TryToValue,)+
+        {
+            fn try_to_value(&self) -> Result<Value, DxrError> {
+                Ok(Value::array(Array::new(self.try_to_params()?)))
+            }
+        }
+
+        impl<$($t),+> TryFromValue for ($($t,)+)
+        where
+            $($t: TryFromValue,)+
+        {
+            fn try_from_value(value: &Value) -> Result<Self, DxrError> {
+                if let Type::Array { data } = value.inner() {
+                    TryFromParams::try_from_params(data.inner())
+                } else {
+                    Err(DxrError::wrong_type(value.inner().name(), "array"))
+                }
+            }
+        }
+
+        impl<$($t),+> TryToParams for ($($t,)+)
+        where
+            $($t: TryToValue,)+
+        {
+            fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+                Ok(vec![$(self.$idx.try_to_value()?),+])
+            }
+        }
+
+        impl<$($t),+> TryFromParams for ($($t,)+)
+        where
+            $($t: TryFromValue,)+
+        {
+            fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+                match values.len() {
+                    $len => Ok(($($t::try_from_value(&values[$idx]).map_err(|error| error.in_index($idx))?,)+)),
+                    n => Err(DxrError::parameter_mismatch(n, $len)),
+                }
+            }
+        }
+    };
+}
+
+// Tuples with a Variadic as their last member are treated as a fixed number of leading parameters,
+// followed by an arbitrary number of trailing parameters of the same type.
+macro_rules! variadic_impls {
+    ($len:tt => $($idx:tt $t:ident)+) => {
+        impl<$($t,)+ V> TryToParams for ($($t,)+ Variadic<V>)
+        where
+            $($t: TryToValue,)+
+            V: TryToValue,
+        {
+            fn try_to_params(&self) -> Result<Vec<Value>, DxrError> {
+                let mut values = vec![$(self.$idx.try_to_value()?),+];
+
+                for value in &self.$len.0 {
+                    values.push(value.try_to_value()?);
+                }
+
+                Ok(values)
+            }
+        }
+
+        impl<$($t,)+ V> TryFromParams for ($($t,)+ Variadic<V>)
+        where
+            $($t: TryFromValue,)+
+            V: TryFromValue,
+        {
+            fn try_from_params(values: &[Value]) -> Result<Self, DxrError> {
+                if values.len() < $len {
+                    return Err(DxrError::parameter_mismatch(values.len(), $len));
+                }
+
+                let (head, tail) = values.split_at($len);
+                let tail = tail
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| V::try_from_value(value).map_err(|error| error.in_index($len + index)))
+                    .collect::<Result<Vec<V>, DxrError>>()?;
+
+                Ok(($($t::try_from_value(&head[$idx]).map_err(|error| error.in_index($idx))?,)+ Variadic(tail)))
+            }
+        }
+    };
+}
+
+tuple_impls!(1 => 0 A);
+tuple_impls!(2 => 0 A 1 B);
+tuple_impls!(3 => 0 A 1 B 2 C);
+tuple_impls!(4 => 0 A 1 B 2 C 3 D);
+tuple_impls!(5 => 0 A 1 B 2 C 3 D 4 E);
+tuple_impls!(6 => 0 A 1 B 2 C 3 D 4 E 5 F);
+tuple_impls!(7 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G);
+tuple_impls!(8 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H);
+tuple_impls!(9 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I);
+tuple_impls!(10 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J);
+tuple_impls!(11 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K);
+tuple_impls!(12 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L);
+tuple_impls!(13 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M);
+tuple_impls!(14 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N);
+tuple_impls!(15 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O);
+tuple_impls!(16 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O 15 P);
+
+variadic_impls!(1 => 0 A);
+variadic_impls!(2 => 0 A 1 B);
+variadic_impls!(3 => 0 A 1 B 2 C);
+variadic_impls!(4 => 0 A 1 B 2 C 3 D);
+variadic_impls!(5 => 0 A 1 B 2 C 3 D 4 E);
+variadic_impls!(6 => 0 A 1 B 2 C 3 D 4 E 5 F);
+variadic_impls!(7 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G);
+variadic_impls!(8 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H);
+variadic_impls!(9 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I);
+variadic_impls!(10 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J);
+variadic_impls!(11 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K);
+variadic_impls!(12 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L);
+variadic_impls!(13 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M);
+variadic_impls!(14 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N);
+variadic_impls!(15 => 0 A 1 B 2 C 3 D 4 E 5 F 6 G 7 H 8 I 9 J 10 K 11 L 12 M 13 N 14 O);
@@ -0,0 +1,10 @@
+//! conversions between XML-RPC `<string>` values and URLs from [`url`]
+
+use url::Url;
+
+use super::std_types::{from_string_value, string_impls, to_string_value};
+use crate::error::DxrError;
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::Value;
+
+string_impls!(Url => "URL");
@@ -0,0 +1,13 @@
+//! conversions between XML-RPC `<string>` values and UUIDs from [`uuid`]
+//!
+//! UUIDs are converted into their canonical hyphenated representation. When converting values
+//! back into UUIDs, all formats that are supported by [`Uuid::parse_str`] are accepted.
+
+use uuid::Uuid;
+
+use super::std_types::{from_string_value, string_impls, to_string_value};
+use crate::error::DxrError;
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::Value;
+
+string_impls!(Uuid => "UUID");
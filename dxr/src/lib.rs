@@ -24,41 +24,106 @@
 //!
 //! The table below lists XML-RPC types and their equivalent Rust types.
 //!
-//! | XML-RPC value type | Rust type                 |
-//! |: ----------------- |: ------------------------ |
-//! | `i4`               | [`i32`]                   |
-//! | `i8`               | [`i64`]                   |
-//! | `boolean`          | [`bool`]                  |
-//! | `string`           | [`String`] / [`&str`]     |
-//! | `double`           | [`f64`]                   |
-//! | `dateTime.iso8601` | [`chrono::NaiveDateTime`] |
-//! | `base64`           | [`Vec<u8>`]               |
-//! | `nil`              | [`Option<T>`]             |
+//! | XML-RPC value type | Rust type                                             |
+//! |: ----------------- |: --------------------------------------------------- |
+//! | `i4`               | [`i32`]                                               |
+//! | `i8`               | [`i64`]                                               |
+//! | `boolean`          | [`bool`]                                              |
+//! | `string`           | [`String`] / [`&str`]                                 |
+//! | `double`           | [`f64`]                                               |
+//! | `dateTime.iso8601` | `NaiveDateTime` (chrono) / `PrimitiveDateTime` (time) |
+//! | `base64`           | [`Vec<u8>`]                                           |
+//! | `nil`              | [`Option<T>`]                                         |
 //!
 //! Additionally, the [`TryFromValue`] and [`TryToValue`] traits (which implement the conversion
 //! between XML-RPC value types and Rust types) are implemented for
 //!
 //! - [`Vec<T>`], slices `&[T]`, and fixed-size arrays `[T; N]`,
 //! - smart pointer types like [`Box<T>`], [`Cow<T>`], [`Rc<T>`], and [`Arc<T>`],
-//! - mappings like [`HashMap<String, T>`] / [`HashMap<&str, T>`],
-//! - tuples `(T, ...)` with up to eight members
+//! - mappings like [`HashMap<K, T>`] (where the keys `K` are converted into struct member names
+//!   with their [`Display`] and [`FromStr`] implementations, i.e. [`String`] / [`&str`],
+//!   integers, or enum-like types), and [`BTreeMap<K, T>`],
+//! - tuples `(T, ...)` with up to sixteen members
 //!
 //! (as long as the inner type `T` also implement these traits).
 //!
+//! The conversion traits are also implemented for [`i64`], [`u32`], [`u64`], and [`usize`]. Their
+//! values are converted into `<i4>` or `<i8>` values if they fit into their range, and result in
+//! an error otherwise (no values are silently truncated). Integers that are out of range can be
+//! transmitted as strings instead by wrapping them in a [`StringFallback`]. The optional
+//! `unsigned` feature adds conversions for [`u16`], and allows decoding `<array>` values of small
+//! integers into [`Vec<u8>`] (which is always encoded as `<base64>`).
+//!
+//! Paths ([`PathBuf`]), IP addresses ([`IpAddr`] etc.), and socket addresses ([`SocketAddr`] etc.)
+//! are converted into `<string>` values. A [`Duration`] is converted into its number of seconds,
+//! which is either an `<i4>` value (for whole seconds) or a `<double>` value.
+//!
+//! Method call parameters with a variable number of trailing arguments can be expressed with
+//! tuples that have a [`Variadic`] as their last member.
+//!
+//! Nested values and lists of method call parameters can also be constructed inline with the
+//! JSON-like [`value!`] and [`params!`] macros.
+//!
+//! Large documents can be deserialized into [`ValueRef`]s (or [`MethodResponseRef`]s) instead,
+//! which borrow string contents and struct member names from the XML document instead of
+//! allocating a new [`String`] for each of them.
+//!
+//! In the other direction, the [`WriteXml`] trait writes [`Value`]s and complete XML-RPC documents
+//! directly into a buffer without going through the generic serde support of `quick-xml`, which
+//! is considerably faster and produces identical output. Benchmarks for both code paths can be run
+//! with `cargo bench`.
+//!
+//! XML-RPC servers can also be implemented on top of HTTP frameworks or transports that are not
+//! supported by `dxr_server` (i.e. by embedding an XML-RPC endpoint in an existing service) with the
+//! [`parse_method_call`], [`serialize_response`], and [`serialize_fault`] functions.
+//!
 //! ## Features
 //!
-//! This crate provides optional features, all of which are disabled by default:
+//! This crate provides optional features, all of which (except for `chrono`) are disabled by
+//! default:
 //!
+//! - `chrono`: enable conversion between XML-RPC `dateTime.iso8601` values and the
+//!   `NaiveDateTime` type from the `chrono` crate (enabled by default)
 //! - `derive`: include procedural macros for deriving the [`TryFromValue`] and [`TryToValue`]
 //!   traits for custom structs
 //! - `i8`: enable support for the non-standard `i8` value type
-//! - `nil`: enable support for the non-standard `nil` value type
+//! - `nil`: enable support for the non-standard `nil` value type (derived implementations convert
+//!   missing struct members into `None`, and `#[dxr(option = "omit")]` omits `None` values)
+//! - `serde-bridge`: enable conversion between XML-RPC values and arbitrary types that implement
+//!   [`serde::Serialize`] / [`serde::Deserialize`] with the `to_value` and `from_value` functions,
+//!   and deserialization of method responses directly into these types (without building a
+//!   [`Value`] tree first) with the `from_response_xml` function
+//! - `arbitrary`: implement the `Arbitrary` trait from the `arbitrary` crate for [`Value`],
+//!   [`Struct`], [`Array`], and [`Member`] (for property-based tests and fuzzing)
+//! - `fuzz`: include the `fuzz` module, which contains entry points for fuzzing the XML-RPC parser
+//! - `json`: enable conversion between XML-RPC values and JSON values (the `Value` type from the
+//!   `serde_json` crate)
+//! - `time`: enable conversion between XML-RPC `dateTime.iso8601` values and the
+//!   `PrimitiveDateTime` / `OffsetDateTime` types from the `time` crate (this can be used as an
+//!   alternative to `chrono` by disabling default features)
+//! - `indexmap`: enable conversion between XML-RPC `struct` values and the `IndexMap` type from
+//!   the `indexmap` crate (which preserves the order of struct members)
+//! - `url`: enable conversion between XML-RPC `string` values and the `Url` type from the `url`
+//!   crate
+//! - `uuid`: enable conversion between XML-RPC `string` values and the `Uuid` type from the `uuid`
+//!   crate
 
 // imports for intra-doc links
 #[cfg(doc)]
-use std::{borrow::Cow, collections::HashMap, rc::Rc, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
-// re-export chrono: DateTime / Utc are part of the public API
+// re-export chrono: NaiveDateTime is part of the public API
+#[cfg(feature = "chrono")]
 pub use chrono;
 
 #[cfg(feature = "derive")]
@@ -69,22 +134,41 @@ mod base64;
 mod error;
 pub use error::*;
 
-mod fault;
-pub use fault::*;
+mod fallback;
+pub use fallback::*;
+
+pub mod fault;
+pub use fault::{Fault, FaultCategory};
+
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 
 mod impls;
 
+mod macros;
+
 #[cfg(feature = "multicall")]
 mod multicall;
 #[cfg(feature = "multicall")]
 pub use multicall::*;
 
+#[cfg(feature = "serde-bridge")]
+mod serde_bridge;
+#[cfg(feature = "serde-bridge")]
+pub use serde_bridge::*;
+
+mod protocol;
+pub use protocol::*;
+
 mod traits;
 pub use traits::*;
 
 mod values;
 pub use values::*;
 
+mod variadic;
+pub use variadic::*;
+
 mod xml;
 pub use xml::*;
 
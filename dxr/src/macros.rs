@@ -0,0 +1,102 @@
+/// Construct an XML-RPC [`Value`](crate::Value) from a JSON-like literal.
+///
+/// Arrays are written as `[...]` and are converted to `<array>` values, structs are written as
+/// `{"name": ...}` (with string literals as member names) and are converted to `<struct>` values,
+/// and `null` is converted to a `<nil/>` value (if the `nil` feature is enabled). Any other
+/// expression is converted with its [`TryToValue`](crate::TryToValue) implementation:
+///
+/// ```
+/// use dxr::{value, Value};
+///
+/// let name = "ubuntu.iso";
+/// let value = value!({
+///     "name": name,
+///     "sizes": [1, 2, 3],
+///     "peers": [{"address": "127.0.0.1", "port": 6881}],
+/// });
+///
+/// assert_eq!(value.pointer("peers/0/port"), Some(&Value::i4(6881)));
+/// ```
+///
+/// # Panics
+///
+/// This macro panics if converting one of the expressions fails, i.e. for unsigned or 64-bit integers
+/// that are out of the range of `<i4>` values (or of `<i8>` values, if the `i8` feature is enabled).
+/// Values that might not be convertible should be converted with
+/// [`TryToValue`](crate::TryToValue) first.
+#[macro_export]
+macro_rules! value {
+    // arrays: munch comma-separated elements
+    (@array [$($elems:expr,)*]) => {
+        <::std::vec::Vec<$crate::Value>>::from([$($elems,)*])
+    };
+    (@array [$($elems:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elems,)* $crate::value!(null),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elems,)* $crate::value!([$($array)*]),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] {$($members:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elems,)* $crate::value!({$($members)*}),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elems,)* $crate::value!($next),] $($($rest)*)?)
+    };
+
+    // structs: munch comma-separated "name": value pairs
+    (@struct [$($members:expr,)*]) => {
+        <::std::vec::Vec<$crate::Member>>::from([$($members,)*])
+    };
+    (@struct [$($members:expr,)*] $name:literal : null $(, $($rest:tt)*)?) => {
+        $crate::value!(@struct [$($members,)* $crate::value!(@member $name, $crate::value!(null)),] $($($rest)*)?)
+    };
+    (@struct [$($members:expr,)*] $name:literal : [$($array:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::value!(@struct [$($members,)* $crate::value!(@member $name, $crate::value!([$($array)*])),] $($($rest)*)?)
+    };
+    (@struct [$($members:expr,)*] $name:literal : {$($inner:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::value!(@struct [$($members,)* $crate::value!(@member $name, $crate::value!({$($inner)*})),] $($($rest)*)?)
+    };
+    (@struct [$($members:expr,)*] $name:literal : $next:expr $(, $($rest:tt)*)?) => {
+        $crate::value!(@struct [$($members,)* $crate::value!(@member $name, $crate::value!($next)),] $($($rest)*)?)
+    };
+    (@member $name:literal, $value:expr) => {
+        $crate::Member::new(::std::string::String::from($name), $value)
+    };
+
+    (null) => {
+        $crate::Value::nil()
+    };
+    ([$($array:tt)*]) => {
+        $crate::Value::array($crate::Array::new($crate::value!(@array [] $($array)*)))
+    };
+    ({$($members:tt)*}) => {
+        $crate::Value::structure($crate::Struct::new($crate::value!(@struct [] $($members)*)))
+    };
+    ($other:expr) => {
+        $crate::TryToValue::try_to_value(&$other).expect("Failed to convert expression into XML-RPC value.")
+    };
+}
+
+/// Construct a list of XML-RPC method call parameters from JSON-like literals.
+///
+/// Every parameter is converted in the same way as by the [`value!`](crate::value!) macro:
+///
+/// ```
+/// use dxr::{params, MethodCall};
+///
+/// let call = MethodCall::new(String::from("d.multicall2"), params!["", "main", ["d.hash=", "d.name="]]);
+/// assert_eq!(call.params().len(), 3);
+/// ```
+///
+/// # Panics
+///
+/// This macro panics if converting one of the expressions fails, i.e. for unsigned or 64-bit integers
+/// that are out of the range of `<i4>` values (or of `<i8>` values, if the `i8` feature is enabled).
+/// Values that might not be convertible should be converted with
+/// [`TryToValue`](crate::TryToValue) first.
+#[macro_export]
+macro_rules! params {
+    ($($params:tt)*) => {
+        $crate::value!(@array [] $($params)*)
+    };
+}
@@ -0,0 +1,77 @@
+//! helpers for handling XML-RPC method calls with custom servers and transports
+
+use crate::error::DxrError;
+use crate::fault::Fault;
+use crate::values::{FaultResponse, MethodCall, MethodResponse, Value};
+use crate::xml::{deserialize_xml_with, DeserializeOptions, SerializeOptions, WriteXml};
+
+/// Parse an XML-RPC method call (i.e. the body of an HTTP request).
+///
+/// This function (together with [`serialize_response`] and [`serialize_fault`]) can be used for
+/// implementing XML-RPC servers on top of HTTP frameworks or transports that are not supported by
+/// `dxr_server`:
+///
+/// ```
+/// use dxr::{parse_method_call, serialize_fault, serialize_response, Fault, Value};
+///
+/// let body = "<methodCall><methodName>add</methodName><params>\
+/// <param><value><i4>2</i4></value></param><param><value><i4>3</i4></value></param>\
+/// </params></methodCall>";
+///
+/// let call = parse_method_call(body).unwrap();
+/// let response = match call.name() {
+///     "add" => {
+///         let (a, b): (i32, i32) = dxr::TryFromParams::try_from_params(&call.params()).unwrap();
+///         serialize_response(Value::i4(a + b))
+///     },
+///     name => serialize_fault(Fault::method_not_found(name)),
+/// };
+///
+/// assert_eq!(
+///     response.unwrap(),
+///     "<methodResponse><params><param><value><i4>5</i4></value></param></params></methodResponse>"
+/// );
+/// ```
+///
+/// Errors for malformed XML documents include the byte offset of the syntax error, if it can be
+/// determined.
+pub fn parse_method_call(xml: &str) -> Result<MethodCall, DxrError> {
+    parse_method_call_with(xml, DeserializeOptions::default())
+}
+
+/// Parse an XML-RPC method call like [`parse_method_call`], but with custom
+/// [`DeserializeOptions`] (i.e. for adjusting the limits for the nesting depth and size of
+/// requests).
+pub fn parse_method_call_with(xml: &str, options: DeserializeOptions) -> Result<MethodCall, DxrError> {
+    deserialize_xml_with(xml, options).map_err(|error| DxrError::invalid_xml(xml, error))
+}
+
+/// Serialize the return value of a successful XML-RPC method call into a method response.
+pub fn serialize_response(value: Value) -> Result<String, DxrError> {
+    serialize_response_with(value, SerializeOptions::default())
+}
+
+/// Serialize the return value of a successful XML-RPC method call into a method response like
+/// [`serialize_response`], but with custom [`SerializeOptions`].
+pub fn serialize_response_with(value: Value, options: SerializeOptions) -> Result<String, DxrError> {
+    write_document(&MethodResponse::new(value), options)
+}
+
+/// Serialize a [`Fault`] into a fault response.
+pub fn serialize_fault(fault: Fault) -> Result<String, DxrError> {
+    serialize_fault_with(fault, SerializeOptions::default())
+}
+
+/// Serialize a [`Fault`] into a fault response like [`serialize_fault`], but with custom
+/// [`SerializeOptions`].
+pub fn serialize_fault_with(fault: Fault, options: SerializeOptions) -> Result<String, DxrError> {
+    write_document(&FaultResponse::from(fault), options)
+}
+
+fn write_document<T: WriteXml>(document: &T, options: SerializeOptions) -> Result<String, DxrError> {
+    let mut xml = String::new();
+    document
+        .write_xml(&mut xml, options)
+        .map_err(DxrError::invalid_data_from)?;
+    Ok(xml)
+}
@@ -0,0 +1,128 @@
+//! conversion between XML-RPC values and arbitrary types that implement serde traits
+
+use std::fmt::Display;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::DxrError;
+use crate::fault::Fault;
+use crate::values::{FaultResponse, MethodResponse, Value};
+use crate::xml::{check_document, deserialize_xml_with, with_options, DeserializeOptions};
+
+// imports for intra-doc links
+#[cfg(doc)]
+use crate::{TryFromValue, TryToValue};
+
+mod de;
+mod ser;
+mod stream;
+
+/// Convert any value that implements [`Serialize`] into an XML-RPC [`Value`].
+///
+/// This function is similar to `serde_json::to_value`, and can be used as an alternative to
+/// implementing (or deriving) the [`TryToValue`] trait for existing types. The serde data model is
+/// mapped to XML-RPC value types as follows:
+///
+/// - booleans are converted to `<boolean>` values
+/// - signed and unsigned integers are converted to `<i4>` values (or to `<i8>` values if they are
+///   out of range for `<i4>` and the `i8` feature is enabled)
+/// - floating point numbers are converted to `<double>` values
+/// - characters and strings are converted to `<string>` values
+/// - byte arrays are converted to `<base64>` values
+/// - sequences and tuples are converted to `<array>` values
+/// - maps (with string keys) and structs are converted to `<struct>` values
+/// - missing optional values and the unit type are converted to `<nil/>` values if the `nil`
+///   feature is enabled, and to empty `<array>` values otherwise
+/// - unit enum variants are converted to `<string>` values of the variant name, and other enum
+///   variants are converted to a `<struct>` with the variant name as the only member
+pub fn to_value<T>(value: &T) -> Result<Value, DxrError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ser::ValueSerializer)
+}
+
+/// Convert an XML-RPC [`Value`] into any type that implements [`Deserialize`].
+///
+/// This function is similar to `serde_json::from_value`, and can be used as an alternative to
+/// implementing (or deriving) the [`TryFromValue`] trait for existing types. Values are converted
+/// with the same mapping as documented for [`to_value`], so empty `<array>` values are converted to
+/// `None` if the `nil` feature is disabled (which means that `Some` with an empty sequence cannot
+/// be told apart from `None`). Additionally, `<dateTime.iso8601>` values are deserialized as
+/// strings in the XML-RPC date & time format.
+pub fn from_value<'de, T>(value: &'de Value) -> Result<T, DxrError>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(value)
+}
+
+/// Deserialize the return value of an XML-RPC method response directly into any type that
+/// implements [`Deserialize`].
+///
+/// This function is equivalent to deserializing the response into a [`MethodResponse`] and
+/// converting its return value with [`from_value`], but it reads the XML document as a stream of
+/// events and passes values straight to the target type, without building an intermediate
+/// [`Value`] tree. This roughly halves the time and memory that is needed for parsing large
+/// responses (i.e. long arrays of structs). Fault responses are returned as the inner [`Err`]
+/// variant:
+///
+/// ```
+/// use dxr::{from_response_xml, Fault};
+///
+/// let xml = "<methodResponse><params><param><value><array><data>\
+/// <value><i4>1</i4></value><value><i4>2</i4></value>\
+/// </data></array></value></param></params></methodResponse>";
+/// let numbers: Vec<i32> = from_response_xml(xml).unwrap().unwrap();
+/// assert_eq!(numbers, vec![1, 2]);
+///
+/// let xml = "<methodResponse><fault><value><struct>\
+/// <member><name>faultCode</name><value><i4>4</i4></value></member>\
+/// <member><name>faultString</name><value><string>Too many parameters.</string></value></member>\
+/// </struct></value></fault></methodResponse>";
+/// let fault = from_response_xml::<Vec<i32>>(xml).unwrap().unwrap_err();
+/// assert_eq!(fault, Fault::new(4, String::from("Too many parameters.")));
+/// ```
+pub fn from_response_xml<T>(xml: &str) -> Result<Result<T, Fault>, DxrError>
+where
+    T: DeserializeOwned,
+{
+    from_response_xml_with(xml, DeserializeOptions::default())
+}
+
+/// Deserialize the return value of an XML-RPC method response like [`from_response_xml`], but
+/// with custom [`DeserializeOptions`].
+pub fn from_response_xml_with<T>(xml: &str, options: DeserializeOptions) -> Result<Result<T, Fault>, DxrError>
+where
+    T: DeserializeOwned,
+{
+    check_document(xml, &options).map_err(|error| DxrError::invalid_xml(xml, error))?;
+
+    match with_options(options, || stream::from_response(xml))? {
+        stream::Parsed::Value(value) => Ok(Ok(value)),
+        stream::Parsed::Fault => {
+            let response: FaultResponse =
+                deserialize_xml_with(xml, options).map_err(|error| DxrError::invalid_xml(xml, error))?;
+            Ok(Err(Fault::try_from(response)?))
+        },
+        // duplicate struct members are handled according to the configured policy
+        stream::Parsed::DuplicateMembers => {
+            let response: MethodResponse =
+                deserialize_xml_with(xml, options).map_err(|error| DxrError::invalid_xml(xml, error))?;
+            Ok(Ok(from_value(&response.inner())?))
+        },
+    }
+}
+
+impl serde::ser::Error for DxrError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DxrError::invalid_data(msg.to_string())
+    }
+}
+
+impl serde::de::Error for DxrError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DxrError::invalid_data(msg.to_string())
+    }
+}
@@ -0,0 +1,218 @@
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::error::DxrError;
+use crate::values::{Member, Type, Value};
+
+impl<'de> de::Deserializer<'de> for &'de Value {
+    type Error = DxrError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.inner() {
+            Type::Integer(int) => visitor.visit_i32(*int),
+            #[cfg(feature = "i8")]
+            Type::Long(long) => visitor.visit_i64(*long),
+            Type::Boolean(boolean) => visitor.visit_bool(*boolean),
+            Type::String(string) => visitor.visit_borrowed_str(string),
+            Type::Double(double) => visitor.visit_f64(*double),
+            Type::DateTime(datetime) => visitor.visit_string(datetime.to_string()),
+            Type::Base64(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Type::Struct { members } => visitor.visit_map(StructAccess {
+                members: members.iter(),
+                value: None,
+            }),
+            Type::Array { data } => visitor.visit_seq(ArrayAccess {
+                values: data.inner().iter(),
+            }),
+            #[cfg(feature = "nil")]
+            Type::Nil => visitor.visit_unit(),
+            Type::Custom(custom) => visitor.visit_borrowed_str(custom.text()),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        // without the nil feature, None is serialized as an empty array
+        match self.inner() {
+            #[cfg(feature = "nil")]
+            Type::Nil => visitor.visit_none(),
+            #[cfg(not(feature = "nil"))]
+            Type::Array { data } if data.inner().is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.inner() {
+            #[cfg(feature = "nil")]
+            Type::Nil => visitor.visit_unit(),
+            Type::Array { data } if data.inner().is_empty() => visitor.visit_unit(),
+            other => Err(DxrError::wrong_type(other.name(), "array | nil")),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.inner() {
+            // unit variants are represented by their name
+            Type::String(string) => visitor.visit_enum(string.as_str().into_deserializer()),
+            // other variants are represented by a struct with the variant name as only member
+            Type::Struct { members } => match members.as_slice() {
+                [member] => visitor.visit_enum(EnumAccess { member }),
+                _ => Err(DxrError::parameter_mismatch(members.len(), 1)),
+            },
+            other => Err(DxrError::wrong_type(other.name(), "string | struct")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct ArrayAccess<'de> {
+    values: std::slice::Iter<'de, Value>,
+}
+
+impl<'de> de::SeqAccess<'de> for ArrayAccess<'de> {
+    type Error = DxrError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DxrError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.values.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct StructAccess<'de> {
+    members: std::slice::Iter<'de, Member>,
+    value: Option<&'de Value>,
+}
+
+impl<'de> de::MapAccess<'de> for StructAccess<'de> {
+    type Error = DxrError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, DxrError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.members.next() {
+            Some(member) => {
+                self.value = Some(member.inner());
+                seed.deserialize(member.name().into_deserializer()).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, DxrError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(DxrError::invalid_data(String::from(
+                "Struct member value requested before its name",
+            ))),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.members.len())
+    }
+}
+
+struct EnumAccess<'de> {
+    member: &'de Member,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = DxrError;
+    type Variant = VariantAccess<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantAccess<'de>), DxrError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let name: de::value::BorrowedStrDeserializer<'de, DxrError> =
+            de::value::BorrowedStrDeserializer::new(self.member.name());
+        let variant = seed.deserialize(name)?;
+        Ok((
+            variant,
+            VariantAccess {
+                value: self.member.inner(),
+            },
+        ))
+    }
+}
+
+struct VariantAccess<'de> {
+    value: &'de Value,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+    type Error = DxrError;
+
+    fn unit_variant(self) -> Result<(), DxrError> {
+        de::Deserialize::deserialize(self.value)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, DxrError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.value, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.value, visitor)
+    }
+}
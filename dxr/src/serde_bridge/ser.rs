@@ -0,0 +1,538 @@
+use serde::ser::{self, Serialize};
+
+use crate::error::DxrError;
+use crate::values::{Array, Member, Struct, Value};
+
+pub(super) struct ValueSerializer;
+
+fn empty() -> Value {
+    #[cfg(feature = "nil")]
+    {
+        Value::nil()
+    }
+    #[cfg(not(feature = "nil"))]
+    {
+        Value::array(Array::new(Vec::new()))
+    }
+}
+
+fn variant(name: &str, value: Value) -> Value {
+    Value::structure(Struct::new(vec![Member::new(name.to_owned(), value)]))
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = DxrError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeStruct;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeStruct;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, DxrError> {
+        Ok(Value::boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, DxrError> {
+        Ok(Value::i4(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, DxrError> {
+        Ok(Value::i4(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, DxrError> {
+        Ok(Value::i4(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, DxrError> {
+        match i32::try_from(v) {
+            Ok(v) => Ok(Value::i4(v)),
+            #[cfg(feature = "i8")]
+            Err(_) => Ok(Value::i8(v)),
+            #[cfg(not(feature = "i8"))]
+            Err(_) => Err(DxrError::invalid_data(format!("Integer out of range for i4: {}", v))),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, DxrError> {
+        Ok(Value::i4(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, DxrError> {
+        Ok(Value::i4(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, DxrError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, DxrError> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => Err(DxrError::invalid_data(format!("Integer out of range for i8: {}", v))),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, DxrError> {
+        Ok(Value::double(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, DxrError> {
+        Ok(Value::double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, DxrError> {
+        Ok(Value::string(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, DxrError> {
+        Ok(Value::string(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, DxrError> {
+        Ok(Value::base64(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value, DxrError> {
+        Ok(empty())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, DxrError> {
+        Ok(empty())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, DxrError> {
+        Ok(empty())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, DxrError> {
+        Ok(Value::string(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(self::variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, DxrError> {
+        Ok(SerializeArray {
+            variant: None,
+            values: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, DxrError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray, DxrError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, DxrError> {
+        Ok(SerializeArray {
+            variant: Some(variant),
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeStruct, DxrError> {
+        Ok(SerializeStruct {
+            variant: None,
+            members: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeStruct, DxrError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStruct, DxrError> {
+        Ok(SerializeStruct {
+            variant: Some(variant),
+            members: Vec::with_capacity(len),
+            key: None,
+        })
+    }
+}
+
+pub(super) struct SerializeArray {
+    variant: Option<&'static str>,
+    values: Vec<Value>,
+}
+
+impl SerializeArray {
+    fn push<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, DxrError> {
+        let value = Value::array(Array::new(self.values));
+
+        match self.variant {
+            Some(name) => Ok(variant(name, value)),
+            None => Ok(value),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+pub(super) struct SerializeStruct {
+    variant: Option<&'static str>,
+    members: Vec<Member>,
+    key: Option<String>,
+}
+
+impl SerializeStruct {
+    fn insert<T>(&mut self, key: String, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.members.push(Member::new(key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, DxrError> {
+        let value = Value::structure(Struct::new(self.members));
+
+        match self.variant {
+            Some(name) => Ok(variant(name, value)),
+            None => Ok(value),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeStruct {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| DxrError::invalid_data(String::from("Map value serialized before its key")))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeStruct {
+    type Ok = Value;
+    type Error = DxrError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Value, DxrError> {
+        self.finish()
+    }
+}
+
+// XML-RPC struct member names are always strings
+struct KeySerializer;
+
+fn key_must_be_a_string() -> DxrError {
+    DxrError::invalid_data(String::from("Struct member names must be strings"))
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = DxrError;
+
+    type SerializeSeq = ser::Impossible<String, DxrError>;
+    type SerializeTuple = ser::Impossible<String, DxrError>;
+    type SerializeTupleStruct = ser::Impossible<String, DxrError>;
+    type SerializeTupleVariant = ser::Impossible<String, DxrError>;
+    type SerializeMap = ser::Impossible<String, DxrError>;
+    type SerializeStruct = ser::Impossible<String, DxrError>;
+    type SerializeStructVariant = ser::Impossible<String, DxrError>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, DxrError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, DxrError> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<String, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, DxrError> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<String, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, DxrError>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, DxrError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, DxrError> {
+        Err(key_must_be_a_string())
+    }
+}
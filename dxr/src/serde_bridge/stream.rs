@@ -0,0 +1,728 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::error::DxrError;
+use crate::values::custom::Normalizer;
+use crate::values::ser_de::{base64, boolean, datetime, double};
+#[cfg(not(feature = "nil"))]
+use crate::values::Array;
+use crate::values::Value;
+use crate::xml::{current_options, DuplicateMembers, MultipleParams};
+
+// result of deserializing a method response without building a value tree
+pub(super) enum Parsed<T> {
+    Value(T),
+    // fault responses are small, so they are deserialized with the generic code path instead
+    Fault,
+    // structs with duplicate member names cannot be deduplicated while streaming
+    DuplicateMembers,
+}
+
+pub(super) fn from_response<T: DeserializeOwned>(xml: &str) -> Result<Parsed<T>, DxrError> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    reader.expand_empty_elements(true);
+
+    let mut deserializer = XmlDeserializer {
+        reader,
+        xml,
+        head: None,
+        duplicate_members: false,
+    };
+
+    match deserializer.response() {
+        Err(_) if deserializer.duplicate_members => Ok(Parsed::DuplicateMembers),
+        result => result,
+    }
+}
+
+// element inside a <value> element
+enum Head<'de> {
+    // the start tag of the type element was read
+    Typed(Kind),
+    // the untyped string value was read completely (including the </value> end tag)
+    Untyped(Cow<'de, str>),
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Integer,
+    #[cfg(feature = "i8")]
+    Long,
+    Boolean,
+    String,
+    Double,
+    DateTime,
+    Base64,
+    Struct,
+    Array,
+    #[cfg(feature = "nil")]
+    Nil,
+    Custom(&'static str, Normalizer),
+    Unknown,
+}
+
+impl Kind {
+    fn from_name(name: &[u8]) -> Option<Kind> {
+        match name {
+            b"i4" | b"int" => Some(Kind::Integer),
+            #[cfg(feature = "i8")]
+            b"i8" | b"ex:i8" => Some(Kind::Long),
+            b"boolean" => Some(Kind::Boolean),
+            b"string" => Some(Kind::String),
+            b"double" => Some(Kind::Double),
+            b"dateTime.iso8601" => Some(Kind::DateTime),
+            b"base64" => Some(Kind::Base64),
+            b"struct" => Some(Kind::Struct),
+            b"array" => Some(Kind::Array),
+            #[cfg(feature = "nil")]
+            b"nil" | b"ex:nil" => Some(Kind::Nil),
+            _ => {
+                let options = current_options();
+                match std::str::from_utf8(name)
+                    .ok()
+                    .and_then(|name| options.custom_scalar(name))
+                {
+                    Some((tag, normalize)) => Some(Kind::Custom(tag, normalize)),
+                    None if options.unknown_types() => Some(Kind::Unknown),
+                    None => None,
+                }
+            },
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Integer => "i4",
+            #[cfg(feature = "i8")]
+            Kind::Long => "i8",
+            Kind::Boolean => "boolean",
+            Kind::String | Kind::Unknown => "string",
+            Kind::Custom(tag, _) => tag,
+            Kind::Double => "double",
+            Kind::DateTime => "dateTime.iso8601",
+            Kind::Base64 => "base64",
+            Kind::Struct => "struct",
+            Kind::Array => "array",
+            #[cfg(feature = "nil")]
+            Kind::Nil => "nil",
+        }
+    }
+}
+
+struct XmlDeserializer<'de> {
+    reader: Reader<&'de [u8]>,
+    xml: &'de str,
+    head: Option<Head<'de>>,
+    duplicate_members: bool,
+}
+
+impl<'de> XmlDeserializer<'de> {
+    fn response<T: DeserializeOwned>(&mut self) -> Result<Parsed<T>, DxrError> {
+        self.expect_start(b"methodResponse")?;
+
+        let value = match self.next()? {
+            Event::Start(start) if start.name().as_ref() == b"params" => match self.next()? {
+                Event::Start(start) if start.name().as_ref() == b"param" => {
+                    self.expect_start(b"value")?;
+                    let value = T::deserialize(&mut *self)?;
+                    self.expect_end()?;
+                    self.extra_params()?;
+                    self.expect_end()?;
+                    value
+                },
+                Event::End(_) => {
+                    self.expect_end()?;
+                    missing_params()?
+                },
+                event => return Err(self.unexpected(&event)),
+            },
+            Event::Start(start) if start.name().as_ref() == b"fault" => return Ok(Parsed::Fault),
+            Event::End(_) => missing_params()?,
+            event => return Err(self.unexpected(&event)),
+        };
+
+        match self.next()? {
+            Event::Eof => Ok(Parsed::Value(value)),
+            event => Err(self.unexpected(&event)),
+        }
+    }
+
+    // skips all parameters after the first one (including the </params> end tag)
+    fn extra_params(&mut self) -> Result<(), DxrError> {
+        let mut count = 1;
+        loop {
+            match self.next()? {
+                Event::Start(start) if start.name().as_ref() == b"param" => {
+                    self.skip()?;
+                    count += 1;
+                },
+                Event::End(_) => break,
+                event => return Err(self.unexpected(&event)),
+            }
+        }
+
+        if count > 1 && current_options().multiple_params_policy() == MultipleParams::Error {
+            return Err(DxrError::invalid_data(format!(
+                "expected one response parameter, got {}",
+                count
+            )));
+        }
+
+        Ok(())
+    }
+
+    // next event that is relevant for deserialization
+    fn next(&mut self) -> Result<Event<'de>, DxrError> {
+        loop {
+            match self.reader.read_event() {
+                Ok(Event::Comment(_) | Event::PI(_) | Event::Decl(_)) => continue,
+                Ok(event) => return Ok(event),
+                Err(error) => return Err(DxrError::invalid_xml(self.xml, error)),
+            }
+        }
+    }
+
+    fn expect_start(&mut self, name: &[u8]) -> Result<(), DxrError> {
+        match self.next()? {
+            Event::Start(start) if start.name().as_ref() == name => Ok(()),
+            event => Err(self.unexpected(&event)),
+        }
+    }
+
+    // end tags always match the current element, since end tag names are checked by the reader
+    fn expect_end(&mut self) -> Result<(), DxrError> {
+        match self.next()? {
+            Event::End(_) => Ok(()),
+            event => Err(self.unexpected(&event)),
+        }
+    }
+
+    fn unexpected(&self, event: &Event) -> DxrError {
+        let description = match event {
+            Event::Start(start) => format!("element <{}>", String::from_utf8_lossy(start.name().as_ref())),
+            Event::End(end) => format!("end tag </{}>", String::from_utf8_lossy(end.name().as_ref())),
+            Event::Eof => String::from("end of document"),
+            _ => String::from("text"),
+        };
+        DxrError::invalid_data(format!("Unexpected {}", description)).in_document(self.xml)
+    }
+
+    // skips the rest of the current element (including its end tag)
+    fn skip(&mut self) -> Result<(), DxrError> {
+        let mut depth: usize = 0;
+        loop {
+            match self.next()? {
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => return Ok(()),
+                Event::End(_) => depth -= 1,
+                Event::Eof => return Err(self.unexpected(&Event::Eof)),
+                _ => (),
+            }
+        }
+    }
+
+    // reads the text content of the current element (including its end tag)
+    fn text(&mut self, mut text: Option<Cow<'de, str>>) -> Result<Cow<'de, str>, DxrError> {
+        loop {
+            let chunk = match self.next()? {
+                Event::Text(chunk) => unescape(decode(chunk.into_inner())?)?,
+                Event::CData(chunk) => decode(chunk.into_inner())?,
+                Event::End(_) => return Ok(text.unwrap_or(Cow::Borrowed(""))),
+                event => return Err(self.unexpected(&event)),
+            };
+
+            text = Some(match text {
+                Some(previous) => Cow::Owned(previous.into_owned() + &chunk),
+                None => chunk,
+            });
+        }
+    }
+
+    // reads the text content of a scalar value (including the </value> end tag)
+    fn scalar(&mut self) -> Result<Cow<'de, str>, DxrError> {
+        let text = self.text(None)?;
+        self.expect_end()?;
+        Ok(text)
+    }
+
+    fn head(&mut self) -> Result<Head<'de>, DxrError> {
+        if let Some(head) = self.head.take() {
+            return Ok(head);
+        }
+
+        match self.next()? {
+            Event::Start(start) => match Kind::from_name(start.name().as_ref()) {
+                Some(kind) => Ok(Head::Typed(kind)),
+                None => Err(DxrError::invalid_data(format!(
+                    "Unsupported value type: <{}>",
+                    String::from_utf8_lossy(start.name().as_ref())
+                ))
+                .in_document(self.xml)),
+            },
+            Event::Text(text) => {
+                let text = unescape(decode(text.into_inner())?)?;
+                Ok(Head::Untyped(self.text(Some(text))?))
+            },
+            Event::CData(text) => {
+                let text = decode(text.into_inner())?;
+                Ok(Head::Untyped(self.text(Some(text))?))
+            },
+            // <value></value>
+            Event::End(_) => Ok(Head::Untyped(Cow::Borrowed(""))),
+            event => Err(self.unexpected(&event)),
+        }
+    }
+
+    fn typed<V>(&mut self, kind: Kind, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match kind {
+            Kind::Integer => {
+                let value: i32 = parse(&self.scalar()?)?;
+                visitor.visit_i32(value)
+            },
+            #[cfg(feature = "i8")]
+            Kind::Long => {
+                let value: i64 = parse(&self.scalar()?)?;
+                visitor.visit_i64(value)
+            },
+            Kind::Boolean => {
+                let value = boolean::from_str(&self.scalar()?).map_err(DxrError::invalid_data)?;
+                visitor.visit_bool(value)
+            },
+            Kind::String | Kind::Unknown => visit_text(self.scalar()?, visitor),
+            Kind::Custom(_, normalize) => visitor.visit_string(normalize(&self.scalar()?)?),
+            Kind::Double => {
+                let value = double::check(parse(&self.scalar()?)?).map_err(DxrError::invalid_data)?;
+                visitor.visit_f64(value)
+            },
+            Kind::DateTime => {
+                let value = datetime::from_str(&self.scalar()?).map_err(DxrError::invalid_data)?;
+                visitor.visit_string(value.to_string())
+            },
+            Kind::Base64 => {
+                let value =
+                    base64::from_str(&self.scalar()?).map_err(|error| DxrError::invalid_data(error.to_string()))?;
+                visitor.visit_byte_buf(value)
+            },
+            Kind::Struct => {
+                let mut access = StructAccess {
+                    de: &mut *self,
+                    names: HashSet::new(),
+                    done: false,
+                };
+                let value = visitor.visit_map(&mut access)?;
+                // skip members that were not consumed by the visitor
+                if !access.done {
+                    self.skip()?;
+                }
+                self.expect_end()?;
+                Ok(value)
+            },
+            Kind::Array => {
+                // <array></array> is accepted as an empty array
+                let has_data = match self.next()? {
+                    Event::Start(start) if start.name().as_ref() == b"data" => true,
+                    Event::End(_) => false,
+                    event => return Err(self.unexpected(&event)),
+                };
+
+                let mut access = ArrayAccess {
+                    de: &mut *self,
+                    done: !has_data,
+                };
+                let value = visitor.visit_seq(&mut access)?;
+                // skip elements that were not consumed by the visitor
+                if !access.done {
+                    self.skip()?;
+                }
+                if has_data {
+                    self.expect_end()?;
+                }
+                self.expect_end()?;
+                Ok(value)
+            },
+            #[cfg(feature = "nil")]
+            Kind::Nil => {
+                self.expect_end()?;
+                self.expect_end()?;
+                visitor.visit_unit()
+            },
+        }
+    }
+
+    // reads a struct with a single member that represents an enum variant (including the </value>
+    // end tag)
+    fn variant_struct<V>(&mut self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        let name = match self.next()? {
+            Event::Start(start) if start.name().as_ref() == b"member" => {
+                self.expect_start(b"name")?;
+                let name = self.text(None)?;
+                self.expect_start(b"value")?;
+                name
+            },
+            Event::End(_) => return Err(DxrError::parameter_mismatch(0, 1)),
+            event => return Err(self.unexpected(&event)),
+        };
+
+        let value = visitor.visit_enum(EnumAccess { de: &mut *self, name })?;
+        self.expect_end()?;
+
+        let mut count = 1;
+        loop {
+            match self.next()? {
+                Event::Start(start) if start.name().as_ref() == b"member" => {
+                    self.skip()?;
+                    count += 1;
+                },
+                Event::End(_) => break,
+                event => return Err(self.unexpected(&event)),
+            }
+        }
+
+        if count > 1 {
+            return Err(DxrError::parameter_mismatch(count, 1));
+        }
+
+        self.expect_end()?;
+        Ok(value)
+    }
+
+    // reads the rest of an array value if it is empty (including the </value> end tag)
+    fn empty_array(&mut self) -> Result<bool, DxrError> {
+        let empty = match self.next()? {
+            Event::End(_) => true,
+            Event::Start(start) if start.name().as_ref() == b"data" => match self.next()? {
+                Event::End(_) => {
+                    self.expect_end()?;
+                    true
+                },
+                Event::Start(_) => false,
+                event => return Err(self.unexpected(&event)),
+            },
+            event => return Err(self.unexpected(&event)),
+        };
+
+        if empty {
+            self.expect_end()?;
+        }
+
+        Ok(empty)
+    }
+}
+
+// method responses without parameters are deserialized as a response with an empty return value
+fn missing_params<T: DeserializeOwned>() -> Result<T, DxrError> {
+    if !current_options().missing_params() {
+        return Err(DxrError::invalid_data(String::from("missing field `params`")));
+    }
+
+    #[cfg(feature = "nil")]
+    let value = Value::nil();
+    #[cfg(not(feature = "nil"))]
+    let value = Value::array(Array::new(Vec::new()));
+
+    T::deserialize(&value)
+}
+
+fn decode(bytes: Cow<'_, [u8]>) -> Result<Cow<'_, str>, DxrError> {
+    let result = match bytes {
+        Cow::Borrowed(bytes) => std::str::from_utf8(bytes).map(Cow::Borrowed),
+        Cow::Owned(bytes) => String::from_utf8(bytes)
+            .map(Cow::Owned)
+            .map_err(|error| error.utf8_error()),
+    };
+    result.map_err(DxrError::invalid_data_from)
+}
+
+fn unescape(text: Cow<'_, str>) -> Result<Cow<'_, str>, DxrError> {
+    let result = match text {
+        Cow::Borrowed(text) => quick_xml::escape::unescape(text),
+        Cow::Owned(text) => quick_xml::escape::unescape(&text).map(|text| Cow::Owned(text.into_owned())),
+    };
+    result.map_err(DxrError::invalid_data_from)
+}
+
+fn parse<T>(text: &str) -> Result<T, DxrError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    text.parse()
+        .map_err(|error: T::Err| DxrError::invalid_data(error.to_string()))
+}
+
+fn visit_text<'de, V>(text: Cow<'de, str>, visitor: V) -> Result<V::Value, DxrError>
+where
+    V: Visitor<'de>,
+{
+    match text {
+        Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+        Cow::Owned(text) => visitor.visit_string(text),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut XmlDeserializer<'de> {
+    type Error = DxrError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.head()? {
+            Head::Typed(kind) => self.typed(kind, visitor),
+            Head::Untyped(text) => visit_text(text, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.head()? {
+            #[cfg(feature = "nil")]
+            Head::Typed(Kind::Nil) => {
+                self.expect_end()?;
+                self.expect_end()?;
+                visitor.visit_none()
+            },
+            // without the nil feature, None is serialized as an empty array
+            #[cfg(not(feature = "nil"))]
+            Head::Typed(Kind::Array) => {
+                let reader = self.reader.clone();
+                if self.empty_array()? {
+                    return visitor.visit_none();
+                }
+                // non-empty arrays are deserialized from the start again
+                self.reader = reader;
+                self.head = Some(Head::Typed(Kind::Array));
+                visitor.visit_some(self)
+            },
+            head => {
+                self.head = Some(head);
+                visitor.visit_some(self)
+            },
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.head()? {
+            #[cfg(feature = "nil")]
+            Head::Typed(Kind::Nil) => self.typed(Kind::Nil, visitor),
+            Head::Typed(Kind::Array) => match self.empty_array()? {
+                true => visitor.visit_unit(),
+                false => Err(DxrError::wrong_type("array", "array | nil")),
+            },
+            Head::Typed(kind) => Err(DxrError::wrong_type(kind.name(), "array | nil")),
+            Head::Untyped(_) => Err(DxrError::wrong_type("string", "array | nil")),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        let text = match self.head()? {
+            // other variants are represented by a struct with the variant name as only member
+            Head::Typed(Kind::Struct) => return self.variant_struct(visitor),
+            // unit variants are represented by their name
+            Head::Typed(Kind::String | Kind::Unknown) => self.scalar()?,
+            Head::Untyped(text) => text,
+            Head::Typed(kind) => return Err(DxrError::wrong_type(kind.name(), "string | struct")),
+        };
+
+        match text {
+            Cow::Borrowed(text) => visitor.visit_enum(de::value::BorrowedStrDeserializer::new(text)),
+            Cow::Owned(text) => visitor.visit_enum(text.into_deserializer()),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.head.take() {
+            Some(Head::Typed(_)) => {
+                self.skip()?;
+                self.expect_end()?;
+            },
+            Some(Head::Untyped(_)) => (),
+            None => self.skip()?,
+        }
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct ArrayAccess<'a, 'de> {
+    de: &'a mut XmlDeserializer<'de>,
+    done: bool,
+}
+
+impl<'de, 'a, 'b> de::SeqAccess<'de> for &'b mut ArrayAccess<'a, 'de> {
+    type Error = DxrError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DxrError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.done {
+            return Ok(None);
+        }
+
+        match self.de.next()? {
+            Event::Start(start) if start.name().as_ref() == b"value" => seed.deserialize(&mut *self.de).map(Some),
+            Event::End(_) => {
+                self.done = true;
+                Ok(None)
+            },
+            event => Err(self.de.unexpected(&event)),
+        }
+    }
+}
+
+struct StructAccess<'a, 'de> {
+    de: &'a mut XmlDeserializer<'de>,
+    names: HashSet<Cow<'de, str>>,
+    done: bool,
+}
+
+impl<'de, 'a, 'b> de::MapAccess<'de> for &'b mut StructAccess<'a, 'de> {
+    type Error = DxrError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, DxrError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.done {
+            return Ok(None);
+        }
+
+        let name = match self.de.next()? {
+            Event::Start(start) if start.name().as_ref() == b"member" => {
+                self.de.expect_start(b"name")?;
+                self.de.text(None)?
+            },
+            Event::End(_) => {
+                self.done = true;
+                return Ok(None);
+            },
+            event => return Err(self.de.unexpected(&event)),
+        };
+
+        if !self.names.insert(name.clone()) {
+            if current_options().duplicate_members_policy() != DuplicateMembers::Error {
+                self.de.duplicate_members = true;
+            }
+            return Err(DxrError::invalid_data(format!("Duplicate struct member: {}", name)));
+        }
+
+        self.de.expect_start(b"value")?;
+
+        match name {
+            Cow::Borrowed(name) => seed
+                .deserialize(de::value::BorrowedStrDeserializer::new(name))
+                .map(Some),
+            Cow::Owned(name) => seed.deserialize(name.into_deserializer()).map(Some),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, DxrError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.expect_end()?;
+        Ok(value)
+    }
+}
+
+struct EnumAccess<'a, 'de> {
+    de: &'a mut XmlDeserializer<'de>,
+    name: Cow<'de, str>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = DxrError;
+    type Variant = VariantAccess<'a, 'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantAccess<'a, 'de>), DxrError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = match self.name {
+            Cow::Borrowed(name) => seed.deserialize(de::value::BorrowedStrDeserializer::new(name))?,
+            Cow::Owned(name) => seed.deserialize(name.into_deserializer())?,
+        };
+        Ok((variant, VariantAccess { de: self.de }))
+    }
+}
+
+struct VariantAccess<'a, 'de> {
+    de: &'a mut XmlDeserializer<'de>,
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for VariantAccess<'a, 'de> {
+    type Error = DxrError;
+
+    fn unit_variant(self) -> Result<(), DxrError> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, DxrError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, DxrError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
@@ -1,4 +1,14 @@
 #![allow(clippy::unwrap_used)]
 
+mod access;
+mod display;
+mod error;
+mod fault;
+#[cfg(feature = "fuzz")]
+mod fuzz;
 mod impls;
+mod macros;
+mod protocol;
+#[cfg(feature = "serde-bridge")]
+mod serde_bridge;
 mod xml;
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "chrono")]
+use chrono::{NaiveDateTime, SubsecRound, Utc};
+
+use crate::traits::TryToValue;
+use crate::values::datetime::DateTime;
+use crate::values::{Array, Member, Struct, Value};
+
+fn nested() -> Value {
+    let peer = HashMap::from([("address", Value::string(String::from("127.0.0.1")))]);
+    let config = HashMap::from([("peers", vec![peer])]);
+    HashMap::from([("config", config)]).try_to_value().unwrap()
+}
+
+#[test]
+fn as_scalars() {
+    assert_eq!(Value::i4(42).as_i32(), Some(42));
+    assert_eq!(Value::boolean(true).as_bool(), Some(true));
+    assert_eq!(Value::string(String::from("foo")).as_str(), Some("foo"));
+    assert_eq!(Value::double(1.5).as_f64(), Some(1.5));
+    assert_eq!(Value::base64(b"foo".to_vec()).as_bytes(), Some(b"foo".as_slice()));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn as_datetime() {
+    let datetime: NaiveDateTime = Utc::now().round_subsecs(0).naive_utc();
+
+    assert_eq!(Value::datetime(datetime).as_datetime(), Some(datetime));
+    assert_eq!(Value::string(String::from("42")).as_datetime(), None);
+}
+
+#[test]
+fn as_scalars_fail() {
+    let value = Value::string(String::from("42"));
+
+    assert_eq!(value.as_i32(), None);
+    assert_eq!(value.as_bool(), None);
+    assert_eq!(value.as_f64(), None);
+    assert_eq!(value.as_bytes(), None);
+    assert!(value.as_struct().is_none());
+    assert!(value.as_array().is_none());
+    assert_eq!(Value::i4(42).as_str(), None);
+}
+
+#[test]
+fn type_names() {
+    assert_eq!(Value::i4(42).type_name(), "i4");
+    assert_eq!(Value::string(String::from("foo")).type_name(), "string");
+    assert_eq!(
+        Value::from_datetime(DateTime::default()).type_name(),
+        "dateTime.iso8601"
+    );
+    assert_eq!(Value::array(Array::new(vec![])).type_name(), "array");
+}
+
+#[cfg(feature = "i8")]
+#[test]
+fn as_i64() {
+    assert_eq!(Value::i8(42).as_i64(), Some(42));
+    assert_eq!(Value::i4(42).as_i64(), None);
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn is_nil() {
+    assert!(Value::nil().is_nil());
+    assert!(!Value::i4(0).is_nil());
+}
+
+#[test]
+fn as_struct() {
+    let value = HashMap::from([("foo", 1)]).try_to_value().unwrap();
+    let members = value.as_struct().unwrap();
+
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].name(), "foo");
+    assert_eq!(members[0].inner(), &Value::i4(1));
+}
+
+#[test]
+fn as_array() {
+    let value = vec![1, 2].try_to_value().unwrap();
+    let expected = [Value::i4(1), Value::i4(2)];
+
+    assert_eq!(value.as_array().unwrap(), expected.as_slice());
+}
+
+#[test]
+fn get() {
+    let value = HashMap::from([("foo", 1), ("bar", 2)]).try_to_value().unwrap();
+
+    assert_eq!(value.get("bar"), Some(&Value::i4(2)));
+    assert_eq!(value.get("baz"), None);
+    assert_eq!(Value::i4(1).get("foo"), None);
+}
+
+#[test]
+fn get_index() {
+    let value = vec![1, 2].try_to_value().unwrap();
+
+    assert_eq!(value.get_index(1), Some(&Value::i4(2)));
+    assert_eq!(value.get_index(2), None);
+    assert_eq!(Value::i4(1).get_index(0), None);
+}
+
+#[test]
+fn pointer() {
+    let value = nested();
+    let expected = Value::string(String::from("127.0.0.1"));
+
+    assert_eq!(value.pointer("config/peers/0/address"), Some(&expected));
+    assert_eq!(value.pointer("/config/peers/0/address"), Some(&expected));
+    assert_eq!(value.pointer(""), Some(&value));
+}
+
+#[test]
+fn pointer_fail() {
+    let value = nested();
+
+    assert_eq!(value.pointer("config/peers/1/address"), None);
+    assert_eq!(value.pointer("config/peers/first/address"), None);
+    assert_eq!(value.pointer("config/peers/0/address/0"), None);
+    assert_eq!(value.pointer("config/seeds"), None);
+}
+
+#[test]
+fn from_primitives() {
+    assert_eq!(Value::from(42), Value::i4(42));
+    assert_eq!(Value::from(true), Value::boolean(true));
+    assert_eq!(Value::from("foo"), Value::string(String::from("foo")));
+    assert_eq!(Value::from(String::from("foo")), Value::string(String::from("foo")));
+    assert_eq!(Value::from(1.5), Value::double(1.5));
+    assert_eq!(Value::from(b"foo".as_slice()), Value::base64(b"foo".to_vec()));
+    assert_eq!(
+        Value::from(vec![Value::from(1), Value::from(2)]),
+        vec![1, 2].try_to_value().unwrap()
+    );
+}
+
+#[cfg(feature = "i8")]
+#[test]
+fn from_i64() {
+    assert_eq!(Value::from(42i64), Value::i8(42));
+}
+
+#[test]
+fn index() {
+    let value = nested();
+
+    assert_eq!(value["config"]["peers"][0]["address"], Value::from("127.0.0.1"));
+    assert_eq!(Array::new(vec![Value::from(1)])[0], Value::i4(1));
+
+    let members = vec![Member::new(String::from("foo"), Value::from(1))];
+    assert_eq!(Struct::from(members)["foo"], Value::i4(1));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+fn index_out_of_bounds() {
+    let _ = &nested()["config"]["peers"][1];
+}
+
+#[test]
+#[should_panic(expected = "struct has no member with name \"seeds\"")]
+fn index_missing_member() {
+    let _ = &nested()["config"]["seeds"];
+}
+
+#[test]
+#[should_panic(expected = "cannot index into <i4> value with string")]
+fn index_wrong_type() {
+    let _ = &Value::from(42)["foo"];
+}
+
+#[test]
+fn struct_get() {
+    let members = vec![
+        Member::new(String::from("foo"), Value::from(1)),
+        Member::new(String::from("foo"), Value::from(2)),
+    ];
+    let value = Struct::new(members);
+
+    assert_eq!(value.get("foo"), Some(&Value::i4(1)));
+    assert_eq!(value.get("bar"), None);
+}
+
+#[test]
+fn struct_conversions() {
+    let map = HashMap::from([
+        (String::from("foo"), Value::from(1)),
+        (String::from("bar"), Value::from(2)),
+    ]);
+    let value = Struct::from(map.clone());
+    assert_eq!(value.members().len(), 2);
+    assert_eq!(value["bar"], Value::i4(2));
+
+    let mut value: Struct = vec![(String::from("foo"), Value::from(1))].into_iter().collect();
+    value.extend([(String::from("bar"), Value::from(2))]);
+    assert_eq!(value, Struct::from(map));
+
+    let names: Vec<&str> = (&value).into_iter().map(Member::name).collect();
+    assert_eq!(names, ["foo", "bar"]);
+
+    let members: Vec<Member> = value.into_iter().collect();
+    assert_eq!(members.len(), 2);
+}
+
+#[test]
+fn array_conversions() {
+    let mut value: Array = (1..=2).map(Value::from).collect();
+    value.extend([Value::from(3)]);
+
+    assert_eq!(value.len(), 3);
+    assert_eq!(value.first(), Some(&Value::i4(1)));
+    assert_eq!((&value).into_iter().filter_map(Value::as_i32).sum::<i32>(), 6);
+
+    let values: Vec<Value> = value.into_iter().collect();
+    assert_eq!(values, vec![Value::i4(1), Value::i4(2), Value::i4(3)]);
+}
@@ -0,0 +1,111 @@
+use crate::values::{Array, Member, Struct, Value};
+use crate::xml::{deserialize_xml as from_str, serialize_xml_pretty as to_string_pretty};
+
+fn nested() -> Value {
+    Value::structure(Struct::new(vec![
+        Member::new(String::from("id"), Value::i4(42)),
+        Member::new(
+            String::from("tags"),
+            Value::array(Array::new(vec![
+                Value::string(String::from("foo")),
+                Value::string(String::from("bar")),
+            ])),
+        ),
+        Member::new(String::from("empty"), Value::array(Array::new(vec![]))),
+    ]))
+}
+
+#[test]
+fn display_scalars() {
+    assert_eq!(Value::i4(42).to_string(), "42");
+    assert_eq!(Value::boolean(true).to_string(), "true");
+    assert_eq!(Value::string(String::from("a \"b\"")).to_string(), r#""a \"b\"""#);
+    assert_eq!(Value::double(1.0).to_string(), "1.0");
+    assert_eq!(Value::base64(b"foo".to_vec()).to_string(), "base64(Zm9v)");
+}
+
+#[test]
+fn display_compact() {
+    assert_eq!(
+        nested().to_string(),
+        r#"{"id": 42, "tags": ["foo", "bar"], "empty": []}"#
+    );
+}
+
+#[test]
+fn display_pretty() {
+    let expected = r#"{
+    "id": 42,
+    "tags": [
+        "foo",
+        "bar",
+    ],
+    "empty": [],
+}"#;
+
+    assert_eq!(format!("{:#}", nested()), expected);
+}
+
+#[test]
+fn debug_compact() {
+    assert_eq!(format!("{:?}", Value::i4(1)), "Value(1)");
+    assert_eq!(
+        format!("{:?}", Array::new(vec![Value::i4(1), Value::i4(2)])),
+        "Array([1, 2])"
+    );
+    assert_eq!(
+        format!("{:?}", Member::new(String::from("a"), Value::i4(1))),
+        r#"Member("a": 1)"#
+    );
+}
+
+#[test]
+fn xml_pretty() {
+    let expected = "\
+<value>
+  <struct>
+    <member>
+      <name>id</name>
+      <value>
+        <i4>42</i4>
+      </value>
+    </member>
+    <member>
+      <name>tags</name>
+      <value>
+        <array>
+          <data>
+            <value>
+              <string>foo</string>
+            </value>
+            <value>
+              <string>bar</string>
+            </value>
+          </data>
+        </array>
+      </value>
+    </member>
+    <member>
+      <name>empty</name>
+      <value>
+        <array>
+          <data/>
+        </array>
+      </value>
+    </member>
+  </struct>
+</value>";
+
+    let value = nested();
+    let xml = to_string_pretty(&value).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn xml_pretty_string_whitespace() {
+    let value = Value::string(String::from("  foo  "));
+    let xml = to_string_pretty(&value).unwrap();
+
+    assert_eq!(xml, "<value>\n  <string>  foo  </string>\n</value>");
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::error::{DxrError, PathSegment};
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::{MethodResponse, Value};
+
+#[test]
+fn nested_path() {
+    let files = vec![HashMap::from([("size", Value::string(String::from("42")))])];
+    let value = vec![HashMap::from([("files", files)])].try_to_value().unwrap();
+
+    let error = <Vec<HashMap<String, Vec<HashMap<String, i32>>>>>::try_from_value(&value).unwrap_err();
+    let context = error.context().unwrap();
+
+    assert_eq!(
+        context.path(),
+        [
+            PathSegment::Index(0),
+            PathSegment::Member(String::from("files")),
+            PathSegment::Index(0),
+            PathSegment::Member(String::from("size")),
+        ]
+    );
+    assert_eq!(context.path_string(), "[0].files[0].size");
+    assert!(error.is_wrong_type());
+    assert_eq!(error.as_wrong_type(), Some(("string", "i4")));
+}
+
+#[test]
+fn merged_context() {
+    let error = DxrError::wrong_type("string", "i4")
+        .in_member("size")
+        .in_index(3)
+        .in_member("result")
+        .in_method("d.multicall2");
+
+    assert_eq!(
+        error.to_string(),
+        "Error in method call 'd.multicall2' at result[3].size: Type mismatch: got string, expected i4"
+    );
+    assert_eq!(error.context().unwrap().method(), Some("d.multicall2"));
+    assert_eq!(error.as_wrong_type(), Some(("string", "i4")));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn tuple_index() {
+    let value = (1, "two").try_to_value().unwrap();
+    let error = <(i32, i32)>::try_from_value(&value).unwrap_err();
+
+    assert_eq!(error.context().unwrap().path_string(), "[1]");
+}
+
+#[test]
+fn invalid_xml_offset() {
+    let parse = |xml| crate::deserialize_xml::<MethodResponse>(xml).unwrap_err();
+
+    let xml = "<methodResponse><params></param></methodResponse>";
+    let error = DxrError::invalid_xml(xml, parse(xml));
+
+    assert!(error.is_invalid_data());
+    assert_eq!(error.context().unwrap().offset(), Some(26));
+    assert!(error
+        .to_string()
+        .starts_with("Error at byte offset 26: Failed to parse XML data: "));
+
+    // truncated documents are not well-formed either
+    let xml = "<methodResponse><params>";
+    let error = DxrError::invalid_xml(xml, parse(xml));
+    assert_eq!(error.context().unwrap().offset(), Some(24));
+
+    // well-formed documents do not have a syntax error
+    let xml = "<methodResponse><params><param></param></params></methodResponse>";
+    let error = DxrError::invalid_xml(xml, parse(xml));
+    assert!(error.context().is_none());
+}
+
+#[test]
+fn source_chain() {
+    let xml = "<methodResponse><params></param></methodResponse>";
+    let error = DxrError::invalid_xml(xml, crate::deserialize_xml::<MethodResponse>(xml).unwrap_err());
+    let source = error.source().unwrap();
+    assert!(source.is::<quick_xml::DeError>());
+    assert!(error.to_string().ends_with(&source.to_string()));
+
+    // the source is kept when context is added
+    let error = DxrError::invalid_data_from("forty-two".parse::<i32>().unwrap_err())
+        .in_member("answer")
+        .in_method("answer");
+    assert!(error.source().unwrap().is::<std::num::ParseIntError>());
+    assert_eq!(
+        error.to_string(),
+        "Error in method call 'answer' at answer: Failed to parse XML data: invalid digit found in string"
+    );
+
+    // errors without underlying error do not have a source
+    assert!(DxrError::invalid_data(String::from("invalid")).source().is_none());
+}
+
+#[test]
+fn match_with_context() {
+    let error = DxrError::missing_field("Torrent", "hash").in_index(0);
+
+    // variants can still be matched directly when the error carries context
+    assert!(matches!(error, DxrError::MissingField { ref field, .. } if field == "hash"));
+    assert_eq!(error.as_missing_field(), Some(("Torrent", "hash")));
+    assert_eq!(error.to_string(), "Error at [0]: Struct 'Torrent' missing field: hash");
+}
@@ -0,0 +1,39 @@
+use crate::error::DxrError;
+use crate::fault::{self, Fault, FaultCategory};
+
+#[test]
+fn constructors() {
+    assert_eq!(Fault::parse_error(String::from("")).code(), fault::PARSE_ERROR);
+    assert_eq!(Fault::invalid_request(String::from("")).code(), fault::INVALID_REQUEST);
+    assert_eq!(Fault::invalid_params(String::from("")).code(), fault::INVALID_PARAMS);
+    assert_eq!(Fault::internal_error(String::from("")).code(), fault::INTERNAL_ERROR);
+
+    let fault = Fault::method_not_found("system.shutdown");
+    assert_eq!(fault.code(), fault::METHOD_NOT_FOUND);
+    assert_eq!(fault.string(), "Unknown method: system.shutdown");
+}
+
+#[test]
+fn categories() {
+    let category = |code| Fault::new(code, String::new()).category();
+
+    assert_eq!(category(fault::PARSE_ERROR), FaultCategory::Parse);
+    assert_eq!(category(fault::UNSUPPORTED_ENCODING), FaultCategory::Parse);
+    assert_eq!(category(fault::INVALID_CHARACTER), FaultCategory::Parse);
+    assert_eq!(category(fault::INVALID_REQUEST), FaultCategory::InvalidRequest);
+    assert_eq!(category(fault::METHOD_NOT_FOUND), FaultCategory::MethodNotFound);
+    assert_eq!(category(fault::INVALID_PARAMS), FaultCategory::InvalidParams);
+    assert_eq!(category(fault::INTERNAL_ERROR), FaultCategory::Internal);
+    assert_eq!(category(fault::APPLICATION_ERROR), FaultCategory::Application);
+    assert_eq!(category(fault::APPLICATION_ERROR - 99), FaultCategory::Application);
+    assert_eq!(category(fault::SYSTEM_ERROR), FaultCategory::System);
+    assert_eq!(category(fault::TRANSPORT_ERROR), FaultCategory::Transport);
+    assert_eq!(category(404), FaultCategory::Other);
+    assert_eq!(category(-32000), FaultCategory::Other);
+}
+
+#[test]
+fn from_error() {
+    let fault = Fault::from(DxrError::parameter_mismatch(1, 2));
+    assert_eq!(fault.category(), FaultCategory::InvalidParams);
+}
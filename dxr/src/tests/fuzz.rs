@@ -0,0 +1,40 @@
+use crate::fault::Fault;
+use crate::fuzz::{parse_call, parse_response, parse_value};
+use crate::values::Value;
+
+#[test]
+fn fuzz_parse_call() {
+    let xml = b"<methodCall><methodName>add</methodName><params><param><value><i4>1</i4></value></param></params></methodCall>";
+    let call = parse_call(xml).unwrap();
+
+    assert_eq!(call.name(), "add");
+    assert_eq!(call.params(), vec![Value::i4(1)]);
+
+    assert!(parse_call(b"<methodCall>").is_err());
+    assert!(parse_call(b"\xff\xfe").is_err());
+}
+
+#[test]
+fn fuzz_parse_response() {
+    let xml = b"<methodResponse><params><param><value><i4>42</i4></value></param></params></methodResponse>";
+    assert_eq!(parse_response(xml).unwrap(), Ok(Value::i4(42)));
+
+    let xml = b"<methodResponse><fault><value><struct><member><name>faultCode</name><value><i4>4</i4></value></member><member><name>faultString</name><value><string>Too many parameters.</string></value></member></struct></value></fault></methodResponse>";
+    assert_eq!(
+        parse_response(xml).unwrap(),
+        Err(Fault::new(4, String::from("Too many parameters.")))
+    );
+
+    // empty responses are accepted with the default options
+    assert!(parse_response(b"<methodResponse/>").unwrap().is_ok());
+    assert!(parse_response(b"<methodResponse><params><param/></params></methodResponse>").is_err());
+}
+
+#[test]
+fn fuzz_parse_value() {
+    let xml = b"<value><struct><member><name>answer</name><value>forty-two</value></member></struct></value>";
+    let value = parse_value(xml).unwrap();
+    assert_eq!(value.get("answer").and_then(Value::as_str), Some("forty-two"));
+
+    assert!(parse_value(b"<value><i4>forty-two</i4></value>").is_err());
+}
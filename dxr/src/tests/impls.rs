@@ -1,2 +1,13 @@
+#[cfg(feature = "indexmap")]
+mod indexmap_support;
+#[cfg(feature = "json")]
+mod json_support;
 mod params;
+mod std_types;
+#[cfg(feature = "time")]
+mod time_support;
+#[cfg(feature = "url")]
+mod url_support;
+#[cfg(feature = "uuid")]
+mod uuid_support;
 mod values;
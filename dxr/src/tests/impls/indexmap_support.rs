@@ -0,0 +1,37 @@
+use indexmap::IndexMap;
+
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::{Member, Struct, Value};
+use crate::xml::serialize_xml;
+
+#[test]
+fn to_indexmap() {
+    let mut value = IndexMap::new();
+    value.insert("zeta", 1);
+    value.insert("alpha", 2);
+
+    let expected = "<value><struct><member><name>zeta</name><value><i4>1</i4></value></member><member><name>alpha</name><value><i4>2</i4></value></member></struct></value>";
+
+    assert_eq!(serialize_xml(&value.try_to_value().unwrap()).unwrap(), expected);
+}
+
+#[test]
+fn from_indexmap() {
+    let value = Value::structure(Struct::new(vec![
+        Member::new(String::from("zeta"), Value::i4(1)),
+        Member::new(String::from("alpha"), Value::i4(2)),
+    ]));
+
+    let map = IndexMap::<String, i32>::try_from_value(&value).unwrap();
+    let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+
+    assert_eq!(keys, ["zeta", "alpha"]);
+}
+
+#[test]
+fn from_indexmap_fail() {
+    let value = Value::array(crate::values::Array::new(vec![]));
+    assert!(IndexMap::<String, i32>::try_from_value(&value)
+        .unwrap_err()
+        .is_wrong_type());
+}
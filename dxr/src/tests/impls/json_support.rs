@@ -0,0 +1,121 @@
+use serde_json::json;
+
+use crate::traits::{TryFromParams, TryFromValue, TryToParams, TryToValue};
+use crate::values::datetime::DateTime;
+use crate::values::{Array, Member, Struct, Value};
+
+#[test]
+fn to_json() {
+    let datetime = DateTime::new(2023, 1, 2, 3, 4, 5, 600_000_000).unwrap();
+
+    let value = Value::structure(Struct::new(vec![
+        Member::new(String::from("int"), Value::i4(-12)),
+        Member::new(String::from("flag"), Value::boolean(true)),
+        Member::new(String::from("text"), Value::string(String::from("hello"))),
+        Member::new(String::from("double"), Value::double(1.5)),
+        Member::new(String::from("date"), Value::from_datetime(datetime)),
+        Member::new(String::from("bytes"), Value::base64(b"dxr".to_vec())),
+        Member::new(
+            String::from("list"),
+            Value::array(Array::new(vec![Value::i4(1), Value::i4(2)])),
+        ),
+    ]));
+
+    let expected = json!({
+        "int": -12,
+        "flag": true,
+        "text": "hello",
+        "double": 1.5,
+        "date": "2023-01-02T03:04:05.600Z",
+        "bytes": "ZHhy",
+        "list": [1, 2],
+    });
+
+    assert_eq!(serde_json::Value::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn to_json_non_finite() {
+    let value = Value::double(f64::NAN);
+    assert_eq!(serde_json::Value::try_from_value(&value).unwrap(), json!("NaN"));
+
+    let value = Value::double(f64::NEG_INFINITY);
+    assert_eq!(serde_json::Value::try_from_value(&value).unwrap(), json!("-inf"));
+}
+
+#[test]
+fn from_json() {
+    let value = json!({
+        "int": 42,
+        "double": 0.25,
+        "text": "hello",
+        "nested": { "flag": false },
+        "list": ["a", 1],
+    });
+
+    let expected = Value::structure(Struct::new(vec![
+        Member::new(String::from("int"), Value::i4(42)),
+        Member::new(String::from("double"), Value::double(0.25)),
+        Member::new(String::from("text"), Value::string(String::from("hello"))),
+        Member::new(
+            String::from("nested"),
+            Value::structure(Struct::new(vec![Member::new(
+                String::from("flag"),
+                Value::boolean(false),
+            )])),
+        ),
+        Member::new(
+            String::from("list"),
+            Value::array(Array::new(vec![Value::string(String::from("a")), Value::i4(1)])),
+        ),
+    ]));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[cfg(feature = "i8")]
+#[test]
+fn from_json_long() {
+    let value = json!(i64::MAX);
+    assert_eq!(value.try_to_value().unwrap(), Value::i8(i64::MAX));
+}
+
+#[cfg(not(feature = "i8"))]
+#[test]
+fn from_json_long_fail() {
+    let value = json!(i64::MAX);
+    assert!(value.try_to_value().is_err());
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn json_null() {
+    assert_eq!(json!(null).try_to_value().unwrap(), Value::nil());
+    assert_eq!(serde_json::Value::try_from_value(&Value::nil()).unwrap(), json!(null));
+}
+
+#[cfg(not(feature = "nil"))]
+#[test]
+fn from_json_null_fail() {
+    assert!(json!(null).try_to_value().unwrap_err().is_invalid_data());
+}
+
+#[test]
+fn json_round_trip() {
+    let value = json!({ "name": "dxr", "versions": [1, 2.5], "stable": true });
+    let xmlrpc = value.try_to_value().unwrap();
+
+    assert_eq!(serde_json::Value::try_from_value(&xmlrpc).unwrap(), value);
+}
+
+#[test]
+fn json_params() {
+    let value = json!(["hello", 42]);
+    let params = value.try_to_params().unwrap();
+
+    assert_eq!(params, vec![Value::string(String::from("hello")), Value::i4(42)]);
+    assert_eq!(serde_json::Value::try_from_params(&params).unwrap(), value);
+
+    let value = json!({ "id": 1 });
+    assert_eq!(value.try_to_params().unwrap().len(), 1);
+}
@@ -1,6 +1,7 @@
+#[cfg(feature = "chrono")]
 use chrono::{NaiveDateTime, SubsecRound, Utc};
 
-use crate::{TryFromParams, TryToParams, Value};
+use crate::{TryFromParams, TryToParams, Value, Variadic};
 
 #[test]
 fn to_value() {
@@ -42,6 +43,23 @@ fn from_i4() {
     assert_eq!(value.try_to_params().unwrap(), expected);
 }
 
+#[test]
+fn to_u32() {
+    let value = vec![Value::i4(12)];
+    let expected = 12u32;
+
+    assert_eq!(u32::try_from_params(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_u64() {
+    let value = 12u64;
+    let expected = vec![Value::i4(12)];
+
+    assert_eq!(value.try_to_params().unwrap(), expected);
+    assert!(u64::MAX.try_to_params().is_err());
+}
+
 #[cfg(feature = "i8")]
 #[test]
 fn to_i8() {
@@ -116,6 +134,7 @@ fn from_double() {
     assert_eq!(value.try_to_params().unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn to_datetime() {
     let expected = Utc::now().round_subsecs(0).naive_utc();
@@ -124,6 +143,7 @@ fn to_datetime() {
     assert_eq!(NaiveDateTime::try_from_params(&value).unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn from_from_datetimedouble() {
     let value = Utc::now().round_subsecs(0).naive_utc();
@@ -200,7 +220,7 @@ fn to_nil_fail() {
         .is_parameter_mismatch());
 }
 
-#[cfg(feature = "nil")]
+#[cfg(all(feature = "nil", feature = "chrono"))]
 #[test]
 fn from_nil_none() {
     let value: Option<NaiveDateTime> = None;
@@ -209,7 +229,7 @@ fn from_nil_none() {
     assert_eq!(value.try_to_params().unwrap(), expected);
 }
 
-#[cfg(feature = "nil")]
+#[cfg(all(feature = "nil", feature = "chrono"))]
 #[test]
 fn from_nil_none_ref() {
     let value: Option<NaiveDateTime> = None;
@@ -218,7 +238,7 @@ fn from_nil_none_ref() {
     assert_eq!(<&Option<NaiveDateTime>>::try_to_params(&&value).unwrap(), expected);
 }
 
-#[cfg(feature = "nil")]
+#[cfg(all(feature = "nil", feature = "chrono"))]
 #[test]
 fn from_nil_some() {
     let dt = Utc::now().round_subsecs(0).naive_utc();
@@ -228,7 +248,7 @@ fn from_nil_some() {
     assert_eq!(value.try_to_params().unwrap(), expected);
 }
 
-#[cfg(feature = "nil")]
+#[cfg(all(feature = "nil", feature = "chrono"))]
 #[test]
 fn from_nil_some_ref() {
     let dt = Utc::now().round_subsecs(0).naive_utc();
@@ -535,3 +555,119 @@ fn from_tuple_8() {
 
     assert_eq!(value.try_to_params().unwrap(), expected);
 }
+
+#[test]
+fn to_tuple_16() {
+    let value: Vec<Value> = (0..16).map(Value::i4).collect();
+
+    // tuples with more than twelve members implement neither Debug nor PartialEq
+    let (a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p) = <(
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+    )>::try_from_params(&value)
+    .unwrap();
+
+    assert_eq!(
+        vec![a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p],
+        (0..16).collect::<Vec<i32>>()
+    );
+}
+
+#[test]
+fn from_tuple_16() {
+    let value = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    let expected: Vec<Value> = (0..16).map(Value::i4).collect();
+
+    assert_eq!(value.try_to_params().unwrap(), expected);
+}
+
+#[test]
+fn to_tuple_16_fail() {
+    let value: Vec<Value> = (0..15).map(Value::i4).collect();
+
+    assert!(<(
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32
+    )>::try_from_params(&value)
+    .err()
+    .unwrap()
+    .is_parameter_mismatch());
+}
+
+#[test]
+fn to_variadic() {
+    let value = vec![
+        Value::string(String::from("")),
+        Value::string(String::from("main")),
+        Value::string(String::from("d.hash=")),
+        Value::string(String::from("d.name=")),
+    ];
+    let expected = (
+        String::from(""),
+        String::from("main"),
+        Variadic(vec![String::from("d.hash="), String::from("d.name=")]),
+    );
+
+    assert_eq!(
+        <(String, String, Variadic<String>)>::try_from_params(&value).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn to_variadic_empty() {
+    let value = vec![Value::i4(1)];
+    let expected = (1, Variadic(vec![]));
+
+    assert_eq!(<(i32, Variadic<Value>)>::try_from_params(&value).unwrap(), expected);
+}
+
+#[test]
+fn to_variadic_fail() {
+    let value = vec![Value::i4(1)];
+
+    assert!(<(i32, i32, Variadic<Value>)>::try_from_params(&value)
+        .unwrap_err()
+        .is_parameter_mismatch());
+}
+
+#[test]
+fn from_variadic() {
+    let value = (String::from("main"), Variadic(vec![1, 2, 3]));
+    let expected = vec![
+        Value::string(String::from("main")),
+        Value::i4(1),
+        Value::i4(2),
+        Value::i4(3),
+    ];
+
+    assert_eq!(value.try_to_params().unwrap(), expected);
+}
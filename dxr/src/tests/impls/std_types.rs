@@ -0,0 +1,133 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::Value;
+
+#[test]
+fn to_path() {
+    let value = PathBuf::from("/tmp/file.txt");
+    let expected = Value::string(String::from("/tmp/file.txt"));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+    assert_eq!(value.as_path().try_to_value().unwrap(), expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn to_path_fail() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let value = Path::new(OsStr::from_bytes(b"/tmp/\xff"));
+    assert!(value.try_to_value().unwrap_err().is_invalid_data());
+}
+
+#[test]
+fn from_path() {
+    let value = Value::string(String::from("/tmp/file.txt"));
+    let expected = PathBuf::from("/tmp/file.txt");
+
+    assert_eq!(PathBuf::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_path_fail() {
+    let value = Value::i4(42);
+    assert!(PathBuf::try_from_value(&value).unwrap_err().is_wrong_type());
+}
+
+#[test]
+fn to_ip_addr() {
+    let value = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    let expected = Value::string(String::from("192.168.0.1"));
+    assert_eq!(value.try_to_value().unwrap(), expected);
+
+    let value = Ipv6Addr::LOCALHOST;
+    let expected = Value::string(String::from("::1"));
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[test]
+fn from_ip_addr() {
+    let value = Value::string(String::from("192.168.0.1"));
+    let expected = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+    assert_eq!(IpAddr::try_from_value(&value).unwrap(), expected);
+
+    let value = Value::string(String::from("::1"));
+    assert_eq!(Ipv6Addr::try_from_value(&value).unwrap(), Ipv6Addr::LOCALHOST);
+}
+
+#[test]
+fn from_ip_addr_fail() {
+    let value = Value::string(String::from("192.168.0.256"));
+    assert!(IpAddr::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::string(String::from("::1"));
+    assert!(Ipv4Addr::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::i4(42);
+    assert!(IpAddr::try_from_value(&value).unwrap_err().is_wrong_type());
+}
+
+#[test]
+fn to_socket_addr() {
+    let value = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8080);
+    let expected = Value::string(String::from("[::1]:8080"));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[test]
+fn from_socket_addr() {
+    let value = Value::string(String::from("127.0.0.1:8080"));
+    let expected = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080);
+
+    assert_eq!(SocketAddr::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_socket_addr_fail() {
+    let value = Value::string(String::from("127.0.0.1"));
+    assert!(SocketAddr::try_from_value(&value).unwrap_err().is_invalid_data());
+}
+
+#[test]
+fn to_duration() {
+    let value = Duration::from_secs(42);
+    assert_eq!(value.try_to_value().unwrap(), Value::i4(42));
+
+    let value = Duration::from_millis(1500);
+    assert_eq!(value.try_to_value().unwrap(), Value::double(1.5));
+
+    let value = Duration::from_secs(u64::from(u32::MAX));
+    assert_eq!(value.try_to_value().unwrap(), Value::double(f64::from(u32::MAX)));
+}
+
+#[test]
+fn from_duration() {
+    let value = Value::i4(42);
+    assert_eq!(Duration::try_from_value(&value).unwrap(), Duration::from_secs(42));
+
+    let value = Value::double(1.5);
+    assert_eq!(Duration::try_from_value(&value).unwrap(), Duration::from_millis(1500));
+}
+
+#[test]
+fn from_duration_fail() {
+    let value = Value::i4(-1);
+    assert!(Duration::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::double(-0.5);
+    assert!(Duration::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::double(f64::INFINITY);
+    assert!(Duration::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::double(1e20);
+    assert!(Duration::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::string(String::from("1s"));
+    assert!(Duration::try_from_value(&value).unwrap_err().is_wrong_type());
+}
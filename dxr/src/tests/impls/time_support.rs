@@ -0,0 +1,67 @@
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::datetime::DateTime;
+use crate::values::Value;
+use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string};
+
+fn primitive() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_calendar_date(2023, Month::April, 1).unwrap(),
+        Time::from_hms_nano(12, 30, 15, 250_000_000).unwrap(),
+    )
+}
+
+fn expected() -> Value {
+    Value::from_datetime(DateTime::new(2023, 4, 1, 12, 30, 15, 250_000_000).unwrap())
+}
+
+#[test]
+fn to_primitive_datetime() {
+    assert_eq!(primitive().try_to_value().unwrap(), expected());
+    assert_eq!(Value::primitive_datetime(primitive()), expected());
+}
+
+#[test]
+fn from_primitive_datetime() {
+    assert_eq!(PrimitiveDateTime::try_from_value(&expected()).unwrap(), primitive());
+    assert_eq!(expected().as_primitive_datetime(), Some(primitive()));
+}
+
+#[test]
+fn from_primitive_datetime_fail() {
+    let value = Value::i4(42);
+    assert!(PrimitiveDateTime::try_from_value(&value).is_err());
+    assert_eq!(value.as_primitive_datetime(), None);
+
+    // out of range for the time crate
+    let value = Value::from_datetime(DateTime::new(100_000, 1, 1, 0, 0, 0, 0).unwrap());
+    assert!(PrimitiveDateTime::try_from_value(&value).is_err());
+}
+
+#[test]
+fn to_offset_datetime() {
+    let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+    let datetime = primitive().assume_utc().to_offset(offset);
+
+    assert_eq!(datetime.try_to_value().unwrap(), expected());
+    assert_eq!(Value::offset_datetime(datetime), expected());
+}
+
+#[test]
+fn from_offset_datetime() {
+    let expected_datetime = primitive().assume_utc();
+
+    assert_eq!(OffsetDateTime::try_from_value(&expected()).unwrap(), expected_datetime);
+    assert_eq!(expected().as_offset_datetime(), Some(expected_datetime));
+}
+
+#[test]
+fn primitive_datetime_xml() {
+    let xml = "<value><dateTime.iso8601>20230401T12:30:15</dateTime.iso8601></value>";
+    let datetime = primitive().replace_nanosecond(0).unwrap();
+
+    let value: Value = from_str(xml).unwrap();
+    assert_eq!(PrimitiveDateTime::try_from_value(&value).unwrap(), datetime);
+    assert_eq!(to_string(&datetime.try_to_value().unwrap()).unwrap(), xml);
+}
@@ -0,0 +1,29 @@
+use url::Url;
+
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::Value;
+
+#[test]
+fn to_url() {
+    let value = Url::parse("https://example.com/RPC2?key=value").unwrap();
+    let expected = Value::string(String::from("https://example.com/RPC2?key=value"));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[test]
+fn from_url() {
+    let value = Value::string(String::from("https://example.com/RPC2"));
+    let expected = Url::parse("https://example.com/RPC2").unwrap();
+
+    assert_eq!(Url::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_url_fail() {
+    let value = Value::string(String::from("example.com"));
+    assert!(Url::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::i4(42);
+    assert!(Url::try_from_value(&value).unwrap_err().is_wrong_type());
+}
@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::traits::{TryFromValue, TryToValue};
+use crate::values::Value;
+
+const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[test]
+fn to_uuid() {
+    let value = Uuid::parse_str(UUID).unwrap();
+    let expected = Value::string(String::from(UUID));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[test]
+fn from_uuid() {
+    let expected = Uuid::parse_str(UUID).unwrap();
+
+    let value = Value::string(String::from(UUID));
+    assert_eq!(Uuid::try_from_value(&value).unwrap(), expected);
+
+    let value = Value::string(String::from("67e5504410b1426f9247bb680e5fe0c8"));
+    assert_eq!(Uuid::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_uuid_fail() {
+    let value = Value::string(String::from("67e55044-10b1-426f"));
+    assert!(Uuid::try_from_value(&value).unwrap_err().is_invalid_data());
+
+    let value = Value::i4(42);
+    assert!(Uuid::try_from_value(&value).unwrap_err().is_wrong_type());
+}
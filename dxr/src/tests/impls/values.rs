@@ -2,10 +2,12 @@ use std::borrow::Cow;
 use std::rc::Rc;
 use std::sync::Arc;
 
+#[cfg(feature = "chrono")]
 use chrono::{NaiveDateTime, Utc};
 
 use crate::traits::{TryFromValue, TryToValue};
 use crate::values::Value;
+use crate::StringFallback;
 
 #[test]
 fn to_i32() {
@@ -54,6 +56,125 @@ fn from_i64_fail() {
     assert!(i64::try_from_value(&value).unwrap_err().is_wrong_type());
 }
 
+#[test]
+fn from_i64_widen() {
+    let value = Value::i4(-42);
+    let expected = -42i64;
+
+    assert_eq!(i64::try_from_value(&value).unwrap(), expected);
+}
+
+#[cfg(not(feature = "i8"))]
+#[test]
+fn to_i64_checked() {
+    assert_eq!(42i64.try_to_value().unwrap(), Value::i4(42));
+    assert!(i64::MAX.try_to_value().is_err());
+}
+
+#[test]
+fn to_u32() {
+    assert_eq!(42u32.try_to_value().unwrap(), Value::i4(42));
+
+    #[cfg(feature = "i8")]
+    assert_eq!(u32::MAX.try_to_value().unwrap(), Value::i8(i64::from(u32::MAX)));
+    #[cfg(not(feature = "i8"))]
+    assert!(u32::MAX.try_to_value().is_err());
+}
+
+#[test]
+fn from_u32() {
+    assert_eq!(u32::try_from_value(&Value::i4(42)).unwrap(), 42);
+    #[cfg(feature = "i8")]
+    assert_eq!(u32::try_from_value(&Value::i8(i64::from(u32::MAX))).unwrap(), u32::MAX);
+}
+
+#[test]
+fn from_u32_fail() {
+    assert!(u32::try_from_value(&Value::i4(-1)).is_err());
+    #[cfg(feature = "i8")]
+    assert!(u32::try_from_value(&Value::i8(i64::from(u32::MAX) + 1)).is_err());
+    assert!(u32::try_from_value(&Value::boolean(true)).unwrap_err().is_wrong_type());
+}
+
+#[cfg(feature = "unsigned")]
+#[test]
+fn to_u16() {
+    assert_eq!(u16::MAX.try_to_value().unwrap(), Value::i4(i32::from(u16::MAX)));
+}
+
+#[cfg(feature = "unsigned")]
+#[test]
+fn from_u16() {
+    assert_eq!(u16::try_from_value(&Value::i4(42)).unwrap(), 42);
+    assert!(u16::try_from_value(&Value::i4(-1)).is_err());
+    assert!(u16::try_from_value(&Value::i4(i32::from(u16::MAX) + 1)).is_err());
+}
+
+#[cfg(feature = "unsigned")]
+#[test]
+fn bytes_from_array() {
+    let value = Value::array(crate::Array::new(vec![Value::i4(0), Value::i4(255)]));
+    assert_eq!(Vec::<u8>::try_from_value(&value).unwrap(), vec![0, 255]);
+
+    // byte vectors are always encoded as base64
+    assert_eq!(vec![0u8, 255].try_to_value().unwrap(), Value::base64(vec![0, 255]));
+}
+
+#[cfg(feature = "unsigned")]
+#[test]
+fn bytes_from_array_fail() {
+    let value = Value::array(crate::Array::new(vec![Value::i4(0), Value::i4(256)]));
+    assert!(Vec::<u8>::try_from_value(&value).is_err());
+
+    let value = Value::array(crate::Array::new(vec![Value::i4(-1)]));
+    assert!(Vec::<u8>::try_from_value(&value).is_err());
+
+    let value = Value::array(crate::Array::new(vec![Value::string(String::from("1"))]));
+    assert!(Vec::<u8>::try_from_value(&value).unwrap_err().is_wrong_type());
+}
+
+#[test]
+fn to_u64() {
+    assert_eq!(42u64.try_to_value().unwrap(), Value::i4(42));
+    assert!(u64::MAX.try_to_value().is_err());
+}
+
+#[test]
+fn from_u64() {
+    assert_eq!(u64::try_from_value(&Value::i4(42)).unwrap(), 42);
+    assert!(u64::try_from_value(&Value::i4(-42)).is_err());
+}
+
+#[test]
+fn to_usize() {
+    assert_eq!(42usize.try_to_value().unwrap(), Value::i4(42));
+    assert_eq!(usize::try_from_value(&Value::i4(42)).unwrap(), 42);
+}
+
+#[test]
+fn string_fallback() {
+    let value = StringFallback(u64::MAX).try_to_value().unwrap();
+    assert_eq!(value, Value::string(u64::MAX.to_string()));
+    assert_eq!(
+        StringFallback::<u64>::try_from_value(&value).unwrap(),
+        StringFallback(u64::MAX)
+    );
+
+    let value = StringFallback(42u64).try_to_value().unwrap();
+    assert_eq!(value, Value::i4(42));
+    assert_eq!(
+        StringFallback::<u64>::try_from_value(&value).unwrap(),
+        StringFallback(42)
+    );
+}
+
+#[test]
+fn string_fallback_fail() {
+    assert!(StringFallback::<u64>::try_from_value(&Value::string(String::from("-1"))).is_err());
+    assert!(StringFallback::<u64>::try_from_value(&Value::string(String::from("foo"))).is_err());
+    assert!(StringFallback::<u32>::try_from_value(&Value::i4(-1)).is_err());
+}
+
 #[test]
 fn to_boolean() {
     let value = true;
@@ -120,6 +241,7 @@ fn from_double_fail() {
     assert!(f64::try_from_value(&value).unwrap_err().is_wrong_type());
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn to_datetime() {
     let now = Utc::now().naive_utc();
@@ -130,6 +252,7 @@ fn to_datetime() {
     assert_eq!(value.try_to_value().unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn from_datetime() {
     let now = Utc::now().naive_utc();
@@ -140,6 +263,7 @@ fn from_datetime() {
     assert_eq!(NaiveDateTime::try_from_value(&value).unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn from_datetime_fail() {
     let value = Value::boolean(false);
@@ -242,6 +366,7 @@ fn to_cow_borrowed() {
     assert_eq!(value.try_to_value().unwrap(), expected);
 }
 
+#[allow(clippy::owned_cow)]
 #[test]
 fn to_cow_owned_bytes() {
     let value: Cow<'_, Vec<u8>> = Cow::Owned(b"123".to_vec());
@@ -385,6 +510,212 @@ fn from_hashmap_fail() {
     assert!(TestMap::try_from_value(&value).unwrap_err().is_wrong_type());
 }
 
+#[cfg(feature = "derive")]
+#[test]
+fn to_struct_member_order() {
+    use crate::TryToValue;
+
+    #[derive(Debug, PartialEq, TryToValue)]
+    struct Ordered {
+        zulu: i32,
+        alpha: i32,
+        mike: String,
+        bravo: bool,
+    }
+
+    let value = Ordered {
+        zulu: 1,
+        alpha: 2,
+        mike: String::from("three"),
+        bravo: true,
+    };
+
+    let expected = "<value><struct>\
+        <member><name>zulu</name><value><i4>1</i4></value></member>\
+        <member><name>alpha</name><value><i4>2</i4></value></member>\
+        <member><name>mike</name><value><string>three</string></value></member>\
+        <member><name>bravo</name><value><boolean>1</boolean></value></member>\
+        </struct></value>";
+
+    assert_eq!(crate::serialize_xml(&value.try_to_value().unwrap()).unwrap(), expected);
+}
+
+#[test]
+fn to_btreemap() {
+    use std::collections::BTreeMap;
+
+    let value: BTreeMap<u32, &str> = BTreeMap::from([(10, "ten"), (2, "two")]);
+    let converted = value.try_to_value().unwrap();
+
+    let names: Vec<&str> = converted.as_struct().unwrap().iter().map(|m| m.name()).collect();
+    assert_eq!(names, ["2", "10"]);
+}
+
+#[test]
+fn from_btreemap() {
+    use crate::values::{Member, Struct};
+    use std::collections::BTreeMap;
+
+    let value = Value::structure(Struct::new(vec![
+        Member::new(String::from("b"), Value::i4(2)),
+        Member::new(String::from("a"), Value::i4(1)),
+    ]));
+    let expected: BTreeMap<String, i32> = BTreeMap::from([(String::from("a"), 1), (String::from("b"), 2)]);
+
+    assert_eq!(BTreeMap::<String, i32>::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_btreemap_fail() {
+    use std::collections::BTreeMap;
+
+    let value = Value::i4(42);
+    assert!(BTreeMap::<String, i32>::try_from_value(&value)
+        .unwrap_err()
+        .is_wrong_type());
+}
+
+#[test]
+fn to_hashmap_integer_keys() {
+    use crate::values::{Member, Struct};
+    use std::collections::HashMap;
+
+    let value: HashMap<u32, bool> = HashMap::from([(1, true)]);
+    let expected = Value::structure(Struct::new(vec![Member::new(String::from("1"), Value::boolean(true))]));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[test]
+fn from_hashmap_integer_keys() {
+    use crate::values::{Member, Struct};
+    use std::collections::HashMap;
+
+    let value = Value::structure(Struct::new(vec![
+        Member::new(String::from("1"), Value::boolean(true)),
+        Member::new(String::from("-2"), Value::boolean(false)),
+    ]));
+    let expected: HashMap<i32, bool> = HashMap::from([(1, true), (-2, false)]);
+
+    assert_eq!(HashMap::<i32, bool>::try_from_value(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_hashmap_integer_keys_fail() {
+    use crate::values::{Member, Struct};
+    use std::collections::HashMap;
+
+    let value = Value::structure(Struct::new(vec![Member::new(
+        String::from("one"),
+        Value::boolean(true),
+    )]));
+    assert!(HashMap::<i32, bool>::try_from_value(&value)
+        .unwrap_err()
+        .is_invalid_data());
+}
+
+#[test]
+fn hashmap_enum_keys() {
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    enum View {
+        Main,
+        Seeding,
+    }
+
+    impl fmt::Display for View {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                View::Main => "main",
+                View::Seeding => "seeding",
+            })
+        }
+    }
+
+    impl FromStr for View {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "main" => Ok(View::Main),
+                "seeding" => Ok(View::Seeding),
+                _ => Err(()),
+            }
+        }
+    }
+
+    let value: HashMap<View, i32> = HashMap::from([(View::Main, 3), (View::Seeding, 1)]);
+    let converted = value.try_to_value().unwrap();
+
+    assert_eq!(converted.get("main").unwrap(), &Value::i4(3));
+    assert_eq!(HashMap::<View, i32>::try_from_value(&converted).unwrap(), value);
+    assert_eq!(HashMap::<String, i32>::try_from_value(&converted).unwrap().len(), 2);
+
+    let value: HashMap<&str, i32> = HashMap::from([("leeching", 0)]);
+    assert!(HashMap::<View, i32>::try_from_value(&value.try_to_value().unwrap())
+        .unwrap_err()
+        .is_invalid_data());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn to_transparent() {
+    use crate::TryToValue;
+
+    #[derive(Debug, PartialEq, TryToValue)]
+    #[dxr(transparent)]
+    struct TorrentHash(String);
+
+    let value = TorrentHash(String::from("CAFEBABE"));
+    let expected = Value::string(String::from("CAFEBABE"));
+
+    assert_eq!(value.try_to_value().unwrap(), expected);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn from_transparent() {
+    use crate::TryFromValue;
+
+    #[derive(Debug, PartialEq, TryFromValue)]
+    #[dxr(transparent)]
+    struct TorrentHash(String);
+
+    let value = Value::string(String::from("CAFEBABE"));
+    let expected = TorrentHash(String::from("CAFEBABE"));
+
+    assert_eq!(TorrentHash::try_from_value(&value).unwrap(), expected);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn from_transparent_fail() {
+    use crate::TryFromValue;
+
+    #[derive(Debug, PartialEq, TryFromValue)]
+    #[dxr(transparent)]
+    struct TorrentHash(String);
+
+    let value = Value::i4(42);
+
+    assert!(TorrentHash::try_from_value(&value).unwrap_err().is_wrong_type());
+}
+
+#[test]
+fn to_unit() {
+    #[cfg(feature = "nil")]
+    let expected = Value::nil();
+    #[cfg(not(feature = "nil"))]
+    let expected = Vec::<Value>::new().try_to_value().unwrap();
+
+    let value = ().try_to_value().unwrap();
+    assert_eq!(value, expected);
+    <()>::try_from_value(&value).unwrap();
+}
+
 #[test]
 fn from_unit() {
     let value = Vec::<Value>::new().try_to_value().unwrap();
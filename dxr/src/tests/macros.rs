@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::traits::TryToValue;
+use crate::values::Value;
+use crate::{params, value};
+
+#[test]
+fn value_scalar() {
+    let name = "foo";
+
+    assert_eq!(value!(42), Value::i4(42));
+    assert_eq!(value!(-1.5), Value::double(-1.5));
+    assert_eq!(value!(name), Value::string(String::from("foo")));
+    assert_eq!(value!(1 + 2), Value::i4(3));
+}
+
+#[test]
+fn value_array() {
+    let expected = vec![Value::i4(1), Value::boolean(true), Value::string(String::from("three"))]
+        .try_to_value()
+        .unwrap();
+
+    assert_eq!(value!([1, true, "three"]), expected);
+    assert_eq!(value!([1, true, "three",]), expected);
+    assert_eq!(value!([]), Vec::<Value>::new().try_to_value().unwrap());
+}
+
+#[test]
+fn value_struct() {
+    let expected = HashMap::from([
+        ("name", Value::string(String::from("x"))),
+        ("sizes", vec![1, 2, 3].try_to_value().unwrap()),
+        (
+            "nested",
+            HashMap::from([("empty", Vec::<i32>::new())]).try_to_value().unwrap(),
+        ),
+    ])
+    .try_to_value()
+    .unwrap();
+
+    assert_eq!(
+        value!({"name": "x", "sizes": [1, 2, 3], "nested": {"empty": []}}),
+        expected
+    );
+    assert_eq!(value!({}), HashMap::<String, i32>::new().try_to_value().unwrap());
+}
+
+#[test]
+fn value_nested() {
+    let value = value!([{"peers": [{"address": "127.0.0.1", "port": 6881}]}, [[1], [2]]]);
+
+    assert_eq!(value.pointer("0/peers/0/port"), Some(&Value::i4(6881)));
+    assert_eq!(value.pointer("1/1/0"), Some(&Value::i4(2)));
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn value_null() {
+    assert_eq!(value!(null), Value::nil());
+    assert_eq!(value!([null, 1]).get_index(0), Some(&Value::nil()));
+    assert_eq!(value!({"missing": null}).get("missing"), Some(&Value::nil()));
+}
+
+#[test]
+fn params_list() {
+    let expected = vec![
+        Value::string(String::new()),
+        Value::string(String::from("main")),
+        vec!["d.hash=", "d.name="].try_to_value().unwrap(),
+    ];
+
+    assert_eq!(params!["", "main", ["d.hash=", "d.name="]], expected);
+    assert_eq!(params![], Vec::<Value>::new());
+}
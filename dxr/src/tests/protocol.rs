@@ -0,0 +1,44 @@
+use crate::fault::Fault;
+use crate::protocol::*;
+use crate::values::Value;
+use crate::xml::{DeserializeOptions, NonFinitePolicy, SerializeOptions};
+
+#[test]
+fn method_calls() {
+    let call = parse_method_call("<methodCall><methodName>hello</methodName></methodCall>").unwrap();
+    assert_eq!(call.name(), "hello");
+    assert!(call.params().is_empty());
+
+    let error = parse_method_call("<methodCall><methodName>hello</params></methodCall>").unwrap_err();
+    assert!(error.is_invalid_data());
+    assert!(error.context().unwrap().offset().is_some());
+
+    let options = DeserializeOptions::default().max_depth(2);
+    let xml = "<methodCall><methodName>nested</methodName><params><param><value><array><data>\
+<value><array><data><value><array><data/></array></value></data></array></value>\
+</data></array></value></param></params></methodCall>";
+    assert!(parse_method_call(xml).is_ok());
+    assert!(parse_method_call_with(xml, options).is_err());
+}
+
+#[test]
+fn responses() {
+    assert_eq!(
+        serialize_response(Value::boolean(true)).unwrap(),
+        "<methodResponse><params><param><value><boolean>1</boolean></value></param></params></methodResponse>"
+    );
+
+    let options = SerializeOptions::default().non_finite_doubles(NonFinitePolicy::AsString);
+    assert!(serialize_response(Value::double(f64::NAN)).is_err());
+    assert!(serialize_response_with(Value::double(f64::NAN), options)
+        .unwrap()
+        .contains("<string>NaN</string>"));
+}
+
+#[test]
+fn faults() {
+    let xml = serialize_fault(Fault::method_not_found("hello")).unwrap();
+    assert!(xml.starts_with("<methodResponse><fault><value><struct>"));
+    assert!(xml.contains("<i4>-32601</i4>"));
+    assert!(serialize_fault_with(Fault::method_not_found("hello"), SerializeOptions::default()).is_ok());
+}
@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::DxrError;
+use crate::fault::Fault;
+use crate::protocol::{serialize_fault, serialize_response};
+use crate::serde_bridge::{from_response_xml, from_response_xml_with, from_value, to_value};
+use crate::traits::TryToValue;
+use crate::values::{CustomScalar, CustomScalarType, Value};
+use crate::xml::{DeserializeOptions, DuplicateMembers, MultipleParams};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Peer {
+    address: String,
+    port: u16,
+    seeder: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Config {
+    name: String,
+    ratio: f64,
+    peers: Vec<Peer>,
+    data: serde_bytes_like::Bytes,
+}
+
+// minimal stand-in for serde_bytes to exercise (de)serialization of byte arrays
+mod serde_bytes_like {
+    use serde::de::{Deserializer, Error, Visitor};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    #[derive(Debug, PartialEq)]
+    pub(super) struct Bytes(pub(super) Vec<u8>);
+
+    impl Serialize for Bytes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Bytes {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct BytesVisitor;
+
+            impl<'de> Visitor<'de> for BytesVisitor {
+                type Value = Bytes;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    formatter.write_str("bytes")
+                }
+
+                fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Bytes, E> {
+                    Ok(Bytes(v.to_vec()))
+                }
+            }
+
+            deserializer.deserialize_bytes(BytesVisitor)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Priority {
+    Off,
+    Normal,
+    High(i32),
+    Custom { level: i32 },
+}
+
+fn config() -> Config {
+    Config {
+        name: String::from("ubuntu.iso"),
+        ratio: 1.5,
+        peers: vec![Peer {
+            address: String::from("127.0.0.1"),
+            port: 6881,
+            seeder: true,
+        }],
+        data: serde_bytes_like::Bytes(b"hello".to_vec()),
+    }
+}
+
+#[test]
+fn to_struct() {
+    let value = config();
+
+    let mut peer = HashMap::new();
+    peer.insert("address", Value::string(String::from("127.0.0.1")));
+    peer.insert("port", Value::i4(6881));
+    peer.insert("seeder", Value::boolean(true));
+
+    let mut expected = HashMap::new();
+    expected.insert("name", Value::string(String::from("ubuntu.iso")));
+    expected.insert("ratio", Value::double(1.5));
+    expected.insert("peers", vec![peer.try_to_value().unwrap()].try_to_value().unwrap());
+    expected.insert("data", Value::base64(b"hello".to_vec()));
+
+    assert_eq!(to_value(&value).unwrap(), expected.try_to_value().unwrap());
+}
+
+#[test]
+fn from_struct() {
+    let value = to_value(&config()).unwrap();
+    let expected = config();
+
+    assert_eq!(from_value::<Config>(&value).unwrap(), expected);
+}
+
+#[test]
+fn from_struct_fail() {
+    let value = Value::i4(42);
+    assert!(from_value::<Config>(&value).is_err());
+}
+
+#[test]
+fn to_map_fail() {
+    let mut value = HashMap::new();
+    value.insert(1, 2);
+
+    assert!(to_value(&value).unwrap_err().is_invalid_data());
+}
+
+#[test]
+fn from_borrowed_str() {
+    let value = Value::string(String::from("borrowed"));
+    let expected = "borrowed";
+
+    assert_eq!(from_value::<&str>(&value).unwrap(), expected);
+}
+
+#[test]
+fn to_from_tuple() {
+    let value = (1, String::from("two"), 3.0);
+
+    let converted = to_value(&value).unwrap();
+    assert_eq!(converted, value.try_to_value().unwrap());
+    assert_eq!(from_value::<(i32, String, f64)>(&converted).unwrap(), value);
+}
+
+#[test]
+fn to_from_enum() {
+    let values = vec![
+        Priority::Off,
+        Priority::Normal,
+        Priority::High(3),
+        Priority::Custom { level: 2 },
+    ];
+
+    for value in values {
+        let converted = to_value(&value).unwrap();
+        assert_eq!(from_value::<Priority>(&converted).unwrap(), value);
+    }
+
+    assert_eq!(
+        to_value(&Priority::Normal).unwrap(),
+        Value::string(String::from("Normal"))
+    );
+}
+
+#[cfg(feature = "i8")]
+#[test]
+fn to_long() {
+    let value = u32::MAX;
+    let expected = Value::i8(i64::from(u32::MAX));
+
+    assert_eq!(to_value(&value).unwrap(), expected);
+}
+
+#[cfg(not(feature = "i8"))]
+#[test]
+fn to_long_fail() {
+    let value = u32::MAX;
+    assert!(to_value(&value).unwrap_err().is_invalid_data());
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn to_from_option() {
+    let value: Option<i32> = None;
+
+    let converted = to_value(&value).unwrap();
+    assert_eq!(converted, Value::nil());
+    assert_eq!(from_value::<Option<i32>>(&converted).unwrap(), value);
+
+    let value: Option<i32> = Some(42);
+
+    let converted = to_value(&value).unwrap();
+    assert_eq!(converted, Value::i4(42));
+    assert_eq!(from_value::<Option<i32>>(&converted).unwrap(), value);
+}
+
+fn response(value: &str) -> String {
+    format!("<methodResponse><params><param><value>{value}</value></param></params></methodResponse>")
+}
+
+#[test]
+fn from_response_struct() {
+    let xml = serialize_response(to_value(&config()).unwrap()).unwrap();
+    let expected = config();
+
+    assert_eq!(from_response_xml::<Config>(&xml).unwrap().unwrap(), expected);
+}
+
+#[test]
+fn from_response_enum() {
+    let values = vec![
+        Priority::Off,
+        Priority::Normal,
+        Priority::High(3),
+        Priority::Custom { level: 2 },
+    ];
+
+    for value in values {
+        let xml = serialize_response(to_value(&value).unwrap()).unwrap();
+        assert_eq!(from_response_xml::<Priority>(&xml).unwrap().unwrap(), value);
+    }
+}
+
+#[test]
+fn from_response_strings() {
+    let xml = response(
+        "<array><data>\
+        <value>untyped</value>\
+        <value></value>\
+        <value><string/></value>\
+        <value><string>a &amp; b</string></value>\
+        <value><string>a<![CDATA[<b>]]>c</string></value>\
+        </data></array>",
+    );
+    let expected = vec!["untyped", "", "", "a & b", "a<b>c"];
+
+    assert_eq!(from_response_xml::<Vec<String>>(&xml).unwrap().unwrap(), expected);
+}
+
+#[test]
+fn from_response_ignored_members() {
+    let xml = response(
+        "<struct>\
+        <member><name>extra</name><value><array><data><value><i4>1</i4></value></data></array></value></member>\
+        <member><name>address</name><value>127.0.0.1</value></member>\
+        <member><name>port</name><value><i4>6881</i4></value></member>\
+        <member><name>seeder</name><value><boolean>0</boolean></value></member>\
+        </struct>",
+    );
+    let expected = Peer {
+        address: String::from("127.0.0.1"),
+        port: 6881,
+        seeder: false,
+    };
+
+    assert_eq!(from_response_xml::<Peer>(&xml).unwrap().unwrap(), expected);
+}
+
+#[test]
+fn from_response_duplicate_members() {
+    let xml = response(
+        "<struct>\
+        <member><name>a</name><value><i4>1</i4></value></member>\
+        <member><name>a</name><value><i4>2</i4></value></member>\
+        </struct>",
+    );
+
+    let value: HashMap<String, i32> = from_response_xml(&xml).unwrap().unwrap();
+    assert_eq!(value.get("a"), Some(&2));
+
+    let options = DeserializeOptions::default().duplicate_members(DuplicateMembers::FirstWins);
+    let value: HashMap<String, i32> = from_response_xml_with(&xml, options).unwrap().unwrap();
+    assert_eq!(value.get("a"), Some(&1));
+
+    let options = DeserializeOptions::default().duplicate_members(DuplicateMembers::Error);
+    assert!(from_response_xml_with::<HashMap<String, i32>>(&xml, options)
+        .unwrap_err()
+        .is_invalid_data());
+}
+
+#[test]
+fn from_response_multiple_params() {
+    let xml = "<methodResponse><params>\
+    <param><value><i4>1</i4></value></param>\
+    <param><value><i4>2</i4></value></param>\
+    </params></methodResponse>";
+
+    assert!(from_response_xml::<i32>(xml).unwrap_err().is_invalid_data());
+
+    let options = DeserializeOptions::default().multiple_params(MultipleParams::First);
+    assert_eq!(from_response_xml_with::<i32>(xml, options).unwrap().unwrap(), 1);
+}
+
+#[test]
+fn from_response_missing_params() {
+    let xml = "<methodResponse><params></params></methodResponse>";
+
+    from_response_xml::<()>(xml).unwrap().unwrap();
+    assert!(from_response_xml_with::<()>(xml, DeserializeOptions::strict()).is_err());
+}
+
+#[test]
+fn from_response_fault() {
+    let fault = Fault::new(4, String::from("Too many parameters."));
+    let xml = serialize_fault(fault.clone()).unwrap();
+
+    assert_eq!(from_response_xml::<Config>(&xml).unwrap().unwrap_err(), fault);
+}
+
+#[test]
+fn from_response_fail() {
+    // wrong type
+    let xml = response("<i4>42</i4>");
+    assert!(from_response_xml::<Config>(&xml).is_err());
+
+    // invalid scalar value
+    let xml = response("<i4>forty-two</i4>");
+    assert!(from_response_xml::<i32>(&xml).unwrap_err().is_invalid_data());
+
+    // malformed document
+    let xml = "<methodResponse><params><param><value><i4>42</i4></value></param></params>";
+    assert!(from_response_xml::<i32>(xml).unwrap_err().is_invalid_data());
+
+    // trailing elements
+    let xml = format!("{}<extra/>", response("<i4>42</i4>"));
+    assert!(from_response_xml::<i32>(&xml).unwrap_err().is_invalid_data());
+}
+
+#[test]
+fn from_response_custom() {
+    struct Percent(u8);
+
+    impl CustomScalar for Percent {
+        const TAG: &'static str = "percent";
+
+        fn parse(text: &str) -> Result<Self, DxrError> {
+            let number = text.trim_end_matches('%');
+            number
+                .parse()
+                .map(Percent)
+                .map_err(|_| DxrError::invalid_data(format!("Invalid percentage: {text}")))
+        }
+
+        fn format(&self) -> String {
+            format!("{}%", self.0)
+        }
+    }
+
+    const CUSTOM_SCALARS: &[CustomScalarType] = &[CustomScalarType::of::<Percent>()];
+    let options = DeserializeOptions::default().custom_scalars(CUSTOM_SCALARS);
+
+    // custom values are deserialized from their normalized text contents
+    let xml = response("<percent>42</percent>");
+    assert_eq!(from_response_xml_with::<String>(&xml, options).unwrap().unwrap(), "42%");
+    assert_eq!(from_value::<String>(&Value::custom(&Percent(42))).unwrap(), "42%");
+    assert!(from_response_xml::<String>(&xml).unwrap_err().is_invalid_data());
+
+    let xml = response("<percent>many</percent>");
+    assert!(from_response_xml_with::<String>(&xml, options)
+        .unwrap_err()
+        .is_invalid_data());
+}
+
+#[cfg(not(feature = "nil"))]
+#[test]
+fn option_round_trip() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Optional {
+        name: Option<String>,
+        ports: Option<Vec<u16>>,
+    }
+
+    for value in [
+        Optional {
+            name: None,
+            ports: None,
+        },
+        Optional {
+            name: Some(String::from("seeder")),
+            ports: Some(vec![6881, 6882]),
+        },
+    ] {
+        let converted = to_value(&value).unwrap();
+        assert_eq!(from_value::<Optional>(&converted).unwrap(), value);
+
+        let xml = serialize_response(converted).unwrap();
+        assert_eq!(from_response_xml::<Optional>(&xml).unwrap().unwrap(), value);
+    }
+
+    let xml = response("<array><data><value><array><data/></array></value><value><i4>42</i4></value></data></array>");
+    let expected = vec![None, Some(42)];
+    assert_eq!(from_response_xml::<Vec<Option<i32>>>(&xml).unwrap().unwrap(), expected);
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn from_response_option() {
+    let xml = response("<array><data><value><nil/></value><value><i4>42</i4></value></data></array>");
+    let expected = vec![None, Some(42)];
+
+    assert_eq!(from_response_xml::<Vec<Option<i32>>>(&xml).unwrap().unwrap(), expected);
+}
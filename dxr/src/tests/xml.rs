@@ -1,5 +1,8 @@
 mod arrays;
+mod borrowed;
 mod call;
+mod custom;
+mod options;
 mod response;
 mod structs;
 mod types;
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use crate::values::{MethodResponse, MethodResponseRef, Value, ValueRef};
+use crate::xml::{deserialize_xml as from_str, deserialize_xml_with as from_str_with, DeserializeOptions};
+
+fn is_borrowed_from(value: &ValueRef, xml: &str) -> bool {
+    let range = xml.as_bytes().as_ptr_range();
+    matches!(value.as_cow_str(), Some(Cow::Borrowed(string)) if range.contains(&string.as_ptr()))
+}
+
+#[test]
+fn from_string_borrowed() {
+    let xml = "<value><string>Hello, World!</string></value>";
+    let value: ValueRef = from_str(xml).unwrap();
+
+    assert_eq!(value.as_str(), Some("Hello, World!"));
+    assert!(is_borrowed_from(&value, xml));
+}
+
+#[test]
+fn from_untyped_string_borrowed() {
+    let xml = "<value>Hello, World!</value>";
+    let value: ValueRef = from_str(xml).unwrap();
+
+    assert_eq!(value.as_str(), Some("Hello, World!"));
+    assert!(is_borrowed_from(&value, xml));
+}
+
+#[test]
+fn from_string_with_escape_owned() {
+    let xml = "<value><string>a&amp;b</string></value>";
+    let value: ValueRef = from_str(xml).unwrap();
+
+    assert_eq!(value.as_str(), Some("a&b"));
+    assert!(matches!(value.as_cow_str().unwrap(), Cow::Owned(_)));
+}
+
+#[test]
+fn from_empty_string() {
+    for xml in [
+        "<value></value>",
+        "<value/>",
+        "<value><string></string></value>",
+        "<value><string/></value>",
+    ] {
+        let value: ValueRef = from_str(xml).unwrap();
+        assert_eq!(value.as_str(), Some(""));
+    }
+}
+
+#[test]
+fn from_struct_borrowed() {
+    let xml = "<value><struct><member><name>name</name><value><string>foo</string></value></member><member><name>size</name><value><i4>42</i4></value></member></struct></value>";
+    let value: ValueRef = from_str(xml).unwrap();
+
+    let members = value.as_struct().unwrap();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].name(), "name");
+    assert!(xml.as_bytes().as_ptr_range().contains(&members[0].name().as_ptr()));
+
+    assert!(is_borrowed_from(value.get("name").unwrap(), xml));
+    assert_eq!(value.get("size").and_then(ValueRef::as_i32), Some(42));
+    assert_eq!(value.get("missing"), None);
+}
+
+#[test]
+fn from_array_borrowed() {
+    let xml = "<value><array><data><value>foo</value><value><boolean>1</boolean></value><value><double>1.5</double></value></data></array></value>";
+    let value: ValueRef = from_str(xml).unwrap();
+
+    assert_eq!(value.as_array().unwrap().len(), 3);
+    assert!(is_borrowed_from(value.get_index(0).unwrap(), xml));
+    assert_eq!(value.get_index(1).and_then(ValueRef::as_bool), Some(true));
+    assert_eq!(value.get_index(2).and_then(ValueRef::as_f64), Some(1.5));
+    assert_eq!(value.get_index(3), None);
+}
+
+#[test]
+fn into_owned() {
+    let xml = "<value><struct><member><name>a&amp;b</name><value><array><data><value><i4>1</i4></value><value><dateTime.iso8601>20220101T00:00:00</dateTime.iso8601></value><value><base64>aGVsbG8=</base64></value><value>x</value></data></array></value></member><member><name>empty</name><value><array><data></data></array></value></member><member><name>none</name><value><struct></struct></value></member></struct></value>";
+
+    let borrowed: ValueRef = from_str(xml).unwrap();
+    let owned: Value = from_str(xml).unwrap();
+
+    assert_eq!(borrowed.into_owned(), owned);
+}
+
+#[test]
+fn from_invalid() {
+    assert!(from_str::<ValueRef>("<value><boolean>yes</boolean></value>").is_err());
+    assert!(from_str::<ValueRef>("<value><i4>foo</i4></value>").is_err());
+    assert!(from_str::<ValueRef>("<value><ex:i2>42</ex:i2></value>").is_err());
+}
+
+#[test]
+fn from_with_options() {
+    let xml = "<value><ex:i2>42</ex:i2></value>";
+    let value: ValueRef = from_str_with(xml, DeserializeOptions::lenient()).unwrap();
+    assert_eq!(value.as_str(), Some("42"));
+
+    let xml = "<value><boolean>true</boolean></value>";
+    let value: ValueRef = from_str_with(xml, DeserializeOptions::lenient()).unwrap();
+    assert_eq!(value.as_bool(), Some(true));
+}
+
+#[test]
+fn from_response() {
+    let xml = "<?xml version=\"1.0\"?><methodResponse><params><param><value><string>foo</string></value></param></params></methodResponse>";
+
+    let borrowed: MethodResponseRef = from_str(xml).unwrap();
+    let owned: MethodResponse = from_str(xml).unwrap();
+
+    let value = borrowed.inner();
+    assert!(is_borrowed_from(&value, xml));
+    assert_eq!(value.into_owned(), owned.inner());
+}
@@ -1,5 +1,10 @@
+use std::io;
+
 use crate::values::{MethodCall, Value};
-use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string};
+use crate::xml::{
+    deserialize_xml as from_str, serialize_xml as to_string, serialize_xml_into as to_writer,
+    serialize_xml_into_buf as to_buf, serialize_xml_into_buf_with as to_buf_with, SerializeOptions,
+};
 
 #[test]
 fn to_method_call_no_args() {
@@ -53,3 +58,69 @@ fn from_method_call_two_args() {
 
     assert_eq!(from_str::<MethodCall>(value).unwrap(), expected);
 }
+
+fn call() -> MethodCall {
+    MethodCall::new(
+        String::from("hello"),
+        vec![Value::string(String::from("a&b\r")), Value::i4(42)],
+    )
+}
+
+#[test]
+fn to_method_call_writer() {
+    let expected = "<methodCall><methodName>hello</methodName><params><param><value><string>a&amp;b&#xD;</string></value></param><param><value><i4>42</i4></value></param></params></methodCall>";
+
+    let mut buf: Vec<u8> = Vec::new();
+    to_writer(&call(), &mut buf).unwrap();
+
+    assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    assert_eq!(to_string(&call()).unwrap(), expected);
+}
+
+#[test]
+fn to_method_call_writer_fail() {
+    struct Broken;
+
+    impl io::Write for Broken {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = to_writer(&call(), Broken).unwrap_err();
+    assert!(error.to_string().contains("broken pipe"));
+}
+
+#[test]
+fn to_method_call_buf() {
+    let mut buf = String::from("<?xml version=\"1.0\"?>");
+    to_buf(&call(), &mut buf).unwrap();
+
+    assert_eq!(buf, format!("<?xml version=\"1.0\"?>{}", to_string(&call()).unwrap()));
+    assert_eq!(from_str::<MethodCall>(&buf).unwrap(), call());
+
+    // buffers can be reused
+    buf.clear();
+    to_buf(&call(), &mut buf).unwrap();
+    assert_eq!(buf, to_string(&call()).unwrap());
+}
+
+#[test]
+fn to_method_call_buf_cdata() {
+    let value = MethodCall::new(String::from("hello"), vec![Value::string(String::from("<a&b>"))]);
+    let expected = "<methodCall><methodName>hello</methodName><params><param><value><string><![CDATA[<a&b>]]></string></value></param></params></methodCall>";
+
+    let mut buf = String::new();
+    to_buf_with(&value, &mut buf, SerializeOptions::default().cdata_strings(Some(0))).unwrap();
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn to_method_call_buf_nul() {
+    let value = MethodCall::new(String::from("hello"), vec![Value::string(String::from("a\0b"))]);
+    assert!(to_buf(&value, &mut String::new()).is_err());
+}
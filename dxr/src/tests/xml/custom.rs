@@ -0,0 +1,106 @@
+use crate::error::DxrError;
+use crate::values::{CustomScalar, CustomScalarType, Value, ValueRef};
+use crate::xml::{
+    deserialize_xml as from_str, deserialize_xml_with as from_str_with, serialize_xml as to_str,
+    serialize_xml_with as to_str_with, DeserializeOptions, SerializeOptions,
+};
+
+#[derive(Debug, PartialEq)]
+struct Money {
+    cents: i64,
+}
+
+impl CustomScalar for Money {
+    const TAG: &'static str = "money";
+
+    fn parse(text: &str) -> Result<Self, DxrError> {
+        let invalid = || DxrError::invalid_data(format!("Invalid amount of money: {text}"));
+        let (units, cents) = text.split_once('.').unwrap_or((text, "0"));
+        let units: i64 = units.parse().map_err(|_| invalid())?;
+        let cents: i64 = cents.parse().map_err(|_| invalid())?;
+        Ok(Money {
+            cents: units * 100 + cents,
+        })
+    }
+
+    fn format(&self) -> String {
+        format!("{}.{:02}", self.cents / 100, self.cents % 100)
+    }
+}
+
+const CUSTOM_SCALARS: &[CustomScalarType] = &[CustomScalarType::of::<Money>()];
+
+// this type is never registered
+struct Decimal;
+
+impl CustomScalar for Decimal {
+    const TAG: &'static str = "decimal";
+
+    fn parse(_text: &str) -> Result<Self, DxrError> {
+        Ok(Decimal)
+    }
+
+    fn format(&self) -> String {
+        String::from("0")
+    }
+}
+
+#[test]
+fn to_custom() {
+    let value = Value::custom(&Money { cents: 1250 });
+    let expected = "<value><money>12.50</money></value>";
+
+    assert_eq!(value.type_name(), "money");
+    assert_eq!(value.as_custom(), Some(("money", "12.50")));
+    assert_eq!(to_str(&value).unwrap(), expected);
+    assert_eq!(
+        to_str_with(&value, SerializeOptions::default().cdata_strings(Some(0))).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn from_custom() {
+    let options = DeserializeOptions::default().custom_scalars(CUSTOM_SCALARS);
+
+    let value: Value = from_str_with("<value><money>0012.50</money></value>", options).unwrap();
+    assert_eq!(value.to_custom::<Money>().unwrap(), Money { cents: 1250 });
+    // contents are normalized when parsing
+    assert_eq!(value, Value::custom(&Money { cents: 1250 }));
+    assert_eq!(value.as_custom(), Some(("money", "12.50")));
+
+    let value: ValueRef = from_str_with("<value><money>3</money></value>", options).unwrap();
+    assert_eq!(value.as_custom(), Some(("money", "3.00")));
+    assert_eq!(value.into_owned(), Value::custom(&Money { cents: 300 }));
+
+    // registered types are accepted even with strict options
+    let strict = DeserializeOptions::strict().custom_scalars(CUSTOM_SCALARS);
+    let value: Value = from_str_with("<value><money>1.01</money></value>", strict).unwrap();
+    assert_eq!(value.to_custom::<Money>().unwrap(), Money { cents: 101 });
+
+    assert!(from_str_with::<Value>("<value><money>twelve</money></value>", options).is_err());
+    assert!(from_str_with::<ValueRef>("<value><money>twelve</money></value>", options).is_err());
+
+    // types are only accepted if they are registered with the options that are used
+    assert!(from_str::<Value>("<value><money>12.50</money></value>").is_err());
+}
+
+#[test]
+fn from_unregistered() {
+    let value = "<value><decimal>1.5</decimal></value>";
+
+    assert!(from_str::<Value>(value).is_err());
+    assert_eq!(
+        from_str_with::<Value>(value, DeserializeOptions::lenient()).unwrap(),
+        Value::string(String::from("1.5"))
+    );
+}
+
+#[test]
+fn to_custom_mismatch() {
+    let error = Value::i4(42).to_custom::<Money>().unwrap_err();
+    assert_eq!(error, DxrError::wrong_type("i4", "money"));
+
+    let error = Value::custom(&Decimal).to_custom::<Money>().unwrap_err();
+    assert_eq!(error, DxrError::wrong_type("decimal", "money"));
+}
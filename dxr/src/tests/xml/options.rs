@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use crate::traits::TryFromValue;
+use crate::values::{Array, Member, MethodResponse, Struct, Value, ValueRef};
+use crate::xml::{
+    deserialize_xml as from_str, deserialize_xml_with as from_str_with, DeserializeOptions, DuplicateMembers,
+    MultipleParams, DEFAULT_MAX_DEPTH,
+};
+
+#[test]
+fn from_unknown_type() {
+    let value = "<value><ex:i2>42</ex:i2></value>";
+
+    assert!(from_str::<Value>(value).is_err());
+    assert!(from_str_with::<Value>(value, DeserializeOptions::strict()).is_err());
+    assert_eq!(
+        from_str_with::<Value>(value, DeserializeOptions::lenient()).unwrap(),
+        Value::string(String::from("42"))
+    );
+}
+
+#[test]
+fn from_whitespace() {
+    let values = [
+        "<value><i4> 42</i4></value>",
+        "<value><i4>42 </i4></value>",
+        "<value> <i4>42</i4></value>",
+        "<value><i4>42</i4>\n</value>",
+    ];
+
+    for value in values {
+        assert_eq!(from_str::<Value>(value).unwrap(), Value::i4(42));
+        assert_eq!(
+            from_str_with::<Value>(value, DeserializeOptions::lenient()).unwrap(),
+            Value::i4(42)
+        );
+        assert!(from_str_with::<Value>(value, DeserializeOptions::strict()).is_err());
+    }
+}
+
+#[test]
+fn from_whitespace_strict() {
+    let value = "\
+<value>
+  <array>
+    <data>
+      <value><i4>42</i4></value>
+      <value> foo </value>
+    </data>
+  </array>
+</value>";
+    let expected = Value::array(Array::new(vec![Value::i4(42), Value::string(String::from("foo"))]));
+
+    assert_eq!(
+        from_str_with::<Value>(value, DeserializeOptions::strict()).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn from_boolean_words() {
+    let values = [
+        ("<value><boolean>true</boolean></value>", true),
+        ("<value><boolean>false</boolean></value>", false),
+        ("<value><boolean>True</boolean></value>", true),
+        ("<value><boolean>FALSE</boolean></value>", false),
+    ];
+
+    for (value, expected) in values {
+        assert!(from_str::<Value>(value).is_err());
+        assert!(from_str_with::<Value>(value, DeserializeOptions::strict()).is_err());
+        assert_eq!(
+            from_str_with::<Value>(value, DeserializeOptions::lenient()).unwrap(),
+            Value::boolean(expected)
+        );
+    }
+
+    let options = DeserializeOptions::default().allow_boolean_words(true);
+    assert!(from_str_with::<Value>("<value><boolean>yes</boolean></value>", options).is_err());
+    assert_eq!(
+        from_str_with::<Value>("<value><boolean>1</boolean></value>", options).unwrap(),
+        Value::boolean(true)
+    );
+}
+
+#[test]
+fn from_non_finite_doubles() {
+    for value in ["NaN", "nan", "inf", "-inf", "infinity"] {
+        let value = format!("<value><double>{value}</double></value>");
+
+        let default = from_str::<Value>(&value).unwrap().as_f64().unwrap();
+        let lenient = from_str_with::<Value>(&value, DeserializeOptions::lenient())
+            .unwrap()
+            .as_f64()
+            .unwrap();
+
+        assert!(!default.is_finite(), "{value}");
+        assert!(!lenient.is_finite(), "{value}");
+        assert!(from_str_with::<Value>(&value, DeserializeOptions::strict()).is_err());
+    }
+}
+
+#[test]
+fn from_missing_params() {
+    #[cfg(feature = "nil")]
+    let expected = MethodResponse::new(Value::nil());
+    #[cfg(not(feature = "nil"))]
+    let expected = MethodResponse::new(Value::array(Array::new(vec![])));
+
+    for value in [
+        "<methodResponse></methodResponse>",
+        "<methodResponse><params></params></methodResponse>",
+    ] {
+        assert_eq!(from_str::<MethodResponse>(value).unwrap(), expected);
+        assert!(from_str_with::<MethodResponse>(value, DeserializeOptions::strict()).is_err());
+        assert_eq!(
+            from_str_with::<MethodResponse>(value, DeserializeOptions::lenient()).unwrap(),
+            expected
+        );
+
+        // empty responses can be converted into the unit type
+        <()>::try_from_value(&from_str::<MethodResponse>(value).unwrap().inner()).unwrap();
+    }
+
+    // malformed fault responses are not treated as empty responses
+    let value = "<methodResponse><fault><value><i4>1</i4></value></fault></methodResponse>";
+    assert!(from_str_with::<MethodResponse>(value, DeserializeOptions::lenient()).is_err());
+}
+
+#[test]
+fn from_multiple_params() {
+    let value = "<methodResponse><params>\
+<param><value><i4>1</i4></value></param>\
+<param><value><i4>2</i4></value></param>\
+</params></methodResponse>";
+
+    assert!(from_str::<MethodResponse>(value).is_err());
+    assert!(from_str_with::<MethodResponse>(value, DeserializeOptions::strict()).is_err());
+    assert!(from_str_with::<MethodResponse>(value, DeserializeOptions::lenient()).is_err());
+
+    let options = DeserializeOptions::default().multiple_params(MultipleParams::First);
+    let response = from_str_with::<MethodResponse>(value, options).unwrap();
+    assert_eq!(response, MethodResponse::new(Value::i4(1)));
+    assert_eq!(response.into_params(), vec![Value::i4(1)]);
+
+    let options = DeserializeOptions::default().multiple_params(MultipleParams::All);
+    let response = from_str_with::<MethodResponse>(value, options).unwrap();
+    assert_eq!(response.clone().inner(), Value::i4(1));
+    assert_eq!(response.into_params(), vec![Value::i4(1), Value::i4(2)]);
+}
+
+#[test]
+fn from_single_param() {
+    let value = "<methodResponse><params><param><value><i4>1</i4></value></param></params></methodResponse>";
+    let options = DeserializeOptions::default().multiple_params(MultipleParams::All);
+
+    let response = from_str_with::<MethodResponse>(value, options).unwrap();
+    assert_eq!(response, MethodResponse::new(Value::i4(1)));
+    assert_eq!(response.into_params(), vec![Value::i4(1)]);
+}
+
+fn nested_arrays(levels: usize) -> String {
+    format!(
+        "{}<value><i4>1</i4></value>{}",
+        "<value><array><data>".repeat(levels),
+        "</data></array></value>".repeat(levels)
+    )
+}
+
+#[test]
+fn from_nested_within_limit() {
+    let value = nested_arrays(DEFAULT_MAX_DEPTH / 3 - 1);
+    assert!(from_str::<Value>(&value).is_ok());
+}
+
+#[test]
+fn from_nested_too_deep() {
+    // this would overflow the stack without a depth limit
+    let value = nested_arrays(100_000);
+    assert!(from_str::<Value>(&value).is_err());
+    assert!(from_str_with::<Value>(&value, DeserializeOptions::lenient()).is_err());
+}
+
+#[test]
+fn from_nested_custom_depth() {
+    let value = nested_arrays(2);
+
+    assert!(from_str_with::<Value>(&value, DeserializeOptions::default().max_depth(8)).is_ok());
+    assert!(from_str_with::<Value>(&value, DeserializeOptions::default().max_depth(7)).is_err());
+}
+
+#[test]
+fn from_too_large() {
+    let value = "<value><string>Hello, World!</string></value>";
+
+    assert!(from_str_with::<Value>(value, DeserializeOptions::default().max_size(value.len())).is_ok());
+    assert!(from_str_with::<Value>(value, DeserializeOptions::default().max_size(value.len() - 1)).is_err());
+}
+
+#[test]
+fn from_billion_laughs() {
+    let value = r#"<?xml version="1.0"?>
+<!DOCTYPE lolz [
+  <!ENTITY lol "lol">
+  <!ENTITY lol1 "&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;">
+  <!ENTITY lol2 "&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;">
+]>
+<value><string>&lol2;</string></value>"#;
+
+    assert!(from_str::<Value>(value).is_err());
+    assert!(from_str_with::<Value>(value, DeserializeOptions::lenient()).is_err());
+}
+
+#[test]
+fn from_external_entity() {
+    let value = r#"<?xml version="1.0"?>
+<!DOCTYPE value [<!ENTITY xxe SYSTEM "file:///etc/passwd">]>
+<value><string>&xxe;</string></value>"#;
+
+    assert!(from_str::<Value>(value).is_err());
+}
+
+#[test]
+fn from_doctype() {
+    let value = r#"<?xml version="1.0"?><!DOCTYPE value><value><string>a</string></value>"#;
+    assert!(from_str::<Value>(value).is_err());
+}
+
+#[test]
+fn from_nul_character() {
+    assert!(from_str::<Value>("<value><string>a\0b</string></value>").is_err());
+    assert!(from_str::<Value>("<value>\0</value>").is_err());
+    assert!(from_str::<ValueRef>("<value>\0</value>").is_err());
+}
+
+const DUPLICATE_MEMBERS: &str = "<value><struct>\
+<member><name>a</name><value><i4>1</i4></value></member>\
+<member><name>b</name><value><i4>2</i4></value></member>\
+<member><name>a</name><value><i4>3</i4></value></member>\
+</struct></value>";
+
+#[test]
+fn from_duplicate_members_last_wins() {
+    let expected = Value::structure(Struct::new(vec![
+        Member::new(String::from("b"), Value::i4(2)),
+        Member::new(String::from("a"), Value::i4(3)),
+    ]));
+
+    assert_eq!(from_str::<Value>(DUPLICATE_MEMBERS).unwrap(), expected);
+    assert_eq!(
+        from_str_with::<Value>(DUPLICATE_MEMBERS, DeserializeOptions::lenient()).unwrap(),
+        expected
+    );
+
+    let value: ValueRef = from_str(DUPLICATE_MEMBERS).unwrap();
+    assert_eq!(value.into_owned(), expected);
+}
+
+#[test]
+fn from_duplicate_members_first_wins() {
+    let options = DeserializeOptions::default().duplicate_members(DuplicateMembers::FirstWins);
+    let expected = Value::structure(Struct::new(vec![
+        Member::new(String::from("a"), Value::i4(1)),
+        Member::new(String::from("b"), Value::i4(2)),
+    ]));
+
+    assert_eq!(from_str_with::<Value>(DUPLICATE_MEMBERS, options).unwrap(), expected);
+}
+
+#[test]
+fn from_duplicate_members_error() {
+    let options = DeserializeOptions::default().duplicate_members(DuplicateMembers::Error);
+
+    assert!(from_str_with::<Value>(DUPLICATE_MEMBERS, options).is_err());
+    assert!(from_str_with::<ValueRef>(DUPLICATE_MEMBERS, options).is_err());
+    assert!(from_str_with::<Value>(DUPLICATE_MEMBERS, DeserializeOptions::strict()).is_err());
+}
+
+#[test]
+fn options_restored() {
+    let value = "<value><ex:i2>42</ex:i2></value>";
+
+    assert!(from_str_with::<Value>(value, DeserializeOptions::lenient()).is_ok());
+    assert!(from_str::<Value>(value).is_err());
+}
+
+#[test]
+fn options_builder() {
+    let options = DeserializeOptions::strict().allow_unknown_types(true);
+    let value = "<value><ex:i2>42</ex:i2></value>";
+
+    assert!(from_str_with::<Value>(value, options).is_ok());
+    assert!(from_str_with::<Value>("<value><i4> 42</i4></value>", options).is_err());
+}
+
+#[test]
+fn interned_member_names() {
+    let value = "<value><array><data>\
+        <value><struct><member><name>size</name><value><i4>1</i4></value></member></struct></value>\
+        <value><struct><member><name>size</name><value><i4>2</i4></value></member></struct></value>\
+        </data></array></value>";
+
+    let names = |value: &Value| -> Vec<Arc<str>> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item.as_struct().unwrap()[0].shared_name().clone())
+            .collect()
+    };
+
+    let interned: Value = from_str_with(value, DeserializeOptions::default().intern_member_names(true)).unwrap();
+    let names_interned = names(&interned);
+    assert!(Arc::ptr_eq(&names_interned[0], &names_interned[1]));
+
+    let separate: Value = from_str(value).unwrap();
+    let names_separate = names(&separate);
+    assert!(!Arc::ptr_eq(&names_separate[0], &names_separate[1]));
+
+    // interning does not affect the deserialized values
+    assert_eq!(interned, separate);
+}
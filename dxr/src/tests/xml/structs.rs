@@ -82,6 +82,38 @@ fn from_struct_two() {
     assert_eq!(from_str::<Struct>(value).unwrap(), expected);
 }
 
+#[test]
+fn struct_member_order() {
+    let value = Struct::new(vec![
+        Member::new(String::from("question"), Value::string(String::from("?"))),
+        Member::new(String::from("answer"), Value::i4(42)),
+    ]);
+    let expected = "<struct><member><name>question</name><value><string>?</string></value></member><member><name>answer</name><value><i4>42</i4></value></member></struct>";
+
+    assert_eq!(to_string(&value).unwrap(), expected);
+
+    // member order is preserved when re-serializing deserialized structs
+    let roundtrip = from_str::<Struct>(expected).unwrap();
+    assert_eq!(to_string(&roundtrip).unwrap(), expected);
+
+    // member order is irrelevant for equality
+    let reversed = Struct::new(vec![
+        Member::new(String::from("answer"), Value::i4(42)),
+        Member::new(String::from("question"), Value::string(String::from("?"))),
+    ]);
+    assert_eq!(value, reversed);
+    assert_eq!(Value::structure(value), Value::structure(reversed));
+}
+
+#[test]
+fn struct_empty_member_name() {
+    let value = Struct::new(vec![Member::new(String::new(), Value::i4(42))]);
+    let expected = "<struct><member><name/><value><i4>42</i4></value></member></struct>";
+
+    assert_eq!(to_string(&value).unwrap(), expected);
+    assert_eq!(from_str::<Struct>(expected).unwrap(), value);
+}
+
 #[test]
 fn to_value_struct() {
     let value = Value::structure(Struct::new(vec![Member::new(String::from("answer"), Value::i4(42))]));
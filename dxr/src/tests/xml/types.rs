@@ -1,6 +1,9 @@
+#[cfg(feature = "chrono")]
 use chrono::{SubsecRound, Utc};
 
-use crate::values::{Type, XML_RPC_DATE_FORMAT};
+use crate::values::Type;
+#[cfg(feature = "chrono")]
+use crate::values::XML_RPC_DATE_FORMAT;
 use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string};
 
 #[test]
@@ -93,24 +96,26 @@ fn from_double() {
     assert_eq!(from_str::<Type>(value).unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn to_datetime() {
     let datetime = Utc::now().naive_utc();
     let datetime_str = datetime.format(XML_RPC_DATE_FORMAT).to_string();
 
-    let value = Type::DateTime(datetime);
+    let value = Type::DateTime(datetime.into());
     let expected = format!("<dateTime.iso8601>{datetime_str}</dateTime.iso8601>");
 
     assert_eq!(to_string(&value).unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn from_datetime() {
     let datetime = Utc::now().round_subsecs(0).naive_utc();
     let datetime_str = datetime.format(XML_RPC_DATE_FORMAT).to_string();
 
     let value = format!("<dateTime.iso8601>{datetime_str}</dateTime.iso8601>");
-    let expected = Type::DateTime(datetime);
+    let expected = Type::DateTime(datetime.into());
 
     assert_eq!(from_str::<Type>(&value).unwrap(), expected);
 }
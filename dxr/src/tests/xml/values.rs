@@ -1,7 +1,15 @@
+#[cfg(feature = "chrono")]
 use chrono::{SubsecRound, Utc};
 
-use crate::values::{Value, XML_RPC_DATE_FORMAT};
-use crate::xml::{deserialize_xml as from_str, serialize_xml as to_string};
+use crate::values::datetime::DateTime;
+use crate::values::ser_de::datetime;
+#[cfg(feature = "chrono")]
+use crate::values::XML_RPC_DATE_FORMAT;
+use crate::values::{Array, Member, MethodCall, Struct, Value};
+use crate::xml::{
+    deserialize_xml as from_str, serialize_xml as to_string, serialize_xml_with as to_string_with, DateTimeFormat,
+    NonFinitePolicy, SerializeOptions, StringFormat, WriteXml,
+};
 
 #[test]
 fn to_i4() {
@@ -45,6 +53,25 @@ fn from_i8() {
     assert_eq!(from_str::<Value>(value).unwrap(), expected);
 }
 
+#[cfg(feature = "i8")]
+#[test]
+fn to_i8_apache() {
+    let value = Value::i8(-12);
+    let expected = r#"<value><ex:i8 xmlns:ex="http://ws.apache.org/xmlrpc/namespaces/extensions">-12</ex:i8></value>"#;
+
+    let options = SerializeOptions::default().apache_extensions(true);
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[cfg(feature = "i8")]
+#[test]
+fn from_i8_apache() {
+    let value = "<value><ex:i8>-12</ex:i8></value>";
+    let expected = Value::i8(-12);
+
+    assert_eq!(from_str::<Value>(value).unwrap(), expected);
+}
+
 #[test]
 fn to_boolean() {
     let value = Value::boolean(true);
@@ -103,6 +130,113 @@ fn from_string_with_escape() {
     assert_eq!(from_str::<Value>(value).unwrap(), expected);
 }
 
+#[test]
+fn to_str_with_markup() {
+    let value = Value::string(String::from(r#"<a href="x">'&'</a>"#));
+    let expected = "<value><string>&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;</string></value>";
+
+    let xml = to_string(&value).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn to_str_with_control_characters() {
+    let value = Value::string(String::from("a\x7fb\r\n\tc&"));
+    let expected = "<value><string>a\x7fb&#xD;\n\tc&amp;</string></value>";
+
+    let xml = to_string(&value).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn to_str_with_nul() {
+    let value = Value::string(String::from("a\0b"));
+    assert!(to_string(&value).is_err());
+}
+
+#[test]
+fn to_str_with_forbidden_control_characters() {
+    for c in ['\x01', '\x08', '\x0b', '\x0c', '\x1b', '\x1f'] {
+        let value = Value::string(format!("a{c}b"));
+        assert!(to_string(&value).is_err());
+
+        let options = SerializeOptions::default().cdata_strings(Some(0));
+        assert!(to_string_with(&value, options).is_err());
+    }
+}
+
+#[test]
+fn to_struct_name_with_escape() {
+    let value = Value::structure(Struct::new(vec![Member::new(String::from("a&b\r"), Value::i4(1))]));
+    let expected =
+        "<value><struct><member><name>a&amp;b&#xD;</name><value><i4>1</i4></value></member></struct></value>";
+
+    let xml = to_string(&value).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn to_str_cdata() {
+    let value = Value::string(String::from("<a>&amp;</a>"));
+    let expected = "<value><string><![CDATA[<a>&amp;</a>]]></string></value>";
+
+    let options = SerializeOptions::default().cdata_strings(Some(8));
+    let xml = to_string_with(&value, options).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn to_str_cdata_short() {
+    let value = Value::string(String::from("a&b"));
+    let expected = "<value><string>a&amp;b</string></value>";
+
+    let options = SerializeOptions::default().cdata_strings(Some(8));
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_str_cdata_with_terminator() {
+    let value = Value::string(String::from("a]]>b"));
+    let expected = "<value><string><![CDATA[a]]]]><![CDATA[>b]]></string></value>";
+
+    let options = SerializeOptions::default().cdata_strings(Some(0));
+    let xml = to_string_with(&value, options).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+}
+
+#[test]
+fn to_str_cdata_with_control_characters() {
+    let value = Value::string(String::from("a\rb"));
+    let expected = "<value><string>a&#xD;b</string></value>";
+
+    let options = SerializeOptions::default().cdata_strings(Some(0));
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_untyped_str_cdata() {
+    let value = MethodCall::new(
+        String::from("a&b"),
+        vec![Value::structure(Struct::new(vec![Member::new(
+            String::from("c&d"),
+            Value::string(String::from("e&f")),
+        )]))],
+    );
+    let expected = "<methodCall><methodName>a&amp;b</methodName><params><param><value><struct><member><name>c&amp;d</name><value><![CDATA[e&f]]></value></member></struct></value></param></params></methodCall>";
+
+    let options = SerializeOptions::default()
+        .strings(StringFormat::Untyped)
+        .cdata_strings(Some(0));
+    let xml = to_string_with(&value, options).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<MethodCall>(&xml).unwrap(), value);
+}
+
 #[test]
 fn from_untyped_string() {
     let value = "<value>Hello, World!</value>";
@@ -135,6 +269,83 @@ fn from_untyped_string_with_escape() {
     assert_eq!(from_str::<Value>(value).unwrap(), expected);
 }
 
+#[test]
+fn to_untyped_string() {
+    let value = Value::string(String::from("Hello, World!"));
+    let expected = "<value>Hello, World!</value>";
+
+    let options = SerializeOptions::default().strings(StringFormat::Untyped);
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_untyped_empty_string() {
+    let value = Value::string(String::new());
+    let expected = "<value><string></string></value>";
+
+    let options = SerializeOptions::default().strings(StringFormat::Untyped);
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_bare_strings() {
+    let options = SerializeOptions::default().strings(StringFormat::Bare);
+
+    let value = Value::string(String::from("Hello, World!"));
+    assert_eq!(to_string_with(&value, options).unwrap(), "<value>Hello, World!</value>");
+
+    let value = Value::string(String::new());
+    let xml = to_string_with(&value, options).unwrap();
+    assert_eq!(xml, "<value/>");
+    assert_eq!(from_str::<Value>(&xml).unwrap(), value);
+
+    let mut buf = String::new();
+    value.write_xml(&mut buf, options).unwrap();
+    assert_eq!(buf, "<value/>");
+}
+
+#[test]
+fn to_typed_strings() {
+    let options = SerializeOptions::default()
+        .strings(StringFormat::Untyped)
+        .strings(StringFormat::Typed);
+
+    let value = Value::string(String::from("Hello, World!"));
+    assert_eq!(
+        to_string_with(&value, options).unwrap(),
+        "<value><string>Hello, World!</string></value>"
+    );
+}
+
+#[test]
+fn to_untyped_string_with_escape() {
+    let value = Value::string(String::from("a&b"));
+    let expected = "<value>a&amp;b</value>";
+
+    let options = SerializeOptions::default().strings(StringFormat::Untyped);
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_untyped_string_nested() {
+    let value = MethodCall::new(
+        String::from("hello"),
+        vec![Value::array(Array::new(vec![
+            Value::string(String::from("world")),
+            Value::i4(1),
+        ]))],
+    );
+    let expected = "<methodCall><methodName>hello</methodName><params><param><value><array><data><value>world</value><value><i4>1</i4></value></data></array></value></param></params></methodCall>";
+
+    let options = SerializeOptions::default().strings(StringFormat::Untyped);
+    let xml = to_string_with(&value, options).unwrap();
+    assert_eq!(xml, expected);
+    assert_eq!(from_str::<MethodCall>(&xml).unwrap(), value);
+
+    // options are only used for the call they were passed to
+    assert!(to_string(&value).unwrap().contains("<string>world</string>"));
+}
+
 #[test]
 fn to_double() {
     let value = Value::double(1.5);
@@ -151,6 +362,43 @@ fn from_double() {
     assert_eq!(from_str::<Value>(value).unwrap(), expected);
 }
 
+#[test]
+fn to_double_non_finite() {
+    for double in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(to_string(&Value::double(double)).is_err());
+    }
+}
+
+#[test]
+fn to_double_non_finite_as_string() {
+    let options = SerializeOptions::default().non_finite_doubles(NonFinitePolicy::AsString);
+
+    for (double, expected) in [(f64::NAN, "NaN"), (f64::INFINITY, "inf"), (f64::NEG_INFINITY, "-inf")] {
+        let expected = format!("<value><string>{expected}</string></value>");
+        assert_eq!(to_string_with(&Value::double(double), options).unwrap(), expected);
+    }
+}
+
+#[test]
+fn to_double_non_finite_skip() {
+    let options = SerializeOptions::default().non_finite_doubles(NonFinitePolicy::Skip);
+
+    let value = Value::array(Array::new(vec![Value::double(f64::NAN), Value::double(1.5)]));
+    let expected = "<value><array><data><value><double>1.5</double></value></data></array></value>";
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+
+    let value = Value::structure(Struct::new(vec![
+        Member::new(String::from("a"), Value::double(f64::INFINITY)),
+        Member::new(String::from("b"), Value::double(1.5)),
+    ]));
+    let expected = "<value><struct><member><name>b</name><value><double>1.5</double></value></member></struct></value>";
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+
+    // values that are not contained in an array or struct cannot be skipped
+    assert!(to_string_with(&Value::double(f64::NAN), options).is_err());
+}
+
+#[cfg(feature = "chrono")]
 #[test]
 fn to_datetime() {
     let datetime = Utc::now().naive_utc();
@@ -162,6 +410,7 @@ fn to_datetime() {
     assert_eq!(to_string(&value).unwrap(), expected);
 }
 
+#[cfg(feature = "chrono")]
 #[test]
 fn from_datetime() {
     let datetime = Utc::now().round_subsecs(0).naive_utc();
@@ -175,15 +424,178 @@ fn from_datetime() {
 
 #[test]
 fn from_datetime_fail() {
-    let datetime = Utc::now().round_subsecs(0).naive_utc();
-    let datetime_str = datetime.format("%Y%Y%Y").to_string();
+    for invalid in [
+        "202320232023",
+        "2023-02-29T12:00:00",
+        "20230401T24:00:00",
+        "2023-04-01T12:00",
+        "20230401",
+    ] {
+        let value = format!("<value><dateTime.iso8601>{invalid}</dateTime.iso8601></value>");
+
+        assert!(
+            from_str::<Value>(&value)
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid date format"),
+            "{invalid}"
+        );
+    }
+}
 
-    let value = format!("<value><dateTime.iso8601>{datetime_str}</dateTime.iso8601></value>");
+#[test]
+fn from_datetime_variants() {
+    let expected = DateTime::new(2023, 4, 1, 12, 0, 0, 0).unwrap();
+    let variants = [
+        "20230401T12:00:00",
+        "2023-04-01T12:00:00",
+        "20230401T120000",
+        "2023-04-01 12:00:00",
+        "20230401T12:00:00Z",
+        "2023-04-01T14:00:00+02:00",
+        "2023-04-01T14:00:00+0200",
+        "2023-04-01T14:00:00+02",
+        "2023-04-01T09:30:00-02:30",
+        "2023-04-02T01:00:00+13:00",
+        "2023-03-31T23:00:00-13:00",
+    ];
+
+    for variant in variants {
+        let value = format!("<value><dateTime.iso8601>{variant}</dateTime.iso8601></value>");
+        assert_eq!(
+            from_str::<Value>(&value).unwrap(),
+            Value::from_datetime(expected),
+            "{variant}"
+        );
+    }
+}
+
+#[test]
+fn from_datetime_fractional() {
+    let expected = DateTime::new(2023, 4, 1, 12, 0, 0, 250_000_000).unwrap();
+
+    for variant in [
+        "20230401T12:00:00.25",
+        "2023-04-01T12:00:00.250Z",
+        "2023-04-01T13:00:00.25+01:00",
+    ] {
+        let value = format!("<value><dateTime.iso8601>{variant}</dateTime.iso8601></value>");
+        assert_eq!(
+            from_str::<Value>(&value).unwrap(),
+            Value::from_datetime(expected),
+            "{variant}"
+        );
+    }
+}
 
-    assert!(from_str::<Value>(&value)
+#[test]
+fn from_datetime_offset_fail() {
+    let value = "<value><dateTime.iso8601>2023-04-01T12:00:00+2</dateTime.iso8601></value>";
+
+    assert!(from_str::<Value>(value)
         .unwrap_err()
         .to_string()
-        .contains("Invalid date format"));
+        .contains("Invalid timezone offset"));
+}
+
+#[test]
+fn to_datetime_custom_format() {
+    let value = Value::from_datetime(DateTime::new(2023, 4, 1, 12, 0, 0, 0).unwrap());
+    let expected = "<value><dateTime.iso8601>2023-04-01T12:00:00Z</dateTime.iso8601></value>";
+
+    let options = SerializeOptions::default().datetime(DateTimeFormat::custom("%Y-%m-%dT%H:%M:%SZ").unwrap());
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[test]
+fn to_datetime_invalid_format() {
+    assert!(DateTimeFormat::custom("%d.%m.%Y %H:%M:%S").is_ok());
+
+    // timezone specifiers cannot be rendered for timezone-unaware values
+    for format in ["%z", "%:z", "%Z", "%+", "%c %Z", "%Q", "%"] {
+        assert!(
+            DateTimeFormat::custom(format).unwrap_err().is_invalid_data(),
+            "{format}"
+        );
+    }
+
+    // format strings that cannot be rendered result in an error instead of a panic
+    let datetime = DateTime::new(2023, 4, 1, 12, 0, 0, 0).unwrap();
+    assert!(datetime::to_string(&datetime, "%z").is_err());
+}
+
+#[test]
+fn to_datetime_specifiers() {
+    let datetime = DateTime::new(2023, 4, 1, 12, 0, 5, 250_000_000).unwrap();
+
+    assert_eq!(
+        datetime::to_string(&datetime, "%F %T%.f").unwrap(),
+        "2023-04-01 12:00:05.250"
+    );
+    assert_eq!(
+        datetime::to_string(&datetime, "%y/%m/%d %3f %.6f").unwrap(),
+        "23/04/01 250 .250000"
+    );
+    assert_eq!(datetime::to_string(&datetime, "%f%%").unwrap(), "250000000%");
+
+    // fractional seconds are omitted if they are zero
+    let datetime = DateTime::new(2023, 4, 1, 12, 0, 5, 0).unwrap();
+    assert_eq!(datetime::to_string(&datetime, "%H%M%S%.f").unwrap(), "120005");
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn to_datetime_specifiers_chrono() {
+    let now = Utc::now().naive_utc();
+
+    // supported specifiers are rendered the same way as by chrono
+    for format in [
+        "%Y%m%dT%H:%M:%S",
+        "%F %T",
+        "%y %f",
+        "%.f",
+        "%.3f%.6f%.9f",
+        "%3f %6f %9f",
+        "%%",
+    ] {
+        assert_eq!(
+            datetime::to_string(&now.into(), format).unwrap(),
+            now.format(format).to_string(),
+            "{format}"
+        );
+    }
+}
+
+#[test]
+fn to_datetime_formats() {
+    let value = Value::from_datetime(DateTime::new(2023, 4, 1, 12, 0, 0, 0).unwrap());
+
+    let formatted = |format| {
+        let options = SerializeOptions::default().datetime(format);
+        to_string_with(&value, options).unwrap()
+    };
+
+    assert_eq!(
+        formatted(DateTimeFormat::XmlRpc),
+        "<value><dateTime.iso8601>20230401T12:00:00</dateTime.iso8601></value>"
+    );
+    assert_eq!(
+        formatted(DateTimeFormat::Iso8601),
+        "<value><dateTime.iso8601>2023-04-01T12:00:00</dateTime.iso8601></value>"
+    );
+    assert_eq!(
+        formatted(DateTimeFormat::Rfc3339),
+        "<value><dateTime.iso8601>2023-04-01T12:00:00Z</dateTime.iso8601></value>"
+    );
+    assert_eq!(
+        formatted(DateTimeFormat::custom("%d.%m.%Y %H:%M").unwrap()),
+        "<value><dateTime.iso8601>01.04.2023 12:00</dateTime.iso8601></value>"
+    );
+
+    // all predefined formats can be deserialized again
+    for format in [DateTimeFormat::XmlRpc, DateTimeFormat::Iso8601, DateTimeFormat::Rfc3339] {
+        assert_eq!(from_str::<Value>(&formatted(format)).unwrap(), value);
+    }
 }
 
 #[test]
@@ -225,3 +637,22 @@ fn from_nil() {
 
     assert_eq!(from_str::<Value>(value).unwrap(), expected);
 }
+
+#[cfg(feature = "nil")]
+#[test]
+fn to_nil_apache() {
+    let value = Value::nil();
+    let expected = r#"<value><ex:nil xmlns:ex="http://ws.apache.org/xmlrpc/namespaces/extensions"/></value>"#;
+
+    let options = SerializeOptions::default().apache_extensions(true);
+    assert_eq!(to_string_with(&value, options).unwrap(), expected);
+}
+
+#[cfg(feature = "nil")]
+#[test]
+fn from_nil_apache() {
+    let value = r#"<value><ex:nil xmlns:ex="http://ws.apache.org/xmlrpc/namespaces/extensions"/></value>"#;
+    let expected = Value::nil();
+
+    assert_eq!(from_str::<Value>(value).unwrap(), expected);
+}
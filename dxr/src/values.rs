@@ -1,10 +1,33 @@
 /// Date & time format used by the XML-RPC `dateTime.iso8601` value type
 ///
 /// This string represents the format of the timezone-unaware date & time format used by the XML-RPC
-/// `dateTime.iso8601` value type, to be used with [`chrono::NaiveDateTime::parse_from_str`].
+/// `dateTime.iso8601` value type, in the `strftime`-like syntax that is supported by `chrono` and
+/// by [`DateTimeFormat::custom`](crate::DateTimeFormat::custom).
+///
+/// This format is used for serializing `dateTime.iso8601` values by default. When deserializing,
+/// other common variants of ISO 8601 are accepted as well (with dashes, fractional seconds, or
+/// timezone designators like `Z` or `+02:00`), and values with timezone offsets are normalized to
+/// UTC.
 pub const XML_RPC_DATE_FORMAT: &str = "%Y%m%dT%H:%M:%S";
 
-mod ser_de;
+mod access;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_support;
+
+mod borrowed;
+pub use borrowed::*;
+
+pub(crate) mod custom;
+pub use custom::{CustomScalar, CustomScalarType};
+
+pub(crate) mod datetime;
+
+mod display;
+
+pub(crate) mod ser_de;
+
+mod std_traits;
 
 mod types;
 pub use types::*;
@@ -0,0 +1,162 @@
+//! accessors for the contents of XML-RPC values
+
+use super::types::{Member, Type, Value};
+
+impl Value {
+    /// getter for the name of the type of this value (i.e. `i4` or `string`)
+    pub fn type_name(&self) -> &'static str {
+        self.inner().name()
+    }
+
+    /// getter for the contents of `<i4>` values
+    pub fn as_i32(&self) -> Option<i32> {
+        match self.inner() {
+            Type::Integer(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<i8>` values
+    #[cfg(feature = "i8")]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.inner() {
+            Type::Long(long) => Some(*long),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<boolean>` values
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.inner() {
+            Type::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<string>` values
+    pub fn as_str(&self) -> Option<&str> {
+        match self.inner() {
+            Type::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<double>` values
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.inner() {
+            Type::Double(double) => Some(*double),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<dateTime.iso8601>` values
+    ///
+    /// This returns [`None`] if the value is not a `<dateTime.iso8601>` value, or if the date is
+    /// outside the range that is supported by the `chrono` crate.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        match self.inner() {
+            Type::DateTime(datetime) => datetime.to_chrono(),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<dateTime.iso8601>` values as [`time::PrimitiveDateTime`]
+    ///
+    /// This returns [`None`] if the value is not a `<dateTime.iso8601>` value, or if the date is
+    /// outside the range that is supported by the `time` crate.
+    #[cfg(feature = "time")]
+    pub fn as_primitive_datetime(&self) -> Option<time::PrimitiveDateTime> {
+        match self.inner() {
+            Type::DateTime(datetime) => datetime.to_time().ok(),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<dateTime.iso8601>` values as [`time::OffsetDateTime`] in UTC
+    #[cfg(feature = "time")]
+    pub fn as_offset_datetime(&self) -> Option<time::OffsetDateTime> {
+        self.as_primitive_datetime().map(time::PrimitiveDateTime::assume_utc)
+    }
+
+    /// getter for the contents of `<base64>` values
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self.inner() {
+            Type::Base64(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// getter for the members of `<struct>` values
+    pub fn as_struct(&self) -> Option<&[Member]> {
+        match self.inner() {
+            Type::Struct { members } => Some(members),
+            _ => None,
+        }
+    }
+
+    /// getter for the elements of `<array>` values
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self.inner() {
+            Type::Array { data } => Some(data.inner()),
+            _ => None,
+        }
+    }
+
+    /// check whether this is a `<nil/>` value
+    #[cfg(feature = "nil")]
+    pub fn is_nil(&self) -> bool {
+        matches!(self.inner(), Type::Nil)
+    }
+
+    /// getter for the value of the struct member with the given name
+    ///
+    /// This returns [`None`] if this is not a `<struct>` value, or if the struct does not have a
+    /// member with the given name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.as_struct()?
+            .iter()
+            .find(|member| member.name() == name)
+            .map(Member::inner)
+    }
+
+    /// getter for the array element at the given index
+    ///
+    /// This returns [`None`] if this is not an `<array>` value, or if the index is out of bounds.
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        self.as_array()?.get(index)
+    }
+
+    /// getter for values that are nested inside structs and arrays
+    ///
+    /// The pointer consists of struct member names and array indices, separated by `/` characters,
+    /// and is resolved one segment at a time, starting at this value. Segments are interpreted as
+    /// struct member names for `<struct>` values, and as array indices for `<array>` values. An
+    /// empty pointer refers to this value itself.
+    ///
+    /// ```
+    /// use dxr::{TryToValue, Value};
+    /// use std::collections::HashMap;
+    ///
+    /// let peer = HashMap::from([("address", "127.0.0.1")]);
+    /// let value = HashMap::from([("peers", vec![peer])]).try_to_value().unwrap();
+    ///
+    /// assert_eq!(
+    ///     value.pointer("peers/0/address").and_then(Value::as_str),
+    ///     Some("127.0.0.1")
+    /// );
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let pointer = pointer.strip_prefix('/').unwrap_or(pointer);
+
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        pointer.split('/').try_fold(self, |value, segment| match value.inner() {
+            Type::Struct { .. } => value.get(segment),
+            Type::Array { .. } => value.get_index(segment.parse().ok()?),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! implementations of the [`Arbitrary`] trait for XML-RPC values
+//!
+//! Generated values are restricted to values that can be serialized as XML-RPC and deserialized
+//! again without any loss of information, which makes them suitable for round-trip tests and for
+//! fuzzing:
+//!
+//! - strings do not contain NUL or other control characters (except tab, line feed, and carriage
+//!   return), which cannot be represented in XML, and they have no
+//!   leading or trailing whitespace (which is discarded by the XML parser),
+//! - doubles are always finite,
+//! - date & time values have no fractional seconds and years between 1 and 9999,
+//! - struct member names are unique within each struct,
+//! - arrays and structs are nested at most four levels deep.
+
+use std::collections::HashSet;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::datetime::DateTime;
+use super::types::{Array, Member, Struct, Value};
+use crate::xml::is_forbidden;
+
+// maximum nesting depth of arrays and structs in generated values
+const MAX_DEPTH: usize = 4;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Integer,
+    #[cfg(feature = "i8")]
+    Long,
+    Boolean,
+    String,
+    Double,
+    DateTime,
+    Base64,
+    #[cfg(feature = "nil")]
+    Nil,
+    Struct,
+    Array,
+}
+
+const KINDS: &[Kind] = &[
+    Kind::Integer,
+    #[cfg(feature = "i8")]
+    Kind::Long,
+    Kind::Boolean,
+    Kind::String,
+    Kind::Double,
+    Kind::DateTime,
+    Kind::Base64,
+    #[cfg(feature = "nil")]
+    Kind::Nil,
+    Kind::Struct,
+    Kind::Array,
+];
+
+// number of scalar value types, which are listed before structs and arrays
+const SCALARS: usize = KINDS.len() - 2;
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Member {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Member::new(string(u)?, value(u, 1)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Struct {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        structure(u, 1)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Array {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        array(u, 1)
+    }
+}
+
+fn value(u: &mut Unstructured, depth: usize) -> Result<Value> {
+    let kinds = if depth < MAX_DEPTH { KINDS } else { &KINDS[..SCALARS] };
+
+    Ok(match u.choose(kinds)? {
+        Kind::Integer => Value::i4(u.arbitrary()?),
+        #[cfg(feature = "i8")]
+        Kind::Long => Value::i8(u.arbitrary()?),
+        Kind::Boolean => Value::boolean(u.arbitrary()?),
+        Kind::String => Value::string(string(u)?),
+        Kind::Double => Value::double(double(u)?),
+        Kind::DateTime => Value::from_datetime(datetime(u)?),
+        Kind::Base64 => Value::base64(u.arbitrary()?),
+        #[cfg(feature = "nil")]
+        Kind::Nil => Value::nil(),
+        Kind::Struct => Value::structure(structure(u, depth + 1)?),
+        Kind::Array => Value::array(array(u, depth + 1)?),
+    })
+}
+
+fn string(u: &mut Unstructured) -> Result<String> {
+    let string: String = u.arbitrary()?;
+    let string: String = string.chars().filter(|c| !is_forbidden(*c)).collect();
+    Ok(string.trim().to_owned())
+}
+
+fn double(u: &mut Unstructured) -> Result<f64> {
+    let double: f64 = u.arbitrary()?;
+    Ok(if double.is_finite() { double } else { 0.0 })
+}
+
+fn datetime(u: &mut Unstructured) -> Result<DateTime> {
+    // 0001-01-01T00:00:00 to 9999-12-31T23:59:59
+    let timestamp = u.int_in_range(-62_135_596_800..=253_402_300_799)?;
+    DateTime::from_unix_timestamp(timestamp).ok_or(arbitrary::Error::IncorrectFormat)
+}
+
+fn structure(u: &mut Unstructured, depth: usize) -> Result<Struct> {
+    let mut names = HashSet::new();
+    let mut members = Vec::new();
+
+    for _ in 0..u.arbitrary_len::<(String, Value)>()? {
+        let name = string(u)?;
+        let value = value(u, depth)?;
+
+        if names.insert(name.clone()) {
+            members.push(Member::new(name, value));
+        }
+    }
+
+    Ok(Struct::new(members))
+}
+
+fn array(u: &mut Unstructured, depth: usize) -> Result<Array> {
+    let mut values = Vec::new();
+
+    for _ in 0..u.arbitrary_len::<Value>()? {
+        values.push(value(u, depth)?);
+    }
+
+    Ok(Array::new(values))
+}
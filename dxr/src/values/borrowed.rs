@@ -0,0 +1,361 @@
+//! borrowed representation of XML-RPC values for zero-copy deserialization
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+
+use super::custom::CustomValue;
+use super::datetime::DateTime;
+use super::ser_de::value::Field;
+use super::types::{Array, Member, Struct, Value};
+
+/// # Borrowed XML-RPC value type
+///
+/// The [`ValueRef`] type is a variant of [`Value`] that borrows the contents of `<string>` values
+/// and the names of struct members from the XML document it was deserialized from, instead of
+/// allocating a new [`String`] for every one of them. This can considerably reduce the overhead of
+/// parsing large documents (i.e. responses that contain long arrays of structs).
+///
+/// Strings that contain escaped characters (like `&amp;`) cannot be borrowed, since they need to
+/// be unescaped first, so they are transparently stored as owned values instead.
+///
+/// ```
+/// use dxr::{deserialize_xml, ValueRef};
+///
+/// let xml = "<value><struct><member><name>id</name><value>abc</value></member></struct></value>";
+/// let value: ValueRef = deserialize_xml(xml).unwrap();
+///
+/// assert_eq!(value.get("id").and_then(ValueRef::as_str), Some("abc"));
+/// ```
+///
+/// Values can be converted into an owned [`Value`] with [`ValueRef::into_owned`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueRef<'a> {
+    value: TypeRef<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TypeRef<'a> {
+    Integer(i32),
+    #[cfg(feature = "i8")]
+    Long(i64),
+    Boolean(bool),
+    String(Cow<'a, str>),
+    Double(f64),
+    DateTime(DateTime),
+    Base64(Vec<u8>),
+    Struct(Vec<MemberRef<'a>>),
+    Array(Vec<ValueRef<'a>>),
+    #[cfg(feature = "nil")]
+    Nil,
+    Custom(&'static str, String),
+}
+
+/// # Borrowed XML-RPC struct member type
+///
+/// The [`MemberRef`] type is the borrowed equivalent of [`Member`].
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename = "member")]
+pub struct MemberRef<'a> {
+    #[serde(borrow)]
+    name: NameRef<'a>,
+    #[serde(borrow)]
+    value: ValueRef<'a>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename = "name")]
+struct NameRef<'a> {
+    #[serde(borrow, default, rename = "$value")]
+    name: Cow<'a, str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "struct")]
+struct StructRef<'a> {
+    #[serde(borrow, default, rename = "member")]
+    members: Vec<MemberRef<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "array")]
+struct ArrayRef<'a> {
+    #[serde(borrow, default)]
+    data: ArrayDataRef<'a>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename = "data")]
+struct ArrayDataRef<'a> {
+    #[serde(borrow, default, rename = "value")]
+    values: Vec<ValueRef<'a>>,
+}
+
+// wrapper for deserializing string contents without copying them (if possible)
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct CowStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+impl<'a> ValueRef<'a> {
+    /// method for converting borrowed values into owned [`Value`]s
+    pub fn into_owned(self) -> Value {
+        match self.value {
+            TypeRef::Integer(int) => Value::i4(int),
+            #[cfg(feature = "i8")]
+            TypeRef::Long(long) => Value::i8(long),
+            TypeRef::Boolean(boolean) => Value::boolean(boolean),
+            TypeRef::String(string) => Value::string(string.into_owned()),
+            TypeRef::Double(double) => Value::double(double),
+            TypeRef::DateTime(datetime) => Value::from_datetime(datetime),
+            TypeRef::Base64(bytes) => Value::base64(bytes),
+            TypeRef::Struct(members) => {
+                Value::structure(Struct::new(members.into_iter().map(MemberRef::into_owned).collect()))
+            },
+            TypeRef::Array(values) => Value::array(Array::new(values.into_iter().map(ValueRef::into_owned).collect())),
+            #[cfg(feature = "nil")]
+            TypeRef::Nil => Value::nil(),
+            TypeRef::Custom(tag, text) => Value::from_custom(CustomValue::new(tag, text)),
+        }
+    }
+
+    /// getter for the contents of `<i4>` values
+    pub fn as_i32(&self) -> Option<i32> {
+        match &self.value {
+            TypeRef::Integer(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<i8>` values
+    #[cfg(feature = "i8")]
+    pub fn as_i64(&self) -> Option<i64> {
+        match &self.value {
+            TypeRef::Long(long) => Some(*long),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<boolean>` values
+    pub fn as_bool(&self) -> Option<bool> {
+        match &self.value {
+            TypeRef::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<string>` values
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            TypeRef::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<string>` values (borrowed from the XML document, if possible)
+    pub fn as_cow_str(&self) -> Option<&Cow<'a, str>> {
+        match &self.value {
+            TypeRef::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<double>` values
+    pub fn as_f64(&self) -> Option<f64> {
+        match &self.value {
+            TypeRef::Double(double) => Some(*double),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<dateTime.iso8601>` values
+    ///
+    /// This returns [`None`] if the value is not a `<dateTime.iso8601>` value, or if the date is
+    /// outside the range that is supported by the `chrono` crate.
+    #[cfg(feature = "chrono")]
+    pub fn as_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        match &self.value {
+            TypeRef::DateTime(datetime) => datetime.to_chrono(),
+            _ => None,
+        }
+    }
+
+    /// getter for the contents of `<base64>` values
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.value {
+            TypeRef::Base64(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// getter for the name of the type element and the text contents of custom scalar values
+    pub fn as_custom(&self) -> Option<(&'static str, &str)> {
+        match &self.value {
+            TypeRef::Custom(tag, text) => Some((tag, text)),
+            _ => None,
+        }
+    }
+
+    /// getter for the members of `<struct>` values
+    pub fn as_struct(&self) -> Option<&[MemberRef<'a>]> {
+        match &self.value {
+            TypeRef::Struct(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// getter for the elements of `<array>` values
+    pub fn as_array(&self) -> Option<&[ValueRef<'a>]> {
+        match &self.value {
+            TypeRef::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// check whether this is a `<nil/>` value
+    #[cfg(feature = "nil")]
+    pub fn is_nil(&self) -> bool {
+        matches!(self.value, TypeRef::Nil)
+    }
+
+    /// getter for the value of the struct member with the given name
+    pub fn get(&self, name: &str) -> Option<&ValueRef<'a>> {
+        self.as_struct()?
+            .iter()
+            .find(|member| member.name() == name)
+            .map(MemberRef::inner)
+    }
+
+    /// getter for the array element at the given index
+    pub fn get_index(&self, index: usize) -> Option<&ValueRef<'a>> {
+        self.as_array()?.get(index)
+    }
+}
+
+impl<'a> From<ValueRef<'a>> for Value {
+    fn from(value: ValueRef<'a>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl<'a> MemberRef<'a> {
+    /// getter method for the name of the struct member
+    pub fn name(&self) -> &str {
+        &self.name.name
+    }
+
+    /// getter method for the value of the struct member
+    pub fn inner(&self) -> &ValueRef<'a> {
+        &self.value
+    }
+
+    /// method for converting borrowed struct members into owned [`Member`]s
+    pub fn into_owned(self) -> Member {
+        Member::new(self.name.name.into_owned(), self.value.into_owned())
+    }
+}
+
+/// # Borrowed XML-RPC method response type
+///
+/// The [`MethodResponseRef`] type is the borrowed equivalent of [`MethodResponse`], which contains
+/// a [`ValueRef`] instead of a [`Value`].
+///
+/// [`MethodResponse`]: super::MethodResponse
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename = "methodResponse")]
+pub struct MethodResponseRef<'a> {
+    #[serde(borrow)]
+    params: ResponseParametersRef<'a>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename = "params")]
+struct ResponseParametersRef<'a> {
+    #[serde(borrow, rename = "param")]
+    params: ResponseParameterRef<'a>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename = "param")]
+struct ResponseParameterRef<'a> {
+    #[serde(borrow, rename = "value")]
+    params: ValueRef<'a>,
+}
+
+impl<'a> MethodResponseRef<'a> {
+    /// getter method for the returned value
+    pub fn inner(self) -> ValueRef<'a> {
+        self.params.params.params
+    }
+}
+
+struct ValueRefVisitor;
+
+impl<'de> Visitor<'de> for ValueRefVisitor {
+    type Value = ValueRef<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid XML-RPC scalar value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let value = match map.next_key()? {
+            Some(Field::I4) => TypeRef::Integer(map.next_value()?),
+            #[cfg(feature = "i8")]
+            Some(Field::I8) => TypeRef::Long(map.next_value()?),
+            Some(Field::Boolean) => {
+                let CowStr(string) = map.next_value()?;
+                TypeRef::Boolean(super::ser_de::boolean::from_str(&string).map_err(de::Error::custom)?)
+            },
+            // values with unknown types are treated like untyped string values
+            Some(Field::String | Field::Unknown) => {
+                let CowStr(string) = map.next_value()?;
+                TypeRef::String(string)
+            },
+            Some(Field::Double) => {
+                let double = map.next_value()?;
+                TypeRef::Double(super::ser_de::double::check(double).map_err(de::Error::custom)?)
+            },
+            Some(Field::DateTime) => {
+                let CowStr(string) = map.next_value()?;
+                TypeRef::DateTime(super::ser_de::datetime::from_str(&string).map_err(de::Error::custom)?)
+            },
+            Some(Field::Base64) => {
+                let CowStr(string) = map.next_value()?;
+                TypeRef::Base64(super::ser_de::base64::from_str(&string).map_err(de::Error::custom)?)
+            },
+            Some(Field::Struct) => {
+                let StructRef { members } = map.next_value()?;
+                let members = super::ser_de::members::dedup(members, MemberRef::name).map_err(de::Error::custom)?;
+                TypeRef::Struct(members)
+            },
+            Some(Field::Array) => {
+                let ArrayRef { data } = map.next_value()?;
+                TypeRef::Array(data.values)
+            },
+            #[cfg(feature = "nil")]
+            Some(Field::Nil) => TypeRef::Nil,
+            Some(Field::Custom(tag, normalize)) => {
+                let CowStr(string) = map.next_value()?;
+                TypeRef::Custom(tag, normalize(&string).map_err(de::Error::custom)?)
+            },
+            // <value></value>
+            None => TypeRef::String(Cow::Borrowed("")),
+        };
+
+        Ok(ValueRef { value })
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ValueRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueRefVisitor)
+    }
+}
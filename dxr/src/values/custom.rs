@@ -0,0 +1,158 @@
+//! extension point for custom (non-standard) scalar value types
+
+use std::fmt::{self, Debug, Formatter};
+
+use crate::error::DxrError;
+
+use super::types::{Type, Value};
+
+/// # Trait for custom scalar value types
+///
+/// Some XML-RPC implementations use non-standard type elements inside `<value>` elements (i.e.
+/// `<decimal>` or vendor-specific types like `<money>`). Implementing this trait for a Rust type
+/// makes it possible to construct [`Value`]s with such type elements with [`Value::custom`], and to
+/// convert them back with [`Value::to_custom`]. After registering the type with
+/// [`DeserializeOptions::custom_scalars`](crate::DeserializeOptions::custom_scalars), values with
+/// this type element are also accepted when deserializing XML-RPC documents with these options.
+///
+/// ```
+/// use dxr::{CustomScalar, CustomScalarType, DeserializeOptions, DxrError, Value};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Money {
+///     cents: i64,
+/// }
+///
+/// impl CustomScalar for Money {
+///     const TAG: &'static str = "money";
+///
+///     fn parse(text: &str) -> Result<Self, DxrError> {
+///         let (units, cents) = text.split_once('.').unwrap_or((text, "00"));
+///         let units: i64 = units.parse().map_err(|_| DxrError::invalid_data(text.to_owned()))?;
+///         let cents: i64 = cents.parse().map_err(|_| DxrError::invalid_data(text.to_owned()))?;
+///         Ok(Money { cents: units * 100 + cents })
+///     }
+///
+///     fn format(&self) -> String {
+///         format!("{}.{:02}", self.cents / 100, self.cents % 100)
+///     }
+/// }
+///
+/// const CUSTOM_SCALARS: &[CustomScalarType] = &[CustomScalarType::of::<Money>()];
+/// let options = DeserializeOptions::default().custom_scalars(CUSTOM_SCALARS);
+///
+/// let value: Value = dxr::deserialize_xml_with("<value><money>12.50</money></value>", options).unwrap();
+/// assert_eq!(value.to_custom::<Money>().unwrap(), Money { cents: 1250 });
+/// assert_eq!(dxr::serialize_xml(&value).unwrap(), "<value><money>12.50</money></value>");
+/// ```
+pub trait CustomScalar: Sized {
+    /// name of the type element (i.e. `money` for `<money>` values)
+    ///
+    /// Names of standard XML-RPC types (and of supported extension types) cannot be overridden.
+    const TAG: &'static str;
+
+    /// method for parsing a value from the text contents of its type element
+    fn parse(text: &str) -> Result<Self, DxrError>;
+
+    /// method for formatting a value as the text contents of its type element
+    fn format(&self) -> String;
+}
+
+/// function that validates and normalizes the text contents of a custom type element
+pub(crate) type Normalizer = fn(&str) -> Result<String, DxrError>;
+
+fn normalize<T: CustomScalar>(text: &str) -> Result<String, DxrError> {
+    T::parse(text).map(|value| value.format())
+}
+
+/// # Custom scalar type that is accepted when deserializing XML-RPC documents
+///
+/// Custom scalar types are registered for deserialization with
+/// [`DeserializeOptions::custom_scalars`](crate::DeserializeOptions::custom_scalars). Since
+/// [`CustomScalarType::of`] is a `const fn`, the list of registered types can be a constant.
+#[derive(Clone, Copy)]
+pub struct CustomScalarType {
+    tag: &'static str,
+    normalize: Normalizer,
+}
+
+impl CustomScalarType {
+    /// constructor for the registration of a [`CustomScalar`] type
+    pub const fn of<T: CustomScalar>() -> CustomScalarType {
+        CustomScalarType {
+            tag: T::TAG,
+            normalize: normalize::<T>,
+        }
+    }
+
+    /// getter method for the name of the type element of this custom scalar type
+    pub const fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub(crate) fn normalizer(&self) -> Normalizer {
+        self.normalize
+    }
+}
+
+impl Debug for CustomScalarType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomScalarType").field("tag", &self.tag).finish()
+    }
+}
+
+// registrations are identified by the name of their type element
+impl PartialEq for CustomScalarType {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl Eq for CustomScalarType {}
+
+/// contents of values with custom scalar types
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CustomValue {
+    tag: &'static str,
+    text: String,
+}
+
+impl CustomValue {
+    pub(crate) fn new(tag: &'static str, text: String) -> CustomValue {
+        CustomValue { tag, text }
+    }
+
+    pub(crate) fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Value {
+    /// constructor for values with custom scalar types
+    ///
+    /// Values with custom types are serialized as `<value><tag>text</tag></value>`, where the name
+    /// of the type element and its contents are determined by the [`CustomScalar`] implementation.
+    pub fn custom<T: CustomScalar>(value: &T) -> Value {
+        Value::from_custom(CustomValue::new(T::TAG, value.format()))
+    }
+
+    /// getter for the name of the type element and the text contents of custom scalar values
+    pub fn as_custom(&self) -> Option<(&'static str, &str)> {
+        match self.inner() {
+            Type::Custom(custom) => Some((custom.tag(), custom.text())),
+            _ => None,
+        }
+    }
+
+    /// method for converting custom scalar values into the corresponding Rust type
+    pub fn to_custom<T: CustomScalar>(&self) -> Result<T, DxrError> {
+        match self.as_custom() {
+            Some((tag, text)) if tag == T::TAG => T::parse(text),
+            _ => Err(DxrError::wrong_type(self.type_name(), T::TAG)),
+        }
+    }
+}
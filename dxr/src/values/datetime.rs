@@ -0,0 +1,425 @@
+//! backend-independent representation of the contents of `dateTime.iso8601` values
+//!
+//! Parsing and formatting is implemented here (instead of relying on `chrono`) so that date & time
+//! values can be handled with either the `chrono` or the `time` crate, or without any of them.
+
+use std::fmt::{self, Display, Formatter, Write};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// date & time contents of `dateTime.iso8601` values (timezone-unaware, UTC by convention)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct DateTime {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+impl DateTime {
+    // constructor that checks that the date is valid, and that the time is in range (leap seconds
+    // are not supported)
+    pub(crate) fn new(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Option<DateTime> {
+        let valid = (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60
+            && nanosecond < 1_000_000_000;
+
+        valid.then_some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    }
+
+    // constructor from the number of seconds since 1970-01-01T00:00:00
+    pub(crate) fn from_unix_timestamp(timestamp: i64) -> Option<DateTime> {
+        let days = timestamp.div_euclid(SECONDS_PER_DAY);
+        let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+
+        let (year, month, day) = civil_from_days(days);
+        let year = i32::try_from(year).ok()?;
+
+        DateTime::new(
+            year,
+            month,
+            day,
+            (seconds / 3600) as u8,
+            (seconds % 3600 / 60) as u8,
+            (seconds % 60) as u8,
+            0,
+        )
+    }
+
+    fn unix_timestamp(&self) -> i64 {
+        let days = days_from_civil(i64::from(self.year), self.month, self.day);
+        days * SECONDS_PER_DAY + i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second)
+    }
+
+    // shift the value by a number of minutes (used for normalizing values with timezone offsets)
+    fn checked_sub_minutes(self, minutes: i64) -> Option<DateTime> {
+        let shifted = DateTime::from_unix_timestamp(self.unix_timestamp() - minutes * 60)?;
+        Some(DateTime {
+            nanosecond: self.nanosecond,
+            ..shifted
+        })
+    }
+
+    // parse a date & time value in one of the supported variants of ISO 8601 (values with
+    // timezone designators are normalized to UTC)
+    pub(crate) fn parse(s: &str) -> Result<DateTime, String> {
+        let (local, offset) = split_offset(s.trim())?;
+
+        parse_local(local)
+            .and_then(|datetime| datetime.checked_sub_minutes(offset))
+            .ok_or_else(|| format!("Invalid date format: {}", s))
+    }
+
+    // format the value with a format string that uses a subset of strftime specifiers
+    pub(crate) fn format(&self, format: &str) -> Result<String, String> {
+        let invalid = || format!("Invalid format string for dateTime.iso8601 values: {format}");
+
+        let mut string = String::new();
+        self.write_format(&mut string, format).map_err(|_| invalid())?;
+        Ok(string)
+    }
+
+    fn write_format(&self, w: &mut impl Write, format: &str) -> fmt::Result {
+        let mut chars = format.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                w.write_char(c)?;
+                continue;
+            }
+
+            match chars.next().ok_or(fmt::Error)? {
+                'Y' => self.write_year(w)?,
+                'y' => write!(w, "{:02}", self.year.rem_euclid(100))?,
+                'm' => write!(w, "{:02}", self.month)?,
+                'd' => write!(w, "{:02}", self.day)?,
+                'H' => write!(w, "{:02}", self.hour)?,
+                'M' => write!(w, "{:02}", self.minute)?,
+                'S' => write!(w, "{:02}", self.second)?,
+                'F' => {
+                    self.write_year(w)?;
+                    write!(w, "-{:02}-{:02}", self.month, self.day)?;
+                },
+                'T' => write!(w, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?,
+                'f' => write!(w, "{:09}", self.nanosecond)?,
+                '%' => w.write_char('%')?,
+                digits @ ('3' | '6' | '9') => {
+                    if chars.next() != Some('f') {
+                        return Err(fmt::Error);
+                    }
+                    self.write_fraction(w, digits)?;
+                },
+                '.' => match chars.next() {
+                    // fractional seconds with as many digits as necessary (or none)
+                    Some('f') => match self.nanosecond {
+                        0 => (),
+                        n if n % 1_000_000 == 0 => write!(w, ".{:03}", n / 1_000_000)?,
+                        n if n % 1_000 == 0 => write!(w, ".{:06}", n / 1_000)?,
+                        n => write!(w, ".{:09}", n)?,
+                    },
+                    Some(digits @ ('3' | '6' | '9')) => {
+                        if chars.next() != Some('f') {
+                            return Err(fmt::Error);
+                        }
+                        w.write_char('.')?;
+                        self.write_fraction(w, digits)?;
+                    },
+                    _ => return Err(fmt::Error),
+                },
+                // other specifiers (i.e. names of weekdays or months, or timezone information)
+                // are not supported
+                _ => return Err(fmt::Error),
+            }
+        }
+
+        Ok(())
+    }
+
+    // years outside the range of four digits are written with an explicit sign (like chrono)
+    fn write_year(&self, w: &mut impl Write) -> fmt::Result {
+        if (0..=9999).contains(&self.year) {
+            write!(w, "{:04}", self.year)
+        } else {
+            write!(w, "{:+05}", self.year)
+        }
+    }
+
+    fn write_fraction(&self, w: &mut impl Write, digits: char) -> fmt::Result {
+        match digits {
+            '3' => write!(w, "{:03}", self.nanosecond / 1_000_000),
+            '6' => write!(w, "{:06}", self.nanosecond / 1_000),
+            _ => write!(w, "{:09}", self.nanosecond),
+        }
+    }
+
+    // check whether a format string only contains supported specifiers
+    pub(crate) fn check_format(format: &str) -> Result<(), String> {
+        DateTime::default().format(format).map(drop)
+    }
+}
+
+impl Default for DateTime {
+    // 1970-01-01T00:00:00
+    fn default() -> Self {
+        DateTime {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+    }
+}
+
+// values are displayed in the format from the XML-RPC specification
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_year(f)?;
+        write!(
+            f,
+            "{:02}{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// number of days since 1970-01-01 in the proleptic Gregorian calendar
+// (algorithm from http://howardhinnant.github.io/date_algorithms.html)
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+// inverse of days_from_civil
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+// parse a fixed number of ASCII digits
+fn number(s: &str, len: usize) -> Option<(u32, &str)> {
+    let digits = s.get(..len)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((digits.parse().ok()?, &s[len..]))
+}
+
+// strip a separator character if it is used by the format
+fn separator(s: &str, separator: char, used: bool) -> Option<&str> {
+    if used {
+        s.strip_prefix(separator)
+    } else {
+        Some(s)
+    }
+}
+
+// supported variants of ISO 8601 date & time formats (without timezone designator), fractional
+// seconds are optional for all of them:
+//
+// - 20230401T12:00:00
+// - 2023-04-01T12:00:00
+// - 20230401T120000
+// - 2023-04-01 12:00:00
+fn parse_local(s: &str) -> Option<DateTime> {
+    let (year, rest) = number(s, 4)?;
+    let extended = rest.starts_with('-');
+
+    let (month, rest) = number(separator(rest, '-', extended)?, 2)?;
+    let (day, rest) = number(separator(rest, '-', extended)?, 2)?;
+
+    let rest = match rest.strip_prefix('T') {
+        Some(rest) => rest,
+        None if extended => rest.strip_prefix(' ')?,
+        None => return None,
+    };
+
+    let (hour, rest) = number(rest, 2)?;
+    let colons = rest.starts_with(':');
+    if extended && !colons {
+        return None;
+    }
+
+    let (minute, rest) = number(separator(rest, ':', colons)?, 2)?;
+    let (second, rest) = number(separator(rest, ':', colons)?, 2)?;
+
+    let nanosecond = match rest.strip_prefix('.') {
+        Some(fraction) if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            // digits beyond nanosecond precision are ignored
+            let digits = &fraction[..fraction.len().min(9)];
+            let padded = format!("{:0<9}", digits);
+            padded.parse().ok()?
+        },
+        Some(_) => return None,
+        None if rest.is_empty() => 0,
+        None => return None,
+    };
+
+    DateTime::new(
+        year as i32,
+        month as u8,
+        day as u8,
+        hour as u8,
+        minute as u8,
+        second as u8,
+        nanosecond,
+    )
+}
+
+// split off an optional timezone designator ("Z", "+HH:MM", "+HHMM", or "+HH"), and return the
+// offset in minutes
+fn split_offset(s: &str) -> Result<(&str, i64), String> {
+    if let Some(local) = s.strip_suffix('Z').or_else(|| s.strip_suffix('z')) {
+        return Ok((local, 0));
+    }
+
+    // the timezone designator can only be part of the time component
+    let time_start = match s.find(['T', ' ']) {
+        Some(index) => index + 1,
+        None => return Ok((s, 0)),
+    };
+
+    let sign_index = match s[time_start..].rfind(['+', '-']) {
+        Some(index) => time_start + index,
+        None => return Ok((s, 0)),
+    };
+
+    let (local, designator) = s.split_at(sign_index);
+    let sign = if designator.starts_with('-') { -1 } else { 1 };
+    let digits: String = designator[1..].chars().filter(|c| *c != ':').collect();
+
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.as_str(), "0"),
+        4 => digits.split_at(2),
+        _ => return Err(format!("Invalid timezone offset: {}", designator)),
+    };
+
+    let invalid = |_| format!("Invalid timezone offset: {}", designator);
+    let hours: i64 = hours.parse().map_err(invalid)?;
+    let minutes: i64 = minutes.parse().map_err(invalid)?;
+
+    Ok((local, sign * (hours * 60 + minutes)))
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+    use super::DateTime;
+
+    impl From<NaiveDateTime> for DateTime {
+        fn from(datetime: NaiveDateTime) -> Self {
+            DateTime {
+                year: datetime.year(),
+                month: datetime.month() as u8,
+                day: datetime.day() as u8,
+                hour: datetime.hour() as u8,
+                minute: datetime.minute() as u8,
+                second: datetime.second() as u8,
+                // leap seconds are represented with nanoseconds >= 1e9 by chrono
+                nanosecond: datetime.nanosecond().min(999_999_999),
+            }
+        }
+    }
+
+    impl DateTime {
+        // dates can be outside the range that is supported by chrono if they were constructed
+        // from values of the time crate
+        pub(crate) fn to_chrono(self) -> Option<NaiveDateTime> {
+            NaiveDate::from_ymd_opt(self.year, u32::from(self.month), u32::from(self.day))?.and_hms_nano_opt(
+                u32::from(self.hour),
+                u32::from(self.minute),
+                u32::from(self.second),
+                self.nanosecond,
+            )
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_support {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    use super::DateTime;
+    use crate::error::DxrError;
+
+    impl From<PrimitiveDateTime> for DateTime {
+        fn from(datetime: PrimitiveDateTime) -> Self {
+            DateTime {
+                year: datetime.year(),
+                month: u8::from(datetime.month()),
+                day: datetime.day(),
+                hour: datetime.hour(),
+                minute: datetime.minute(),
+                second: datetime.second(),
+                nanosecond: datetime.nanosecond(),
+            }
+        }
+    }
+
+    impl DateTime {
+        pub(crate) fn to_time(self) -> Result<PrimitiveDateTime, DxrError> {
+            let invalid = DxrError::invalid_data_from;
+
+            let month = Month::try_from(self.month).map_err(invalid)?;
+            let date = Date::from_calendar_date(self.year, month, self.day).map_err(invalid)?;
+            let time = Time::from_hms_nano(self.hour, self.minute, self.second, self.nanosecond).map_err(invalid)?;
+
+            Ok(PrimitiveDateTime::new(date, time))
+        }
+    }
+}
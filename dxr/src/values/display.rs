@@ -0,0 +1,142 @@
+//! human-readable rendering of XML-RPC values
+//!
+//! Values are rendered in a compact, JSON-like notation. Using the alternate flag (i.e. `{:#}` or
+//! `{:#?}`) renders nested structs and arrays on multiple lines, with one member or element per
+//! line, indented by four spaces per level of nesting.
+
+use std::fmt;
+
+use super::types::{Array, Member, Struct, Type, Value};
+
+const INDENT: &str = "    ";
+
+fn write_indent(f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        f.write_str(INDENT)?;
+    }
+    Ok(())
+}
+
+fn write_value(f: &mut fmt::Formatter, value: &Value, depth: usize) -> fmt::Result {
+    match value.inner() {
+        Type::Integer(int) => write!(f, "{int}"),
+        #[cfg(feature = "i8")]
+        Type::Long(long) => write!(f, "{long}"),
+        Type::Boolean(boolean) => write!(f, "{boolean}"),
+        Type::String(string) => write!(f, "{string:?}"),
+        Type::Double(double) => write!(f, "{double:?}"),
+        Type::DateTime(datetime) => write!(f, "dateTime({datetime})"),
+        Type::Base64(bytes) => write!(f, "base64({})", crate::base64::encode(bytes)),
+        Type::Struct { members } => write_members(f, members, depth),
+        Type::Array { data } => write_values(f, data.inner(), depth),
+        #[cfg(feature = "nil")]
+        Type::Nil => f.write_str("nil"),
+        Type::Custom(custom) => write!(f, "{}({:?})", custom.tag(), custom.text()),
+    }
+}
+
+fn write_member(f: &mut fmt::Formatter, member: &Member, depth: usize) -> fmt::Result {
+    write!(f, "{:?}: ", member.name())?;
+    write_value(f, member.inner(), depth)
+}
+
+fn write_members(f: &mut fmt::Formatter, members: &[Member], depth: usize) -> fmt::Result {
+    write_list(f, ('{', '}'), members, depth, write_member)
+}
+
+fn write_values(f: &mut fmt::Formatter, values: &[Value], depth: usize) -> fmt::Result {
+    write_list(f, ('[', ']'), values, depth, write_value)
+}
+
+fn write_list<T>(
+    f: &mut fmt::Formatter,
+    (open, close): (char, char),
+    items: &[T],
+    depth: usize,
+    write_item: fn(&mut fmt::Formatter, &T, usize) -> fmt::Result,
+) -> fmt::Result {
+    if items.is_empty() {
+        return write!(f, "{open}{close}");
+    }
+
+    let pretty = f.alternate();
+    write!(f, "{open}")?;
+
+    for (i, item) in items.iter().enumerate() {
+        if pretty {
+            f.write_str("\n")?;
+            write_indent(f, depth + 1)?;
+        } else if i > 0 {
+            f.write_str(" ")?;
+        }
+
+        write_item(f, item, depth + 1)?;
+
+        if pretty || i + 1 < items.len() {
+            f.write_str(",")?;
+        }
+    }
+
+    if pretty {
+        f.write_str("\n")?;
+        write_indent(f, depth)?;
+    }
+
+    write!(f, "{close}")
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_value(f, self, 0)
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Value(")?;
+        write_value(f, self, 0)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_members(f, self.members(), 0)
+    }
+}
+
+impl fmt::Debug for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Struct(")?;
+        write_members(f, self.members(), 0)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for Array {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_values(f, self.values(), 0)
+    }
+}
+
+impl fmt::Debug for Array {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Array(")?;
+        write_values(f, self.values(), 0)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_member(f, self, 0)
+    }
+}
+
+impl fmt::Debug for Member {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Member(")?;
+        write_member(f, self, 0)?;
+        write!(f, ")")
+    }
+}
@@ -1,25 +1,29 @@
 pub(crate) mod datetime {
-    use chrono::NaiveDateTime;
     use serde::{Deserialize, Deserializer, Serializer};
 
-    use crate::values::XML_RPC_DATE_FORMAT;
+    use crate::values::datetime::DateTime;
+    use crate::xml::current_serialize_options;
 
-    pub(crate) fn from_str(s: &str) -> Result<NaiveDateTime, String> {
-        match NaiveDateTime::parse_from_str(s, XML_RPC_DATE_FORMAT) {
-            Ok(date) => Ok(date),
-            Err(error) => Err(format!("Invalid date format: {}", error)),
-        }
+    pub(crate) fn from_str(s: &str) -> Result<DateTime, String> {
+        DateTime::parse(s)
+    }
+
+    // format strings with unsupported specifiers (i.e. timezone specifiers, which cannot be
+    // rendered for timezone-unaware values) result in an error instead of a panic
+    pub(crate) fn to_string(datetime: &DateTime, format: &str) -> Result<String, String> {
+        datetime.format(format)
     }
 
-    pub(crate) fn serialize<S>(datetime: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    pub(crate) fn serialize<S>(datetime: &DateTime, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let string = datetime.format(XML_RPC_DATE_FORMAT).to_string();
+        let format = current_serialize_options().format_datetime();
+        let string = to_string(datetime, format).map_err(serde::ser::Error::custom)?;
         serializer.serialize_str(&string)
     }
 
-    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -28,13 +32,181 @@ pub(crate) mod datetime {
     }
 }
 
+pub(crate) mod double {
+    use serde::ser::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::values::{Member, Type, Value};
+    use crate::xml::{current_options, current_serialize_options, NonFinitePolicy};
+
+    pub(crate) fn to_string(double: f64) -> String {
+        if double.is_nan() {
+            String::from("NaN")
+        } else if double.is_sign_positive() {
+            String::from("inf")
+        } else {
+            String::from("-inf")
+        }
+    }
+
+    pub(crate) fn check(double: f64) -> Result<f64, String> {
+        if double.is_finite() || current_options().non_finite_doubles() {
+            Ok(double)
+        } else {
+            Err(format!("Unsupported double value: {}", to_string(double)))
+        }
+    }
+
+    pub(crate) fn serialize<S>(double: &f64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if double.is_finite() {
+            serializer.serialize_f64(*double)
+        } else {
+            Err(S::Error::custom(format!(
+                "Cannot serialize non-finite double value: {}",
+                to_string(*double)
+            )))
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let double = f64::deserialize(deserializer)?;
+        check(double).map_err(serde::de::Error::custom)
+    }
+
+    fn is_skipped(value: &Value) -> bool {
+        matches!(value.inner(), Type::Double(double) if !double.is_finite())
+            && current_serialize_options().non_finite_policy() == NonFinitePolicy::Skip
+    }
+
+    pub(crate) fn serialize_members<S>(members: &[Member], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(members.iter().filter(|member| !is_skipped(member.inner())))
+    }
+
+    pub(crate) fn serialize_values<S>(values: &[Value], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(values.iter().filter(|value| !is_skipped(value)))
+    }
+}
+
+pub(crate) mod members {
+    use std::collections::HashSet;
+
+    use serde::{Deserialize, Deserializer};
+
+    use crate::values::Member;
+    use crate::xml::{current_options, DuplicateMembers};
+
+    // apply the configured policy for struct members with duplicate names
+    pub(crate) fn dedup<T>(mut members: Vec<T>, name: impl Fn(&T) -> &str) -> Result<Vec<T>, String> {
+        if members.len() < 2 {
+            return Ok(members);
+        }
+
+        let policy = current_options().duplicate_members_policy();
+        let mut seen = HashSet::with_capacity(members.len());
+        let mut keep = vec![true; members.len()];
+
+        let indices: Box<dyn Iterator<Item = usize>> = match policy {
+            DuplicateMembers::LastWins => Box::new((0..members.len()).rev()),
+            _ => Box::new(0..members.len()),
+        };
+
+        for index in indices {
+            let member = name(&members[index]);
+            if !seen.insert(member) {
+                if policy == DuplicateMembers::Error {
+                    return Err(format!("Duplicate struct member: {}", member));
+                }
+                keep[index] = false;
+            }
+        }
+
+        if keep.contains(&false) {
+            let mut keep = keep.into_iter();
+            members.retain(|_| keep.next().unwrap_or(true));
+        }
+
+        Ok(members)
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Member>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let members = Vec::<Member>::deserialize(deserializer)?;
+        dedup(members, Member::name).map_err(serde::de::Error::custom)
+    }
+}
+
+pub(crate) mod member_name {
+    use std::fmt;
+    use std::sync::Arc;
+
+    use serde::de::{Deserializer, Visitor};
+    use serde::Serializer;
+
+    use crate::xml::intern_member_name;
+
+    pub(crate) fn empty() -> Arc<str> {
+        Arc::from("")
+    }
+
+    pub(crate) fn serialize<S>(name: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(name)
+    }
+
+    // member names are deserialized without allocating if they are interned and already known
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = Arc<str>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a struct member name")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(intern_member_name(v))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
 pub(crate) mod boolean {
     use serde::{Deserialize, Deserializer, Serializer};
 
+    use crate::xml::current_options;
+
     pub(crate) fn from_str(s: &str) -> Result<bool, String> {
         match s {
             "1" => Ok(true),
             "0" => Ok(false),
+            // non-standard values that are only accepted in lenient mode
+            _ if current_options().boolean_words() && s.eq_ignore_ascii_case("true") => Ok(true),
+            _ if current_options().boolean_words() && s.eq_ignore_ascii_case("false") => Ok(false),
             _ => Err(format!("Unsupported boolean value: {}", s)),
         }
     }
@@ -98,95 +270,110 @@ pub(crate) mod value {
     };
     use std::fmt;
 
+    use crate::values::custom::{CustomValue, Normalizer};
     use crate::values::Value;
+    use crate::xml::current_options;
 
-    struct ValueVisitor {}
+    const FIELDS: &[&str] = &[
+        "i4",
+        "int",
+        #[cfg(feature = "i8")]
+        "i8",
+        #[cfg(feature = "i8")]
+        "ex:i8",
+        "boolean",
+        "string",
+        "double",
+        "dateTime.iso8601",
+        "base64",
+        "struct",
+        "array",
+        #[cfg(feature = "nil")]
+        "nil",
+        #[cfg(feature = "nil")]
+        "ex:nil",
+    ];
 
-    impl<'de> Visitor<'de> for ValueVisitor {
-        type Value = Value;
-
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a valid XML-RPC scalar value")
-        }
+    pub(crate) enum Field {
+        I4,
+        #[cfg(feature = "i8")]
+        I8,
+        Boolean,
+        String,
+        Double,
+        DateTime,
+        Base64,
+        Struct,
+        Array,
+        #[cfg(feature = "nil")]
+        Nil,
+        Custom(&'static str, Normalizer),
+        Unknown,
+    }
 
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
         where
-            A: serde::de::MapAccess<'de>,
+            D: Deserializer<'de>,
         {
-            const FIELDS: &[&str] = &[
-                "i4",
-                "int",
-                #[cfg(feature = "i8")]
-                "i8",
-                "boolean",
-                "string",
-                "double",
-                "dateTime.iso8601",
-                "base64",
-                "struct",
-                "array",
-                #[cfg(feature = "nil")]
-                "nil",
-            ];
-
-            enum Field {
-                I4,
-                #[cfg(feature = "i8")]
-                I8,
-                Boolean,
-                String,
-                Double,
-                DateTime,
-                Base64,
-                Struct,
-                Array,
-                #[cfg(feature = "nil")]
-                Nil,
-            }
+            struct FieldVisitor;
+
+            impl<'de> Visitor<'de> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("field should be called `value`")
+                }
 
-            impl<'de> Deserialize<'de> for Field {
-                fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+                fn visit_str<E>(self, value: &str) -> Result<Field, E>
                 where
-                    D: Deserializer<'de>,
+                    E: de::Error,
                 {
-                    struct FieldVisitor;
-
-                    impl<'de> Visitor<'de> for FieldVisitor {
-                        type Value = Field;
-
-                        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                            formatter.write_str("field should be called `value`")
-                        }
-
-                        fn visit_str<E>(self, value: &str) -> Result<Field, E>
-                        where
-                            E: de::Error,
-                        {
-                            match value {
-                                "i4" => Ok(Field::I4),
-                                "int" => Ok(Field::I4),
-                                #[cfg(feature = "i8")]
-                                "i8" => Ok(Field::I8),
-                                "boolean" => Ok(Field::Boolean),
-                                "string" => Ok(Field::String),
-                                "double" => Ok(Field::Double),
-                                "dateTime.iso8601" => Ok(Field::DateTime),
-                                "base64" => Ok(Field::Base64),
-                                "struct" => Ok(Field::Struct),
-                                "array" => Ok(Field::Array),
-                                #[cfg(feature = "nil")]
-                                "nil" => Ok(Field::Nil),
-                                "$value" => Ok(Field::String),
-                                "$text" => Ok(Field::String),
-                                _ => Err(de::Error::unknown_field(value, FIELDS)),
+                    match value {
+                        "i4" => Ok(Field::I4),
+                        "int" => Ok(Field::I4),
+                        #[cfg(feature = "i8")]
+                        "i8" | "ex:i8" => Ok(Field::I8),
+                        "boolean" => Ok(Field::Boolean),
+                        "string" => Ok(Field::String),
+                        "double" => Ok(Field::Double),
+                        "dateTime.iso8601" => Ok(Field::DateTime),
+                        "base64" => Ok(Field::Base64),
+                        "struct" => Ok(Field::Struct),
+                        "array" => Ok(Field::Array),
+                        #[cfg(feature = "nil")]
+                        "nil" | "ex:nil" => Ok(Field::Nil),
+                        "$value" => Ok(Field::String),
+                        "$text" => Ok(Field::String),
+                        _ => {
+                            let options = current_options();
+                            match options.custom_scalar(value) {
+                                Some((tag, normalize)) => Ok(Field::Custom(tag, normalize)),
+                                None if options.unknown_types() => Ok(Field::Unknown),
+                                None => Err(de::Error::unknown_field(value, FIELDS)),
                             }
-                        }
+                        },
                     }
-
-                    deserializer.deserialize_identifier(FieldVisitor)
                 }
             }
 
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct ValueVisitor {}
+
+    impl<'de> Visitor<'de> for ValueVisitor {
+        type Value = Value;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a valid XML-RPC scalar value")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
             if let Some(key) = map.next_key()? {
                 match key {
                     Field::I4 => {
@@ -210,12 +397,14 @@ pub(crate) mod value {
                     },
                     Field::Double => {
                         let value = map.next_value()?;
-                        Ok(Value::double(value))
+                        super::double::check(value)
+                            .map(Value::double)
+                            .map_err(de::Error::custom)
                     },
                     Field::DateTime => {
                         let string: String = map.next_value()?;
                         super::datetime::from_str(&string)
-                            .map(Value::datetime)
+                            .map(Value::from_datetime)
                             .map_err(de::Error::custom)
                     },
                     Field::Base64 => {
@@ -234,6 +423,17 @@ pub(crate) mod value {
                     },
                     #[cfg(feature = "nil")]
                     Field::Nil => Ok(Value::nil()),
+                    Field::Custom(tag, normalize) => {
+                        let text: String = map.next_value()?;
+                        normalize(&text)
+                            .map(|text| Value::from_custom(CustomValue::new(tag, text)))
+                            .map_err(de::Error::custom)
+                    },
+                    Field::Unknown => {
+                        // values with unknown types are treated like untyped string values
+                        let value: String = map.next_value()?;
+                        Ok(Value::string(value))
+                    },
                 }
             } else {
                 // <value></value>
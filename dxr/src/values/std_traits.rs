@@ -0,0 +1,240 @@
+//! infallible conversions into XML-RPC values, indexing, and iteration of structs and arrays
+
+use std::collections::HashMap;
+use std::ops::{Deref, Index};
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDateTime;
+
+use super::types::{Array, Member, Struct, Value};
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::i4(value)
+    }
+}
+
+#[cfg(feature = "i8")]
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::i8(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::boolean(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::string(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::string(value.to_owned())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::double(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<NaiveDateTime> for Value {
+    fn from(value: NaiveDateTime) -> Self {
+        Value::datetime(value)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Value::base64(value)
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(value: &[u8]) -> Self {
+        Value::base64(value.to_vec())
+    }
+}
+
+impl From<Struct> for Value {
+    fn from(value: Struct) -> Self {
+        Value::structure(value)
+    }
+}
+
+impl From<Array> for Value {
+    fn from(value: Array) -> Self {
+        Value::array(value)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(value: Vec<Value>) -> Self {
+        Value::array(Array::new(value))
+    }
+}
+
+impl From<Vec<Value>> for Array {
+    fn from(value: Vec<Value>) -> Self {
+        Array::new(value)
+    }
+}
+
+impl From<Vec<Member>> for Struct {
+    fn from(value: Vec<Member>) -> Self {
+        Struct::new(value)
+    }
+}
+
+// the order of members is unspecified, since it depends on the iteration order of the map
+impl From<HashMap<String, Value>> for Struct {
+    fn from(value: HashMap<String, Value>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl FromIterator<Member> for Struct {
+    fn from_iter<I: IntoIterator<Item = Member>>(iter: I) -> Self {
+        Struct::new(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<(String, Value)> for Struct {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        iter.into_iter().map(|(name, value)| Member::new(name, value)).collect()
+    }
+}
+
+// members are appended, even if the struct already contains members with the same names
+impl Extend<Member> for Struct {
+    fn extend<I: IntoIterator<Item = Member>>(&mut self, iter: I) {
+        self.members_mut().extend(iter);
+    }
+}
+
+impl Extend<(String, Value)> for Struct {
+    fn extend<I: IntoIterator<Item = (String, Value)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(name, value)| Member::new(name, value)));
+    }
+}
+
+impl IntoIterator for Struct {
+    type Item = Member;
+    type IntoIter = std::vec::IntoIter<Member>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_members().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Struct {
+    type Item = &'a Member;
+    type IntoIter = std::slice::Iter<'a, Member>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members().iter()
+    }
+}
+
+impl FromIterator<Value> for Array {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Array::new(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Value> for Array {
+    fn extend<I: IntoIterator<Item = Value>>(&mut self, iter: I) {
+        self.values_mut().extend(iter);
+    }
+}
+
+impl IntoIterator for Array {
+    type Item = Value;
+    type IntoIter = std::vec::IntoIter<Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_values().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Array {
+    type Item = &'a Value;
+    type IntoIter = std::slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values().iter()
+    }
+}
+
+impl Deref for Array {
+    type Target = [Value];
+
+    fn deref(&self) -> &Self::Target {
+        self.values()
+    }
+}
+
+// panics with the same message as slice indexing
+fn index_out_of_bounds(len: usize, index: usize) -> ! {
+    panic!("index out of bounds: the len is {len} but the index is {index}")
+}
+
+impl Index<usize> for Array {
+    type Output = Value;
+
+    /// Indexing panics if the index is out of bounds, use [`Array::values`] and [`slice::get`] for
+    /// a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        let values = self.values();
+        values
+            .get(index)
+            .unwrap_or_else(|| index_out_of_bounds(values.len(), index))
+    }
+}
+
+impl Index<&str> for Struct {
+    type Output = Value;
+
+    /// Indexing panics if the struct has no member with the given name.
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name)
+            .unwrap_or_else(|| panic!("struct has no member with name \"{name}\""))
+    }
+}
+
+impl Index<usize> for Value {
+    type Output = Value;
+
+    /// Indexing panics if this is not an `<array>` value, or if the index is out of bounds. Use
+    /// [`Value::get_index`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        let Some(values) = self.as_array() else {
+            panic!("cannot index into <{}> value with integer", self.inner().name());
+        };
+        values
+            .get(index)
+            .unwrap_or_else(|| index_out_of_bounds(values.len(), index))
+    }
+}
+
+impl Index<&str> for Value {
+    type Output = Value;
+
+    /// Indexing panics if this is not a `<struct>` value, or if the struct has no member with the
+    /// given name. Use [`Value::get`] for a non-panicking alternative.
+    fn index(&self, name: &str) -> &Self::Output {
+        if self.as_struct().is_none() {
+            panic!("cannot index into <{}> value with string", self.inner().name());
+        }
+        self.get(name)
+            .unwrap_or_else(|| panic!("struct has no member with name \"{name}\""))
+    }
+}
@@ -1,9 +1,15 @@
 //! definitions of XML-RPC data types with (de)serialization implementations
 
-use chrono::NaiveDateTime;
-use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 
 use crate::fault::Fault;
+use crate::xml::{current_options, current_serialize_options, MultipleParams, NonFinitePolicy};
+
+use super::custom::CustomValue;
+use super::datetime::DateTime;
 
 // imports for intra-doc links
 #[cfg(doc)]
@@ -22,13 +28,80 @@ use std::collections::HashMap;
 /// traits is recommended, as they provide a consistent interface across all types, including
 /// [`Vec`], arrays, slices, tuples, [`HashMap`]s, and even custom structs, when using the
 /// [`TryFromValue`] and [`TryToValue`] derive macros (or implementing the traits manually).
-#[derive(Clone, Debug, PartialEq, Serialize)]
-#[serde(rename = "value")]
+///
+/// For quickly building and inspecting values by hand, [`Value`] also implements [`From`] for
+/// primitive types, and the contents of `<struct>` and `<array>` values can be accessed with
+/// indexing (which panics if the value has a different type or if there is no such element):
+///
+/// ```
+/// use dxr::{Member, Struct, Value};
+///
+/// let files = Value::from(vec![Value::from("README.md"), Value::from(42)]);
+/// let value = Value::structure(Struct::new(vec![Member::new(String::from("files"), files)]));
+///
+/// assert_eq!(value["files"][0], Value::from("README.md"));
+/// assert_eq!(value["files"][1].as_i32(), Some(42));
+/// ```
+#[derive(Clone, PartialEq)]
 pub struct Value {
-    #[serde(rename = "$value")]
     value: Type,
 }
 
+// custom Serialize impl: string values can optionally be serialized without type element, and
+// non-standard types can optionally be serialized with Apache XML-RPC extension elements
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let options = current_serialize_options();
+
+        let mut state = serializer.serialize_struct("value", 1)?;
+        match &self.value {
+            // empty strings without type element are serialized as self-closing "<value/>" elements
+            Type::String(string) if options.omit_string_tags(string) => state.serialize_field("$text", string)?,
+            Type::Double(double) if !double.is_finite() && options.non_finite_policy() == NonFinitePolicy::AsString => {
+                state.serialize_field("string", &super::ser_de::double::to_string(*double))?
+            },
+            #[cfg(feature = "i8")]
+            Type::Long(long) if options.use_apache_extensions() => {
+                state.serialize_field("ex:i8", &ExtensionElement::new(Some(long)))?
+            },
+            #[cfg(feature = "nil")]
+            Type::Nil if options.use_apache_extensions() => {
+                state.serialize_field("ex:nil", &ExtensionElement::<()>::new(None))?
+            },
+            Type::Custom(custom) => state.serialize_field(custom.tag(), custom.text())?,
+            value => state.serialize_field("$value", value)?,
+        }
+        state.end()
+    }
+}
+
+/// XML namespace of the Apache XML-RPC extension types
+#[cfg(any(feature = "i8", feature = "nil"))]
+const APACHE_EXTENSIONS_NAMESPACE: &str = "http://ws.apache.org/xmlrpc/namespaces/extensions";
+
+// element for Apache XML-RPC extension types, which declares the "ex" namespace prefix locally
+#[cfg(any(feature = "i8", feature = "nil"))]
+#[derive(Serialize)]
+struct ExtensionElement<T> {
+    #[serde(rename = "@xmlns:ex")]
+    namespace: &'static str,
+    #[serde(rename = "$text", skip_serializing_if = "Option::is_none")]
+    value: Option<T>,
+}
+
+#[cfg(any(feature = "i8", feature = "nil"))]
+impl<T> ExtensionElement<T> {
+    fn new(value: Option<T>) -> Self {
+        ExtensionElement {
+            namespace: APACHE_EXTENSIONS_NAMESPACE,
+            value,
+        }
+    }
+}
+
 impl Value {
     fn new(value: Type) -> Value {
         Value { value }
@@ -38,6 +111,14 @@ impl Value {
         &self.value
     }
 
+    pub(crate) fn from_custom(value: CustomValue) -> Value {
+        Value::new(Type::Custom(value))
+    }
+
+    pub(crate) fn from_datetime(value: DateTime) -> Value {
+        Value::new(Type::DateTime(value))
+    }
+
     /// constructor for `<i4>` values (signed 32-bit integers)
     pub fn i4(value: i32) -> Value {
         Value::new(Type::Integer(value))
@@ -71,8 +152,25 @@ impl Value {
     ///
     /// Note that the date & time format used by XML-RPC does not include sub-second precision, nor
     /// any timezone information.
-    pub fn datetime(value: NaiveDateTime) -> Value {
-        Value::new(Type::DateTime(value))
+    #[cfg(feature = "chrono")]
+    pub fn datetime(value: chrono::NaiveDateTime) -> Value {
+        Value::from_datetime(DateTime::from(value))
+    }
+
+    /// constructor for `<dateTime.iso8601>` values from [`time::PrimitiveDateTime`] values
+    #[cfg(feature = "time")]
+    pub fn primitive_datetime(value: time::PrimitiveDateTime) -> Value {
+        Value::from_datetime(DateTime::from(value))
+    }
+
+    /// constructor for `<dateTime.iso8601>` values from [`time::OffsetDateTime`] values
+    ///
+    /// The date & time value is normalized to UTC, since XML-RPC date & time values do not include
+    /// timezone information.
+    #[cfg(feature = "time")]
+    pub fn offset_datetime(value: time::OffsetDateTime) -> Value {
+        let utc = value.to_offset(time::UtcOffset::UTC);
+        Value::primitive_datetime(time::PrimitiveDateTime::new(utc.date(), utc.time()))
     }
 
     /// constructor for `<base64>` values (base64-encoded arbitrary bytes)
@@ -80,11 +178,13 @@ impl Value {
         Value::new(Type::Base64(value))
     }
 
-    pub(crate) fn structure(value: Struct) -> Value {
+    /// constructor for `<struct>` values
+    pub fn structure(value: Struct) -> Value {
         Value::new(Type::Struct { members: value.members })
     }
 
-    pub(crate) fn array(value: Array) -> Value {
+    /// constructor for `<array>` values
+    pub fn array(value: Array) -> Value {
         Value::new(Type::Array { data: value.data })
     }
 
@@ -105,26 +205,31 @@ impl Value {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) enum Type {
     #[serde(rename = "i4", alias = "int")]
     Integer(#[serde(rename = "$value")] i32),
     #[cfg(feature = "i8")]
-    #[serde(rename = "i8")]
+    #[serde(rename = "i8", alias = "ex:i8")]
     Long(#[serde(rename = "$value")] i64),
     #[serde(rename = "boolean", with = "super::ser_de::boolean")]
     Boolean(#[serde(rename = "$value")] bool),
     #[serde(rename = "string")]
     String(#[serde(rename = "$value")] String),
-    #[serde(rename = "double")]
+    #[serde(rename = "double", with = "super::ser_de::double")]
     Double(#[serde(rename = "$value")] f64),
     #[serde(rename = "dateTime.iso8601", with = "super::ser_de::datetime")]
-    DateTime(#[serde(rename = "$value")] NaiveDateTime),
+    DateTime(#[serde(rename = "$value")] DateTime),
     #[serde(rename = "base64", with = "super::ser_de::base64")]
     Base64(#[serde(rename = "$value")] Vec<u8>),
     #[serde(rename = "struct")]
     Struct {
-        #[serde(default, rename = "member")]
+        #[serde(
+            default,
+            rename = "member",
+            serialize_with = "super::ser_de::double::serialize_members",
+            deserialize_with = "super::ser_de::members::deserialize"
+        )]
         members: Vec<Member>,
     },
     #[serde(rename = "array")]
@@ -133,8 +238,33 @@ pub(crate) enum Type {
         data: ArrayData,
     },
     #[cfg(feature = "nil")]
-    #[serde(rename = "nil")]
+    #[serde(rename = "nil", alias = "ex:nil")]
     Nil,
+    // values with custom types are serialized by the Serialize impl of Value
+    #[serde(skip)]
+    Custom(CustomValue),
+}
+
+// custom PartialEq impl: the order of struct members is irrelevant
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Type::Integer(a), Type::Integer(b)) => a == b,
+            #[cfg(feature = "i8")]
+            (Type::Long(a), Type::Long(b)) => a == b,
+            (Type::Boolean(a), Type::Boolean(b)) => a == b,
+            (Type::String(a), Type::String(b)) => a == b,
+            (Type::Double(a), Type::Double(b)) => a == b,
+            (Type::DateTime(a), Type::DateTime(b)) => a == b,
+            (Type::Base64(a), Type::Base64(b)) => a == b,
+            (Type::Struct { members: a }, Type::Struct { members: b }) => members_eq(a, b),
+            (Type::Array { data: a }, Type::Array { data: b }) => a == b,
+            #[cfg(feature = "nil")]
+            (Type::Nil, Type::Nil) => true,
+            (Type::Custom(a), Type::Custom(b)) => a == b,
+            _ => false,
+        }
+    }
 }
 
 impl Type {
@@ -152,45 +282,96 @@ impl Type {
             Type::Array { .. } => "array",
             #[cfg(feature = "nil")]
             Type::Nil => "nil",
+            Type::Custom(custom) => custom.tag(),
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// # XML-RPC struct type
+///
+/// The [`Struct`] type is the Rust equivalent of the contents of XML-RPC `<struct>` values, which
+/// consist of a list of named [`Member`]s. Members are kept in the order in which they were passed
+/// to [`Struct::new`] (or in which they appeared in the deserialized XML document).
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(rename = "struct")]
-pub(crate) struct Struct {
-    #[serde(default, rename = "member")]
+pub struct Struct {
+    #[serde(
+        default,
+        rename = "member",
+        serialize_with = "super::ser_de::double::serialize_members",
+        deserialize_with = "super::ser_de::members::deserialize"
+    )]
     members: Vec<Member>,
 }
 
 impl Struct {
-    pub(crate) fn new(mut members: Vec<Member>) -> Struct {
-        members.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+    /// constructor for [`Struct`] values from a list of members
+    ///
+    /// The order of members is preserved when serializing the struct, since some XML-RPC servers
+    /// are sensitive to the order of struct members.
+    pub fn new(members: Vec<Member>) -> Struct {
         Struct { members }
     }
+
+    /// getter method for the members of the struct
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// getter method for the value of the member with the given name
+    ///
+    /// If the struct contains multiple members with the same name, the value of the first one is
+    /// returned.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.members
+            .iter()
+            .find(|member| member.name() == name)
+            .map(Member::inner)
+    }
+
+    pub(crate) fn members_mut(&mut self) -> &mut Vec<Member> {
+        &mut self.members
+    }
+
+    pub(crate) fn into_members(self) -> Vec<Member> {
+        self.members
+    }
 }
 
 // custom PartialEq impl: the order of struct members is irrelevant
 impl PartialEq for Struct {
     fn eq(&self, other: &Self) -> bool {
-        // fast path: different numbers of members
-        if self.members.len() != other.members.len() {
-            return false;
-        }
+        members_eq(&self.members, &other.members)
+    }
+}
 
-        // sort members by name before comparing
-        let mut self_members: Vec<&Member> = self.members.iter().collect();
-        let mut other_members: Vec<&Member> = other.members.iter().collect();
-        self_members.sort_by(|a, b| a.name.name.cmp(&b.name.name));
-        other_members.sort_by(|a, b| a.name.name.cmp(&b.name.name));
+fn members_eq(a: &[Member], b: &[Member]) -> bool {
+    // fast path: different numbers of members
+    if a.len() != b.len() {
+        return false;
+    }
 
-        self_members == other_members
+    // fast path: members are in the same order
+    if a == b {
+        return true;
     }
+
+    // sort members by name before comparing
+    let mut a_members: Vec<&Member> = a.iter().collect();
+    let mut b_members: Vec<&Member> = b.iter().collect();
+    a_members.sort_by(|x, y| x.name.name.cmp(&y.name.name));
+    b_members.sort_by(|x, y| x.name.name.cmp(&y.name.name));
+
+    a_members == b_members
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// # XML-RPC struct member type
+///
+/// The [`Member`] type is the Rust equivalent of members of XML-RPC `<struct>` values, which
+/// consist of a name and a [`Value`].
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
 #[serde(rename = "member")]
-pub(crate) struct Member {
+pub struct Member {
     name: MemberName,
     value: Value,
 }
@@ -198,46 +379,92 @@ pub(crate) struct Member {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename = "name")]
 struct MemberName {
-    #[serde(rename = "$value")]
-    name: String,
+    // empty names are serialized as self-closing <name/> elements
+    #[serde(
+        default = "super::ser_de::member_name::empty",
+        rename = "$value",
+        with = "super::ser_de::member_name"
+    )]
+    name: Arc<str>,
 }
 
 impl Member {
-    pub(crate) fn new(name: String, value: Value) -> Member {
+    /// constructor for struct members from name and value
+    pub fn new(name: String, value: Value) -> Member {
+        Member::with_shared_name(Arc::from(name), value)
+    }
+
+    /// constructor for struct members from a shared name and value
+    ///
+    /// Sharing names avoids allocating them again for every member with the same name (i.e. for
+    /// large arrays of structs). Names of members in deserialized structs can be shared
+    /// automatically (see [`DeserializeOptions::intern_member_names`]).
+    ///
+    /// [`DeserializeOptions::intern_member_names`]: crate::DeserializeOptions::intern_member_names
+    pub fn with_shared_name(name: Arc<str>, value: Value) -> Member {
         Member {
             name: MemberName { name },
             value,
         }
     }
 
-    pub(crate) fn name(&self) -> &str {
-        self.name.name.as_str()
+    /// getter method for the name of the struct member
+    pub fn name(&self) -> &str {
+        &self.name.name
     }
 
-    pub(crate) fn inner(&self) -> &Value {
+    /// getter method for the shared name of the struct member
+    pub fn shared_name(&self) -> &Arc<str> {
+        &self.name.name
+    }
+
+    /// getter method for the value of the struct member
+    pub fn inner(&self) -> &Value {
         &self.value
     }
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// # XML-RPC array type
+///
+/// The [`Array`] type is the Rust equivalent of the contents of XML-RPC `<array>` values, which
+/// consist of a list of [`Value`]s of possibly different types.
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
 #[serde(rename = "array")]
-pub(crate) struct Array {
+pub struct Array {
     #[serde(default)]
     data: ArrayData,
 }
 
 impl Array {
-    pub(crate) fn new(values: Vec<Value>) -> Array {
+    /// constructor for [`Array`] values from a list of values
+    pub fn new(values: Vec<Value>) -> Array {
         Array {
             data: ArrayData { values },
         }
     }
+
+    /// getter method for the values contained in the array
+    pub fn values(&self) -> &[Value] {
+        self.data.inner()
+    }
+
+    pub(crate) fn values_mut(&mut self) -> &mut Vec<Value> {
+        &mut self.data.values
+    }
+
+    pub(crate) fn into_values(self) -> Vec<Value> {
+        self.data.values
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename = "data")]
 pub(crate) struct ArrayData {
-    #[serde(default, rename = "value")]
+    #[serde(
+        default,
+        rename = "value",
+        serialize_with = "super::ser_de::double::serialize_values"
+    )]
     values: Vec<Value>,
 }
 
@@ -285,12 +512,16 @@ impl MethodCall {
     pub fn params(self) -> Vec<Value> {
         self.params.params.into_iter().map(|param| param.value).collect()
     }
+
+    pub(crate) fn param_values(&self) -> impl Iterator<Item = &Value> {
+        self.params.params.iter().map(|param| &param.value)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename = "methodName")]
 struct MethodName {
-    #[serde(rename = "$value")]
+    #[serde(default, rename = "$value")]
     name: String,
 }
 
@@ -298,11 +529,14 @@ struct MethodName {
 ///
 /// The [`MethodResponse`] type is the Rust equivalent of the contents of an XML-RPC response.
 ///
-/// It contains exactly one return value as a parameter.
+/// It contains exactly one return value as a parameter. Additional parameters that were sent by
+/// non-conformant servers are only kept if they are allowed by the [`MultipleParams`] policy.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
-#[serde(rename = "methodResponse")]
+#[serde(rename = "methodResponse", try_from = "RawMethodResponse")]
 pub struct MethodResponse {
     params: ResponseParameters,
+    #[serde(skip)]
+    extra: Vec<Value>,
 }
 
 impl MethodResponse {
@@ -312,6 +546,7 @@ impl MethodResponse {
             params: ResponseParameters {
                 params: ResponseParameter { params: value },
             },
+            extra: Vec::new(),
         }
     }
 
@@ -319,6 +554,79 @@ impl MethodResponse {
     pub fn inner(self) -> Value {
         self.params.params.params
     }
+
+    /// getter method for all returned values
+    ///
+    /// This contains more than one value only for responses of non-conformant servers that were
+    /// deserialized with the [`MultipleParams::All`] policy.
+    pub fn into_params(self) -> Vec<Value> {
+        let mut params = vec![self.params.params.params];
+        params.extend(self.extra);
+        params
+    }
+
+    pub(crate) fn value(&self) -> &Value {
+        &self.params.params.params
+    }
+}
+
+// method response with optional parameters, which are accepted unless in strict mode
+#[derive(Deserialize)]
+#[serde(rename = "methodResponse")]
+struct RawMethodResponse {
+    #[serde(default)]
+    params: Option<RawResponseParameters>,
+    // (malformed) fault responses must not be mistaken for empty responses
+    #[serde(default)]
+    fault: Option<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "params")]
+struct RawResponseParameters {
+    #[serde(default, rename = "param")]
+    params: Vec<ResponseParameter>,
+}
+
+impl TryFrom<RawMethodResponse> for MethodResponse {
+    type Error = String;
+
+    fn try_from(raw: RawMethodResponse) -> Result<Self, Self::Error> {
+        if raw.fault.is_some() {
+            return Err(String::from("unexpected field `fault`"));
+        }
+
+        let mut params = raw.params.map(|params| params.params).unwrap_or_default().into_iter();
+
+        match params.next() {
+            Some(first) => {
+                let extra: Vec<Value> = params.map(|param| param.params).collect();
+
+                let extra = match current_options().multiple_params_policy() {
+                    _ if extra.is_empty() => extra,
+                    MultipleParams::Error => {
+                        return Err(format!("expected one response parameter, got {}", extra.len() + 1))
+                    },
+                    MultipleParams::First => Vec::new(),
+                    MultipleParams::All => extra,
+                };
+
+                Ok(MethodResponse {
+                    params: ResponseParameters { params: first },
+                    extra,
+                })
+            },
+            None if current_options().missing_params() => {
+                #[cfg(feature = "nil")]
+                let value = Value::nil();
+                #[cfg(not(feature = "nil"))]
+                let value = Value::array(Array::new(Vec::new()));
+
+                Ok(MethodResponse::new(value))
+            },
+            None => Err(String::from("missing field `params`")),
+        }
+    }
 }
 
 /// # XML-RPC fault response type
@@ -0,0 +1,25 @@
+// imports for intra-doc links
+#[cfg(doc)]
+use crate::{TryFromParams, TryToParams};
+
+/// # Variadic tail of method call parameters
+///
+/// Some XML-RPC methods take a fixed number of leading parameters, followed by an arbitrary number
+/// of trailing parameters (for example, the `d.multicall2` or `f.multicall` methods of rtorrent).
+///
+/// Parameter lists like this can be expressed with tuples that have a [`Variadic`] as their last
+/// member. The [`TryToParams`] and [`TryFromParams`] traits are implemented for these tuples
+/// with up to fifteen leading members:
+///
+/// ```
+/// use dxr::{TryFromParams, TryToParams, Variadic};
+///
+/// let params = ("", "main", Variadic(vec!["d.hash=", "d.name="])).try_to_params().unwrap();
+/// assert_eq!(params.len(), 4);
+///
+/// let (target, view, Variadic(commands)): (String, String, Variadic<String>) =
+///     TryFromParams::try_from_params(&params).unwrap();
+/// assert_eq!(commands, vec!["d.hash=", "d.name="]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Variadic<T>(pub Vec<T>);
@@ -1,8 +1,25 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+use std::thread::LocalKey;
+
 use quick_xml::de::DeError;
+use quick_xml::events::Event;
 use quick_xml::se::Serializer;
+use quick_xml::{Error as QuickXmlError, Reader, Writer};
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::DxrError;
+use crate::values::custom::Normalizer;
+use crate::values::datetime::DateTime;
+use crate::values::{CustomScalarType, XML_RPC_DATE_FORMAT};
+
+mod writer;
+pub use writer::*;
+
 /// Custom function for serializing values as XML.
 ///
 /// This function uses a custom [`Serializer`] that expands empty XML elements
@@ -12,25 +29,979 @@ use serde::{Deserialize, Serialize};
 ///
 /// This should be a drop-in replacement for [`quick_xml::se::to_string`].
 pub fn serialize_xml<T>(value: &T) -> Result<String, DeError>
+where
+    T: Serialize,
+{
+    serialize_xml_with(value, SerializeOptions::default())
+}
+
+/// Custom function for serializing values as XML with custom [`SerializeOptions`].
+pub fn serialize_xml_with<T>(value: &T, options: SerializeOptions) -> Result<String, DeError>
 where
     T: Serialize,
 {
     let mut buf = String::new();
+    serialize_xml_into_buf_with(value, &mut buf, options)?;
+    Ok(buf)
+}
+
+/// Function for serializing values as XML into an existing buffer.
+///
+/// This function works like [`serialize_xml`], but it appends the XML document to a caller-provided
+/// buffer (any type that implements [`std::fmt::Write`], like [`String`] or `bytes::BytesMut`)
+/// instead of allocating a new [`String`]. This makes it possible to reuse buffers across calls.
+pub fn serialize_xml_into_buf<T, W>(value: &T, buf: &mut W) -> Result<(), DeError>
+where
+    T: Serialize,
+    W: fmt::Write,
+{
+    serialize_xml_into_buf_with(value, buf, SerializeOptions::default())
+}
+
+/// Function for serializing values as XML into an existing buffer with custom [`SerializeOptions`].
+///
+/// Documents are written to the buffer directly while they are serialized, unless emitting CDATA
+/// sections is enabled (see [`SerializeOptions::cdata_strings`]), which requires the document to
+/// be serialized into a temporary buffer first.
+pub fn serialize_xml_into_buf_with<T, W>(value: &T, buf: &mut W, options: SerializeOptions) -> Result<(), DeError>
+where
+    T: Serialize,
+    W: fmt::Write,
+{
+    match options.cdata_strings {
+        Some(min_length) => {
+            let mut xml = String::new();
+            serialize_escaped(value, &mut xml, options)?;
+            Ok(buf.write_str(&emit_cdata(&xml, min_length)?)?)
+        },
+        None => serialize_escaped(value, buf, options),
+    }
+}
+
+/// Function for serializing values as XML into a writer.
+///
+/// This function works like [`serialize_xml`], but it writes the XML document directly into an
+/// implementation of [`std::io::Write`] (like a [`Vec<u8>`], a file, or a socket) instead of
+/// returning a [`String`]. The writer is not flushed and should be buffered, since the document is
+/// written in many small pieces.
+pub fn serialize_xml_into<T, W>(value: &T, writer: W) -> Result<(), DeError>
+where
+    T: Serialize,
+    W: io::Write,
+{
+    serialize_xml_into_with(value, writer, SerializeOptions::default())
+}
+
+/// Function for serializing values as XML into a writer with custom [`SerializeOptions`].
+pub fn serialize_xml_into_with<T, W>(value: &T, writer: W, options: SerializeOptions) -> Result<(), DeError>
+where
+    T: Serialize,
+    W: io::Write,
+{
+    let mut adapter = IoWriter {
+        inner: writer,
+        error: None,
+    };
+
+    serialize_xml_into_buf_with(value, &mut adapter, options).map_err(|error| match adapter.error.take() {
+        Some(io_error) => DeError::from(QuickXmlError::from(io_error)),
+        None => error,
+    })
+}
+
+// adapter for using implementations of io::Write as the output of the serializer
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+fn serialize_escaped<T, W>(value: &T, buf: &mut W, options: SerializeOptions) -> Result<(), DeError>
+where
+    T: Serialize,
+    W: fmt::Write,
+{
+    let mut writer = EscapingWriter {
+        inner: buf,
+        forbidden: false,
+    };
 
     // initialize custom serializer that expands empty elements
-    let mut serializer = Serializer::new(&mut buf);
+    let mut serializer = Serializer::new(&mut writer);
     serializer.expand_empty_elements(true);
 
-    value.serialize(serializer)?;
-    Ok(buf)
+    let _guard = OptionsGuard::set(&SERIALIZE_OPTIONS, options);
+    let result = value.serialize(serializer);
+
+    if writer.forbidden {
+        return Err(forbidden_character());
+    }
+
+    result.map(|_| ())
+}
+
+// The serializer only escapes markup characters, but not control characters. Since they can only
+// occur in text content (and never in markup), they are escaped (or rejected) when they are written.
+struct EscapingWriter<W> {
+    inner: W,
+    forbidden: bool,
+}
+
+impl<W: fmt::Write> fmt::Write for EscapingWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+
+        for (index, c) in s
+            .char_indices()
+            .filter(|(_, c)| needs_reference(*c) || is_forbidden(*c))
+        {
+            if is_forbidden(c) {
+                self.forbidden = true;
+                return Err(fmt::Error);
+            }
+
+            self.inner.write_str(&s[start..index])?;
+            write!(self.inner, "&#x{:X};", c as u32)?;
+            start = index + c.len_utf8();
+        }
+
+        self.inner.write_str(&s[start..])
+    }
+}
+
+// characters that are not preserved by XML parsers unless they are written as character references
+fn needs_reference(c: char) -> bool {
+    c == '\r'
+}
+
+// control characters that are not allowed in XML 1.0 documents, not even as character references
+pub(crate) fn is_forbidden(c: char) -> bool {
+    matches!(c, '\0'..='\x1f') && !matches!(c, '\t' | '\n' | '\r')
+}
+
+fn forbidden_character() -> DeError {
+    DeError::Custom(String::from(
+        "NUL and other control characters (except tab, line feed, and carriage return) cannot be represented in XML",
+    ))
+}
+
+// re-writes the text content of string values as CDATA sections
+fn emit_cdata(xml: &str, min_length: usize) -> Result<String, DeError> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+
+    let mut in_string = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Eof => break,
+            // CDATA sections cannot contain character references
+            Event::Text(text) if in_string => {
+                let unescaped = text.unescape()?;
+                if unescaped.len() >= min_length && !unescaped.chars().any(needs_reference) {
+                    write_cdata(writer.get_mut(), &unescaped);
+                } else {
+                    writer.write_event(Event::Text(text))?;
+                }
+            },
+            event => {
+                in_string =
+                    matches!(&event, Event::Start(start) if matches!(start.name().as_ref(), b"string" | b"value"));
+                writer.write_event(event)?;
+            },
+        }
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|error| DeError::from(QuickXmlError::from(error.utf8_error())))
+}
+
+fn write_cdata(buf: &mut Vec<u8>, text: &str) {
+    // the "]]>" sequence terminates CDATA sections, so it is split across two sections
+    buf.extend(b"<![CDATA[");
+    buf.extend(text.replace("]]>", "]]]]><![CDATA[>").as_bytes());
+    buf.extend(b"]]>");
+}
+
+/// # Options for serializing XML-RPC documents
+///
+/// By default, all values are serialized with an explicit type element. Some XML-RPC
+/// implementations (for example, many PHP servers) emit string values without the optional
+/// `<string>` element instead (i.e. `<value>hello</value>` instead of
+/// `<value><string>hello</string></value>`), which is valid according to the spec. Some legacy
+/// endpoints only accept one of the two forms, so the form that is emitted can be selected with a
+/// [`StringFormat`].
+///
+/// The non-standard `<i8>` and `<nil/>` values can optionally be serialized with the namespaced
+/// extension elements that are used by Apache XML-RPC (i.e. `<ex:i8>` and `<ex:nil/>`), which
+/// are expected by some Java-based XML-RPC servers.
+///
+/// The format of `dateTime.iso8601` values can be selected from a list of commonly used formats, or
+/// customized with a format string (see [`DateTimeFormat`]). The default format is
+/// [`XML_RPC_DATE_FORMAT`].
+///
+/// The XML-RPC spec does not allow `<double>` values that are not finite (i.e. NaN or infinite), so
+/// the way they are handled can be configured with a [`NonFinitePolicy`].
+///
+/// Markup characters (`&`, `<`, `>`, and quotes) in text content are always escaped. Carriage
+/// returns (which would otherwise be normalized to line feeds by XML parsers) are written as numeric
+/// character references (`&#xD;`). Other control characters (except tab and line feed) cannot be
+/// represented in XML 1.0 documents at all, not even as character references, so serializing text
+/// that contains them fails with an error. String values can optionally be written as CDATA
+/// sections instead, which is more compact for large text blobs that contain many markup characters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SerializeOptions {
+    strings: StringFormat,
+    apache_extensions: bool,
+    datetime_format: &'static str,
+    non_finite: NonFinitePolicy,
+    cdata_strings: Option<usize>,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions {
+            strings: StringFormat::Typed,
+            apache_extensions: false,
+            datetime_format: XML_RPC_DATE_FORMAT,
+            non_finite: NonFinitePolicy::Error,
+            cdata_strings: None,
+        }
+    }
+}
+
+/// # Forms for serializing string values
+///
+/// Empty string values without a `<string>` element are written as self-closing `<value/>`
+/// elements, which are not understood by some parsers. For this reason, the
+/// [`StringFormat::Untyped`] form keeps the `<string>` element of empty strings.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StringFormat {
+    /// `<value><string>hello</string></value>` (default)
+    #[default]
+    Typed,
+    /// `<value>hello</value>`, except for empty strings (`<value><string></string></value>`)
+    Untyped,
+    /// `<value>hello</value>`, including empty strings
+    Bare,
+}
+
+/// # Policy for serializing `<double>` values that are not finite
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NonFinitePolicy {
+    /// serialization fails with an error (default)
+    #[default]
+    Error,
+    /// values are serialized as `<string>` values (`NaN`, `inf`, and `-inf`)
+    AsString,
+    /// struct members and array elements are omitted (other values result in an error)
+    Skip,
+}
+
+/// # Formats for serializing `dateTime.iso8601` values
+///
+/// XML-RPC implementations disagree about the format of `dateTime.iso8601` values: the format from
+/// the XML-RPC specification (without dashes, i.e. `20230401T12:00:00`) is expected by rtorrent
+/// and the Python `xmlrpc` module, while other servers require the extended ISO 8601 format (with
+/// dashes) or full RFC 3339 timestamps with a timezone designator.
+///
+/// Since XML-RPC date & time values are timezone-unaware, the [`DateTimeFormat::Rfc3339`] format
+/// assumes that values are in UTC (which is also how values with timezone offsets are normalized
+/// when they are deserialized).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DateTimeFormat {
+    /// format from the XML-RPC specification: `20230401T12:00:00` (default)
+    #[default]
+    XmlRpc,
+    /// extended ISO 8601 format: `2023-04-01T12:00:00`
+    Iso8601,
+    /// RFC 3339 format in UTC: `2023-04-01T12:00:00Z`
+    Rfc3339,
+    /// custom format string (see [`DateTimeFormat::custom`])
+    Custom(CustomDateTimeFormat),
+}
+
+/// # Custom format string for `dateTime.iso8601` values
+///
+/// Custom format strings are checked when they are constructed with [`DateTimeFormat::custom`], so
+/// serializing values with them cannot fail.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CustomDateTimeFormat(&'static str);
+
+impl CustomDateTimeFormat {
+    /// getter for the format string
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl DateTimeFormat {
+    /// constructor for a custom date & time format from a format string
+    ///
+    /// The format string can contain the following `strftime`-like specifiers, which are rendered
+    /// the same way as by `chrono`:
+    ///
+    /// - `%Y` (year), `%y` (year modulo 100), `%m` (month), `%d` (day), and `%F` (`%Y-%m-%d`)
+    /// - `%H` (hour), `%M` (minute), `%S` (second), and `%T` (`%H:%M:%S`)
+    /// - `%f` (nanoseconds), `%3f` / `%6f` / `%9f` (fractional seconds with a fixed number of
+    ///   digits), `%.3f` / `%.6f` / `%.9f` (the same, with a leading dot), and `%.f` (fractional
+    ///   seconds with a leading dot and as many digits as necessary, if they are not zero)
+    /// - `%%` (a literal `%` character)
+    ///
+    /// Format strings that are malformed or that contain other specifiers (like the timezone
+    /// specifier `%z`, which cannot be rendered for timezone-unaware values) are rejected.
+    pub fn custom(format: &'static str) -> Result<DateTimeFormat, DxrError> {
+        DateTime::check_format(format).map_err(DxrError::invalid_data)?;
+        Ok(DateTimeFormat::Custom(CustomDateTimeFormat(format)))
+    }
+
+    /// getter for the format string of this date & time format
+    pub const fn format_string(&self) -> &'static str {
+        match self {
+            DateTimeFormat::XmlRpc => XML_RPC_DATE_FORMAT,
+            DateTimeFormat::Iso8601 => "%Y-%m-%dT%H:%M:%S",
+            DateTimeFormat::Rfc3339 => "%Y-%m-%dT%H:%M:%SZ",
+            DateTimeFormat::Custom(format) => format.as_str(),
+        }
+    }
+}
+
+impl SerializeOptions {
+    /// method for selecting the form in which string values are serialized
+    pub const fn strings(mut self, format: StringFormat) -> Self {
+        self.strings = format;
+        self
+    }
+
+    /// method for emitting Apache XML-RPC extension elements for `<i8>` and `<nil/>` values
+    pub const fn apache_extensions(mut self, apache: bool) -> Self {
+        self.apache_extensions = apache;
+        self
+    }
+
+    /// method for selecting the format of `dateTime.iso8601` values
+    ///
+    /// This can be one of the commonly used formats, or a custom format string that was checked
+    /// with [`DateTimeFormat::custom`].
+    pub const fn datetime(mut self, format: DateTimeFormat) -> Self {
+        self.datetime_format = format.format_string();
+        self
+    }
+
+    /// method for overriding the policy for serializing NaN and infinite `<double>` values
+    pub const fn non_finite_doubles(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
+    }
+
+    /// method for emitting string values with at least `min_length` bytes as CDATA sections
+    ///
+    /// Strings that contain carriage returns are always escaped instead, since CDATA sections
+    /// cannot contain character references.
+    pub const fn cdata_strings(mut self, min_length: Option<usize>) -> Self {
+        self.cdata_strings = min_length;
+        self
+    }
+
+    pub(crate) fn non_finite_policy(&self) -> NonFinitePolicy {
+        self.non_finite
+    }
+
+    pub(crate) fn format_datetime(&self) -> &'static str {
+        self.datetime_format
+    }
+
+    pub(crate) fn omit_string_tags(&self, string: &str) -> bool {
+        match self.strings {
+            StringFormat::Typed => false,
+            StringFormat::Untyped => !string.is_empty(),
+            StringFormat::Bare => true,
+        }
+    }
+
+    #[cfg_attr(not(any(feature = "i8", feature = "nil")), allow(dead_code))]
+    pub(crate) fn use_apache_extensions(&self) -> bool {
+        self.apache_extensions
+    }
+}
+
+/// Custom function for serializing values as indented, human-readable XML.
+///
+/// This function works like [`serialize_xml`], but it puts nested XML elements on separate lines
+/// and indents them by two spaces per level of nesting, which is useful for logging and debugging.
+/// Elements that only contain text (i.e. scalar values and struct member names) are kept on one
+/// line, so the contents of string values are not modified.
+pub fn serialize_xml_pretty<T>(value: &T) -> Result<String, DeError>
+where
+    T: Serialize,
+{
+    pretty_print_xml(&serialize_xml(value)?)
+}
+
+/// Custom function for re-formatting an existing XML document as indented, human-readable XML.
+///
+/// This function applies the same formatting as [`serialize_xml_pretty`] to documents that were
+/// already serialized (for example, with [`WriteXml`]).
+pub fn pretty_print_xml(xml: &str) -> Result<String, DeError> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+
+    let mut depth: usize = 0;
+    let mut first = true;
+    let mut closing = false;
+
+    loop {
+        let event = reader.read_event()?;
+
+        match &event {
+            Event::Eof => break,
+            Event::Start(_) | Event::Empty(_) | Event::Comment(_) | Event::PI(_) => {
+                if !first {
+                    write_line_break(&mut writer, depth);
+                }
+                // self-closing elements are complete, just like elements that were just closed
+                closing = !matches!(event, Event::Start(_));
+            },
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                if closing {
+                    write_line_break(&mut writer, depth);
+                }
+                closing = true;
+            },
+            _ => closing = false,
+        }
+
+        if let Event::Start(_) = &event {
+            depth += 1;
+        }
+
+        first = false;
+        writer.write_event(event)?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|error| DeError::from(QuickXmlError::from(error.utf8_error())))
+}
+
+fn write_line_break(writer: &mut Writer<Vec<u8>>, depth: usize) {
+    let buf = writer.get_mut();
+    buf.push(b'\n');
+    buf.extend(std::iter::repeat(b' ').take(2 * depth));
+}
+
+/// # Options for deserializing XML-RPC documents
+///
+/// The [`DeserializeOptions`] type selects between strict compliance with the XML-RPC
+/// specification and lenient handling of quirks found in real-world XML-RPC implementations:
+///
+/// - unknown (i.e. non-standard) type elements inside `<value>` elements, which are deserialized
+///   as untyped string values in lenient mode (custom types that were registered with
+///   [`DeserializeOptions::custom_scalars`] are always accepted),
+/// - stray whitespace around the contents of scalar values (like `<i4> 42 </i4>`) and next to the
+///   type element of a scalar value (like `<value> <i4>42</i4> </value>`),
+/// - boolean values that are spelled out (i.e. `<boolean>true</boolean>` or
+///   `<boolean>FALSE</boolean>` instead of `1` and `0`, case-insensitive),
+/// - `<double>` values that are not finite (i.e. `NaN`, `inf`, or `-inf`), which are not allowed
+///   by the spec,
+/// - method responses without a `<params>` element (or with an empty `<params>` element), which
+///   are deserialized as a response with an empty return value (`<nil/>` if the `nil` feature is
+///   enabled, and an empty array otherwise), which can be converted into `()`.
+///
+/// Structs that contain multiple members with the same name are handled according to the
+/// configured [`DuplicateMembers`] policy. By default, the last of these members wins (which
+/// matches the behaviour of most other XML-RPC implementations), but in strict mode, these
+/// structs are rejected.
+///
+/// Method responses that contain more than one parameter are handled according to the configured
+/// [`MultipleParams`] policy. They are rejected by default, and also in lenient mode, since silently
+/// discarding return values could hide bugs.
+///
+/// The default options match the behaviour of [`deserialize_xml`]: stray whitespace, non-finite
+/// doubles, and missing parameters (i.e. responses of methods that do not return anything useful)
+/// are accepted, but unknown types and spelled-out boolean values are rejected.
+///
+/// Independent of these settings, documents that are parsed from untrusted sources must not be able
+/// to exhaust resources of the process that is parsing them. For this reason, documents that
+/// contain a document type declaration (`<!DOCTYPE ...>`) are always rejected, which also rules out
+/// custom or external entities (i.e. "billion laughs" attacks). Documents are also rejected if
+/// their elements are nested more deeply than [`DEFAULT_MAX_DEPTH`] (which would otherwise cause a
+/// stack overflow during deserialization), or if they are larger than [`DEFAULT_MAX_SIZE`] bytes.
+/// Both limits can be adjusted. Since only the predefined XML entities and character references
+/// are expanded, the contents of a document can never be larger than the document itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeserializeOptions {
+    unknown_types: bool,
+    whitespace: bool,
+    boolean_words: bool,
+    non_finite: bool,
+    missing_params: bool,
+    duplicate_members: DuplicateMembers,
+    multiple_params: MultipleParams,
+    max_depth: usize,
+    max_size: usize,
+    intern_member_names: bool,
+    custom_scalars: &'static [CustomScalarType],
+}
+
+/// # Policy for deserializing structs with duplicate member names
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DuplicateMembers {
+    /// the first member with a given name is kept, and all later ones are discarded
+    FirstWins,
+    /// the last member with a given name is kept, and all earlier ones are discarded (default)
+    #[default]
+    LastWins,
+    /// deserialization fails with an error
+    Error,
+}
+
+/// # Policy for deserializing method responses with more than one parameter
+///
+/// The XML-RPC specification requires that method responses contain exactly one `<param>`, but
+/// some non-conformant servers return multiple parameters.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MultipleParams {
+    /// deserialization fails with an error (default)
+    #[default]
+    Error,
+    /// the first parameter is used as the return value, and all other parameters are discarded
+    First,
+    /// the first parameter is used as the return value, but all parameters are kept (and can be
+    /// accessed with [`MethodResponse::into_params`](crate::MethodResponse::into_params))
+    All,
+}
+
+/// default limit for the nesting depth of XML elements (see [`DeserializeOptions::max_depth`])
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// default limit for the size of XML documents in bytes (see [`DeserializeOptions::max_size`])
+pub const DEFAULT_MAX_SIZE: usize = 128 * 1024 * 1024;
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        DeserializeOptions {
+            unknown_types: false,
+            whitespace: true,
+            boolean_words: false,
+            non_finite: true,
+            missing_params: true,
+            duplicate_members: DuplicateMembers::LastWins,
+            multiple_params: MultipleParams::Error,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_size: DEFAULT_MAX_SIZE,
+            intern_member_names: false,
+            custom_scalars: &[],
+        }
+    }
+}
+
+impl DeserializeOptions {
+    /// constructor for options that reject anything that is not valid according to the spec
+    pub const fn strict() -> Self {
+        DeserializeOptions {
+            unknown_types: false,
+            whitespace: false,
+            boolean_words: false,
+            non_finite: false,
+            missing_params: false,
+            duplicate_members: DuplicateMembers::Error,
+            multiple_params: MultipleParams::Error,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_size: DEFAULT_MAX_SIZE,
+            intern_member_names: false,
+            custom_scalars: &[],
+        }
+    }
+
+    /// constructor for options that accept all supported deviations from the spec
+    pub const fn lenient() -> Self {
+        DeserializeOptions {
+            unknown_types: true,
+            whitespace: true,
+            boolean_words: true,
+            non_finite: true,
+            missing_params: true,
+            duplicate_members: DuplicateMembers::LastWins,
+            multiple_params: MultipleParams::Error,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_size: DEFAULT_MAX_SIZE,
+            intern_member_names: false,
+            custom_scalars: &[],
+        }
+    }
+
+    /// method for allowing or rejecting unknown type elements inside `<value>` elements
+    pub const fn allow_unknown_types(mut self, allow: bool) -> Self {
+        self.unknown_types = allow;
+        self
+    }
+
+    /// method for allowing or rejecting stray whitespace around values
+    pub const fn allow_whitespace(mut self, allow: bool) -> Self {
+        self.whitespace = allow;
+        self
+    }
+
+    /// method for allowing or rejecting `true` and `false` as values of `<boolean>` elements
+    pub const fn allow_boolean_words(mut self, allow: bool) -> Self {
+        self.boolean_words = allow;
+        self
+    }
+
+    /// method for allowing or rejecting NaN and infinite `<double>` values
+    pub const fn allow_non_finite_doubles(mut self, allow: bool) -> Self {
+        self.non_finite = allow;
+        self
+    }
+
+    /// method for allowing or rejecting method responses without parameters
+    pub const fn allow_missing_params(mut self, allow: bool) -> Self {
+        self.missing_params = allow;
+        self
+    }
+
+    /// method for overriding the policy for structs with duplicate member names
+    pub const fn duplicate_members(mut self, policy: DuplicateMembers) -> Self {
+        self.duplicate_members = policy;
+        self
+    }
+
+    /// method for overriding the policy for method responses with more than one parameter
+    pub const fn multiple_params(mut self, policy: MultipleParams) -> Self {
+        self.multiple_params = policy;
+        self
+    }
+
+    /// method for overriding the maximum nesting depth of XML elements
+    ///
+    /// Every level of nested arrays or structs takes up three levels of XML elements (i.e.
+    /// `<value><array><data>` or `<value><struct><member>`). The default limit is
+    /// [`DEFAULT_MAX_DEPTH`].
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// method for overriding the maximum size of XML documents (in bytes)
+    ///
+    /// The default limit is [`DEFAULT_MAX_SIZE`].
+    pub const fn max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// method for enabling or disabling interning of struct member names
+    ///
+    /// If interning is enabled, all struct members with the same name in a deserialized document
+    /// share a single allocation for their name (see [`Member::shared_name`]). This considerably
+    /// reduces the memory usage of documents that contain large arrays of structs (i.e. full state
+    /// snapshots of rtorrent), at the cost of a hash table lookup for every member. Interning is
+    /// disabled by default.
+    ///
+    /// [`Member::shared_name`]: crate::Member::shared_name
+    pub const fn intern_member_names(mut self, intern: bool) -> Self {
+        self.intern_member_names = intern;
+        self
+    }
+
+    /// method for registering custom scalar types for deserialization
+    ///
+    /// Values with the type elements of these types are accepted (and their contents are validated
+    /// and normalized with [`CustomScalar::parse`](crate::CustomScalar::parse) and
+    /// [`CustomScalar::format`](crate::CustomScalar::format)), even if unknown types are rejected.
+    /// This replaces any previously registered types. No custom types are registered by default.
+    pub const fn custom_scalars(mut self, types: &'static [CustomScalarType]) -> Self {
+        self.custom_scalars = types;
+        self
+    }
+
+    pub(crate) fn custom_scalar(&self, name: &str) -> Option<(&'static str, Normalizer)> {
+        self.custom_scalars
+            .iter()
+            .find(|custom| custom.tag() == name)
+            .map(|custom| (custom.tag(), custom.normalizer()))
+    }
+
+    /// getter method for the maximum size of XML documents (in bytes)
+    pub const fn max_document_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub(crate) fn unknown_types(&self) -> bool {
+        self.unknown_types
+    }
+
+    pub(crate) fn boolean_words(&self) -> bool {
+        self.boolean_words
+    }
+
+    pub(crate) fn non_finite_doubles(&self) -> bool {
+        self.non_finite
+    }
+
+    pub(crate) fn missing_params(&self) -> bool {
+        self.missing_params
+    }
+
+    pub(crate) fn duplicate_members_policy(&self) -> DuplicateMembers {
+        self.duplicate_members
+    }
+
+    pub(crate) fn multiple_params_policy(&self) -> MultipleParams {
+        self.multiple_params
+    }
+}
+
+thread_local! {
+    static SERIALIZE_OPTIONS: Cell<SerializeOptions> = Cell::new(SerializeOptions::default());
+    static DESERIALIZE_OPTIONS: Cell<DeserializeOptions> = Cell::new(DeserializeOptions::default());
+    static MEMBER_NAMES: RefCell<Option<HashSet<Arc<str>>>> = const { RefCell::new(None) };
+}
+
+/// getter for the options of the serialization that is currently running on this thread
+pub(crate) fn current_serialize_options() -> SerializeOptions {
+    SERIALIZE_OPTIONS.with(Cell::get)
+}
+
+/// getter for the options of the deserialization that is currently running on this thread
+pub(crate) fn current_options() -> DeserializeOptions {
+    DESERIALIZE_OPTIONS.with(Cell::get)
+}
+
+// guard that restores the previous options when (de)serialization has finished
+struct OptionsGuard<T: Copy + 'static> {
+    key: &'static LocalKey<Cell<T>>,
+    previous: T,
+}
+
+impl<T: Copy + 'static> OptionsGuard<T> {
+    fn set(key: &'static LocalKey<Cell<T>>, options: T) -> Self {
+        OptionsGuard {
+            key,
+            previous: key.with(|current| current.replace(options)),
+        }
+    }
+}
+
+impl<T: Copy + 'static> Drop for OptionsGuard<T> {
+    fn drop(&mut self) {
+        let previous = self.previous;
+        self.key.with(|current| current.set(previous));
+    }
 }
 
 /// Function for deserializing values from XML.
 ///
-/// This is a wrapper around [`quick_xml::de::from_str`].
+/// This is a wrapper around [`quick_xml::de::from_str`] that uses the default
+/// [`DeserializeOptions`].
 pub fn deserialize_xml<'de, T>(string: &'de str) -> Result<T, DeError>
 where
     T: Deserialize<'de>,
 {
-    quick_xml::de::from_str(string)
+    deserialize_xml_with(string, DeserializeOptions::default())
+}
+
+/// Function for deserializing values from XML with custom [`DeserializeOptions`].
+pub fn deserialize_xml_with<'de, T>(string: &'de str, options: DeserializeOptions) -> Result<T, DeError>
+where
+    T: Deserialize<'de>,
+{
+    check_document(string, &options)?;
+    with_options(options, || quick_xml::de::from_str(string))
+}
+
+/// checks that are applied to all documents before they are deserialized
+pub(crate) fn check_document(string: &str, options: &DeserializeOptions) -> Result<(), DeError> {
+    // NUL characters are not allowed in XML documents (and they could not be serialized again)
+    if string.contains('\0') {
+        return Err(DeError::Custom(String::from(
+            "NUL characters cannot be represented in XML",
+        )));
+    }
+
+    check_limits(string, options)?;
+
+    if !options.whitespace {
+        check_whitespace(string)?;
+    }
+
+    Ok(())
+}
+
+/// helper for running a deserializer with the given options on this thread
+pub(crate) fn with_options<T>(options: DeserializeOptions, deserialize: impl FnOnce() -> T) -> T {
+    let _guard = OptionsGuard::set(&DESERIALIZE_OPTIONS, options);
+    let _names = options.intern_member_names.then(MemberNamesGuard::set);
+    deserialize()
+}
+
+/// helper for turning struct member names into shared strings, which are interned if this is
+/// enabled for the deserialization that is currently running on this thread
+pub(crate) fn intern_member_name(name: &str) -> Arc<str> {
+    MEMBER_NAMES.with(|names| match names.borrow_mut().as_mut() {
+        Some(names) => match names.get(name) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned: Arc<str> = Arc::from(name);
+                names.insert(interned.clone());
+                interned
+            },
+        },
+        None => Arc::from(name),
+    })
+}
+
+// guard that discards interned member names when deserialization has finished (and restores the
+// interned names of outer deserializations)
+struct MemberNamesGuard {
+    previous: Option<HashSet<Arc<str>>>,
+}
+
+impl MemberNamesGuard {
+    fn set() -> Self {
+        MemberNamesGuard {
+            previous: MEMBER_NAMES.with(|names| names.replace(Some(HashSet::new()))),
+        }
+    }
+}
+
+impl Drop for MemberNamesGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        MEMBER_NAMES.with(|names| names.replace(previous));
+    }
+}
+
+// byte offset of the first syntax error in a document that is not well-formed (documents that end
+// before all elements are closed are reported with the offset of their end)
+pub(crate) fn syntax_error_offset(string: &str) -> Option<usize> {
+    let mut reader = Reader::from_str(string);
+    reader.check_end_names(true);
+    let mut depth: usize = 0;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(_)) => depth += 1,
+            Ok(Event::End(_)) => depth = depth.saturating_sub(1),
+            Ok(Event::Eof) if depth > 0 => return Some(reader.buffer_position()),
+            Ok(Event::Eof) => return None,
+            Ok(_) => (),
+            Err(_) => return Some(reader.buffer_position()),
+        }
+    }
+}
+
+fn check_limits(string: &str, options: &DeserializeOptions) -> Result<(), DeError> {
+    if string.len() > options.max_size {
+        return Err(DeError::Custom(format!(
+            "XML document exceeds the maximum size of {} bytes",
+            options.max_size
+        )));
+    }
+
+    let mut reader = Reader::from_str(string);
+    let mut depth: usize = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Eof => break,
+            Event::Start(_) => {
+                depth += 1;
+                if depth > options.max_depth {
+                    return Err(DeError::Custom(format!(
+                        "XML elements exceed the maximum nesting depth of {}",
+                        options.max_depth
+                    )));
+                }
+            },
+            Event::End(_) => depth = depth.saturating_sub(1),
+            Event::DocType(_) => {
+                return Err(DeError::Custom(String::from(
+                    "XML documents with document type declarations are not supported",
+                )))
+            },
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+// elements of scalar values with contents that must not contain leading or trailing whitespace
+const SCALAR_ELEMENTS: &[&[u8]] = &[
+    b"i4",
+    b"int",
+    b"i8",
+    b"boolean",
+    b"double",
+    b"dateTime.iso8601",
+    b"base64",
+];
+
+fn check_whitespace(string: &str) -> Result<(), DeError> {
+    // stack of open elements: element name, whether it has a scalar child element, and whether it
+    // contains whitespace-only text
+    let mut stack: Vec<(Vec<u8>, bool, bool)> = Vec::new();
+
+    let mut reader = Reader::from_str(string);
+
+    loop {
+        let (child, empty) = match reader.read_event()? {
+            Event::Eof => break,
+            Event::Start(start) => (start.name().as_ref().to_vec(), false),
+            Event::Empty(start) => (start.name().as_ref().to_vec(), true),
+            Event::End(_) => {
+                stack.pop();
+                continue;
+            },
+            Event::Text(text) => {
+                if let Some((name, has_scalar, has_whitespace)) = stack.last_mut() {
+                    let is_blank = text.iter().all(u8::is_ascii_whitespace);
+                    let is_padded = text.first().map_or(false, u8::is_ascii_whitespace)
+                        || text.last().map_or(false, u8::is_ascii_whitespace);
+
+                    if SCALAR_ELEMENTS.contains(&name.as_slice()) && is_padded {
+                        return Err(stray_whitespace(&String::from_utf8_lossy(name)));
+                    }
+
+                    if name == b"value" && is_blank {
+                        if *has_scalar {
+                            return Err(stray_whitespace("value"));
+                        }
+                        *has_whitespace = true;
+                    }
+                }
+                continue;
+            },
+            _ => continue,
+        };
+
+        let is_scalar = SCALAR_ELEMENTS.contains(&child.as_slice()) || child == b"string" || child == b"nil";
+
+        if let Some((name, has_scalar, has_whitespace)) = stack.last_mut() {
+            if name == b"value" && is_scalar {
+                if *has_whitespace {
+                    return Err(stray_whitespace("value"));
+                }
+                *has_scalar = true;
+            }
+        }
+
+        if !empty {
+            stack.push((child, false, false));
+        }
+    }
+
+    Ok(())
+}
+
+fn stray_whitespace(element: &str) -> DeError {
+    DeError::Custom(format!("Unexpected whitespace in <{element}> element"))
 }
@@ -0,0 +1,265 @@
+//! hand-written serializer for XML-RPC values
+//!
+//! This serializer produces exactly the same output as serializing XML-RPC types with
+//! [`serialize_xml_into_buf_with`](super::serialize_xml_into_buf_with), but it writes XML directly
+//! instead of going through the generic serde support of [`quick_xml`], which makes it
+//! considerably faster for large values.
+
+use std::fmt;
+
+use quick_xml::de::DeError;
+
+use super::{forbidden_character, is_forbidden, needs_reference, NonFinitePolicy, SerializeOptions};
+use crate::values::ser_de::{datetime, double};
+use crate::values::{FaultResponse, Member, MethodCall, MethodResponse, Type, Value};
+
+/// # Fast serialization of XML-RPC documents
+///
+/// This trait is implemented for [`Value`], [`MethodCall`], [`MethodResponse`], and
+/// [`FaultResponse`]. It writes XML-RPC documents directly into a buffer without going through
+/// the generic serde support of [`quick_xml`]. The output is identical to the output of
+/// [`serialize_xml_with`](super::serialize_xml_with) (and related functions) with the same
+/// [`SerializeOptions`].
+///
+/// ```
+/// use dxr::{serialize_xml, SerializeOptions, Value, WriteXml};
+///
+/// let value = Value::string(String::from("Hello, World!"));
+///
+/// let mut buf = String::new();
+/// value.write_xml(&mut buf, SerializeOptions::default()).unwrap();
+/// assert_eq!(buf, serialize_xml(&value).unwrap());
+/// ```
+pub trait WriteXml {
+    /// method for appending the XML representation of this value to a buffer
+    fn write_xml<W: fmt::Write>(&self, buf: &mut W, options: SerializeOptions) -> Result<(), DeError>;
+}
+
+impl WriteXml for Value {
+    fn write_xml<W: fmt::Write>(&self, buf: &mut W, options: SerializeOptions) -> Result<(), DeError> {
+        XmlWriter { buf, options }.value(self)
+    }
+}
+
+impl WriteXml for MethodCall {
+    fn write_xml<W: fmt::Write>(&self, buf: &mut W, options: SerializeOptions) -> Result<(), DeError> {
+        let mut writer = XmlWriter { buf, options };
+
+        writer.raw("<methodCall>")?;
+        writer.element("methodName", self.name())?;
+
+        let mut params = self.param_values().peekable();
+        if params.peek().is_some() {
+            writer.raw("<params>")?;
+            for param in params {
+                writer.raw("<param>")?;
+                writer.value(param)?;
+                writer.raw("</param>")?;
+            }
+            writer.raw("</params>")?;
+        }
+
+        writer.raw("</methodCall>")
+    }
+}
+
+impl WriteXml for MethodResponse {
+    fn write_xml<W: fmt::Write>(&self, buf: &mut W, options: SerializeOptions) -> Result<(), DeError> {
+        let mut writer = XmlWriter { buf, options };
+
+        writer.raw("<methodResponse><params><param>")?;
+        writer.value(self.value())?;
+        writer.raw("</param></params></methodResponse>")
+    }
+}
+
+impl WriteXml for FaultResponse {
+    fn write_xml<W: fmt::Write>(&self, buf: &mut W, options: SerializeOptions) -> Result<(), DeError> {
+        let mut writer = XmlWriter { buf, options };
+
+        writer.raw("<methodResponse><fault><value>")?;
+        writer.members(self.members())?;
+        writer.raw("</value></fault></methodResponse>")
+    }
+}
+
+// namespace declaration of the Apache XML-RPC extension elements
+#[cfg(any(feature = "i8", feature = "nil"))]
+const APACHE_EXTENSIONS_NAMESPACE: &str = r#"xmlns:ex="http://ws.apache.org/xmlrpc/namespaces/extensions""#;
+
+struct XmlWriter<'w, W> {
+    buf: &'w mut W,
+    options: SerializeOptions,
+}
+
+impl<'w, W: fmt::Write> XmlWriter<'w, W> {
+    fn raw(&mut self, markup: &str) -> Result<(), DeError> {
+        Ok(self.buf.write_str(markup)?)
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), DeError> {
+        let mut start = 0;
+
+        for (index, c) in text.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                '\'' => "&apos;",
+                c if is_forbidden(c) => return Err(forbidden_character()),
+                c if needs_reference(c) => "",
+                _ => continue,
+            };
+
+            self.buf.write_str(&text[start..index])?;
+            if escaped.is_empty() {
+                write!(self.buf, "&#x{:X};", c as u32)?;
+            } else {
+                self.buf.write_str(escaped)?;
+            }
+            start = index + c.len_utf8();
+        }
+
+        Ok(self.buf.write_str(&text[start..])?)
+    }
+
+    // empty elements are written as self-closing tags, just like quick_xml does
+    fn element(&mut self, name: &str, text: &str) -> Result<(), DeError> {
+        if text.is_empty() {
+            return Ok(write!(self.buf, "<{name}/>")?);
+        }
+
+        write!(self.buf, "<{name}>")?;
+        self.text(text)?;
+        Ok(write!(self.buf, "</{name}>")?)
+    }
+
+    fn string(&mut self, string: &str) -> Result<(), DeError> {
+        match self.options.cdata_strings {
+            // CDATA sections cannot contain character references
+            Some(min_length)
+                if !string.is_empty()
+                    && string.len() >= min_length
+                    && !string.chars().any(|c| needs_reference(c) || is_forbidden(c)) =>
+            {
+                // the "]]>" sequence terminates CDATA sections, so it is split across two sections
+                self.raw("<![CDATA[")?;
+                self.raw(&string.replace("]]>", "]]]]><![CDATA[>"))?;
+                self.raw("]]>")
+            },
+            _ => self.text(string),
+        }
+    }
+
+    fn scalar(&mut self, name: &str, contents: fmt::Arguments) -> Result<(), DeError> {
+        Ok(write!(self.buf, "<{name}>{contents}</{name}>")?)
+    }
+
+    fn value(&mut self, value: &Value) -> Result<(), DeError> {
+        // empty strings without type element are written as self-closing tags, just like quick_xml does
+        if matches!(value.inner(), Type::String(string) if string.is_empty() && self.options.omit_string_tags(string)) {
+            return self.raw("<value/>");
+        }
+
+        self.raw("<value>")?;
+
+        match value.inner() {
+            Type::Integer(int) => self.scalar("i4", format_args!("{int}"))?,
+            #[cfg(feature = "i8")]
+            Type::Long(long) if self.options.use_apache_extensions() => {
+                write!(self.buf, "<ex:i8 {APACHE_EXTENSIONS_NAMESPACE}>{long}</ex:i8>")?
+            },
+            #[cfg(feature = "i8")]
+            Type::Long(long) => self.scalar("i8", format_args!("{long}"))?,
+            Type::Boolean(boolean) => self.scalar("boolean", format_args!("{}", *boolean as i32))?,
+            Type::String(string) if self.options.omit_string_tags(string) => self.string(string)?,
+            Type::String(string) => {
+                self.raw("<string>")?;
+                self.string(string)?;
+                self.raw("</string>")?;
+            },
+            Type::Double(double) if double.is_finite() => self.scalar("double", format_args!("{double}"))?,
+            Type::Double(double) => match self.options.non_finite_policy() {
+                NonFinitePolicy::AsString => {
+                    self.raw("<string>")?;
+                    self.string(&double::to_string(*double))?;
+                    self.raw("</string>")?;
+                },
+                _ => {
+                    return Err(DeError::Custom(format!(
+                        "Cannot serialize non-finite double value: {}",
+                        double::to_string(*double)
+                    )))
+                },
+            },
+            Type::DateTime(datetime) => {
+                // custom formats could contain characters that need to be escaped
+                let string = datetime::to_string(datetime, self.options.format_datetime()).map_err(DeError::Custom)?;
+                self.raw("<dateTime.iso8601>")?;
+                self.text(&string)?;
+                self.raw("</dateTime.iso8601>")?;
+            },
+            Type::Base64(bytes) => self.scalar("base64", format_args!("{}", crate::base64::encode(bytes)))?,
+            Type::Struct { members } => self.members(members)?,
+            Type::Array { data } => {
+                let options = self.options;
+                let mut values = data
+                    .inner()
+                    .iter()
+                    .filter(|value| !is_skipped(value, options))
+                    .peekable();
+
+                if values.peek().is_none() {
+                    self.raw("<array><data/></array>")?;
+                } else {
+                    self.raw("<array><data>")?;
+                    for value in values {
+                        self.value(value)?;
+                    }
+                    self.raw("</data></array>")?;
+                }
+            },
+            #[cfg(feature = "nil")]
+            Type::Nil if self.options.use_apache_extensions() => {
+                write!(self.buf, "<ex:nil {APACHE_EXTENSIONS_NAMESPACE}/>")?
+            },
+            #[cfg(feature = "nil")]
+            Type::Nil => self.raw("<nil></nil>")?,
+            Type::Custom(custom) => {
+                write!(self.buf, "<{}>", custom.tag())?;
+                self.text(custom.text())?;
+                write!(self.buf, "</{}>", custom.tag())?;
+            },
+        }
+
+        self.raw("</value>")
+    }
+
+    fn members(&mut self, members: &[Member]) -> Result<(), DeError> {
+        let options = self.options;
+        let mut members = members
+            .iter()
+            .filter(|member| !is_skipped(member.inner(), options))
+            .peekable();
+
+        if members.peek().is_none() {
+            return self.raw("<struct/>");
+        }
+
+        self.raw("<struct>")?;
+        for member in members {
+            self.raw("<member>")?;
+            self.element("name", member.name())?;
+            self.value(member.inner())?;
+            self.raw("</member>")?;
+        }
+        self.raw("</struct>")
+    }
+}
+
+// non-finite doubles are omitted from structs and arrays with NonFinitePolicy::Skip
+fn is_skipped(value: &Value, options: SerializeOptions) -> bool {
+    matches!(value.inner(), Type::Double(double) if !double.is_finite())
+        && options.non_finite_policy() == NonFinitePolicy::Skip
+}
@@ -7,6 +7,9 @@ fn try_build_pass() {
     t.pass("tests/trybuild/recursive.rs");
     t.pass("tests/trybuild/ownership.rs");
     t.pass("tests/trybuild/collections.rs");
+    t.pass("tests/trybuild/generics.rs");
+    t.pass("tests/trybuild/transparent.rs");
+    t.pass("tests/trybuild/options.rs");
 }
 
 #[test]
@@ -16,4 +19,7 @@ fn try_build_fail() {
     t.compile_fail("tests/trybuild/toref.rs");
     t.compile_fail("tests/trybuild/tuple.rs");
     t.compile_fail("tests/trybuild/enum.rs");
+    t.compile_fail("tests/trybuild/transparent_fields.rs");
+    t.compile_fail("tests/trybuild/option_mode.rs");
+    t.compile_fail("tests/trybuild/option_type.rs");
 }
@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+#[derive(dxr::TryFromValue, dxr::TryToValue)]
+pub struct Page<T> {
+    items: Vec<T>,
+    total: i32,
+}
+
+#[derive(dxr::TryFromValue, dxr::TryToValue)]
+pub struct Borrowed<'a, T, const N: usize>
+where
+    T: Clone,
+{
+    name: Cow<'a, str>,
+    values: [T; N],
+    nested: Page<T>,
+}
+
+#[derive(dxr::TryToValue)]
+pub struct References<'a, T> {
+    item: &'a T,
+    items: &'a [T],
+    page: &'a Page<T>,
+}
+
+mod shadowed {
+    #![allow(dead_code)]
+
+    // the generated code must not be affected by items with conflicting names
+    type Result<T> = std::result::Result<T, ()>;
+    struct HashMap;
+    struct Value;
+
+    #[derive(dxr::TryFromValue, dxr::TryToValue)]
+    pub struct Shadowed<T> {
+        items: Vec<T>,
+    }
+}
+
+fn main() {}
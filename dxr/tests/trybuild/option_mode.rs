@@ -0,0 +1,9 @@
+use dxr::TryToValue;
+
+#[derive(TryToValue)]
+pub struct Settings {
+    #[dxr(option = "skip")]
+    label: Option<String>,
+}
+
+fn main() {}
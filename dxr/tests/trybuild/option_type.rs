@@ -0,0 +1,9 @@
+use dxr::TryToValue;
+
+#[derive(TryToValue)]
+pub struct Settings {
+    #[dxr(option = "omit")]
+    name: String,
+}
+
+fn main() {}
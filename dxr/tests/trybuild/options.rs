@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use dxr::{TryFromValue, TryToValue, Value};
+
+#[derive(Debug, PartialEq, TryFromValue, TryToValue)]
+pub struct Settings {
+    name: String,
+    #[dxr(option = "omit")]
+    label: Option<String>,
+    #[dxr(option = "nil")]
+    comment: Option<String>,
+    size: Option<i32>,
+}
+
+fn main() {
+    let settings = Settings {
+        name: String::from("foo"),
+        label: None,
+        comment: None,
+        size: None,
+    };
+
+    let value = settings.try_to_value().unwrap();
+    let map = HashMap::<String, Value>::try_from_value(&value).unwrap();
+
+    assert!(!map.contains_key("label"));
+    assert_eq!(map.get("comment"), Some(&Value::nil()));
+    assert_eq!(map.get("size"), Some(&Value::nil()));
+    assert_eq!(Settings::try_from_value(&value).unwrap(), settings);
+
+    let settings = Settings {
+        name: String::from("foo"),
+        label: Some(String::from("bar")),
+        comment: Some(String::from("baz")),
+        size: Some(42),
+    };
+
+    let value = settings.try_to_value().unwrap();
+    assert_eq!(Settings::try_from_value(&value).unwrap(), settings);
+
+    // missing members are converted into None
+    let value = HashMap::from([("name", "foo")]).try_to_value().unwrap();
+    let expected = Settings {
+        name: String::from("foo"),
+        label: None,
+        comment: None,
+        size: None,
+    };
+    assert_eq!(Settings::try_from_value(&value).unwrap(), expected);
+
+    // missing members that are not optional are still an error
+    let value = HashMap::<String, Value>::new().try_to_value().unwrap();
+    assert!(Settings::try_from_value(&value).unwrap_err().is_missing_field());
+}
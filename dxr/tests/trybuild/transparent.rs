@@ -0,0 +1,17 @@
+use dxr::{TryFromValue, TryToValue};
+
+#[derive(TryFromValue, TryToValue)]
+#[dxr(transparent)]
+pub struct TorrentHash(String);
+
+#[derive(TryFromValue, TryToValue)]
+#[dxr(transparent)]
+pub struct Id<T>(T);
+
+#[derive(TryFromValue, TryToValue)]
+pub struct Torrent {
+    hash: TorrentHash,
+    ids: Vec<Id<i32>>,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use dxr::TryToValue;
+
+#[derive(TryToValue)]
+#[dxr(transparent)]
+pub struct Pair(String, i32);
+
+fn main() {}
@@ -0,0 +1,285 @@
+//! parser for command-line arguments
+
+use dxr::{deserialize_xml, Value};
+use dxr_client::Url;
+
+pub(crate) const USAGE: &str = "\
+Usage: dxr-cli call [OPTIONS] URL METHOD [ARGS]...
+       dxr-cli codegen [--name NAME] URL
+
+Call the XML-RPC method METHOD of the server at URL (http://, https://, or unix:// for SCGI
+over a unix socket) and print the result, or generate a typed Rust client for all methods that
+are listed by the system.listMethods and system.methodSignature methods of the server.
+
+Arguments are passed as strings by default. Their type can be specified with a prefix (i.e.
+\"i4:42\"). Supported types are i4, int, i8, boolean, string, double, dateTime.iso8601, base64,
+nil, and xml (for arbitrary values in XML-RPC format, i.e. \"xml:<value><array>...</array></value>\").
+
+Options:
+  -t, --type TYPE      type of arguments without prefix [default: string]
+  -o, --output FORMAT  output format: text, json, or xml [default: text]
+  -n, --name NAME      name of the generated client type [default: Api]
+  -h, --help           print this help message";
+
+const TYPES: &[&str] = &[
+    "i4",
+    "int",
+    "i8",
+    "boolean",
+    "string",
+    "double",
+    "dateTime.iso8601",
+    "base64",
+    "nil",
+    "xml",
+];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    Call(Options),
+    Codegen(CodegenOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Options {
+    pub(crate) url: Url,
+    pub(crate) method: String,
+    pub(crate) params: Vec<Value>,
+    pub(crate) output: Output,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct CodegenOptions {
+    pub(crate) url: Url,
+    pub(crate) name: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Output {
+    Text,
+    Json,
+    Xml,
+}
+
+pub(crate) fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("call") => parse_call(args),
+        Some("codegen") => parse_codegen(args),
+        Some("-h" | "--help") => Ok(Command::Help),
+        Some(command) => Err(format!("Unknown command: {command}")),
+        None => Err(String::from("Missing command")),
+    }
+}
+
+fn parse_url(url: Option<String>) -> Result<Url, String> {
+    let url = url.ok_or("Missing URL")?;
+    Url::parse(&url).map_err(|error| format!("Invalid URL: {url}: {error}"))
+}
+
+fn parse_codegen(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut name = String::from("Api");
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-n" | "--name" => name = args.next().ok_or("Missing value for --name")?,
+            option if option.starts_with('-') => return Err(format!("Unknown option: {option}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let url = parse_url(positional.next())?;
+
+    if let Some(extra) = positional.next() {
+        return Err(format!("Unexpected argument: {extra}"));
+    }
+
+    Ok(Command::Codegen(CodegenOptions { url, name }))
+}
+
+fn parse_call(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut default_type = String::from("string");
+    let mut output = Output::Text;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-t" | "--type" => {
+                let value = args.next().ok_or("Missing value for --type")?;
+                if !TYPES.contains(&value.as_str()) {
+                    return Err(format!("Unsupported type: {value}"));
+                }
+                default_type = value;
+            },
+            "-o" | "--output" => {
+                output = match args.next().as_deref() {
+                    Some("text") => Output::Text,
+                    Some("json") => Output::Json,
+                    Some("xml") => Output::Xml,
+                    Some(other) => return Err(format!("Unsupported output format: {other}")),
+                    None => return Err(String::from("Missing value for --output")),
+                }
+            },
+            // all remaining arguments are positional
+            "--" => positional.extend(args.by_ref()),
+            // negative numbers are not options
+            option if option.starts_with('-') && option.parse::<f64>().is_err() && option != "-" => {
+                return Err(format!("Unknown option: {option}"))
+            },
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let url = parse_url(positional.next())?;
+    let method = positional.next().ok_or("Missing method name")?;
+
+    let params = positional
+        .map(|arg| match arg.split_once(':') {
+            Some((prefix, value)) if TYPES.contains(&prefix) => parse_value(prefix, value),
+            _ => parse_value(&default_type, &arg),
+        })
+        .collect::<Result<Vec<Value>, String>>()?;
+
+    Ok(Command::Call(Options {
+        url,
+        method,
+        params,
+        output,
+    }))
+}
+
+fn parse_value(ty: &str, value: &str) -> Result<Value, String> {
+    let invalid = |error| format!("Invalid {ty} argument: {value}: {error}");
+
+    match ty {
+        "string" => Ok(Value::string(String::from(value))),
+        "nil" if value.is_empty() => Ok(Value::nil()),
+        "nil" => Err(format!("Invalid nil argument: {value}")),
+        "xml" => deserialize_xml(value).map_err(invalid),
+        // parse scalar values with the same rules as values in XML-RPC documents
+        _ => deserialize_xml(&format!("<value><{ty}>{}</{ty}></value>", escape(value))).map_err(invalid),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| String::from(*arg)).collect()
+    }
+
+    fn call(arguments: &[&str]) -> Options {
+        match parse(args(arguments)).unwrap() {
+            Command::Call(options) => options,
+            other => panic!("Unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_call() {
+        let options = call(&["call", "http://localhost/RPC2", "add", "i4:1", "int:2", "three"]);
+
+        assert_eq!(options.url, Url::parse("http://localhost/RPC2").unwrap());
+        assert_eq!(options.method, "add");
+        assert_eq!(
+            options.params,
+            vec![Value::i4(1), Value::i4(2), Value::string(String::from("three"))]
+        );
+        assert_eq!(options.output, Output::Text);
+    }
+
+    #[test]
+    fn parse_default_type() {
+        let options = call(&["call", "unix:///tmp/rpc.socket", "add", "1", "-2", "--type", "i4"]);
+
+        assert_eq!(options.url.path(), "/tmp/rpc.socket");
+        assert_eq!(options.params, vec![Value::i4(1), Value::i4(-2)]);
+    }
+
+    #[test]
+    fn parse_types() {
+        let options = call(&[
+            "call",
+            "http://localhost/",
+            "echo",
+            "i8:9000000000",
+            "boolean:1",
+            "double:1.5",
+            "base64:aGVsbG8=",
+            "dateTime.iso8601:20230401T12:30:00",
+            "nil:",
+            "string:i4:42",
+            "http://example.com",
+            "xml:<value><array><data><value><i4>1</i4></value></data></array></value>",
+        ]);
+
+        assert_eq!(options.params[0], Value::i8(9_000_000_000));
+        assert_eq!(options.params[1], Value::boolean(true));
+        assert_eq!(options.params[2], Value::double(1.5));
+        assert_eq!(options.params[3], Value::base64(b"hello".to_vec()));
+        assert!(options.params[4].as_datetime().is_some());
+        assert_eq!(options.params[5], Value::nil());
+        assert_eq!(options.params[6], Value::string(String::from("i4:42")));
+        assert_eq!(options.params[7], Value::string(String::from("http://example.com")));
+        assert_eq!(options.params[8].get_index(0), Some(&Value::i4(1)));
+    }
+
+    #[test]
+    fn parse_output() {
+        assert_eq!(
+            call(&["call", "http://localhost/", "x", "-o", "json"]).output,
+            Output::Json
+        );
+        assert_eq!(
+            call(&["call", "--output", "xml", "http://localhost/", "x"]).output,
+            Output::Xml
+        );
+    }
+
+    #[test]
+    fn parse_codegen() {
+        let expected = CodegenOptions {
+            url: Url::parse("http://localhost/RPC2").unwrap(),
+            name: String::from("Rtorrent"),
+        };
+
+        assert_eq!(
+            parse(args(&["codegen", "--name", "Rtorrent", "http://localhost/RPC2"])).unwrap(),
+            Command::Codegen(expected)
+        );
+        assert!(parse(args(&["codegen"])).is_err());
+        assert!(parse(args(&["codegen", "http://localhost/", "extra"])).is_err());
+    }
+
+    #[test]
+    fn parse_help() {
+        assert_eq!(parse(args(&["--help"])).unwrap(), Command::Help);
+        assert_eq!(parse(args(&["call", "-h"])).unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn parse_fail() {
+        assert!(parse(args(&[])).is_err());
+        assert!(parse(args(&["get"])).is_err());
+        assert!(parse(args(&["call", "http://localhost/"])).is_err());
+        assert!(parse(args(&["call", "localhost", "x"])).is_err());
+        assert!(parse(args(&["call", "http://localhost/", "x", "i4:one"])).is_err());
+        assert!(parse(args(&["call", "http://localhost/", "x", "--type", "i2"])).is_err());
+        assert!(parse(args(&["call", "http://localhost/", "x", "--verbose"])).is_err());
+        assert!(parse(args(&["call", "http://localhost/", "x", "nil:x"])).is_err());
+    }
+}
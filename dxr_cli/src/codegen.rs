@@ -0,0 +1,388 @@
+//! generator for typed client code based on XML-RPC introspection
+//!
+//! The list of methods and their signatures are queried with the `system.listMethods` and
+//! `system.methodSignature` methods, which are supported by many XML-RPC servers.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use dxr::Value;
+use dxr_client::{Call, Client};
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Method {
+    pub(crate) name: String,
+    // every signature consists of the return type, followed by the types of parameters
+    pub(crate) signatures: Vec<Vec<String>>,
+}
+
+pub(crate) async fn introspect(client: &Client) -> Result<Vec<Method>, String> {
+    let names: Vec<String> = client
+        .call(Call::new("system.listMethods", ()))
+        .await
+        .map_err(|error| format!("Failed to list methods: {error}"))?;
+
+    let mut methods = Vec::with_capacity(names.len());
+    for name in names {
+        // servers can return non-array values for methods without known signatures
+        let signatures: Value = client
+            .call(Call::new("system.methodSignature", name.as_str()))
+            .await
+            .map_err(|error| format!("Failed to query signature of {name}: {error}"))?;
+
+        methods.push(Method {
+            signatures: parse_signatures(&signatures),
+            name,
+        });
+    }
+
+    Ok(methods)
+}
+
+fn parse_signatures(value: &Value) -> Vec<Vec<String>> {
+    let signatures = value.as_array().unwrap_or_default();
+
+    signatures
+        .iter()
+        .filter_map(|signature| {
+            signature
+                .as_array()?
+                .iter()
+                .map(|ty| ty.as_str().map(String::from))
+                .collect::<Option<Vec<String>>>()
+        })
+        .filter(|signature| !signature.is_empty())
+        .collect()
+}
+
+// names of the methods of the generated struct itself, which must not be reused for XML-RPC methods
+const RESERVED: &[&str] = &["new", "client"];
+
+pub(crate) fn generate(url: &str, name: &str, methods: &[Method]) -> String {
+    let mut code = String::new();
+    let mut names: HashSet<String> = RESERVED.iter().map(|name| String::from(*name)).collect();
+
+    // writing into a String cannot fail
+    let _ = write!(
+        code,
+        "\
+//! typed XML-RPC client for {url}
+//!
+//! This file was generated by `dxr-cli codegen`.
+
+#![allow(dead_code)]
+
+use dxr::Value;
+use dxr_client::{{Call, Client, ClientError}};
+
+/// typed XML-RPC client for {url}
+#[derive(Debug)]
+pub struct {name} {{
+    client: Client,
+}}
+
+impl {name} {{
+    /// constructor for typed clients from an XML-RPC client
+    pub fn new(client: Client) -> Self {{
+        {name} {{ client }}
+    }}
+
+    /// getter method for the underlying XML-RPC client
+    pub fn client(&self) -> &Client {{
+        &self.client
+    }}
+"
+    );
+
+    for method in methods {
+        let mut function = identifier(&method.name);
+        while !names.insert(function.clone()) {
+            function.push('_');
+        }
+
+        code.push('\n');
+        let _ = writeln!(code, "    /// `{}`", method.name);
+
+        match method.signatures.split_first() {
+            Some((signature, others)) => {
+                let _ = writeln!(code, "    ///");
+                let _ = writeln!(code, "    /// signature: `{}`", signature_string(signature));
+                for other in others {
+                    let _ = writeln!(code, "    /// (other signature: `{}`)", signature_string(other));
+                }
+
+                let (returns, params) = signature.split_first().expect("empty signatures are skipped");
+                let args: Vec<String> = (0..params.len()).map(|index| format!("arg{index}")).collect();
+                let declarations: Vec<String> = params
+                    .iter()
+                    .zip(&args)
+                    .map(|(ty, arg)| format!(", {arg}: {}", param_type(ty)))
+                    .collect();
+                let tuple = match args.len() {
+                    0 => String::from("()"),
+                    1 => format!("({},)", args[0]),
+                    _ => format!("({})", args.join(", ")),
+                };
+
+                let _ = writeln!(
+                    code,
+                    "    pub async fn {function}(&self{}) -> Result<{}, ClientError> {{",
+                    declarations.concat(),
+                    return_type(returns)
+                );
+                let _ = writeln!(
+                    code,
+                    "        self.client.call(Call::new({:?}, {tuple})).await",
+                    method.name
+                );
+            },
+            None => {
+                let _ = writeln!(code, "    ///");
+                let _ = writeln!(code, "    /// The signature of this method is unknown.");
+                let _ = writeln!(
+                    code,
+                    "    pub async fn {function}(&self, params: &[Value]) -> Result<Value, ClientError> {{"
+                );
+                let _ = writeln!(
+                    code,
+                    "        self.client.call(Call::new({:?}, params)).await",
+                    method.name
+                );
+            },
+        }
+
+        let _ = writeln!(code, "    }}");
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+fn signature_string(signature: &[String]) -> String {
+    match signature.split_first() {
+        Some((returns, params)) => format!("{returns} ({})", params.join(", ")),
+        None => String::new(),
+    }
+}
+
+fn param_type(ty: &str) -> &'static str {
+    match ty {
+        "i4" | "int" => "i32",
+        "i8" => "i64",
+        "boolean" => "bool",
+        "string" => "&str",
+        "double" => "f64",
+        "dateTime.iso8601" => "dxr::chrono::NaiveDateTime",
+        "base64" => "&[u8]",
+        _ => "&Value",
+    }
+}
+
+fn return_type(ty: &str) -> &'static str {
+    match ty {
+        "i4" | "int" => "i32",
+        "i8" => "i64",
+        "boolean" => "bool",
+        "string" => "String",
+        "double" => "f64",
+        "dateTime.iso8601" => "dxr::chrono::NaiveDateTime",
+        "base64" => "Vec<u8>",
+        _ => "Value",
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct", "trait",
+    "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+// convert method names like "system.listMethods" into snake_case identifiers
+fn identifier(method: &str) -> String {
+    let mut ident = String::with_capacity(method.len());
+    let mut previous: Option<char> = None;
+
+    for c in method.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous.map_or(false, |p| p.is_ascii_lowercase() || p.is_ascii_digit()) {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+        previous = Some(c);
+    }
+
+    let mut ident = String::from(ident.trim_end_matches('_'));
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert_str(0, "method_");
+    }
+
+    match ident.as_str() {
+        "self" | "super" | "crate" => ident.push('_'),
+        keyword if KEYWORDS.contains(&keyword) => ident.insert_str(0, "r#"),
+        _ => (),
+    }
+
+    ident
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::TryToValue;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(identifier("system.listMethods"), "system_list_methods");
+        assert_eq!(identifier("d.multicall2"), "d_multicall2");
+        assert_eq!(
+            identifier("throttle.global_down.max_rate.set"),
+            "throttle_global_down_max_rate_set"
+        );
+        assert_eq!(identifier("getURL"), "get_url");
+        assert_eq!(identifier("type"), "r#type");
+        assert_eq!(identifier("self"), "self_");
+        assert_eq!(identifier("2fa.check"), "method_2fa_check");
+        assert_eq!(identifier("..."), "method_");
+    }
+
+    #[test]
+    fn signatures() {
+        let value = vec![vec!["string", "string"], vec!["int"]].try_to_value().unwrap();
+        assert_eq!(
+            parse_signatures(&value),
+            vec![
+                vec![String::from("string"), String::from("string")],
+                vec![String::from("int")]
+            ]
+        );
+
+        let value = Value::string(String::from("undef"));
+        assert!(parse_signatures(&value).is_empty());
+    }
+
+    #[test]
+    fn generated() {
+        let methods = vec![
+            Method {
+                name: String::from("add"),
+                signatures: vec![vec![String::from("int"), String::from("int"), String::from("int")]],
+            },
+            Method {
+                name: String::from("hello"),
+                signatures: vec![
+                    vec![String::from("string"), String::from("string")],
+                    vec![String::from("string")],
+                ],
+            },
+            Method {
+                name: String::from("countme"),
+                signatures: vec![vec![String::from("i4")]],
+            },
+            Method {
+                name: String::from("echo"),
+                signatures: vec![],
+            },
+        ];
+
+        let code = generate("http://localhost/", "Api", &methods);
+
+        assert!(code.contains("pub struct Api {"));
+        assert!(code.contains(
+            "    pub async fn add(&self, arg0: i32, arg1: i32) -> Result<i32, ClientError> {\n        \
+             self.client.call(Call::new(\"add\", (arg0, arg1))).await\n    }"
+        ));
+        assert!(code.contains("    pub async fn hello(&self, arg0: &str) -> Result<String, ClientError> {"));
+        assert!(code.contains("    /// (other signature: `string ()`)"));
+        assert!(code.contains("self.client.call(Call::new(\"hello\", (arg0,))).await"));
+        assert!(code.contains("self.client.call(Call::new(\"countme\", ())).await"));
+        assert!(code.contains("    pub async fn echo(&self, params: &[Value]) -> Result<Value, ClientError> {"));
+    }
+
+    #[test]
+    fn generated_duplicate_names() {
+        let methods = vec![
+            Method {
+                name: String::from("d.name"),
+                signatures: vec![],
+            },
+            Method {
+                name: String::from("d_name"),
+                signatures: vec![],
+            },
+        ];
+
+        let code = generate("http://localhost/", "Api", &methods);
+
+        assert!(code.contains("pub async fn d_name(&self"));
+        assert!(code.contains("pub async fn d_name_(&self"));
+    }
+
+    #[test]
+    fn generated_reserved_names() {
+        let methods = vec![
+            Method {
+                name: String::from("new"),
+                signatures: vec![],
+            },
+            Method {
+                name: String::from("client"),
+                signatures: vec![],
+            },
+        ];
+
+        let code = generate("http://localhost/", "Api", &methods);
+
+        assert!(code.contains("pub async fn new_(&self"));
+        assert!(code.contains("pub async fn client_(&self"));
+    }
+
+    // the generated code is compiled and tested as part of the dxr_tests crate
+    #[test]
+    fn generated_fixture() {
+        let code = generate("http://localhost/", "Api", &fixture());
+        assert_eq!(code, include_str!("../../dxr_tests/tests/codegen/api.rs"));
+    }
+
+    fn fixture() -> Vec<Method> {
+        let signature = |types: &[&str]| types.iter().map(|ty| String::from(*ty)).collect::<Vec<String>>();
+
+        vec![
+            Method {
+                name: String::from("add"),
+                signatures: vec![signature(&["int", "int", "int"])],
+            },
+            Method {
+                name: String::from("new"),
+                signatures: vec![signature(&["string", "string"])],
+            },
+            Method {
+                name: String::from("client"),
+                signatures: vec![signature(&["boolean"])],
+            },
+            Method {
+                name: String::from("type"),
+                signatures: vec![signature(&["base64", "base64"])],
+            },
+            Method {
+                name: String::from("system.listMethods"),
+                signatures: vec![signature(&["array"])],
+            },
+            Method {
+                name: String::from("echo"),
+                signatures: vec![],
+            },
+            Method {
+                name: String::from("e.cho"),
+                signatures: vec![],
+            },
+        ]
+    }
+}
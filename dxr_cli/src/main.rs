@@ -0,0 +1,75 @@
+#![deny(unsafe_code)]
+#![warn(explicit_outlives_requirements)]
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(noop_method_call)]
+#![warn(unused_import_braces)]
+#![warn(unused_qualifications)]
+#![warn(unreachable_pub)]
+#![warn(clippy::unwrap_used)]
+
+//! # dxr-cli
+//!
+//! This binary can be used to call methods of XML-RPC servers from the command line. Run it with
+//! `--help` for usage information.
+
+use std::process::ExitCode;
+
+use dxr::Value;
+use dxr_client::{Call, Client, ClientBuilder, Url};
+
+mod args;
+use args::{CodegenOptions, Command, Options, USAGE};
+
+mod codegen;
+
+mod output;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match args::parse(std::env::args().skip(1)) {
+        Ok(Command::Call(options)) => call(options).await,
+        Ok(Command::Codegen(options)) => generate(options).await,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Err(error) => {
+            eprintln!("Error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+
+    match result {
+        Ok(result) => {
+            println!("{result}");
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn client(url: Url) -> Client {
+    ClientBuilder::new(url)
+        .user_agent(concat!("dxr-cli-v", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+async fn call(options: Options) -> Result<String, String> {
+    let client = client(options.url);
+
+    let call: Call<_, Value> = Call::new(&options.method, options.params);
+    let value = client.call(call).await.map_err(|error| error.to_string())?;
+
+    output::format(&value, options.output)
+}
+
+async fn generate(options: CodegenOptions) -> Result<String, String> {
+    let client = client(options.url.clone());
+    let methods = codegen::introspect(&client).await?;
+
+    Ok(codegen::generate(options.url.as_str(), &options.name, &methods))
+}
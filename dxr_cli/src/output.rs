@@ -0,0 +1,67 @@
+//! formatting of method call results
+
+use dxr::{serialize_xml_pretty, TryFromValue, Value};
+use serde_json::Value as Json;
+
+use crate::args::Output;
+
+pub(crate) fn format(value: &Value, output: Output) -> Result<String, String> {
+    match output {
+        Output::Text => Ok(format!("{value:#}")),
+        Output::Json => {
+            let json = Json::try_from_value(value).map_err(|error| error.to_string())?;
+            serde_json::to_string_pretty(&json).map_err(|error| error.to_string())
+        },
+        Output::Xml => serialize_xml_pretty(value).map_err(|error| error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::{Array, Member, Struct};
+
+    fn value() -> Value {
+        Value::structure(Struct::new(vec![
+            Member::new(String::from("name"), Value::string(String::from("ubuntu.iso"))),
+            Member::new(String::from("size"), Value::i8(4_000_000_000)),
+            Member::new(String::from("data"), Value::base64(b"hello".to_vec())),
+            Member::new(
+                String::from("peers"),
+                Value::array(Array::new(vec![Value::boolean(true), Value::double(0.5), Value::nil()])),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn to_text() {
+        assert_eq!(format(&Value::i4(42), Output::Text).unwrap(), "42");
+    }
+
+    #[test]
+    fn to_json() {
+        let expected = r#"{
+  "name": "ubuntu.iso",
+  "size": 4000000000,
+  "data": "aGVsbG8=",
+  "peers": [
+    true,
+    0.5,
+    null
+  ]
+}"#;
+
+        assert_eq!(format(&value(), Output::Json).unwrap(), expected);
+    }
+
+    #[test]
+    fn to_xml() {
+        assert_eq!(
+            format(&Value::i4(42), Output::Xml).unwrap(),
+            "<value>\n  <i4>42</i4>\n</value>"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! opt-in cache for responses of read-mostly XML-RPC methods
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use dxr::MethodResponse;
+
+/// # Cache for XML-RPC responses
+///
+/// This type describes a cache for successful responses to calls of specific XML-RPC methods,
+/// which can be enabled with [`ClientBuilder::response_cache`](crate::ClientBuilder::response_cache).
+/// Only methods that were explicitly added with [`ResponseCache::method`] are cached, since
+/// caching the results of methods with side effects would be wrong.
+///
+/// Responses are cached for the given time-to-live, keyed on the serialized method call (i.e.
+/// both the method name and the method parameters). Faults are never cached. If the cache is
+/// full, expired responses are evicted first, and then the oldest response.
+///
+/// ```
+/// use std::time::Duration;
+/// use dxr_client::ResponseCache;
+///
+/// let cache = ResponseCache::new(Duration::from_secs(5), 64)
+///     .method("system.listMethods")
+///     .method("throttle.global_down.max_rate");
+/// ```
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    capacity: usize,
+    methods: HashSet<String>,
+    entries: Mutex<HashMap<String, (Instant, MethodResponse)>>,
+}
+
+impl ResponseCache {
+    /// constructor for an empty [`ResponseCache`] with the given time-to-live and capacity
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        ResponseCache {
+            ttl,
+            capacity,
+            methods: HashSet::new(),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// method for adding a method name to the set of methods whose responses are cached
+    pub fn method(mut self, name: &str) -> Self {
+        self.methods.insert(name.to_owned());
+        self
+    }
+
+    /// method for removing all cached responses
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub(crate) fn is_cached(&self, method: &str) -> bool {
+        self.capacity > 0 && self.methods.contains(method)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<MethodResponse> {
+        let mut entries = self.lock();
+
+        match entries.get(key) {
+            Some((inserted, response)) if inserted.elapsed() < self.ttl => Some(response.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            },
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: String, response: MethodResponse) {
+        let mut entries = self.lock();
+
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            entries.retain(|_, (inserted, _)| inserted.elapsed() < self.ttl);
+        }
+
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (inserted, _))| *inserted)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(key, (Instant::now(), response));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, MethodResponse)>> {
+        // the cache cannot be left in an inconsistent state, so poisoning can be ignored
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::Value;
+
+    fn response(value: i32) -> MethodResponse {
+        MethodResponse::new(Value::i4(value))
+    }
+
+    #[test]
+    fn methods() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2).method("system.listMethods");
+        assert!(cache.is_cached("system.listMethods"));
+        assert!(!cache.is_cached("load.start"));
+
+        let cache = ResponseCache::new(Duration::from_secs(60), 0).method("system.listMethods");
+        assert!(!cache.is_cached("system.listMethods"));
+    }
+
+    #[test]
+    fn capacity() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2);
+
+        cache.insert(String::from("a"), response(1));
+        cache.insert(String::from("b"), response(2));
+        cache.insert(String::from("c"), response(3));
+
+        // the oldest entry was evicted
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(response(2)));
+        assert_eq!(cache.get("c"), Some(response(3)));
+
+        cache.clear();
+        assert_eq!(cache.get("b"), None);
+    }
+
+    #[test]
+    fn expiry() {
+        let cache = ResponseCache::new(Duration::ZERO, 2);
+
+        cache.insert(String::from("a"), response(1));
+        assert_eq!(cache.get("a"), None);
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use dxr::{DxrError, MethodCall, TryFromValue, TryToParams, Value};
+use dxr::{DxrError, MethodCall, TryFromValue, TryToParams, TryToValue, Value};
 
 /// # XML-RPC method call
 ///
@@ -29,14 +29,15 @@ where
     ///
     /// - primitives (`i32`, `i64`, `String`, `f64`, `DateTime`, bytes / `Vec<u8`, etc.)
     /// - arrays and slices of values of the same type (i.e. `Vec<T`, `[T]`, `&[T]`)
-    /// - tuples up to length 8 of values of possibly different types (i.e. `(i32, bool)`
+    /// - tuples up to length 16 of values of possibly different types (i.e. `(i32, bool)`
+    /// - tuples with a `dxr::Variadic` tail for methods with a variable number of arguments
     ///
     /// For method calls with arguments that have different values, either convert them all to
     /// [`Value`] first and use an array type, or use them directly and pass them as a tuple.
     ///
     /// Note that this method will need type annotations to determine the type `R` of the expected
     /// return value.
-    pub fn new(method: &'a str, params: P) -> Call<P, R> {
+    pub fn new(method: &'a str, params: P) -> Call<'a, P, R> {
         Call {
             method,
             params,
@@ -58,6 +59,62 @@ where
     }
 }
 
+impl<'a> Call<'a, Vec<Value>, Value> {
+    /// Constructor for a [`CallBuilder`], which can be used to add method parameters of
+    /// different types one by one.
+    ///
+    /// ```
+    /// use dxr_client::Call;
+    ///
+    /// let call: Call<_, i32> = Call::builder("add").arg(1i32).arg(2i32).build().unwrap();
+    /// ```
+    pub fn builder(method: &'a str) -> CallBuilder<'a> {
+        CallBuilder {
+            method,
+            params: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+/// # Incremental builder for method calls
+///
+/// This type is returned by [`Call::builder`]. It is useful for methods that take many
+/// parameters of different types (i.e. many rtorrent commands), which would otherwise need to be
+/// expressed as large tuples.
+#[derive(Debug)]
+pub struct CallBuilder<'a> {
+    method: &'a str,
+    params: Vec<Value>,
+    error: Option<DxrError>,
+}
+
+impl<'a> CallBuilder<'a> {
+    /// Method for appending a parameter to the list of method parameters.
+    ///
+    /// If the value cannot be converted into an XML-RPC value, the error is returned from
+    /// [`CallBuilder::build`].
+    pub fn arg<T: TryToValue>(mut self, value: T) -> Self {
+        if self.error.is_none() {
+            match value.try_to_value() {
+                Ok(value) => self.params.push(value),
+                Err(error) => self.error = Some(error),
+            }
+        }
+        self
+    }
+
+    /// Build the [`Call`] from the method name and the list of method parameters.
+    ///
+    /// This returns the first error that occurred while converting method parameters.
+    pub fn build<R: TryFromValue>(self) -> Result<Call<'a, Vec<Value>, R>, DxrError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(Call::new(self.method, self.params)),
+        }
+    }
+}
+
 #[cfg(feature = "multicall")]
 impl<P> Call<'static, P, Vec<Value>>
 where
@@ -74,9 +131,36 @@ where
 mod tests {
     #![allow(clippy::unwrap_used)]
 
-    #[cfg(feature = "multicall")]
     use super::*;
 
+    #[test]
+    fn builder() {
+        let call: Call<_, i32> = Call::builder("load.start")
+            .arg("")
+            .arg(String::from("magnet:?xt=urn:btih:0"))
+            .arg(42i32)
+            .arg(true)
+            .build()
+            .unwrap();
+
+        let expected = MethodCall::new(
+            String::from("load.start"),
+            vec![
+                Value::string(String::new()),
+                Value::string(String::from("magnet:?xt=urn:btih:0")),
+                Value::i4(42),
+                Value::boolean(true),
+            ],
+        );
+        assert_eq!(call.as_xml_rpc().unwrap(), expected);
+    }
+
+    #[test]
+    fn builder_error() {
+        let result = Call::builder("echo").arg(u64::MAX).arg(1i32).build::<Value>();
+        assert!(result.is_err());
+    }
+
     #[cfg(feature = "multicall")]
     #[test]
     fn to_multicall() {
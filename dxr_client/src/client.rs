@@ -0,0 +1,262 @@
+use std::future::Future;
+use std::pin::Pin;
+
+#[cfg(feature = "multicall")]
+use dxr::{Fault, Value};
+use dxr::{MethodCall, MethodResponse, TryFromValue, TryToParams};
+
+use crate::transport::return_value;
+#[cfg(feature = "multicall")]
+use crate::MulticallTypes;
+use crate::{Call, ClientError};
+
+/// type alias for the boxed futures that are returned by the methods of [`DxrClientExt`]
+pub type CallFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ClientError>> + Send + 'a>>;
+
+/// # Object-safe XML-RPC client interface
+///
+/// This trait describes the transport of XML-RPC method calls to a server, without any knowledge
+/// of the types of method parameters and return values. It is implemented for [`Client`], but
+/// application code can depend on `&dyn DxrClient` (or any other type that implements this trait)
+/// instead, and substitute a fake implementation in tests.
+///
+/// Typed method calls are provided by the [`DxrClientExt`] trait, which is implemented for all
+/// types that implement [`DxrClient`] (including trait objects).
+///
+/// ```
+/// use dxr::{MethodCall, MethodResponse, TryToValue};
+/// use dxr_client::{async_trait, Call, ClientError, DxrClient, DxrClientExt};
+///
+/// struct FakeClient;
+///
+/// #[async_trait]
+/// impl DxrClient for FakeClient {
+///     async fn send(&self, _call: MethodCall) -> Result<MethodResponse, ClientError> {
+///         Ok(MethodResponse::new(42.try_to_value()?))
+///     }
+/// }
+///
+/// async fn answer(client: &dyn DxrClient) -> Result<i32, ClientError> {
+///     client.call(Call::new("answer", ())).await
+/// }
+/// ```
+///
+/// [`Client`]: crate::Client
+#[async_trait::async_trait]
+pub trait DxrClient: Send + Sync {
+    /// This method is called for sending an XML-RPC method call to the server and receiving the
+    /// server's response.
+    ///
+    /// Fault responses from the XML-RPC server must be returned as [`ClientError::Fault`] errors.
+    async fn send(&self, call: MethodCall) -> Result<MethodResponse, ClientError>;
+}
+
+/// # Typed method calls for XML-RPC clients
+///
+/// This trait provides methods for submitting [`Call`]s with typed parameters and return values to
+/// any [`DxrClient`] implementation. It cannot be implemented manually, since it is already
+/// implemented for all types that implement [`DxrClient`].
+pub trait DxrClientExt: DxrClient {
+    /// Asynchronous method for handling remote procedure calls with XML-RPC.
+    ///
+    /// Fault responses from the XML-RPC server are transparently converted into [`Fault`] errors.
+    /// Invalid XML-RPC responses or faults will result in an appropriate [`DxrError`].
+    ///
+    /// [`Fault`]: dxr::Fault
+    /// [`DxrError`]: dxr::DxrError
+    fn call<'a, P, R>(&'a self, call: Call<'_, P, R>) -> CallFuture<'a, R>
+    where
+        P: TryToParams,
+        R: TryFromValue + 'a;
+
+    /// Asynchronous method for handling "system.multicall" calls.
+    ///
+    /// *Note*: This method does not check if the number of method calls matches the number of
+    /// returned results.
+    #[cfg(feature = "multicall")]
+    fn multicall<'a, P>(&'a self, call: Call<'_, P, Vec<Value>>) -> CallFuture<'a, Vec<Result<Value, Fault>>>
+    where
+        P: TryToParams;
+
+    /// Asynchronous method for handling "system.multicall" calls with the same return type.
+    ///
+    /// Each result is converted into the expected type, or it is an error if the method call failed
+    /// with a fault, or if the returned value had an unexpected type.
+    #[cfg(feature = "multicall")]
+    fn multicall_typed<'a, P, T>(
+        &'a self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> CallFuture<'a, Vec<Result<T, ClientError>>>
+    where
+        P: TryToParams,
+        T: TryFromValue + Send + 'a;
+
+    /// Asynchronous method for handling "system.multicall" calls with different return types.
+    ///
+    /// The results are paired with the expected return types of the method calls (see
+    /// [`MulticallTypes`] for details), so the number of results must match the number of calls.
+    #[cfg(feature = "multicall")]
+    fn multicall_zip<'a, P, T>(&'a self, call: Call<'_, P, Vec<Value>>) -> CallFuture<'a, T::Output>
+    where
+        P: TryToParams,
+        T: MulticallTypes,
+        T::Output: Send + 'a;
+}
+
+impl<C: DxrClient + ?Sized> DxrClientExt for C {
+    fn call<'a, P, R>(&'a self, call: Call<'_, P, R>) -> CallFuture<'a, R>
+    where
+        P: TryToParams,
+        R: TryFromValue + 'a,
+    {
+        // the method call is serialized eagerly, so the future does not need to hold on to it
+        let request = call.as_xml_rpc();
+
+        Box::pin(async move {
+            let request = request?;
+            let method = request.name().to_owned();
+            let response = self.send(request).await?;
+            Ok(return_value(response, Some(&method))?)
+        })
+    }
+
+    #[cfg(feature = "multicall")]
+    fn multicall<'a, P>(&'a self, call: Call<'_, P, Vec<Value>>) -> CallFuture<'a, Vec<Result<Value, Fault>>>
+    where
+        P: TryToParams,
+    {
+        let response = DxrClientExt::call(self, call);
+        Box::pin(async move { crate::transport::multicall_results(response.await?) })
+    }
+
+    #[cfg(feature = "multicall")]
+    fn multicall_typed<'a, P, T>(&'a self, call: Call<'_, P, Vec<Value>>) -> CallFuture<'a, Vec<Result<T, ClientError>>>
+    where
+        P: TryToParams,
+        T: TryFromValue + Send + 'a,
+    {
+        let results = DxrClientExt::multicall(self, call);
+        Box::pin(async move { Ok(crate::typed_results(results.await?)) })
+    }
+
+    #[cfg(feature = "multicall")]
+    fn multicall_zip<'a, P, T>(&'a self, call: Call<'_, P, Vec<Value>>) -> CallFuture<'a, T::Output>
+    where
+        P: TryToParams,
+        T: MulticallTypes,
+        T::Output: Send + 'a,
+    {
+        let results = DxrClientExt::multicall(self, call);
+        Box::pin(async move { T::from_results(results.await?) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use dxr::{Fault, TryToValue, Value};
+
+    // fake client that records method calls and returns canned responses
+    struct FakeClient {
+        calls: Mutex<Vec<MethodCall>>,
+        response: Result<Value, Fault>,
+    }
+
+    impl FakeClient {
+        fn new(response: Result<Value, Fault>) -> Self {
+            FakeClient {
+                calls: Mutex::new(Vec::new()),
+                response,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DxrClient for FakeClient {
+        async fn send(&self, call: MethodCall) -> Result<MethodResponse, ClientError> {
+            self.calls.lock().unwrap().push(call);
+            match &self.response {
+                Ok(value) => Ok(MethodResponse::new(value.clone())),
+                Err(fault) => Err(fault.clone().into()),
+            }
+        }
+    }
+
+    async fn add(client: &dyn DxrClient, a: i32, b: i32) -> Result<i32, ClientError> {
+        client.call(Call::new("add", (a, b))).await
+    }
+
+    #[tokio::test]
+    async fn call_trait_object() {
+        let client = FakeClient::new(Ok(Value::i4(3)));
+        assert_eq!(add(&client, 1, 2).await.unwrap(), 3);
+
+        let calls = client.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name(), "add");
+        assert_eq!(calls[0].clone().params(), vec![Value::i4(1), Value::i4(2)]);
+    }
+
+    #[tokio::test]
+    async fn call_fault() {
+        let client = FakeClient::new(Err(Fault::new(4, String::from("Too many parameters."))));
+
+        match add(&client, 1, 2).await.unwrap_err() {
+            ClientError::Fault { fault } => assert_eq!(fault.code(), 4),
+            error => panic!("unexpected error: {error}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_wrong_type() {
+        let client = FakeClient::new(Ok("three".try_to_value().unwrap()));
+        assert!(matches!(add(&client, 1, 2).await.unwrap_err(), ClientError::RPC { .. }));
+    }
+
+    #[cfg(feature = "multicall")]
+    #[tokio::test]
+    async fn multicall() {
+        let results = Value::array(dxr::Array::new(vec![
+            vec![Value::i4(3)].try_to_value().unwrap(),
+            Value::structure(dxr::Struct::new(vec![
+                dxr::Member::new(String::from("faultCode"), Value::i4(1)),
+                dxr::Member::new(
+                    String::from("faultString"),
+                    Value::string(String::from("Unknown method.")),
+                ),
+            ])),
+        ]));
+        let client = FakeClient::new(Ok(results));
+
+        let call = Call::multicall(vec![(String::from("add"), (1, 2)), (String::from("nope"), (1, 2))]).unwrap();
+        let results = client.multicall(call).await.unwrap();
+
+        assert_eq!(results[0], Ok(Value::i4(3)));
+        assert_eq!(results[1].as_ref().unwrap_err().code(), 1);
+    }
+
+    #[cfg(feature = "multicall")]
+    #[tokio::test]
+    async fn multicall_typed() {
+        let results = Value::array(dxr::Array::new(vec![
+            vec![Value::i4(3)].try_to_value().unwrap(),
+            vec!["three"].try_to_value().unwrap(),
+        ]));
+        let client = FakeClient::new(Ok(results));
+
+        let call = Call::multicall(vec![(String::from("add"), (1, 2)), (String::from("spell"), (1, 2))]).unwrap();
+        let results = client.multicall_typed::<_, i32>(call).await.unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), &3);
+        assert!(matches!(results[1], Err(ClientError::RPC { .. })));
+
+        let call = Call::multicall(vec![(String::from("add"), (1, 2)), (String::from("spell"), (1, 2))]).unwrap();
+        let (sum, spelled) = client.multicall_zip::<_, (i32, String)>(call).await.unwrap();
+        assert_eq!(sum.unwrap(), 3);
+        assert_eq!(spelled.unwrap(), "three");
+    }
+}
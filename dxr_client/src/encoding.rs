@@ -0,0 +1,144 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+use dxr::DxrError;
+
+/// Function for decoding raw XML-RPC response bodies into strings.
+///
+/// The character encoding of the response is determined from (in that order):
+///
+/// - a byte order mark (BOM) at the start of the response,
+/// - the byte pattern of an XML declaration in UTF-16 without BOM,
+/// - the `encoding` attribute of the XML declaration (i.e. `<?xml version="1.0" encoding="..."?>`).
+///
+/// If none of these are present, the response is assumed to be encoded with UTF-8.
+pub(crate) fn decode_response(bytes: &[u8]) -> Result<Cow<'_, str>, DxrError> {
+    let (encoding, bom_length) = match Encoding::for_bom(bytes) {
+        Some(found) => found,
+        None => (sniff_encoding(bytes)?, 0),
+    };
+
+    encoding
+        .decode_without_bom_handling_and_without_replacement(&bytes[bom_length..])
+        .ok_or_else(|| DxrError::invalid_data(format!("Response is not valid {}.", encoding.name())))
+}
+
+fn sniff_encoding(bytes: &[u8]) -> Result<&'static Encoding, DxrError> {
+    // UTF-16 encoded XML declarations without BOM
+    if bytes.starts_with(b"<\0?\0") {
+        return Ok(UTF_16LE);
+    }
+    if bytes.starts_with(b"\0<\0?") {
+        return Ok(UTF_16BE);
+    }
+
+    match declared_encoding(bytes) {
+        Some(label) => match Encoding::for_label(label) {
+            // UTF-16 declarations without BOM or UTF-16 byte pattern are wrong, since the
+            // declaration itself was readable as ASCII: fall back to the default encoding
+            Some(encoding) if encoding == UTF_16LE || encoding == UTF_16BE => Ok(UTF_8),
+            Some(encoding) => Ok(encoding),
+            None => Err(DxrError::invalid_data(format!(
+                "Unsupported response encoding: {}",
+                String::from_utf8_lossy(label)
+            ))),
+        },
+        None => Ok(UTF_8),
+    }
+}
+
+fn declared_encoding(bytes: &[u8]) -> Option<&[u8]> {
+    let declaration = bytes.strip_prefix(b"<?xml")?;
+    let end = declaration.windows(2).position(|window| window == b"?>")?;
+    let declaration = &declaration[..end];
+
+    let start = declaration.windows(8).position(|window| window == b"encoding")?;
+    let rest = trim_start(trim_start(&declaration[start + 8..]).strip_prefix(b"=")?);
+
+    let quote = *rest.first().filter(|c| **c == b'"' || **c == b'\'')?;
+    let rest = &rest[1..];
+    let end = rest.iter().position(|c| *c == quote)?;
+
+    Some(&rest[..end])
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const RESPONSE: &str = "<methodResponse><params><param><value>Grüße</value></param></params></methodResponse>";
+
+    fn utf16(string: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if bom {
+            bytes.extend(if big_endian { [0xFE, 0xFF] } else { [0xFF, 0xFE] });
+        }
+        for unit in string.encode_utf16() {
+            bytes.extend(if big_endian {
+                unit.to_be_bytes()
+            } else {
+                unit.to_le_bytes()
+            });
+        }
+        bytes
+    }
+
+    #[test]
+    fn decode_utf8() {
+        let body = format!(r#"<?xml version="1.0" encoding="UTF-8"?>{RESPONSE}"#);
+        assert_eq!(decode_response(body.as_bytes()).unwrap(), body);
+
+        // no XML declaration
+        assert_eq!(decode_response(RESPONSE.as_bytes()).unwrap(), RESPONSE);
+
+        // BOM
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend(RESPONSE.as_bytes());
+        assert_eq!(decode_response(&bytes).unwrap(), RESPONSE);
+    }
+
+    #[test]
+    fn decode_utf8_fail() {
+        let body = b"<?xml version=\"1.0\"?><value>Gr\xFC\xDFe</value>";
+        assert!(decode_response(body).is_err());
+    }
+
+    #[test]
+    fn decode_latin1() {
+        let body = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><value>Gr\xFC\xDFe</value>";
+        let expected = r#"<?xml version="1.0" encoding="ISO-8859-1"?><value>Grüße</value>"#;
+        assert_eq!(decode_response(body).unwrap(), expected);
+
+        // single quotes and whitespace around "="
+        let body = b"<?xml version='1.0' encoding = 'latin1' ?><value>Gr\xFC\xDFe</value>";
+        let expected = "<?xml version='1.0' encoding = 'latin1' ?><value>Grüße</value>";
+        assert_eq!(decode_response(body).unwrap(), expected);
+    }
+
+    #[test]
+    fn decode_utf16() {
+        let body = format!(r#"<?xml version="1.0" encoding="UTF-16"?>{RESPONSE}"#);
+
+        assert_eq!(decode_response(&utf16(&body, false, true)).unwrap(), body);
+        assert_eq!(decode_response(&utf16(&body, true, true)).unwrap(), body);
+        assert_eq!(decode_response(&utf16(&body, false, false)).unwrap(), body);
+        assert_eq!(decode_response(&utf16(&body, true, false)).unwrap(), body);
+    }
+
+    #[test]
+    fn decode_unsupported() {
+        let body = br#"<?xml version="1.0" encoding="foo-bar"?><value>foo</value>"#;
+        assert!(decode_response(body).is_err());
+    }
+}
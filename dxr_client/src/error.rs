@@ -0,0 +1,217 @@
+use thiserror::Error;
+
+use dxr::{DxrError, Fault};
+
+/// Error type for XML-RPC clients.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Error variant for XML-RPC server faults.
+    #[error("{}", fault)]
+    Fault {
+        /// Fault returned by the server.
+        #[from]
+        fault: Fault,
+    },
+    /// Error variant for XML-RPC errors.
+    #[error("{}", error)]
+    RPC {
+        /// XML-RPC parsing error.
+        #[from]
+        error: DxrError,
+    },
+    /// Error variant for HTTP responses with an error status code (i.e. `404 Not Found`) that do
+    /// not contain an XML-RPC response.
+    ///
+    /// Responses with error status codes that contain an XML-RPC response (i.e. fault responses
+    /// that are sent with `500 Internal Server Error` by some servers) are parsed like any other
+    /// response instead.
+    #[error("HTTP error {}: {}", status, body_snippet)]
+    HttpStatus {
+        /// HTTP status code of the response.
+        status: u16,
+        /// Beginning of the response body (i.e. of an HTML error page).
+        body_snippet: String,
+    },
+    /// Error variant for networking errors.
+    #[cfg(feature = "reqwest")]
+    #[error("{}", error)]
+    Net {
+        /// Networking error returned by [`reqwest`].
+        #[from]
+        error: reqwest::Error,
+    },
+    /// Error variant for HTTP errors.
+    #[cfg(feature = "hyper-client")]
+    #[error("{}", error)]
+    Http {
+        /// HTTP error returned by [`hyper`].
+        #[from]
+        error: hyper::Error,
+    },
+    /// Error variant for networking errors of the blocking client.
+    #[cfg(feature = "ureq")]
+    #[error("{}", error)]
+    Transport {
+        /// Transport error returned by [`ureq`].
+        #[from]
+        error: Box<ureq::Transport>,
+    },
+    /// Error variant for invalid HTTP header names or values that were supplied to a client
+    /// builder.
+    #[cfg(any(feature = "reqwest", feature = "hyper-client"))]
+    #[error("{}", error)]
+    Header {
+        /// Error returned while parsing the HTTP header name or value.
+        #[from]
+        error: http::Error,
+    },
+    /// Error variant for I/O errors (i.e. for connections to unix sockets).
+    #[error("{}", error)]
+    Io {
+        /// I/O error returned while connecting to or communicating with the server.
+        #[from]
+        error: std::io::Error,
+    },
+}
+
+/// # Classification of client errors
+///
+/// This type is returned by [`ClientError::kind`] and [`ClientError::kind_with`]. It can be used
+/// by retry wrappers to decide whether a failed call should be attempted again.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// transient networking error (i.e. the connection was refused or reset)
+    Transient,
+    /// the request or the connection attempt timed out
+    Timeout,
+    /// server fault that was classified as retryable
+    RetryableFault,
+    /// error that will not go away by retrying the call (i.e. invalid responses)
+    Permanent,
+}
+
+impl ErrorKind {
+    /// check whether errors of this kind can be resolved by retrying the call
+    pub fn is_retryable(self) -> bool {
+        !matches!(self, ErrorKind::Permanent)
+    }
+}
+
+impl ClientError {
+    /// Method for classifying this error.
+    ///
+    /// All server faults are considered to be permanent errors. Use [`ClientError::kind_with`]
+    /// for classifying specific faults (i.e. with known fault codes) as retryable.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind_with(|_| false)
+    }
+
+    /// Method for classifying this error, with a custom function that determines whether a
+    /// server fault is retryable.
+    pub fn kind_with(&self, retryable: impl Fn(&Fault) -> bool) -> ErrorKind {
+        match self {
+            ClientError::Fault { fault } if retryable(fault) => ErrorKind::RetryableFault,
+            ClientError::Fault { .. } => ErrorKind::Permanent,
+            ClientError::RPC { .. } => ErrorKind::Permanent,
+            ClientError::HttpStatus { status, .. } => match status {
+                408 | 504 => ErrorKind::Timeout,
+                429 | 502 | 503 => ErrorKind::Transient,
+                _ => ErrorKind::Permanent,
+            },
+            #[cfg(feature = "reqwest")]
+            ClientError::Net { error } => {
+                if error.is_timeout() {
+                    ErrorKind::Timeout
+                } else if error.is_connect() || error.is_request() || error.is_body() {
+                    ErrorKind::Transient
+                } else {
+                    ErrorKind::Permanent
+                }
+            },
+            #[cfg(feature = "hyper-client")]
+            ClientError::Http { error } => {
+                if error.is_timeout() {
+                    ErrorKind::Timeout
+                } else if error.is_connect() || error.is_incomplete_message() || error.is_closed() {
+                    ErrorKind::Transient
+                } else {
+                    ErrorKind::Permanent
+                }
+            },
+            #[cfg(feature = "ureq")]
+            ClientError::Transport { error } => match error.kind() {
+                ureq::ErrorKind::Dns
+                | ureq::ErrorKind::ConnectionFailed
+                | ureq::ErrorKind::Io
+                | ureq::ErrorKind::ProxyConnect => ErrorKind::Transient,
+                _ => ErrorKind::Permanent,
+            },
+            #[cfg(any(feature = "reqwest", feature = "hyper-client"))]
+            ClientError::Header { .. } => ErrorKind::Permanent,
+            ClientError::Io { error } => match error.kind() {
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => ErrorKind::Timeout,
+                // a missing unix socket usually means that the server is (re)starting
+                std::io::ErrorKind::NotFound
+                | std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::UnexpectedEof
+                | std::io::ErrorKind::Interrupted => ErrorKind::Transient,
+                _ => ErrorKind::Permanent,
+            },
+        }
+    }
+
+    /// Method for checking whether this error can be resolved by retrying the call.
+    ///
+    /// This is a shorthand for `error.kind().is_retryable()`.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+
+    #[test]
+    fn faults() {
+        let error = ClientError::from(Fault::new(503, String::from("Busy")));
+        assert_eq!(error.kind(), ErrorKind::Permanent);
+        assert_eq!(error.kind_with(|fault| fault.code() == 503), ErrorKind::RetryableFault);
+        assert_eq!(error.kind_with(|fault| fault.code() == 500), ErrorKind::Permanent);
+    }
+
+    #[test]
+    fn rpc() {
+        let error = ClientError::from(DxrError::invalid_data(String::from("<invalid>")));
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn http_status() {
+        let error = |status| ClientError::HttpStatus {
+            status,
+            body_snippet: String::new(),
+        };
+        assert_eq!(error(404).kind(), ErrorKind::Permanent);
+        assert_eq!(error(503).kind(), ErrorKind::Transient);
+        assert_eq!(error(504).kind(), ErrorKind::Timeout);
+    }
+
+    #[test]
+    fn io() {
+        let error = ClientError::from(io::Error::from(io::ErrorKind::ConnectionRefused));
+        assert_eq!(error.kind(), ErrorKind::Transient);
+
+        let error = ClientError::from(io::Error::from(io::ErrorKind::TimedOut));
+        assert_eq!(error.kind(), ErrorKind::Timeout);
+
+        let error = ClientError::from(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(error.kind(), ErrorKind::Permanent);
+    }
+}
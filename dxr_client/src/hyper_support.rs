@@ -0,0 +1,271 @@
+use hyper::client::connect::Connect;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT};
+use hyper::{Body, Method, Request, Uri};
+use url::Url;
+
+use dxr::{DeserializeOptions, MethodCall, MethodResponse, SerializeOptions, TryFromValue, TryToParams};
+#[cfg(feature = "multicall")]
+use dxr::{Fault, Value};
+
+use crate::encoding::decode_response;
+use crate::transport::{check_status, request_to_body, response_to_result, WireHooks};
+#[cfg(feature = "multicall")]
+use crate::MulticallTypes;
+use crate::{Call, ClientError, DxrClient, DxrClientExt, DEFAULT_USER_AGENT};
+
+/// Builder that takes parameters for constructing a [`HyperClient`] based on [`hyper::Client`].
+#[derive(Debug)]
+pub struct HyperClientBuilder {
+    url: Url,
+    headers: HeaderMap,
+    user_agent: Option<String>,
+    error: Option<http::Error>,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+}
+
+impl HyperClientBuilder {
+    /// Constructor for [`HyperClientBuilder`] from the URL of the XML-RPC server.
+    ///
+    /// This also sets up the default `Content-Type: text/xml` HTTP header for XML-RPC requests.
+    pub fn new(url: Url) -> Self {
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/xml"));
+
+        HyperClientBuilder {
+            url,
+            headers: default_headers,
+            user_agent: None,
+            error: None,
+            options: DeserializeOptions::default(),
+            output: SerializeOptions::default(),
+            hooks: WireHooks::default(),
+        }
+    }
+
+    /// Method for overriding the default User-Agent header.
+    ///
+    /// The value is validated when the client is built.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Method for providing additional custom HTTP headers.
+    ///
+    /// Header names and values can either be passed as [`HeaderName`] and [`HeaderValue`]s, or
+    /// as strings. Invalid header names or values result in an error when the client is built
+    /// (see [`HyperClientBuilder::try_build`]).
+    pub fn add_header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let header = HeaderName::try_from(name)
+            .map_err(Into::into)
+            .and_then(|name| Ok((name, HeaderValue::try_from(value).map_err(Into::into)?)));
+
+        match header {
+            Ok((name, value)) => {
+                self.headers.insert(name, value);
+            },
+            // only the first error is reported
+            Err(error) => {
+                self.error.get_or_insert(error);
+            },
+        }
+
+        self
+    }
+
+    /// Method for overriding the options that are used for deserializing XML-RPC responses.
+    pub fn deserialize_options(mut self, options: DeserializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Method for overriding the options that are used for serializing XML-RPC method calls.
+    pub fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.output = options;
+        self
+    }
+
+    /// Method for registering a callback that is called with the serialized XML of every method
+    /// call before it is sent to the server.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_request(mut self, hook: fn(&str)) -> Self {
+        self.hooks.request = Some(hook);
+        self
+    }
+
+    /// Method for registering a callback that is called with the (decoded) XML of every response
+    /// before it is deserialized.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_response(mut self, hook: fn(&str)) -> Self {
+        self.hooks.response = Some(hook);
+        self
+    }
+
+    /// Build the [`HyperClient`] with the default [`HttpConnector`], which only supports plain
+    /// HTTP connections.
+    ///
+    /// If no custom value was provided for `User-Agent`, the default value
+    /// ([`DEFAULT_USER_AGENT`]) will be used.
+    ///
+    /// This method panics if an invalid HTTP header name or value was supplied. Use
+    /// [`HyperClientBuilder::try_build`] for handling these errors instead.
+    pub fn build(self) -> HyperClient {
+        self.build_with_connector(HttpConnector::new())
+    }
+
+    /// Build the [`HyperClient`] like [`HyperClientBuilder::build`], but return an error instead
+    /// of panicking if an invalid HTTP header name or value (including the `User-Agent`) was
+    /// supplied.
+    pub fn try_build(self) -> Result<HyperClient, ClientError> {
+        self.try_build_with_connector(HttpConnector::new())
+    }
+
+    /// Build the [`HyperClient`] with a custom connector (for example, for HTTPS connections with
+    /// `hyper-rustls` or `hyper-tls`).
+    ///
+    /// If no custom value was provided for `User-Agent`, the default value
+    /// ([`DEFAULT_USER_AGENT`]) will be used.
+    ///
+    /// This method panics if an invalid HTTP header name or value was supplied. Use
+    /// [`HyperClientBuilder::try_build_with_connector`] for handling these errors instead.
+    pub fn build_with_connector<C>(self, connector: C) -> HyperClient<C>
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        self.try_build_with_connector(connector)
+            .expect("Failed to initialize hyper client.")
+    }
+
+    /// Build the [`HyperClient`] with a custom connector like
+    /// [`HyperClientBuilder::build_with_connector`], but return an error instead of panicking if
+    /// an invalid HTTP header name or value (including the `User-Agent`) was supplied.
+    pub fn try_build_with_connector<C>(mut self, connector: C) -> Result<HyperClient<C>, ClientError>
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        let user_agent = self
+            .user_agent
+            .take()
+            .unwrap_or_else(|| String::from(DEFAULT_USER_AGENT));
+
+        let builder = self.add_header(USER_AGENT, user_agent);
+        if let Some(error) = builder.error {
+            return Err(error.into());
+        }
+
+        let uri = builder.url.as_str().parse().expect("Failed to convert URL into URI.");
+
+        Ok(HyperClient {
+            uri,
+            headers: builder.headers,
+            client: hyper::Client::builder().build(connector),
+            options: builder.options,
+            output: builder.output,
+            hooks: builder.hooks,
+        })
+    }
+}
+
+/// # Minimal XML-RPC client implementation
+///
+/// This type provides an XML-RPC client implementation that is based directly on [`hyper`],
+/// without the additional dependencies of [`reqwest`](https://docs.rs/reqwest). It only supports
+/// plain HTTP connections by default, but other connectors (i.e. for HTTPS) can be supplied with
+/// [`HyperClientBuilder::build_with_connector`].
+#[derive(Debug)]
+pub struct HyperClient<C = HttpConnector> {
+    uri: Uri,
+    headers: HeaderMap,
+    client: hyper::Client<C>,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+}
+
+impl<C> HyperClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    /// Asynchronous method for handling remote procedure calls with XML-RPC.
+    ///
+    /// Fault responses from the XML-RPC server are transparently converted into
+    /// [`Fault`](dxr::Fault) errors. Invalid XML-RPC responses or faults will result in an
+    /// appropriate [`DxrError`](dxr::DxrError).
+    pub async fn call<P: TryToParams, R: TryFromValue>(&self, call: Call<'_, P, R>) -> Result<R, ClientError> {
+        DxrClientExt::call(self, call).await
+    }
+
+    /// Asynchronous method for handling "system.multicall" calls.
+    ///
+    /// *Note*: This method does not check if the number of method calls matches the number of
+    /// returned results.
+    #[cfg(feature = "multicall")]
+    pub async fn multicall<P: TryToParams>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Result<Value, Fault>>, ClientError> {
+        DxrClientExt::multicall(self, call).await
+    }
+
+    /// Asynchronous method for handling "system.multicall" calls with the same return type.
+    ///
+    /// See [`DxrClientExt::multicall_typed`] for details.
+    #[cfg(feature = "multicall")]
+    pub async fn multicall_typed<P: TryToParams, T: TryFromValue + Send>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Result<T, ClientError>>, ClientError> {
+        DxrClientExt::multicall_typed(self, call).await
+    }
+
+    /// Asynchronous method for handling "system.multicall" calls with different return types.
+    ///
+    /// See [`DxrClientExt::multicall_zip`] for details.
+    #[cfg(feature = "multicall")]
+    pub async fn multicall_zip<P: TryToParams, T: MulticallTypes>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<T::Output, ClientError>
+    where
+        T::Output: Send,
+    {
+        DxrClientExt::multicall_zip::<P, T>(self, call).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<C> DxrClient for HyperClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    async fn send(&self, call: MethodCall) -> Result<MethodResponse, ClientError> {
+        let body = request_to_body(&call, self.output)?;
+        self.hooks.on_request(&body);
+
+        let mut request = Request::new(Body::from(body));
+        *request.method_mut() = Method::POST;
+        *request.uri_mut() = self.uri.clone();
+        *request.headers_mut() = self.headers.clone();
+
+        let response = self.client.request(request).await?;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        check_status(status.as_u16(), &bytes)?;
+
+        // deserialize XML-RPC method response
+        let contents = decode_response(&bytes)?;
+        self.hooks.on_response(&contents);
+        response_to_result(&contents, self.options)
+    }
+}
@@ -14,17 +14,122 @@
 //! # dxr_client
 //!
 //! This crate provides generic XML-RPC client functionality based on [`dxr`].
+//!
+//! With the `reqwest` or `hyper-client` features, the `DxrClient` trait describes XML-RPC clients independently of
+//! the transport that is used, so application code can depend on it instead of the `Client` type,
+//! and substitute a fake client in tests.
+//!
+//! With the `scgi` feature, XML-RPC servers that listen on unix sockets (with URLs like
+//! `unix:///path/to/socket`) are accessed with the SCGI protocol. Connections to unix sockets are
+//! established with `tokio` if the `tokio` feature is enabled, or with `async-io` (for `async-std`
+//! and `smol` applications) if the `async-io` feature is enabled instead. Both runtime features
+//! enable the `scgi` feature, and neither is enabled by default. Custom connectors can be supplied
+//! by implementing the `UnixConnector` trait.
+//!
+//! With the `hyper-client` feature, the `HyperClient` type provides a minimal client that is based
+//! directly on `hyper` instead of `reqwest`, for applications that need to keep their number of
+//! dependencies low.
+//!
+//! With the `ureq` feature, the `BlockingClient` type provides a synchronous client that is based
+//! on `ureq`, for small tools that should depend on neither `tokio` nor `reqwest`.
+//!
+//! With the `serde-bridge` feature, return values can be deserialized directly into types that
+//! implement `serde::Deserialize` with `Client::call_deserialize`, which skips building an
+//! intermediate `Value` tree and is considerably faster for large responses.
+//!
+//! With the `reqwest` feature, method calls can carry a correlation ID in the `X-Request-Id` HTTP
+//! header (see `ClientBuilder::request_ids`), which servers can log and echo in their responses.
+//!
+//! The optional `rtorrent` feature enables the `rtorrent` module, which contains typed method
+//! calls for commonly used methods of the XML-RPC interface of rtorrent.
+//!
+//! The `chrono` feature (which is enabled by default) enables the `chrono` feature of `dxr`. When
+//! it is disabled, `dateTime.iso8601` values can be converted with the `time` feature of `dxr`
+//! instead.
 
 mod call;
 pub use call::*;
 
+#[cfg(feature = "reqwest")]
+mod cache;
+#[cfg(feature = "reqwest")]
+pub use cache::*;
+
+#[cfg(any(feature = "reqwest", feature = "hyper-client"))]
+mod client;
+#[cfg(any(feature = "reqwest", feature = "hyper-client"))]
+pub use client::*;
+
+#[cfg(any(feature = "reqwest", feature = "hyper-client", feature = "ureq"))]
+mod encoding;
+
+#[cfg(any(feature = "reqwest", feature = "hyper-client", feature = "ureq"))]
+mod error;
+#[cfg(any(feature = "reqwest", feature = "hyper-client", feature = "ureq"))]
+pub use error::*;
+
+#[cfg(feature = "hyper-client")]
+mod hyper_support;
+#[cfg(feature = "hyper-client")]
+pub use hyper_support::*;
+
+#[cfg(all(
+    feature = "multicall",
+    any(feature = "reqwest", feature = "hyper-client", feature = "ureq")
+))]
+mod multicall;
+#[cfg(all(
+    feature = "multicall",
+    any(feature = "reqwest", feature = "hyper-client", feature = "ureq")
+))]
+pub use multicall::*;
+
 #[cfg(feature = "reqwest")]
 mod reqwest_support;
 #[cfg(feature = "reqwest")]
 pub use reqwest_support::*;
 
-// re-export url::URL, as it is exposed in the the public API
 #[cfg(feature = "reqwest")]
+mod request_id;
+#[cfg(feature = "reqwest")]
+pub use request_id::*;
+
+#[cfg(feature = "rtorrent")]
+pub mod rtorrent;
+
+#[cfg(feature = "reqwest")]
+mod stats;
+#[cfg(feature = "reqwest")]
+pub use stats::*;
+
+#[cfg(feature = "reqwest")]
+mod streaming;
+
+#[cfg(feature = "reqwest")]
+mod timings;
+#[cfg(feature = "reqwest")]
+pub use timings::*;
+
+#[cfg(any(feature = "reqwest", feature = "hyper-client", feature = "ureq"))]
+mod transport;
+#[cfg(all(feature = "reqwest", feature = "scgi"))]
+pub use transport::scgi::*;
+
+#[cfg(feature = "ureq")]
+mod ureq_support;
+#[cfg(feature = "ureq")]
+pub use ureq_support::*;
+
+// re-export the async_trait macro, as it is exposed as part of the public API
+#[cfg(any(feature = "reqwest", feature = "hyper-client"))]
+pub use async_trait::async_trait;
+
+// re-export reqwest's redirect policies, as they are exposed in the public API
+#[cfg(feature = "reqwest")]
+pub use reqwest::redirect;
+
+// re-export url::URL, as it is exposed in the the public API
+#[cfg(any(feature = "reqwest", feature = "hyper-client", feature = "ureq"))]
 pub use url::Url;
 
 /// default value of the `User-Agent` HTTP header for XML-RPC requests
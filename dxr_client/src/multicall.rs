@@ -0,0 +1,259 @@
+//! typed conversion of the results of "system.multicall" calls
+
+use dxr::{DxrError, Fault, TryFromValue, Value};
+#[cfg(feature = "reqwest")]
+use dxr::{SerializeOptions, TryToParams, WriteXml};
+
+use crate::ClientError;
+
+/// # Expected return types of the calls in a "system.multicall" batch
+///
+/// This trait is implemented for tuples of types that implement [`TryFromValue`] (with up to
+/// twelve members), and it is used for pairing the results of a "system.multicall" call with the
+/// expected return types of the submitted method calls (in the same order). Each result is either
+/// converted into the expected type, or it is an error (if the method call failed with a fault, or
+/// if the returned value had an unexpected type):
+///
+/// ```
+/// use dxr::{Fault, Value};
+/// use dxr_client::MulticallTypes;
+///
+/// let results = vec![Ok(Value::i4(3)), Err(Fault::new(1, String::from("Unknown method.")))];
+/// let (sum, product) = <(i32, i32)>::from_results(results).unwrap();
+///
+/// assert_eq!(sum.unwrap(), 3);
+/// assert!(product.is_err());
+/// ```
+///
+/// The number of results must match the number of members of the tuple.
+pub trait MulticallTypes {
+    /// tuple of the results of the individual method calls
+    type Output;
+
+    /// This method converts the results of a "system.multicall" call into the expected types.
+    fn from_results(results: Vec<Result<Value, Fault>>) -> Result<Self::Output, ClientError>;
+}
+
+/// Convert the results of a homogeneous "system.multicall" batch (i.e. calls with the same return
+/// type) into values of the expected type.
+pub fn typed_results<T: TryFromValue>(results: Vec<Result<Value, Fault>>) -> Vec<Result<T, ClientError>> {
+    results.into_iter().map(typed_result).collect()
+}
+
+fn typed_result<T: TryFromValue>(result: Result<Value, Fault>) -> Result<T, ClientError> {
+    Ok(T::try_from_value(&result?)?)
+}
+
+/// # Options for splitting large "system.multicall" calls into batches
+///
+/// Some servers reject (or time out on) "system.multicall" calls that contain a large number of
+/// method calls (i.e. rtorrent with tens of thousands of commands). With these options,
+/// [`Client::multicall_chunked`](crate::Client::multicall_chunked) splits the method calls into
+/// batches with a maximum number of calls, and / or a maximum size of the serialized method calls
+/// (in bytes), and submits them either sequentially (the default) or with a bounded number of
+/// concurrent requests:
+///
+/// ```
+/// use dxr_client::MulticallChunks;
+///
+/// let chunks = MulticallChunks::new().max_calls(1000).max_size(1024 * 1024).concurrency(4);
+/// ```
+///
+/// Method calls that are larger than the maximum size on their own are submitted in a batch of
+/// their own. If no limits are set, all method calls are submitted in a single batch.
+#[cfg(feature = "reqwest")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MulticallChunks {
+    max_calls: Option<usize>,
+    max_size: Option<usize>,
+    concurrency: usize,
+}
+
+#[cfg(feature = "reqwest")]
+impl Default for MulticallChunks {
+    fn default() -> Self {
+        MulticallChunks::new()
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl MulticallChunks {
+    /// constructor for [`MulticallChunks`] without any limits and without concurrent requests
+    pub const fn new() -> MulticallChunks {
+        MulticallChunks {
+            max_calls: None,
+            max_size: None,
+            concurrency: 1,
+        }
+    }
+
+    /// method for setting the maximum number of method calls per batch
+    pub const fn max_calls(mut self, max_calls: usize) -> Self {
+        self.max_calls = Some(max_calls);
+        self
+    }
+
+    /// method for setting the maximum size of the serialized method calls per batch (in bytes)
+    pub const fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// method for setting the maximum number of batches that are submitted concurrently
+    ///
+    /// Values smaller than one are treated as one (i.e. batches are submitted sequentially).
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = if concurrency == 0 { 1 } else { concurrency };
+        self
+    }
+
+    pub(crate) fn concurrent_requests(&self) -> usize {
+        self.concurrency
+    }
+
+    // splits method calls into batches, with the parameters already converted into values
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn split<P: TryToParams>(
+        &self,
+        calls: Vec<(String, P)>,
+    ) -> Result<Vec<Vec<(String, Vec<Value>)>>, DxrError> {
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+        let mut size = 0;
+
+        for (name, params) in calls {
+            let params = params.try_to_params()?;
+            let call_size = match self.max_size {
+                Some(_) => serialized_size(&name, &params)?,
+                None => 0,
+            };
+
+            let full_calls = self.max_calls.map_or(false, |max| batch.len() >= max);
+            let full_size = self.max_size.map_or(false, |max| size + call_size > max);
+
+            if !batch.is_empty() && (full_calls || full_size) {
+                batches.push(std::mem::take(&mut batch));
+                size = 0;
+            }
+
+            batch.push((name, params));
+            size += call_size;
+        }
+
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+
+        Ok(batches)
+    }
+}
+
+// approximate size of a method call within a "system.multicall" call (the struct that wraps the
+// method name and parameters is not included)
+#[cfg(feature = "reqwest")]
+fn serialized_size(name: &str, params: &[Value]) -> Result<usize, DxrError> {
+    let mut buf = String::new();
+    for param in params {
+        param
+            .write_xml(&mut buf, SerializeOptions::default())
+            .map_err(DxrError::invalid_data_from)?;
+    }
+    Ok(name.len() + buf.len())
+}
+
+macro_rules! multicall_types_impls {
+    ($len:tt => $($t:ident)+) => {
+        impl<$($t),+> MulticallTypes for ($($t,)+)
+        where
+            $($t: TryFromValue,)+
+        {
+            type Output = ($(Result<$t, ClientError>,)+);
+
+            // the results are bound to variables with the same names as their type parameters
+            #[allow(non_snake_case)]
+            fn from_results(results: Vec<Result<Value, Fault>>) -> Result<Self::Output, ClientError> {
+                let [$($t,)+]: [Result<Value, Fault>; $len] = results
+                    .try_into()
+                    .map_err(|results: Vec<_>| DxrError::parameter_mismatch(results.len(), $len))?;
+
+                Ok(($(typed_result::<$t>($t),)+))
+            }
+        }
+    };
+}
+
+multicall_types_impls!(1 => A);
+multicall_types_impls!(2 => A B);
+multicall_types_impls!(3 => A B C);
+multicall_types_impls!(4 => A B C D);
+multicall_types_impls!(5 => A B C D E);
+multicall_types_impls!(6 => A B C D E F);
+multicall_types_impls!(7 => A B C D E F G);
+multicall_types_impls!(8 => A B C D E F G H);
+multicall_types_impls!(9 => A B C D E F G H I);
+multicall_types_impls!(10 => A B C D E F G H I J);
+multicall_types_impls!(11 => A B C D E F G H I J K);
+multicall_types_impls!(12 => A B C D E F G H I J K L);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn results() -> Vec<Result<Value, Fault>> {
+        vec![
+            Ok(Value::i4(3)),
+            Ok(Value::string(String::from("three"))),
+            Err(Fault::new(1, String::from("Unknown method."))),
+        ]
+    }
+
+    #[test]
+    fn homogeneous() {
+        let results: Vec<Result<i32, ClientError>> = typed_results(results());
+
+        assert_eq!(results[0].as_ref().unwrap(), &3);
+        assert!(matches!(results[1], Err(ClientError::RPC { .. })));
+        assert!(matches!(results[2], Err(ClientError::Fault { .. })));
+    }
+
+    #[test]
+    fn zipped() {
+        let (first, second, third) = <(i32, String, bool)>::from_results(results()).unwrap();
+
+        assert_eq!(first.unwrap(), 3);
+        assert_eq!(second.unwrap(), "three");
+        assert!(matches!(third, Err(ClientError::Fault { .. })));
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn split_calls() {
+        let calls: Vec<(String, (i32,))> = (0..5).map(|i| (String::from("echo"), (i,))).collect();
+
+        let batches = MulticallChunks::new().split(calls.clone()).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 5);
+
+        let batches = MulticallChunks::new().max_calls(2).split(calls.clone()).unwrap();
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(batches[2][0].1, vec![Value::i4(4)]);
+
+        // every call is "echo" + "<value><i4>0</i4></value>" (29 bytes)
+        let batches = MulticallChunks::new().max_size(60).split(calls.clone()).unwrap();
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+
+        // calls that exceed the maximum size on their own are not dropped
+        let batches = MulticallChunks::new().max_size(10).split(calls).unwrap();
+        assert_eq!(batches.len(), 5);
+    }
+
+    #[test]
+    fn mismatch() {
+        let error = <(i32, String)>::from_results(results()).unwrap_err();
+        assert!(matches!(error, ClientError::RPC { error } if error.is_parameter_mismatch()));
+    }
+}
@@ -0,0 +1,55 @@
+//! generation of request IDs for correlating client and server logs
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// name of the HTTP header that contains the request ID of method calls
+///
+/// Request IDs are only sent if they are enabled with
+/// [`ClientBuilder::request_ids`](crate::ClientBuilder::request_ids) or
+/// [`ClientBuilder::request_id_generator`](crate::ClientBuilder::request_id_generator).
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// default generator for request IDs
+///
+/// Generated IDs consist of 32 hexadecimal digits. They are not cryptographically secure, but
+/// they are unique for all practical purposes (they are derived from a per-process random seed,
+/// the current time, and a counter).
+pub fn generate_request_id() -> String {
+    unique_id()
+}
+
+// unique (but not cryptographically secure) identifiers with 32 hexadecimal digits
+pub(crate) fn unique_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(nanos);
+    let first = hasher.finish();
+    hasher.write_u32(std::process::id());
+    let second = hasher.finish();
+
+    format!("{first:016x}{second:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique() {
+        let first = generate_request_id();
+        let second = generate_request_id();
+
+        assert_eq!(first.len(), 32);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+}
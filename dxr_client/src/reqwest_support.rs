@@ -1,63 +1,58 @@
-#[cfg(feature = "multicall")]
-use std::collections::HashMap;
-use std::fmt::Debug;
-use std::io::{Error, ErrorKind};
-use std::io::prelude::*;
-use std::net::ToSocketAddrs;
-use std::ops::Add;
+use std::net::SocketAddr;
+#[cfg(feature = "scgi")]
 use std::path::Path;
-use std::rc::Rc;
-
-use bytes::{BufMut, BytesMut};
-use futures::{SinkExt, StreamExt};
-use http::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, USER_AGENT};
-use http::request;
-use log::error;
-use thiserror::Error;
-use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
-use tokio::net::{TcpStream, UnixStream};
-use tokio_scgi::client::{SCGICodec, SCGIRequest};
-use tokio_util::codec::Framed;
+#[cfg(feature = "scgi")]
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::io::{AsyncWrite, AsyncWriteExt};
+#[cfg(feature = "multicall")]
+use futures::stream::{self, TryStreamExt};
+use futures::stream::{FuturesOrdered, StreamExt};
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT};
+#[cfg(feature = "serde-bridge")]
+use serde::de::DeserializeOwned;
 use url::Url;
 
-use dxr::{DxrError, Fault, FaultResponse, MethodCall, MethodResponse, TryFromValue, TryToParams};
+use dxr::{
+    DeserializeOptions, MethodCall, MethodResponse, MultipleParams, SerializeOptions, TryFromValue, TryToParams, Value,
+};
 #[cfg(feature = "multicall")]
-use dxr::Value;
-
-use crate::{Call, DEFAULT_USER_AGENT};
-
-/// Error type for XML-RPC clients based on [`reqwest`].
-#[derive(Debug, Error)]
-pub enum ClientError {
-    /// Error variant for XML-RPC server faults.
-    #[error("{}", fault)]
-    Fault {
-        /// Fault returned by the server.
-        #[from]
-        fault: Fault,
-    },
-    /// Error variant for XML-RPC errors.
-    #[error("{}", error)]
-    RPC {
-        /// XML-RPC parsing error.
-        #[from]
-        error: DxrError,
-    },
-    /// Error variant for networking errors.
-    #[error("{}", error)]
-    Net {
-        /// Networking error returned by [`reqwest`].
-        #[from]
-        error: reqwest::Error,
-    },
-}
+use dxr::{DxrError, Fault};
+
+use crate::cache::ResponseCache;
+use crate::encoding::decode_response;
+use crate::redirect;
+use crate::stats::StatsCounters;
+use crate::streaming::Base64Stream;
+use crate::timings::{Timer, TimingsHook};
+use crate::transport::digest::DigestAuth;
+#[cfg(feature = "multicall")]
+use crate::transport::multicall_results;
+#[cfg(feature = "scgi")]
+use crate::transport::scgi::{ScgiHeaders, ScgiTransport, UnixConnector};
+use crate::transport::{check_status, request_to_body, response_to_result, return_value, WireHooks};
+use crate::{generate_request_id, Call, ClientError, ClientStats, DxrClient, TimingsCallback, DEFAULT_USER_AGENT};
+#[cfg(feature = "multicall")]
+use crate::{typed_results, MulticallChunks, MulticallTypes};
 
 /// Builder that takes parameters for constructing a [`Client`] based on [`reqwest::Client`].
 #[derive(Debug)]
 pub struct ClientBuilder {
     url: Url,
     headers: HeaderMap,
-    user_agent: Option<&'static str>,
+    user_agent: Option<String>,
+    error: Option<http::Error>,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+    #[cfg(feature = "scgi")]
+    scgi: ScgiTransport,
+    cache: Option<ResponseCache>,
+    digest: Option<DigestAuth>,
+    request_id: Option<fn() -> String>,
+    timings: Option<TimingsHook>,
+    http: reqwest::ClientBuilder,
 }
 
 impl ClientBuilder {
@@ -72,22 +67,259 @@ impl ClientBuilder {
             url,
             headers: default_headers,
             user_agent: None,
+            error: None,
+            options: DeserializeOptions::default(),
+            output: SerializeOptions::default(),
+            hooks: WireHooks::default(),
+            #[cfg(feature = "scgi")]
+            scgi: ScgiTransport::default(),
+            cache: None,
+            digest: None,
+            request_id: None,
+            timings: None,
+            http: reqwest::Client::builder(),
         }
     }
 
     /// Method for overriding the default User-Agent header.
-    pub fn user_agent(mut self, user_agent: &'static str) -> Self {
-        self.user_agent = Some(user_agent);
+    ///
+    /// The value is validated when the client is built.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
         self
     }
 
     /// Method for providing additional custom HTTP headers.
     ///
-    /// Using [`HeaderName`] constants for the header name is recommended. The [`HeaderValue`]
-    /// argument needs to be parsed (probably from a string) with [`HeaderValue::from_str`] to
-    /// ensure their value is valid.
-    pub fn add_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
-        self.headers.insert(name, value);
+    /// Header names and values can either be passed as [`HeaderName`] and [`HeaderValue`]s, or
+    /// as strings. Invalid header names or values result in an error when the client is built
+    /// (see [`ClientBuilder::try_build`]).
+    pub fn add_header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let header = HeaderName::try_from(name)
+            .map_err(Into::into)
+            .and_then(|name| Ok((name, HeaderValue::try_from(value).map_err(Into::into)?)));
+
+        match header {
+            Ok((name, value)) => {
+                self.headers.insert(name, value);
+            },
+            // only the first error is reported
+            Err(error) => {
+                self.error.get_or_insert(error);
+            },
+        }
+
+        self
+    }
+
+    /// Method for overriding the options that are used for deserializing XML-RPC responses.
+    ///
+    /// This can be used to switch between strict and lenient handling of responses for all calls
+    /// that are made with the [`Client`], and for adjusting the limits for the nesting depth and
+    /// size of responses (i.e. for very large responses).
+    pub fn deserialize_options(mut self, options: DeserializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Method for overriding the options that are used for serializing XML-RPC method calls.
+    ///
+    /// This can be used to omit the `<string>` element of string values in method calls, which is
+    /// expected by some XML-RPC servers.
+    pub fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.output = options;
+        self
+    }
+
+    /// Method for overriding the connector that is used for connecting to unix sockets.
+    ///
+    /// XML-RPC servers that listen on unix sockets (with URLs like `unix:///path/to/socket`) are
+    /// accessed with the SCGI protocol. By default, connections are established with [`tokio`]
+    /// if the `tokio` feature is enabled (or with `async-io`, if only the `async-io` feature is
+    /// enabled). Custom [`UnixConnector`]
+    /// implementations can be used to support other async runtimes.
+    ///
+    /// [`tokio`]: https://docs.rs/tokio
+    #[cfg(feature = "scgi")]
+    pub fn unix_connector(mut self, connector: impl UnixConnector + 'static) -> Self {
+        self.scgi.connector = Arc::new(connector);
+        self
+    }
+
+    /// Method for overriding the headers of SCGI requests to XML-RPC servers that listen on unix
+    /// sockets.
+    ///
+    /// By default, the request URI is reported as [`/RPC`](crate::DEFAULT_SCGI_REQUEST_URI). See
+    /// [`ScgiHeaders`] for details.
+    #[cfg(feature = "scgi")]
+    pub fn scgi_headers(mut self, headers: ScgiHeaders) -> Self {
+        self.scgi.headers = headers;
+        self
+    }
+
+    /// Method for setting a read timeout for SCGI connections to XML-RPC servers that listen on
+    /// unix sockets.
+    ///
+    /// Calls fail with a [`TimedOut`](std::io::ErrorKind::TimedOut) I/O error if no data is
+    /// received from the server for the given duration while waiting for a response (i.e. if the
+    /// server is stuck). This is independent of any timeout for the call as a whole. There is no
+    /// read timeout by default.
+    ///
+    /// Timeouts are enforced by the [`UnixConnector`] (see [`UnixConnector::sleep`]).
+    #[cfg(feature = "scgi")]
+    pub fn scgi_read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.scgi.timeouts.read = timeout;
+        self
+    }
+
+    /// Method for setting a write timeout for SCGI connections to XML-RPC servers that listen on
+    /// unix sockets.
+    ///
+    /// Calls fail with a [`TimedOut`](std::io::ErrorKind::TimedOut) I/O error if sending the
+    /// request takes longer than the given duration. There is no write timeout by default.
+    #[cfg(feature = "scgi")]
+    pub fn scgi_write_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.scgi.timeouts.write = timeout;
+        self
+    }
+
+    /// Method for enabling a cache for the responses of read-mostly XML-RPC methods.
+    ///
+    /// Only the methods that were added to the [`ResponseCache`] are cached, all other method
+    /// calls are always sent to the server.
+    pub fn response_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Method for enabling HTTP Digest authentication with the given credentials.
+    ///
+    /// Some XML-RPC servers (i.e. the web interfaces of older BMC / IPMI devices) require Digest
+    /// authentication, which is not supported by [`reqwest`] itself. Requests that are rejected with
+    /// a Digest challenge (`401 Unauthorized`) are repeated once with credentials, and subsequent
+    /// requests reuse the challenge until the server rejects it. The `MD5` and `SHA-256` algorithms
+    /// (and their session variants) are supported.
+    ///
+    /// Credentials are not added to requests that are built with [`Client::build_request`].
+    pub fn digest_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.digest = Some(DigestAuth::new(username.into(), password.into()));
+        self
+    }
+
+    /// Method for setting the timeout for idle connections in the connection pool.
+    ///
+    /// Pass `None` to keep idle connections open indefinitely. The default timeout of
+    /// [`reqwest`] is 90 seconds.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.http = self.http.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Method for setting the maximum number of idle connections per host in the connection pool.
+    ///
+    /// Setting this to `0` disables connection reuse entirely.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http = self.http.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Method for enabling TCP keep-alive (`SO_KEEPALIVE`) with the given interval.
+    ///
+    /// Pass `None` to leave the socket option unset, which is the default.
+    pub fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.http = self.http.tcp_keepalive(interval);
+        self
+    }
+
+    /// Method for only using HTTP/2 (without negotiating the protocol version first).
+    ///
+    /// This only works with XML-RPC servers that are known to support HTTP/2.
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http = self.http.http2_prior_knowledge();
+        self
+    }
+
+    /// Method for overriding the policy for following HTTP redirects.
+    ///
+    /// By default, up to 10 redirects are followed. Note that `307` and `308` redirects repeat
+    /// the `POST` request with the XML-RPC method call at the new location, while other redirects
+    /// are turned into `GET` requests, which XML-RPC servers will likely reject. Use
+    /// [`redirect::Policy::none`](crate::redirect::Policy::none) to treat redirects as errors
+    /// instead, or [`redirect::Policy::custom`](crate::redirect::Policy::custom) for more control.
+    pub fn redirect(mut self, policy: redirect::Policy) -> Self {
+        self.http = self.http.redirect(policy);
+        self
+    }
+
+    /// Method for overriding DNS resolution of the given host name with a fixed address.
+    ///
+    /// Requests are still sent with the original host name in the `Host` header (and for TLS
+    /// server name indication), which is useful for split-horizon DNS setups or for testing
+    /// against staging instances. The port of the address is ignored, the port from the URL of
+    /// the XML-RPC server is used instead.
+    pub fn resolve(mut self, host: &str, addr: SocketAddr) -> Self {
+        self.http = self.http.resolve(host, addr);
+        self
+    }
+
+    /// Method for attaching a request ID to every method call.
+    ///
+    /// Request IDs are sent in the [`X-Request-Id`](crate::REQUEST_ID_HEADER) HTTP header, and are
+    /// generated with [`generate_request_id`]. Servers can include them in their logs, so the logs
+    /// of clients and servers can be correlated. Requests to XML-RPC servers that listen on unix
+    /// sockets do not contain request IDs.
+    pub fn request_ids(self) -> Self {
+        self.request_id_generator(generate_request_id)
+    }
+
+    /// Method for attaching a request ID to every method call, which is generated by the supplied
+    /// function (i.e. for IDs in a format that is required by a tracing system, or for logging
+    /// IDs on the client side).
+    ///
+    /// See [`ClientBuilder::request_ids`] for details.
+    pub fn request_id_generator(mut self, generator: fn() -> String) -> Self {
+        self.request_id = Some(generator);
+        self
+    }
+
+    /// Method for registering a callback that is called with the serialized XML of every method
+    /// call before it is sent to the server.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_request(mut self, hook: fn(&str)) -> Self {
+        self.hooks.request = Some(hook);
+        self
+    }
+
+    /// Method for registering a callback that is called with the (decoded) XML of every response
+    /// before it is deserialized.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_response(mut self, hook: fn(&str)) -> Self {
+        self.hooks.response = Some(hook);
+        self
+    }
+
+    /// Method for registering a callback that is called with the name of the method and a timing
+    /// breakdown of every method call that received a response.
+    ///
+    /// This is intended for finding out whether latency is caused by the client, the network, or
+    /// the server. For HTTP requests, only the time until the response headers were received and
+    /// the total duration are measured, while SCGI requests also report the time for establishing
+    /// the connection and sending the request (see [`CallTimings`](crate::CallTimings) for details). Timings are not
+    /// reported for calls that were answered from the response cache, or for raw documents that
+    /// were sent with [`Client::send_xml`].
+    ///
+    /// The callback can capture state (i.e. a channel or a shared collection of timings), since it
+    /// is called from the tasks that make the method calls.
+    pub fn on_timings(mut self, hook: TimingsCallback) -> Self {
+        self.timings = Some(TimingsHook(hook));
         self
     }
 
@@ -95,20 +327,44 @@ impl ClientBuilder {
     ///
     /// If no custom value was provided for `User-Agent`, the default value
     /// ([`DEFAULT_USER_AGENT`]) will be used.
+    ///
+    /// This method panics if an invalid HTTP header name or value was supplied, or if the
+    /// [`reqwest::Client`] could not be initialized. Use [`ClientBuilder::try_build`] for handling
+    /// these errors instead.
     pub fn build(self) -> Client {
-        let user_agent = self.user_agent.unwrap_or(DEFAULT_USER_AGENT);
+        self.try_build().expect("Failed to initialize reqwest client.")
+    }
 
-        let builder = self.add_header(USER_AGENT, HeaderValue::from_static(user_agent));
+    /// Build the [`Client`] like [`ClientBuilder::build`], but return an error instead of
+    /// panicking if an invalid HTTP header name or value (including the `User-Agent`) was
+    /// supplied, or if the [`reqwest::Client`] could not be initialized.
+    pub fn try_build(mut self) -> Result<Client, ClientError> {
+        let user_agent = self
+            .user_agent
+            .take()
+            .unwrap_or_else(|| String::from(DEFAULT_USER_AGENT));
+
+        let builder = self.add_header(USER_AGENT, user_agent);
+        if let Some(error) = builder.error {
+            return Err(error.into());
+        }
 
-        let client = reqwest::Client::builder()
-            .default_headers(builder.headers)
-            .build()
-            .expect("Failed to initialize reqwest client.");
+        let client = builder.http.default_headers(builder.headers).build()?;
 
-        Client {
+        Ok(Client {
             url: builder.url,
             client,
-        }
+            options: builder.options,
+            output: builder.output,
+            hooks: builder.hooks,
+            #[cfg(feature = "scgi")]
+            scgi: builder.scgi,
+            cache: builder.cache,
+            digest: builder.digest,
+            request_id: builder.request_id,
+            timings: builder.timings,
+            stats: StatsCounters::default(),
+        })
     }
 }
 
@@ -120,157 +376,411 @@ impl ClientBuilder {
 pub struct Client {
     url: Url,
     client: reqwest::Client,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+    #[cfg(feature = "scgi")]
+    scgi: ScgiTransport,
+    cache: Option<ResponseCache>,
+    digest: Option<DigestAuth>,
+    request_id: Option<fn() -> String>,
+    timings: Option<TimingsHook>,
+    stats: StatsCounters,
 }
 
 impl Client {
     /// Constructor for a [`Client`] from a [`reqwest::Client`] that was already initialized.
+    ///
+    /// The default [`SerializeOptions`] and [`DeserializeOptions`] are used for serializing XML-RPC
+    /// method calls and deserializing XML-RPC responses.
     pub fn with_client(url: Url, client: reqwest::Client) -> Self {
-        Client { url, client }
+        Client {
+            url,
+            client,
+            options: DeserializeOptions::default(),
+            output: SerializeOptions::default(),
+            hooks: WireHooks::default(),
+            #[cfg(feature = "scgi")]
+            scgi: ScgiTransport::default(),
+            cache: None,
+            digest: None,
+            request_id: None,
+            timings: None,
+            stats: StatsCounters::default(),
+        }
+    }
+
+    /// Method for getting a snapshot of the statistics of this client.
+    ///
+    /// See [`ClientStats`] for details about the counters.
+    pub fn stats(&self) -> ClientStats {
+        self.stats.snapshot()
+    }
+
+    /// Method for removing all responses from the response cache (if it is enabled).
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
     }
 
     /// Asynchronous method for handling remote procedure calls with XML-RPC.
     ///
-    /// Fault responses from the XML-RPC server are transparently converted into [`Fault`] errors.
-    /// Invalid XML-RPC responses or faults will result in an appropriate [`DxrError`].
+    /// Fault responses from the XML-RPC server are transparently converted into
+    /// [`Fault`](dxr::Fault) errors. Invalid XML-RPC responses or faults will result in an
+    /// appropriate [`DxrError`](dxr::DxrError).
+    ///
+    /// Calls can be cancelled at any time by dropping the returned future (i.e. with a timeout,
+    /// or by racing it against a cancellation signal with `select`). Connections to unix sockets
+    /// are never reused, so the connection is closed immediately when a pending call is dropped,
+    /// and no partially read response can affect later calls.
     pub async fn call<P: TryToParams, R: TryFromValue>(&self, call: Call<'_, P, R>) -> Result<R, ClientError> {
         // serialize XML-RPC method call
         let request = call.as_xml_rpc()?;
-        let body = request_to_body(&request)?;
+        let result = self.send_call(&request).await?;
 
-        let response = match self.url.clone().scheme() {
-            "unix" => {
-                let path = Path::new(self.url.path());
-                let req = SCGIRequest::Request (
-                    vec![
-                        ("CONTENT_LENGTH".to_owned(), body.len().to_string().to_owned()),
-                        ("SCGI".to_owned(), "1".to_owned()),
-                        ("REQUEST_METHOD".to_owned(), "POST".to_owned()),
-                        ("REQUEST_URI".to_owned(), "/RPC".to_owned()),
-                    ],
-                    BytesMut::from(body.as_bytes())
-                );
-
-                match send_scgi_request(self.url.path(), req).await {
-                    Ok(mut stream) => {
-                        /*stream.write_all(body.as_bytes()).unwrap();
-                        let mut buf = String::new();
-                        stream.read_to_string(&mut buf).unwrap();
-                        buf*/
-                        // println!("Response: {:?}", stream);
-                        stream
-                    }
-                    Err(e) => {
-                        eprintln!("Raw Error OS Code: {:?}", e.raw_os_error());
-                        eprintln!("Failed to connect to rtorrent socket: {:?}", e);
-                        return Err(ClientError::Fault { fault: Fault::new(1, "Failed to connect to rtorrent socket".to_string())});
-                    }
+        // extract return value
+        Ok(return_value(result, Some(request.name()))?)
+    }
+
+    /// Asynchronous method for handling independent remote procedure calls concurrently.
+    ///
+    /// Unlike "system.multicall" calls, every method call is sent as a separate request, so this
+    /// also works with XML-RPC servers that do not support multicalls. At most `max_in_flight`
+    /// calls are pending at the same time (values smaller than one are treated as one). The results
+    /// are returned in the same order as the submitted calls, and failed calls do not affect the
+    /// other calls.
+    pub async fn call_batch<P: TryToParams, R: TryFromValue>(
+        &self,
+        calls: Vec<Call<'_, P, R>>,
+        max_in_flight: usize,
+    ) -> Vec<Result<R, ClientError>> {
+        let mut results = Vec::with_capacity(calls.len());
+        let mut calls = calls.into_iter();
+
+        // stream combinators with closures would make the returned future not Send (the calls
+        // contain a lifetime), so pending calls are managed manually
+        let mut pending = FuturesOrdered::new();
+        loop {
+            while pending.len() < max_in_flight.max(1) {
+                match calls.next() {
+                    Some(call) => pending.push_back(self.call(call)),
+                    None => break,
                 }
             }
-            _ => {
-                // let request = self.client.post(self.url.clone()).body(body).build()?;
-                let request = match self.client.post(self.url.clone()).body(body).build() {
-                    Ok(request) => request,
-                    Err(e) => {
-                        eprintln!("Failed to build the request: {:?}", e);
-                        return Err(ClientError::Net { error: e });
-                    }
-                };
-                self.client.execute(request).await?.text().await?
+
+            match pending.next().await {
+                Some(result) => results.push(result),
+                None => break,
             }
-        };
-        // construct request and send to server
-
-        use std::io::prelude::*;
-
-        async fn send_scgi_request(socket_path: &str, request: SCGIRequest) -> std::io::Result<String> {
-            // Connect to the SCGI server
-            let addr = Path::new(socket_path);
-            let mut client = UnixStream::connect(&addr).await?;
-            let mut framed = Framed::new(client, SCGICodec::new());
-            // Send request
-            framed.send(request).await?;
-
-            let mut none_count = 0;
-            let mut some_count = 0;
-            let mut err_count = 0;
-            let mut resp = String::new();
-
-            loop {
-                match framed.next().await {
-                    None => {
-                        // SCGI response not ready: loop for more rx data
-                        // Shouldn't happen for response data, but this is how it would work...
-                        none_count += 1;
-                        eprintln!("Response data is incomplete, resuming read");
-                    }
-                    Some(Err(e)) => {
-                        err_count += 1;
-                        // RX error: return error and abort
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            format!("Error when waiting for response: {}", e),
-                        ));
-                    }
-                    Some(Ok(response)) => {
-                        // Got SCGI response: if empty, treat as end of response.
-                        some_count += 1;
-                        if response.len() == 0 {
-                            break;
-                        }
-                        let mut res = response.to_owned();
-                        match tokio_util::codec::Decoder::decode(&mut SCGICodec::new(), &mut res){
-                            Ok(Some(s)) => {
-                                match String::from_utf8(response.to_vec()) {
-                                    Ok(s) => {
-                                        //remove text until <?xml version="1.0" encoding="UTF-8"?> to fix invalid xml
-                                        let mut s2 = s.split("<?xml").collect::<Vec<&str>>()[1].to_owned();
-                                        &s2.insert_str(0, "<?xml");
-                                        //println!("Got response: {}", s);
-                                        /*println!("Got response: {}", s);
-                                        resp.push_str(s.as_str());*/
-                                        // println!("Got response: {}", s2);
-                                        resp.push_str(s2.as_str());
-                                    },
-                                    Err(e) => {
-                                        eprintln!(
-                                            "{} byte response is not UTF8 ({}):\n{:?}",
-                                            response.len(),
-                                            e,
-                                            response
-                                        );
-                                    },
-                                }
-                            },
-                            Err(e) => {
-                                eprintln!(
-                                    "{} byte response is not UTF8 ({}):\n{:?}",
-                                    response.len(),
-                                    e,
-                                    response
-                                );
-                            },
-                            Ok(None) => {
-                                eprintln!("Response data is incomplete, resuming read");
-                            }
-                        }
+        }
+
+        results
+    }
+
+    /// Asynchronous method for handling remote procedure calls with XML-RPC at a different URL
+    /// than the one this client was built for.
+    ///
+    /// All other settings of this client (i.e. custom HTTP headers and the connection pool) are
+    /// used for these calls as well, which is useful for falling back to secondary servers without
+    /// setting up a separate [`Client`] for each of them. The response cache is not used for these
+    /// calls.
+    pub async fn call_at<P: TryToParams, R: TryFromValue>(
+        &self,
+        url: &Url,
+        call: Call<'_, P, R>,
+    ) -> Result<R, ClientError> {
+        let request = call.as_xml_rpc()?;
+        let (body, mut timer) = self.serialize(&request)?;
+        let contents = self.send_xml_to(url, &body, &mut timer).await?;
+
+        let response = response_to_result(&contents, self.options);
+        self.report(request.name(), timer);
+        let response = self.stats.response(response)?;
+        Ok(return_value(response, Some(request.name()))?)
+    }
+
+    /// Method for building the HTTP request for an XML-RPC method call without sending it.
+    ///
+    /// This can be used for sending requests through custom middleware stacks or executors, while
+    /// still using this client for serializing method calls. Responses can then be converted into
+    /// return values (or faults) with [`Client::parse_response`].
+    ///
+    /// The request has the `Content-Type: text/xml` header, but the other default headers of this
+    /// client (i.e. `User-Agent` and custom headers) are only added when the request is executed
+    /// by the underlying [`reqwest::Client`]. Requests to XML-RPC servers that listen on unix
+    /// sockets cannot be built, since they do not use HTTP. Request hooks (see
+    /// [`ClientBuilder::on_request`]) are not called for these requests.
+    pub fn build_request<P: TryToParams, R: TryFromValue>(
+        &self,
+        call: &Call<'_, P, R>,
+    ) -> Result<reqwest::Request, ClientError> {
+        if self.url.scheme() == "unix" {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Requests to unix sockets cannot be built as HTTP requests.",
+            )
+            .into());
+        }
+
+        let body = request_to_body(&call.as_xml_rpc()?, self.output)?;
+
+        Ok(self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, HeaderValue::from_static("text/xml"))
+            .body(body)
+            .build()?)
+    }
+
+    /// Method for converting the (decoded) body of an XML-RPC response into a return value.
+    ///
+    /// This uses the [`DeserializeOptions`] of this client, and fault responses are converted into
+    /// [`Fault`](dxr::Fault) errors, just like for [`Client::call`]. Response hooks (see
+    /// [`ClientBuilder::on_response`]) are not called for these responses.
+    pub fn parse_response<R: TryFromValue>(&self, contents: &str) -> Result<R, ClientError> {
+        let response = response_to_result(contents, self.options)?;
+        Ok(return_value(response, None)?)
+    }
+
+    /// Asynchronous method for handling remote procedure calls with XML-RPC without converting
+    /// the returned value.
+    ///
+    /// This is useful when the type of the returned value is not known in advance (i.e. for
+    /// interactive or exploratory use). The expected return type `R` of the [`Call`] is ignored.
+    pub async fn call_value<P: TryToParams, R: TryFromValue>(
+        &self,
+        call: Call<'_, P, R>,
+    ) -> Result<Value, ClientError> {
+        let request = call.as_xml_rpc()?;
+        Ok(self.send_call(&request).await?.inner())
+    }
+
+    /// Asynchronous method for handling remote procedure calls with XML-RPC, with return values
+    /// that are deserialized directly into types that implement [`serde::Deserialize`].
+    ///
+    /// The response is deserialized without building an intermediate [`Value`] tree first (see
+    /// [`dxr::from_response_xml`]), which roughly halves the time and memory that is needed for
+    /// parsing large responses. Fault responses are converted into [`Fault`](dxr::Fault) errors,
+    /// just like for [`Client::call`]. The expected return type `R` of the [`Call`] is ignored, and
+    /// the response cache is not used for these calls.
+    #[cfg(feature = "serde-bridge")]
+    pub async fn call_deserialize<P: TryToParams, R: TryFromValue, T: DeserializeOwned>(
+        &self,
+        call: Call<'_, P, R>,
+    ) -> Result<T, ClientError> {
+        let request = call.as_xml_rpc()?;
+        let (body, mut timer) = self.serialize(&request)?;
+        let contents = self.send_xml_to(&self.url, &body, &mut timer).await?;
+
+        let result = dxr::from_response_xml_with(&contents, self.options);
+        self.report(request.name(), timer);
+        let result = result.map_err(|error| error.in_method(request.name()))?;
+        self.stats.response(result.map_err(ClientError::from))
+    }
+
+    /// Asynchronous method for calling XML-RPC methods of non-conformant servers that return more
+    /// than one `<param>` in their responses.
+    ///
+    /// All returned parameters are returned in the order in which they were received, independent
+    /// of the [`MultipleParams`](dxr::MultipleParams) policy of the [`DeserializeOptions`] of this
+    /// client. Responses of conformant servers result in a single value. The response cache is not
+    /// used for these calls.
+    pub async fn call_multi_return<P: TryToParams>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Value>, ClientError> {
+        let request = call.as_xml_rpc()?;
+        let (body, mut timer) = self.serialize(&request)?;
+        let contents = self.send_xml_to(&self.url, &body, &mut timer).await?;
+
+        let options = self.options.multiple_params(MultipleParams::All);
+        let response = response_to_result(&contents, options);
+        self.report(request.name(), timer);
+        Ok(self.stats.response(response)?.into_params())
+    }
+
+    /// Asynchronous method for calling XML-RPC methods that return a single (large) `<base64>`
+    /// value, which is decoded into the given writer (i.e. a file) while the response is received.
+    ///
+    /// This avoids keeping both the complete response and the decoded value in memory. The parts
+    /// of the response that surround the base64 value are still checked for validity, but only
+    /// after the response was received completely, so an error can be returned after some data
+    /// was already written. The number of bytes that were written is returned on success.
+    ///
+    /// Responses are only decoded incrementally if they are received over HTTP and encoded as
+    /// UTF-8. Response hooks (see [`ClientBuilder::on_response`]) and the response cache are not
+    /// used for these calls.
+    pub async fn call_into_writer<P, W>(&self, call: Call<'_, P, Vec<u8>>, writer: &mut W) -> Result<u64, ClientError>
+    where
+        P: TryToParams,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let request = call.as_xml_rpc()?;
+        let (body, mut timer) = self.serialize(&request)?;
+        self.hooks.on_request(&body);
+        self.stats.sent(body.len());
+
+        let mut stream = Base64Stream::new(self.options);
+        let mut written = 0;
+
+        match self.url.scheme() {
+            // SCGI responses are always read completely
+            "unix" => {
+                let response = self
+                    .stats
+                    .request(self.scgi_request(&self.url, body.as_bytes(), &mut timer).await)?;
+                self.stats.received(response.len());
+
+                let bytes = self.stats.response(stream.push(&response))?;
+                writer.write_all(&bytes).await?;
+                written += bytes.len() as u64;
+            },
+            _ => {
+                let request_id = self.request_id.map(|generate| generate());
+                let mut response = self.stats.request(
+                    crate::transport::http::post(
+                        &self.client,
+                        &self.url,
+                        body,
+                        self.digest.as_ref(),
+                        request_id.as_deref(),
+                        &self.stats,
+                    )
+                    .await,
+                )?;
+                timer.first_byte();
+
+                // bodies of responses with error status codes are checked before they are parsed
+                let status = response.status();
+                if !status.is_success() {
+                    let body = response.bytes().await?;
+                    self.stats.received(body.len());
+                    check_status(status.as_u16(), &body)?;
+
+                    let bytes = self.stats.response(stream.push(&body))?;
+                    writer.write_all(&bytes).await?;
+                    written += bytes.len() as u64;
+                } else {
+                    while let Some(chunk) = response.chunk().await? {
+                        self.stats.received(chunk.len());
+                        let bytes = self.stats.response(stream.push(&chunk))?;
+                        writer.write_all(&bytes).await?;
+                        written += bytes.len() as u64;
                     }
                 }
-            }
+            },
+        }
 
-            Ok(resp)
+        let bytes = stream.finish();
+        self.report(request.name(), timer);
+        let bytes = self.stats.response(bytes)?;
+        writer.write_all(&bytes).await?;
+        writer.flush().await?;
+
+        Ok(written + bytes.len() as u64)
+    }
+
+    /// Asynchronous method for calling XML-RPC methods with untyped parameters.
+    ///
+    /// This is useful for callers that need to construct method calls outside of the typed
+    /// [`Call`] machinery (i.e. generic XML-RPC proxies). Fault responses from the XML-RPC server
+    /// are converted into [`Fault`](dxr::Fault) errors, just like for [`Client::call`].
+    pub async fn call_raw(&self, method: &str, params: Vec<Value>) -> Result<Value, ClientError> {
+        let request = MethodCall::new(method.to_owned(), params);
+        Ok(self.send_call(&request).await?.inner())
+    }
+
+    /// Asynchronous method for sending a raw XML document to the XML-RPC server.
+    ///
+    /// The request body is sent as-is, and the (decoded) response body is returned without
+    /// checking whether it is a valid XML-RPC response, so fault responses are returned like any
+    /// other response.
+    pub async fn send_xml(&self, body: &str) -> Result<String, ClientError> {
+        self.send_xml_to(&self.url, body, &mut Timer::start()).await
+    }
+
+    async fn send_xml_to(&self, url: &Url, body: &str, timer: &mut Timer) -> Result<String, ClientError> {
+        self.hooks.on_request(body);
+        self.stats.sent(body.len());
+
+        let response = match url.scheme() {
+            // XML-RPC servers that listen on unix sockets (i.e. rtorrent) use the SCGI protocol
+            "unix" => self.scgi_request(url, body.as_bytes(), timer).await,
+            _ => {
+                let request_id = self.request_id.map(|generate| generate());
+                crate::transport::http::request(
+                    &self.client,
+                    url,
+                    body.to_owned(),
+                    self.digest.as_ref(),
+                    request_id.as_deref(),
+                    &self.stats,
+                    timer,
+                )
+                .await
+            },
+        };
+        let response = self.stats.request(response)?;
+        self.stats.received(response.len());
+
+        let contents = decode_response(&response)?.into_owned();
+        self.hooks.on_response(&contents);
+        Ok(contents)
+    }
+
+    #[cfg(feature = "scgi")]
+    async fn scgi_request(&self, url: &Url, body: &[u8], timer: &mut Timer) -> Result<Vec<u8>, ClientError> {
+        // connections to unix sockets are never reused
+        self.stats.connection();
+        Ok(self.scgi.request(Path::new(url.path()), body, timer).await?)
+    }
+
+    // unix sockets are only supported with the SCGI transport
+    #[cfg(not(feature = "scgi"))]
+    async fn scgi_request(&self, _url: &Url, _body: &[u8], _timer: &mut Timer) -> Result<Vec<u8>, ClientError> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Unix sockets are not supported without the scgi feature.",
+        )
+        .into())
+    }
+
+    // serialize a method call, and start measuring the timings of the call
+    fn serialize(&self, request: &MethodCall) -> Result<(String, Timer), ClientError> {
+        let mut timer = Timer::start();
+        let body = request_to_body(request, self.output)?;
+        timer.serialized();
+        Ok((body, timer))
+    }
+
+    // report the timings of a method call that received a response
+    fn report(&self, method: &str, timer: Timer) {
+        if let Some(TimingsHook(hook)) = &self.timings {
+            hook(method, &timer.finish());
         }
-        /*
-        CONTENT_LENGTH 179
-        SCGI 1
-        REQUEST_METHOD POST
-        REQUEST_URI /RPC
-        */
+    }
+
+    async fn send_call(&self, request: &MethodCall) -> Result<MethodResponse, ClientError> {
+        let (body, mut timer) = self.serialize(request)?;
+
+        let cache = self.cache.as_ref().filter(|cache| cache.is_cached(request.name()));
+        if let Some(response) = cache.and_then(|cache| cache.get(&body)) {
+            return Ok(response);
+        }
+
+        let contents = self.send_xml_to(&self.url, &body, &mut timer).await?;
+
         // deserialize XML-RPC method response
-        let contents = response;
-        let result = response_to_result(&contents)?;
+        let response = response_to_result(&contents, self.options);
+        self.report(request.name(), timer);
+        let response = self.stats.response(response)?;
 
-        // extract return value
-        Ok(R::try_from_value(&result.inner())?)
+        if let Some(cache) = cache {
+            cache.insert(body, response.clone());
+        }
+
+        Ok(response)
     }
 
     /// Asynchronous method for handling "system.multicall" calls.
@@ -283,78 +793,68 @@ impl Client {
         call: Call<'_, P, Vec<Value>>,
     ) -> Result<Vec<Result<Value, Fault>>, ClientError> {
         let response = self.call(call).await?;
+        multicall_results(response)
+    }
 
-        let mut results = Vec::new();
-        for result in response {
-            // return values for successful calls are arrays that contain a single value
-            if let Ok((value, )) = <(Value, )>::try_from_value(&result) {
-                results.push(Ok(value));
-            };
-
-            // return values for failed calls are structs with two members
-            if let Ok(mut value) = <HashMap<String, Value>>::try_from_value(&result) {
-                let code = match value.remove("faultCode") {
-                    Some(code) => code,
-                    None => return Err(DxrError::missing_field("Fault", "faultCode").into()),
-                };
-
-                let string = match value.remove("faultString") {
-                    Some(string) => string,
-                    None => return Err(DxrError::missing_field("Fault", "faultString").into()),
-                };
-
-                // The value might still contain other struct fields:
-                // Rather than return an error because they are unexpected, they are ignored,
-                // since the required "faultCode" and "faultString" members were present.
-
-                let fault = Fault::new(i32::try_from_value(&code)?, String::try_from_value(&string)?);
-                results.push(Err(fault));
-            }
-        }
+    /// Asynchronous method for handling large "system.multicall" calls in batches.
+    ///
+    /// The method calls are split into batches according to the given [`MulticallChunks`]
+    /// options, which are then submitted as separate "system.multicall" calls. The results of all
+    /// batches are returned in the same order as the method calls. If any of the batches fails
+    /// (or returns an unexpected number of results), an error is returned (and the remaining
+    /// batches are not submitted).
+    #[cfg(feature = "multicall")]
+    pub async fn multicall_chunked<P: TryToParams>(
+        &self,
+        calls: Vec<(String, P)>,
+        chunks: MulticallChunks,
+    ) -> Result<Vec<Result<Value, Fault>>, ClientError> {
+        let batches = chunks.split(calls)?;
 
-        Ok(results)
-    }
-}
+        let responses = stream::iter(batches)
+            .map(|batch| async move {
+                let expected = batch.len();
+                let results = self.multicall(Call::multicall(batch)?).await?;
 
-fn request_to_body(call: &MethodCall) -> Result<String, DxrError> {
-    let body = [
-        r#"<?xml version="1.0"?>"#,
-        dxr::serialize_xml(&call)
-            .map_err(|error| DxrError::invalid_data(error.to_string()))?
-            .as_str(),
-        "",
-    ]
-        .join("\n");
-
-    Ok(body)
-}
+                match results.len() {
+                    n if n == expected => Ok(results),
+                    n => Err(ClientError::from(DxrError::parameter_mismatch(n, expected))),
+                }
+            })
+            .buffered(chunks.concurrent_requests());
 
-fn response_to_result(contents: &str) -> Result<MethodResponse, ClientError> {
-    // need to check for FaultResponse first:
-    // - a missing <params> tag is ambiguous (can be either an empty response, or a fault response)
-    // - a present <fault> tag is unambiguous
-    let error2 = match dxr::deserialize_xml(contents) {
-        Ok(fault) => {
-            let response: FaultResponse = fault;
-            return match Fault::try_from(response) {
-                // server fault: return Fault
-                Ok(fault) => Err(fault.into()),
-                // malformed server fault: return DxrError
-                Err(error) => Err(error.into()),
-            };
-        }
-        Err(error) => error.to_string(),
-    };
+        let batches: Vec<Vec<Result<Value, Fault>>> = responses.try_collect().await?;
+        Ok(batches.into_iter().flatten().collect())
+    }
 
-    let error1 = match dxr::deserialize_xml(contents) {
-        Ok(response) => return Ok(response),
-        Err(error) => error.to_string(),
-    };
+    /// Asynchronous method for handling "system.multicall" calls with the same return type.
+    ///
+    /// Each result is converted into the expected type, or it is an error if the method call failed
+    /// with a fault, or if the returned value had an unexpected type.
+    #[cfg(feature = "multicall")]
+    pub async fn multicall_typed<P: TryToParams, T: TryFromValue>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Result<T, ClientError>>, ClientError> {
+        Ok(typed_results(self.multicall(call).await?))
+    }
 
-    // log errors if the contents could not be deserialized as either response or fault
-    log::debug!("Failed to deserialize response as either value or fault.");
-    log::debug!("Response failed with: {}; Fault failed with: {}", error1, error2);
+    /// Asynchronous method for handling "system.multicall" calls with different return types.
+    ///
+    /// The results are paired with the expected return types of the method calls (see
+    /// [`MulticallTypes`] for details), so the number of results must match the number of calls.
+    #[cfg(feature = "multicall")]
+    pub async fn multicall_zip<P: TryToParams, T: MulticallTypes>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<T::Output, ClientError> {
+        T::from_results(self.multicall(call).await?)
+    }
+}
 
-    // malformed response: return DxrError::InvalidData
-    Err(DxrError::invalid_data(contents.to_owned()).into())
+#[async_trait::async_trait]
+impl DxrClient for Client {
+    async fn send(&self, call: MethodCall) -> Result<MethodResponse, ClientError> {
+        self.send_call(&call).await
+    }
 }
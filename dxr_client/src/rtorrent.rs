@@ -0,0 +1,313 @@
+//! # Typed method calls for rtorrent
+//!
+//! This module contains constructors for [`Call`]s of commonly used methods of the XML-RPC
+//! interface of [rtorrent](https://github.com/rakshasa/rtorrent), and types for their results.
+//! Method calls always pass the "target" argument that is expected by rtorrent 0.9 and newer
+//! (either the empty string or the hash of a download).
+//!
+//! ```no_run
+//! # #[cfg(feature = "reqwest")]
+//! # async fn example() -> Result<(), dxr_client::ClientError> {
+//! use dxr_client::{rtorrent, ClientBuilder, Url};
+//!
+//! // connect to the SCGI socket of rtorrent
+//! let client = ClientBuilder::new(Url::parse("unix:///home/user/.rtorrent/rpc.socket").unwrap()).build();
+//!
+//! for download in client.call(rtorrent::downloads("main")).await? {
+//!     println!("{}: {} / {} bytes", download.name, download.completed_bytes, download.size_bytes);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Sizes and rates are returned as `<i8>` values by rtorrent, so the `i8` feature of `dxr` should
+//! be enabled (which is the default).
+
+use dxr::{DxrError, TryFromValue, Value};
+
+use crate::Call;
+
+/// # Download of an rtorrent instance
+///
+/// This type contains the properties of a download that are returned by [`downloads`] (which
+/// uses the `d.multicall2` method).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Download {
+    /// info hash of the download (`d.hash`)
+    pub hash: String,
+    /// name of the download (`d.name`)
+    pub name: String,
+    /// base directory of the download (`d.directory`)
+    pub directory: String,
+    /// total size in bytes (`d.size_bytes`)
+    pub size_bytes: i64,
+    /// number of completed bytes (`d.completed_bytes`)
+    pub completed_bytes: i64,
+    /// number of uploaded bytes (`d.up.total`)
+    pub up_total: i64,
+    /// upload rate in bytes per second (`d.up.rate`)
+    pub up_rate: i64,
+    /// download rate in bytes per second (`d.down.rate`)
+    pub down_rate: i64,
+    /// upload ratio multiplied by 1000 (`d.ratio`)
+    pub ratio: i64,
+    /// whether the download is complete (`d.complete`)
+    pub complete: bool,
+    /// whether the download is open (`d.is_open`)
+    pub is_open: bool,
+    /// whether the download is active (`d.is_active`)
+    pub is_active: bool,
+}
+
+impl Download {
+    /// commands for the properties of downloads, in the order of the fields of [`Download`]
+    pub const COMMANDS: [&'static str; 12] = [
+        "d.hash=",
+        "d.name=",
+        "d.directory=",
+        "d.size_bytes=",
+        "d.completed_bytes=",
+        "d.up.total=",
+        "d.up.rate=",
+        "d.down.rate=",
+        "d.ratio=",
+        "d.complete=",
+        "d.is_open=",
+        "d.is_active=",
+    ];
+}
+
+impl TryFromValue for Download {
+    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
+        let (
+            hash,
+            name,
+            directory,
+            size_bytes,
+            completed_bytes,
+            up_total,
+            up_rate,
+            down_rate,
+            ratio,
+            complete,
+            is_open,
+            is_active,
+        ): (String, String, String, i64, i64, i64, i64, i64, i64, i64, i64, i64) = TryFromValue::try_from_value(value)?;
+
+        Ok(Download {
+            hash,
+            name,
+            directory,
+            size_bytes,
+            completed_bytes,
+            up_total,
+            up_rate,
+            down_rate,
+            ratio,
+            complete: complete != 0,
+            is_open: is_open != 0,
+            is_active: is_active != 0,
+        })
+    }
+}
+
+/// # Tracker of an rtorrent download
+///
+/// This type contains the properties of a tracker that are returned by [`trackers`] (which uses
+/// the `t.multicall` method).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tracker {
+    /// announce URL of the tracker (`t.url`)
+    pub url: String,
+    /// type of the tracker (`t.type`: 1 for HTTP, 2 for UDP, 3 for DHT)
+    pub tracker_type: i64,
+    /// whether the tracker is enabled (`t.is_enabled`)
+    pub is_enabled: bool,
+    /// number of seeders reported by the last scrape (`t.scrape_complete`)
+    pub scrape_complete: i64,
+    /// number of leechers reported by the last scrape (`t.scrape_incomplete`)
+    pub scrape_incomplete: i64,
+}
+
+impl Tracker {
+    /// commands for the properties of trackers, in the order of the fields of [`Tracker`]
+    pub const COMMANDS: [&'static str; 5] = [
+        "t.url=",
+        "t.type=",
+        "t.is_enabled=",
+        "t.scrape_complete=",
+        "t.scrape_incomplete=",
+    ];
+}
+
+impl TryFromValue for Tracker {
+    fn try_from_value(value: &Value) -> Result<Self, DxrError> {
+        let (url, tracker_type, is_enabled, scrape_complete, scrape_incomplete): (String, i64, i64, i64, i64) =
+            TryFromValue::try_from_value(value)?;
+
+        Ok(Tracker {
+            url,
+            tracker_type,
+            is_enabled: is_enabled != 0,
+            scrape_complete,
+            scrape_incomplete,
+        })
+    }
+}
+
+// arguments for "multicall" methods: target, (optional) view, and commands
+fn multicall_args(target: &str, view: Option<&str>, commands: &[&str]) -> Vec<String> {
+    let mut args = vec![String::from(target)];
+    args.extend(view.map(String::from));
+    args.extend(commands.iter().map(|command| String::from(*command)));
+    args
+}
+
+/// constructor for `download_list` calls, which return the hashes of all downloads in a view
+pub fn download_list(view: &str) -> Call<'static, (String, String), Vec<String>> {
+    Call::new("download_list", (String::new(), String::from(view)))
+}
+
+/// constructor for `d.multicall2` calls, which return the properties of all downloads in a view
+pub fn downloads(view: &str) -> Call<'static, Vec<String>, Vec<Download>> {
+    Call::new("d.multicall2", multicall_args("", Some(view), &Download::COMMANDS))
+}
+
+/// constructor for `d.name` calls, which return the name of the download with the given hash
+pub fn download_name(hash: &str) -> Call<'static, (String,), String> {
+    Call::new("d.name", (String::from(hash),))
+}
+
+/// constructor for `t.multicall` calls, which return the trackers of the download with the given
+/// hash
+pub fn trackers(hash: &str) -> Call<'static, Vec<String>, Vec<Tracker>> {
+    Call::new("t.multicall", multicall_args(hash, Some(""), &Tracker::COMMANDS))
+}
+
+/// constructor for `throttle.global_down.max_rate` calls (in bytes per second, 0 is unlimited)
+pub fn global_down_max_rate() -> Call<'static, (String,), i64> {
+    Call::new("throttle.global_down.max_rate", (String::new(),))
+}
+
+/// constructor for `throttle.global_down.max_rate.set` calls (in bytes per second, 0 is unlimited)
+pub fn set_global_down_max_rate(rate: i64) -> Call<'static, (String, i64), i32> {
+    Call::new("throttle.global_down.max_rate.set", (String::new(), rate))
+}
+
+/// constructor for `throttle.global_up.max_rate` calls (in bytes per second, 0 is unlimited)
+pub fn global_up_max_rate() -> Call<'static, (String,), i64> {
+    Call::new("throttle.global_up.max_rate", (String::new(),))
+}
+
+/// constructor for `throttle.global_up.max_rate.set` calls (in bytes per second, 0 is unlimited)
+pub fn set_global_up_max_rate(rate: i64) -> Call<'static, (String, i64), i32> {
+    Call::new("throttle.global_up.max_rate.set", (String::new(), rate))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::{MethodCall, TryToValue};
+
+    #[test]
+    fn to_downloads() {
+        let call = downloads("main").as_xml_rpc().unwrap();
+
+        let mut params = vec![Value::string(String::new()), Value::string(String::from("main"))];
+        params.extend(Download::COMMANDS.iter().map(|c| Value::string(String::from(*c))));
+
+        assert_eq!(call, MethodCall::new(String::from("d.multicall2"), params));
+    }
+
+    #[test]
+    fn to_trackers() {
+        let call = trackers("ABCDEF").as_xml_rpc().unwrap();
+
+        assert_eq!(call.name(), "t.multicall");
+
+        let params = call.params();
+        assert_eq!(params[0], Value::string(String::from("ABCDEF")));
+        assert_eq!(params[1], Value::string(String::new()));
+        assert_eq!(params.len(), Tracker::COMMANDS.len() + 2);
+    }
+
+    #[test]
+    fn to_set_global_down_max_rate() {
+        let call = set_global_down_max_rate(1024).as_xml_rpc().unwrap();
+        let expected = MethodCall::new(
+            String::from("throttle.global_down.max_rate.set"),
+            vec![Value::string(String::new()), 1024i64.try_to_value().unwrap()],
+        );
+
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn from_download() {
+        let value = vec![
+            Value::string(String::from("ABCDEF")),
+            Value::string(String::from("ubuntu.iso")),
+            Value::string(String::from("/downloads")),
+            Value::i4(2048),
+            Value::i4(1024),
+            Value::i4(512),
+            Value::i4(0),
+            Value::i4(128),
+            Value::i4(500),
+            Value::i4(0),
+            Value::i4(1),
+            Value::i4(1),
+        ]
+        .try_to_value()
+        .unwrap();
+
+        let expected = Download {
+            hash: String::from("ABCDEF"),
+            name: String::from("ubuntu.iso"),
+            directory: String::from("/downloads"),
+            size_bytes: 2048,
+            completed_bytes: 1024,
+            up_total: 512,
+            up_rate: 0,
+            down_rate: 128,
+            ratio: 500,
+            complete: false,
+            is_open: true,
+            is_active: true,
+        };
+
+        assert_eq!(Download::try_from_value(&value).unwrap(), expected);
+    }
+
+    #[test]
+    fn from_download_fail() {
+        let value = vec![Value::string(String::from("ABCDEF"))].try_to_value().unwrap();
+        assert!(Download::try_from_value(&value).is_err());
+    }
+
+    #[test]
+    fn from_tracker() {
+        let value = vec![
+            Value::string(String::from("udp://tracker.example.com:6969")),
+            Value::i4(2),
+            Value::i4(1),
+            Value::i4(10),
+            Value::i4(3),
+        ]
+        .try_to_value()
+        .unwrap();
+
+        let expected = Tracker {
+            url: String::from("udp://tracker.example.com:6969"),
+            tracker_type: 2,
+            is_enabled: true,
+            scrape_complete: 10,
+            scrape_incomplete: 3,
+        };
+
+        assert_eq!(Tracker::try_from_value(&value).unwrap(), expected);
+    }
+}
@@ -0,0 +1,160 @@
+//! counters for calls, faults, and transferred data of a client
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::ClientError;
+
+/// # Statistics of a client
+///
+/// This type contains a snapshot of the counters of a [`Client`](crate::Client), which can be
+/// obtained with [`Client::stats`](crate::Client::stats). The counters are never reset, so
+/// changes between two snapshots can be used for monitoring (i.e. for telling whether latency
+/// spikes coincide with faults, retries, or many new connections).
+///
+/// Connections to XML-RPC servers that listen on unix sockets (with the SCGI transport) are never
+/// reused, so a new connection is established for every request that is sent to them. HTTP
+/// connections are pooled and reused by [`reqwest`], which does not expose whether a connection
+/// was reused, so they are not counted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ClientStats {
+    calls: u64,
+    faults: u64,
+    errors: u64,
+    retries: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+    connections: u64,
+}
+
+impl ClientStats {
+    /// getter method for the number of requests that were sent to the server
+    ///
+    /// Calls that were answered from the response cache are not counted, and "system.multicall"
+    /// calls are counted as a single request.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// getter method for the number of fault responses that were received
+    pub fn faults(&self) -> u64 {
+        self.faults
+    }
+
+    /// getter method for the number of requests that failed without a response (i.e. because the
+    /// connection failed, or the server responded with an HTTP error)
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// getter method for the number of requests that were repeated (i.e. with credentials after
+    /// a Digest authentication challenge)
+    pub fn retries(&self) -> u64 {
+        self.retries
+    }
+
+    /// getter method for the number of bytes that were sent in request bodies
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// getter method for the number of bytes that were received in response bodies
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// getter method for the number of new connections to unix sockets (for the SCGI transport)
+    pub fn connections(&self) -> u64 {
+        self.connections
+    }
+}
+
+/// shared counters that are updated by the client
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    calls: AtomicU64,
+    faults: AtomicU64,
+    errors: AtomicU64,
+    retries: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    connections: AtomicU64,
+}
+
+impl StatsCounters {
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            faults: self.faults.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            connections: self.connections.load(Ordering::Relaxed),
+        }
+    }
+
+    /// count a request and the size of its body
+    pub(crate) fn sent(&self, bytes: usize) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// count (a part of) the body of a response
+    pub(crate) fn received(&self, bytes: usize) {
+        self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "scgi")]
+    pub(crate) fn connection(&self) {
+        self.connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// count failed requests
+    pub(crate) fn request<T, E>(&self, result: Result<T, E>) -> Result<T, E> {
+        if result.is_err() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// count fault responses
+    pub(crate) fn response<T>(&self, result: Result<T, ClientError>) -> Result<T, ClientError> {
+        if let Err(ClientError::Fault { .. }) = &result {
+            self.faults.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::Fault;
+
+    #[test]
+    fn counters() {
+        let counters = StatsCounters::default();
+
+        counters.sent(100);
+        counters.received(20);
+        counters.received(30);
+        let _ = counters.response::<()>(Err(Fault::new(1, String::from("error")).into()));
+        let _ = counters.response(Ok(()));
+        let _ = counters.request::<(), _>(Err(()));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.calls(), 1);
+        assert_eq!(stats.bytes_sent(), 100);
+        assert_eq!(stats.bytes_received(), 50);
+        assert_eq!(stats.faults(), 1);
+        assert_eq!(stats.errors(), 1);
+        assert_eq!(stats.retries(), 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! incremental decoding of XML-RPC responses that contain a single (large) `<base64>` value
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use dxr::{DeserializeOptions, DxrError, TryFromValue};
+
+use crate::encoding::decode_response;
+use crate::transport::response_to_result;
+use crate::ClientError;
+
+const START_TAG: &[u8] = b"<base64>";
+
+enum State {
+    // looking for the start tag of the base64 value (the document is buffered until it is found)
+    Head,
+    // decoding the contents of the base64 value
+    Contents,
+    // buffering the rest of the document after the contents of the base64 value
+    Tail,
+}
+
+/// state machine that decodes the contents of the `<base64>` value in a method response while
+/// the response is received, without buffering them
+///
+/// Everything except the contents of the base64 value is buffered, and checked for validity when
+/// the response is complete. If the response does not contain a `<base64>` start tag (i.e. for
+/// fault responses, empty values, or responses that are not encoded as UTF-8), the response is
+/// buffered and deserialized completely instead.
+pub(crate) struct Base64Stream {
+    state: State,
+    head: Vec<u8>,
+    tail: Vec<u8>,
+    pending: Vec<u8>,
+    padded: bool,
+    options: DeserializeOptions,
+}
+
+impl Base64Stream {
+    pub(crate) fn new(options: DeserializeOptions) -> Self {
+        Base64Stream {
+            state: State::Head,
+            head: Vec::new(),
+            tail: Vec::new(),
+            pending: Vec::new(),
+            padded: false,
+            options,
+        }
+    }
+
+    /// process the next chunk of the response and return the bytes that were decoded from it
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<Vec<u8>, ClientError> {
+        match self.state {
+            State::Head => {
+                // the start tag can be split across chunks, so the search starts a bit earlier
+                let from = self.head.len().saturating_sub(START_TAG.len() - 1);
+                self.head.extend_from_slice(chunk);
+
+                let start = self.head[from..]
+                    .windows(START_TAG.len())
+                    .position(|window| window == START_TAG);
+
+                match start {
+                    Some(start) => {
+                        let contents = self.head.split_off(from + start + START_TAG.len());
+                        self.state = State::Contents;
+                        self.contents(&contents)
+                    },
+                    None => Ok(Vec::new()),
+                }
+            },
+            State::Contents => self.contents(chunk),
+            State::Tail => {
+                self.tail.extend_from_slice(chunk);
+                Ok(Vec::new())
+            },
+        }
+    }
+
+    fn contents(&mut self, chunk: &[u8]) -> Result<Vec<u8>, ClientError> {
+        let (contents, rest) = match chunk.iter().position(|b| *b == b'<') {
+            Some(end) => chunk.split_at(end),
+            None => (chunk, &[][..]),
+        };
+
+        for c in contents.iter().filter(|c| !c.is_ascii_whitespace()) {
+            // padding can only occur at the very end of base64 encoded data
+            if self.padded {
+                return Err(invalid_base64());
+            }
+            self.pending.push(*c);
+        }
+
+        let complete = self.pending.len() - self.pending.len() % 4;
+        let decoded = STANDARD
+            .decode(&self.pending[..complete])
+            .map_err(|_| invalid_base64())?;
+        self.padded = self.pending[..complete].ends_with(b"=");
+        self.pending.drain(..complete);
+
+        if !rest.is_empty() {
+            self.state = State::Tail;
+            self.tail.extend_from_slice(rest);
+        }
+
+        Ok(decoded)
+    }
+
+    /// check the buffered parts of the complete response, and return the bytes that were not
+    /// decoded incrementally
+    pub(crate) fn finish(self) -> Result<Vec<u8>, ClientError> {
+        match self.state {
+            State::Head => {
+                let contents = decode_response(&self.head)?;
+                let response = response_to_result(&contents, self.options)?;
+                Ok(Vec::<u8>::try_from_value(&response.inner())?)
+            },
+            State::Contents => Err(DxrError::invalid_data(String::from("Unterminated <base64> value.")).into()),
+            State::Tail => {
+                if !self.pending.is_empty() {
+                    return Err(invalid_base64());
+                }
+
+                // the response without the contents of the base64 value must still be valid
+                let mut skeleton = self.head;
+                skeleton.extend_from_slice(&self.tail);
+
+                let contents = decode_response(&skeleton)?;
+                let response = response_to_result(&contents, self.options)?;
+                Vec::<u8>::try_from_value(&response.inner())?;
+
+                Ok(Vec::new())
+            },
+        }
+    }
+}
+
+fn invalid_base64() -> ClientError {
+    DxrError::invalid_data(String::from("Invalid base64 encoded data.")).into()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::{Fault, Value};
+
+    fn decode(response: &[u8], chunk_size: usize) -> Result<Vec<u8>, ClientError> {
+        let mut stream = Base64Stream::new(DeserializeOptions::default());
+        let mut bytes = Vec::new();
+
+        for chunk in response.chunks(chunk_size) {
+            bytes.extend(stream.push(chunk)?);
+        }
+        bytes.extend(stream.finish()?);
+
+        Ok(bytes)
+    }
+
+    fn response(value: &Value) -> String {
+        let response = dxr::MethodResponse::new(value.clone());
+        format!("<?xml version=\"1.0\"?>\n{}", dxr::serialize_xml(&response).unwrap())
+    }
+
+    #[test]
+    fn chunked() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let response = response(&Value::base64(bytes.clone()));
+
+        for chunk_size in 1..=32 {
+            assert_eq!(decode(response.as_bytes(), chunk_size).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn line_wrapped() {
+        let response = "<methodResponse><params><param><value><base64>\nSGVs\nbG8h\n</base64></value></param></params></methodResponse>";
+        assert_eq!(decode(response.as_bytes(), 3).unwrap(), b"Hello!");
+    }
+
+    #[test]
+    fn empty() {
+        let response = response(&Value::base64(Vec::new()));
+        assert_eq!(decode(response.as_bytes(), 4).unwrap(), b"");
+    }
+
+    #[test]
+    fn fault() {
+        let fault = Fault::new(404, String::from("Not found."));
+        let response = dxr::serialize_xml(&dxr::FaultResponse::from(fault.clone())).unwrap();
+
+        match decode(response.as_bytes(), 7).unwrap_err() {
+            ClientError::Fault { fault: error } => assert_eq!(error, fault),
+            error => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    #[test]
+    fn wrong_type() {
+        let response = response(&Value::i4(42));
+        assert!(decode(response.as_bytes(), 5).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let response =
+            "<methodResponse><params><param><value><base64>SGVsbG8h</base64></value></param></methodResponse>";
+        assert!(decode(response.as_bytes(), 5).is_err());
+
+        let response = "<methodResponse><params><param><value><base64>SGVsbG8h";
+        assert!(decode(response.as_bytes(), 5).is_err());
+
+        let response =
+            "<methodResponse><params><param><value><base64>SGV=SGVs</base64></value></param></params></methodResponse>";
+        assert!(decode(response.as_bytes(), 5).is_err());
+
+        let response =
+            "<methodResponse><params><param><value><base64>SGVsbG8</base64></value></param></params></methodResponse>";
+        assert!(decode(response.as_bytes(), 5).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! timing breakdown of method calls
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// # Timing breakdown of a method call
+///
+/// Timings are reported to the callback that is registered with
+/// [`ClientBuilder::on_timings`](crate::ClientBuilder::on_timings) for every method call that
+/// received a response. All durations are measured from the start of the call, so the time that
+/// was spent in each phase is the difference between two consecutive timings:
+///
+/// - `serialize`: the method call was serialized,
+/// - `connect`: the connection was established,
+/// - `send`: the request was sent completely,
+/// - `first_byte`: the first part of the response was received,
+/// - `total`: the response was received completely and deserialized.
+///
+/// Which phases are measured depends on the transport:
+///
+/// - **SCGI** (XML-RPC servers that listen on unix sockets): all phases are measured. The
+///   difference between `send` and `first_byte` is the time the server needed for generating the
+///   response.
+/// - **HTTP**: only `serialize`, `first_byte`, and `total` are measured. Connections are
+///   established (or reused from the connection pool) and requests are sent by [`reqwest`], which
+///   does not report when these phases have finished, so `connect` and `send` are always `None`.
+///   The `first_byte` timing is taken when the response headers were received, so it includes the
+///   time for connecting, sending the request, and generating the response.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CallTimings {
+    serialize: Duration,
+    connect: Option<Duration>,
+    send: Option<Duration>,
+    first_byte: Option<Duration>,
+    total: Duration,
+}
+
+impl CallTimings {
+    /// getter method for the time until the method call was serialized
+    pub fn serialize(&self) -> Duration {
+        self.serialize
+    }
+
+    /// getter method for the time until the connection was established
+    ///
+    /// This is only measured for SCGI requests, and it is always `None` for HTTP requests.
+    pub fn connect(&self) -> Option<Duration> {
+        self.connect
+    }
+
+    /// getter method for the time until the request was sent completely
+    ///
+    /// This is only measured for SCGI requests, and it is always `None` for HTTP requests.
+    pub fn send(&self) -> Option<Duration> {
+        self.send
+    }
+
+    /// getter method for the time until the first part of the response was received
+    pub fn first_byte(&self) -> Option<Duration> {
+        self.first_byte
+    }
+
+    /// getter method for the total duration of the method call
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+/// callback for the name of the method and the timing breakdown of method calls
+///
+/// See [`ClientBuilder::on_timings`](crate::ClientBuilder::on_timings) for details.
+pub type TimingsCallback = Arc<dyn Fn(&str, &CallTimings) + Send + Sync>;
+
+// callback that was registered with ClientBuilder::on_timings (closures are not Debug)
+#[derive(Clone)]
+pub(crate) struct TimingsHook(pub(crate) TimingsCallback);
+
+impl Debug for TimingsHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("TimingsHook")
+    }
+}
+
+/// stopwatch for the phases of a single method call
+#[derive(Debug)]
+pub(crate) struct Timer {
+    start: Instant,
+    timings: CallTimings,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Timer {
+        Timer {
+            start: Instant::now(),
+            timings: CallTimings::default(),
+        }
+    }
+
+    pub(crate) fn serialized(&mut self) {
+        self.timings.serialize = self.start.elapsed();
+    }
+
+    #[cfg(feature = "scgi")]
+    pub(crate) fn connected(&mut self) {
+        self.timings.connect = Some(self.start.elapsed());
+    }
+
+    #[cfg(feature = "scgi")]
+    pub(crate) fn sent(&mut self) {
+        self.timings.send = Some(self.start.elapsed());
+    }
+
+    /// record the arrival of the first part of the response (later parts are ignored)
+    pub(crate) fn first_byte(&mut self) {
+        if self.timings.first_byte.is_none() {
+            self.timings.first_byte = Some(self.start.elapsed());
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> CallTimings {
+        self.timings.total = self.start.elapsed();
+        self.timings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered() {
+        let mut timer = Timer::start();
+        timer.serialized();
+        timer.first_byte();
+        let first = timer.timings.first_byte;
+        timer.first_byte();
+        let timings = timer.finish();
+
+        assert_eq!(timings.first_byte(), first);
+        assert!(timings.serialize() <= timings.first_byte().unwrap_or_default());
+        assert!(timings.first_byte().unwrap_or_default() <= timings.total());
+        assert_eq!(timings.connect(), None);
+    }
+}
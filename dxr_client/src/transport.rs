@@ -0,0 +1,150 @@
+//! shared code for serializing XML-RPC method calls and deserializing responses
+
+#[cfg(feature = "multicall")]
+use std::collections::HashMap;
+
+#[cfg(feature = "multicall")]
+use dxr::Value;
+use dxr::{
+    DeserializeOptions, DxrError, Fault, FaultResponse, MethodCall, MethodResponse, SerializeOptions, TryFromValue,
+    WriteXml,
+};
+
+use crate::encoding::decode_response;
+use crate::ClientError;
+
+#[cfg(feature = "reqwest")]
+pub(crate) mod digest;
+
+#[cfg(feature = "reqwest")]
+pub(crate) mod http;
+
+#[cfg(all(feature = "reqwest", feature = "scgi"))]
+pub(crate) mod scgi;
+
+#[cfg(feature = "multicall")]
+pub(crate) fn multicall_results(response: Vec<Value>) -> Result<Vec<Result<Value, Fault>>, ClientError> {
+    let mut results = Vec::new();
+    for result in response {
+        // return values for successful calls are arrays that contain a single value
+        if let Ok((value,)) = <(Value,)>::try_from_value(&result) {
+            results.push(Ok(value));
+        };
+
+        // return values for failed calls are structs with two members
+        if let Ok(mut value) = <HashMap<String, Value>>::try_from_value(&result) {
+            let code = match value.remove("faultCode") {
+                Some(code) => code,
+                None => return Err(DxrError::missing_field("Fault", "faultCode").into()),
+            };
+
+            let string = match value.remove("faultString") {
+                Some(string) => string,
+                None => return Err(DxrError::missing_field("Fault", "faultString").into()),
+            };
+
+            // The value might still contain other struct fields:
+            // Rather than return an error because they are unexpected, they are ignored,
+            // since the required "faultCode" and "faultString" members were present.
+
+            let fault = Fault::new(i32::try_from_value(&code)?, String::try_from_value(&string)?);
+            results.push(Err(fault));
+        }
+    }
+
+    Ok(results)
+}
+
+// optional callbacks for inspecting the serialized XML of requests and responses
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct WireHooks {
+    pub(crate) request: Option<fn(&str)>,
+    pub(crate) response: Option<fn(&str)>,
+}
+
+impl WireHooks {
+    pub(crate) fn on_request(&self, body: &str) {
+        if let Some(hook) = self.request {
+            hook(body);
+        }
+    }
+
+    pub(crate) fn on_response(&self, body: &str) {
+        if let Some(hook) = self.response {
+            hook(body);
+        }
+    }
+}
+
+pub(crate) fn request_to_body(call: &MethodCall, options: SerializeOptions) -> Result<String, DxrError> {
+    let mut body = String::from("<?xml version=\"1.0\"?>\n");
+    call.write_xml(&mut body, options)
+        .map_err(DxrError::invalid_data_from)?;
+    body.push('\n');
+
+    Ok(body)
+}
+
+// maximum length (in bytes) of the beginning of response bodies that is included in HTTP errors
+const MAX_BODY_SNIPPET: usize = 256;
+
+/// check the HTTP status code of a response before its body is parsed
+///
+/// Responses with error status codes are only parsed if they contain an XML-RPC response (i.e. a
+/// fault that was sent with `500 Internal Server Error`).
+pub(crate) fn check_status(status: u16, body: &[u8]) -> Result<(), ClientError> {
+    if (200..300).contains(&status) {
+        return Ok(());
+    }
+
+    if decode_response(body).map_or(false, |contents| contents.contains("<methodResponse")) {
+        return Ok(());
+    }
+
+    let snippet = String::from_utf8_lossy(&body[..body.len().min(MAX_BODY_SNIPPET)]);
+    Err(ClientError::HttpStatus {
+        status,
+        body_snippet: snippet.trim().to_owned(),
+    })
+}
+
+pub(crate) fn response_to_result(contents: &str, options: DeserializeOptions) -> Result<MethodResponse, ClientError> {
+    // need to check for FaultResponse first:
+    // - a missing <params> tag is ambiguous (can be either an empty response, or a fault response)
+    // - a present <fault> tag is unambiguous
+    let error2 = match dxr::deserialize_xml_with(contents, options) {
+        Ok(fault) => {
+            let response: FaultResponse = fault;
+            return match Fault::try_from(response) {
+                // server fault: return Fault
+                Ok(fault) => Err(fault.into()),
+                // malformed server fault: return DxrError
+                Err(error) => Err(error.into()),
+            };
+        },
+        Err(error) => error.to_string(),
+    };
+
+    let error1 = match dxr::deserialize_xml_with(contents, options) {
+        Ok(response) => return Ok(response),
+        Err(error) => error,
+    };
+
+    // log errors if the contents could not be deserialized as either response or fault
+    log::debug!("Failed to deserialize response as either value or fault.");
+    log::debug!("Response failed with: {}; Fault failed with: {}", error1, error2);
+
+    // malformed response: return DxrError::InvalidData (with the position of syntax errors)
+    Err(DxrError::invalid_xml(contents, error1).into())
+}
+
+// conversion errors include the method name and the path of the value that could not be converted
+pub(crate) fn return_value<R: TryFromValue>(response: MethodResponse, method: Option<&str>) -> Result<R, DxrError> {
+    R::try_from_value(&response.inner()).map_err(|error| {
+        let error = error.in_member("result");
+        match method {
+            Some(method) => error.in_method(method),
+            None => error,
+        }
+    })
+}
@@ -0,0 +1,368 @@
+//! HTTP Digest authentication (RFC 7616) for XML-RPC servers that do not support Basic
+//! authentication (i.e. older BMC / IPMI web interfaces)
+//!
+//! Only the `auth` quality of protection is supported (and the legacy mode of RFC 2069 without any
+//! quality of protection), with the MD5 and SHA-256 algorithms (and their session variants).
+
+use std::fmt::{self, Debug, Formatter, Write};
+use std::sync::{Mutex, MutexGuard};
+
+use md5::Md5;
+use reqwest::header::{HeaderMap, WWW_AUTHENTICATE};
+use sha2::{Digest, Sha256};
+
+// credentials and the most recent challenge of the server, which is reused for subsequent requests
+// (with an increasing nonce count) until the server rejects the nonce
+pub(crate) struct DigestAuth {
+    username: String,
+    password: String,
+    state: Mutex<Option<(Challenge, u32)>>,
+}
+
+impl Debug for DigestAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DigestAuth")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DigestAuth {
+    pub(crate) fn new(username: String, password: String) -> DigestAuth {
+        DigestAuth {
+            username,
+            password,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// value of the `Authorization` header for a request, if a challenge was already received
+    pub(crate) fn authorization(&self, method: &str, uri: &str) -> Option<String> {
+        let mut state = self.lock();
+        let (challenge, count) = state.as_mut()?;
+        *count += 1;
+        Some(self.response(challenge, method, uri, *count, &cnonce()?))
+    }
+
+    /// store the Digest challenge from the headers of a `401 Unauthorized` response, and return
+    /// whether the request should be repeated with credentials
+    pub(crate) fn challenge(&self, headers: &HeaderMap) -> bool {
+        let challenge = headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(Challenge::parse);
+
+        match challenge {
+            Some(challenge) => {
+                *self.lock() = Some((challenge, 0));
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<(Challenge, u32)>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn response(&self, challenge: &Challenge, method: &str, uri: &str, count: u32, cnonce: &str) -> String {
+        let algorithm = challenge.algorithm;
+        let count = format!("{count:08x}");
+
+        let mut ha1 = algorithm.hash(&format!("{}:{}:{}", self.username, challenge.realm, self.password));
+        if algorithm.session {
+            ha1 = algorithm.hash(&format!("{ha1}:{}:{cnonce}", challenge.nonce));
+        }
+        let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+
+        let response = if challenge.qop {
+            algorithm.hash(&format!("{ha1}:{}:{count}:{cnonce}:auth:{ha2}", challenge.nonce))
+        } else {
+            algorithm.hash(&format!("{ha1}:{}:{ha2}", challenge.nonce))
+        };
+
+        let mut header = format!(
+            "Digest username={}, realm={}, nonce={}, uri={}, algorithm={}, response=\"{response}\"",
+            quote(&self.username),
+            quote(&challenge.realm),
+            quote(&challenge.nonce),
+            quote(uri),
+            algorithm.name(),
+        );
+        if let Some(opaque) = &challenge.opaque {
+            let _ = write!(header, ", opaque={}", quote(opaque));
+        }
+        if challenge.qop {
+            let _ = write!(header, ", qop=auth, nc={count}, cnonce=\"{cnonce}\"");
+        }
+
+        header
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Challenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: Algorithm,
+    // whether the server supports the "auth" quality of protection
+    qop: bool,
+}
+
+impl Challenge {
+    // returns None for other authentication schemes, and for unsupported Digest challenges
+    fn parse(header: &str) -> Option<Challenge> {
+        let (scheme, params) = header.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("Digest") {
+            return None;
+        }
+
+        let mut realm = None;
+        let mut nonce = None;
+        let mut opaque = None;
+        let mut algorithm = Algorithm::default();
+        let mut qop = false;
+
+        for (name, value) in parse_params(params) {
+            match name.as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => opaque = Some(value),
+                "algorithm" => algorithm = Algorithm::parse(&value)?,
+                "qop" => {
+                    if !value.split(',').any(|qop| qop.trim().eq_ignore_ascii_case("auth")) {
+                        return None;
+                    }
+                    qop = true;
+                },
+                _ => {},
+            }
+        }
+
+        Some(Challenge {
+            realm: realm?,
+            nonce: nonce?,
+            opaque,
+            algorithm,
+            qop,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Algorithm {
+    sha256: bool,
+    session: bool,
+}
+
+impl Algorithm {
+    fn parse(name: &str) -> Option<Algorithm> {
+        let (name, session) = match name.len().checked_sub(5).and_then(|end| name.get(end..)) {
+            Some(suffix) if suffix.eq_ignore_ascii_case("-sess") => (&name[..name.len() - 5], true),
+            _ => (name, false),
+        };
+
+        if name.eq_ignore_ascii_case("MD5") {
+            Some(Algorithm { sha256: false, session })
+        } else if name.eq_ignore_ascii_case("SHA-256") {
+            Some(Algorithm { sha256: true, session })
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match (self.sha256, self.session) {
+            (false, false) => "MD5",
+            (false, true) => "MD5-sess",
+            (true, false) => "SHA-256",
+            (true, true) => "SHA-256-sess",
+        }
+    }
+
+    fn hash(self, data: &str) -> String {
+        if self.sha256 {
+            hex(&Sha256::digest(data))
+        } else {
+            hex(&Md5::digest(data))
+        }
+    }
+}
+
+// parses comma-separated name=value pairs, where values can be quoted strings
+fn parse_params(mut rest: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let Some(eq) = rest.find('=') else {
+            break;
+        };
+
+        let name = rest[..eq].trim().to_ascii_lowercase();
+        rest = rest[eq + 1..].trim_start();
+
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut end = quoted.len();
+            let mut chars = quoted.char_indices();
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                    '"' => {
+                        end = index + 1;
+                        break;
+                    },
+                    c => value.push(c),
+                }
+            }
+            rest = &quoted[end..];
+            value
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            let value = rest[..end].trim().to_owned();
+            rest = &rest[end..];
+            value
+        };
+
+        params.push((name, value));
+    }
+
+    params
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// client nonces are generated from 16 bytes of the random number generator of the operating
+// system (no credentials are sent if it is not available)
+fn cnonce() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).ok()?;
+    Some(hex(&bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            Algorithm::parse("MD5")
+                .unwrap()
+                .hash("Mufasa:testrealm@host.com:Circle Of Life"),
+            "939e7578ed9e3c518a452acee763bce9"
+        );
+        assert_eq!(
+            Algorithm::parse("SHA-256").unwrap().hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn cnonces() {
+        let first = cnonce().unwrap();
+        let second = cnonce().unwrap();
+
+        assert_eq!(first.len(), 32);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+
+    fn parse_challenge(header: &'static str) -> Challenge {
+        let mut headers = HeaderMap::new();
+        headers.append(WWW_AUTHENTICATE, HeaderValue::from_static("Basic realm=\"ignored\""));
+        headers.append(WWW_AUTHENTICATE, HeaderValue::from_static(header));
+
+        let auth = DigestAuth::new(String::new(), String::new());
+        assert!(auth.challenge(&headers));
+        let state = auth.lock().take();
+        state.unwrap().0
+    }
+
+    // example from RFC 2617, section 3.5
+    #[test]
+    fn rfc2617() {
+        let challenge = parse_challenge(
+            "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", \
+nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+        );
+        let auth = DigestAuth::new(String::from("Mufasa"), String::from("Circle Of Life"));
+
+        let header = auth.response(&challenge, "GET", "/dir/index.html", 1, "0a4f113b");
+        assert!(header.starts_with("Digest username=\"Mufasa\", realm=\"testrealm@host.com\""));
+        assert!(header.contains("response=\"6629fae49393a05397450978507c4ef1\""));
+        assert!(header.contains("opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""));
+        assert!(header.ends_with("qop=auth, nc=00000001, cnonce=\"0a4f113b\""));
+    }
+
+    // examples from RFC 7616, section 3.9.1
+    #[test]
+    fn rfc7616() {
+        let auth = DigestAuth::new(String::from("Mufasa"), String::from("Circle of Life"));
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+        let challenge = parse_challenge(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm=SHA-256, \
+nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+        );
+        let header = auth.response(&challenge, "GET", "/dir/index.html", 1, cnonce);
+        assert!(header.contains("algorithm=SHA-256"));
+        assert!(header.contains("response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\""));
+
+        let challenge = parse_challenge(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm=MD5, \
+nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+        );
+        let header = auth.response(&challenge, "GET", "/dir/index.html", 1, cnonce);
+        assert!(header.contains("response=\"8ca523f5e9506fed4657c9700eebdbec\""));
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(Challenge::parse("Basic realm=\"test\"").is_none());
+        assert!(Challenge::parse("Digest realm=\"test\", nonce=\"abc\", qop=\"auth-int\"").is_none());
+        assert!(Challenge::parse("Digest realm=\"test\", nonce=\"abc\", algorithm=SHA-512-256").is_none());
+        assert!(Challenge::parse("Digest realm=\"test\"").is_none());
+
+        let challenge =
+            Challenge::parse("Digest realm=\"a \\\"quoted\\\" realm\", nonce=abc, algorithm=md5-sess").unwrap();
+        assert_eq!(challenge.realm, "a \"quoted\" realm");
+        assert_eq!(challenge.nonce, "abc");
+        assert_eq!(challenge.algorithm.name(), "MD5-sess");
+        assert!(!challenge.qop);
+    }
+
+    #[test]
+    fn nonce_count() {
+        let auth = DigestAuth::new(String::from("user"), String::from("secret"));
+        assert!(auth.authorization("POST", "/RPC2").is_none());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static("Digest realm=\"test\", nonce=\"abc\", qop=\"auth\""),
+        );
+        assert!(auth.challenge(&headers));
+
+        assert!(auth.authorization("POST", "/RPC2").unwrap().contains("nc=00000001"));
+        assert!(auth.authorization("POST", "/RPC2").unwrap().contains("nc=00000002"));
+    }
+}
@@ -0,0 +1,77 @@
+//! HTTP transport for XML-RPC servers (based on reqwest)
+
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Client, Response, StatusCode};
+use url::{Position, Url};
+
+use super::check_status;
+use super::digest::DigestAuth;
+use crate::stats::StatsCounters;
+use crate::timings::Timer;
+use crate::{ClientError, REQUEST_ID_HEADER};
+
+/// send a request body to an XML-RPC server over HTTP and return the (unread) response
+///
+/// With Digest authentication, requests that are rejected with a challenge of the server are
+/// repeated once with credentials (which is counted as a retry). Repeated requests have the same
+/// request ID as the original request.
+pub(crate) async fn post(
+    client: &Client,
+    url: &Url,
+    body: String,
+    digest: Option<&DigestAuth>,
+    request_id: Option<&str>,
+    stats: &StatsCounters,
+) -> Result<Response, reqwest::Error> {
+    let Some(digest) = digest else {
+        return send(client, url, body, None, request_id).await;
+    };
+
+    let uri = &url[Position::BeforePath..];
+    let response = send(client, url, body.clone(), digest.authorization("POST", uri), request_id).await?;
+
+    if response.status() != StatusCode::UNAUTHORIZED || !digest.challenge(response.headers()) {
+        return Ok(response);
+    }
+
+    stats.retry();
+    send(client, url, body, digest.authorization("POST", uri), request_id).await
+}
+
+/// send a request body to an XML-RPC server over HTTP and return the body of the response
+///
+/// Responses with error status codes are rejected, unless they contain an XML-RPC response.
+pub(crate) async fn request(
+    client: &Client,
+    url: &Url,
+    body: String,
+    digest: Option<&DigestAuth>,
+    request_id: Option<&str>,
+    stats: &StatsCounters,
+    timer: &mut Timer,
+) -> Result<Vec<u8>, ClientError> {
+    let response = post(client, url, body, digest, request_id, stats).await?;
+    timer.first_byte();
+
+    let status = response.status();
+    let bytes = response.bytes().await?.to_vec();
+    check_status(status.as_u16(), &bytes)?;
+    Ok(bytes)
+}
+
+async fn send(
+    client: &Client,
+    url: &Url,
+    body: String,
+    authorization: Option<String>,
+    request_id: Option<&str>,
+) -> Result<Response, reqwest::Error> {
+    let mut request = client.post(url.clone()).body(body);
+    if let Some(authorization) = authorization {
+        request = request.header(AUTHORIZATION, authorization);
+    }
+    if let Some(request_id) = request_id {
+        request = request.header(REQUEST_ID_HEADER, request_id);
+    }
+    client.execute(request.build()?).await
+}
@@ -0,0 +1,600 @@
+//! SCGI transport for XML-RPC servers that listen on unix sockets (i.e. rtorrent)
+//!
+//! The transport only relies on the [`AsyncRead`] and [`AsyncWrite`] traits from the `futures`
+//! crate, so it does not depend on a specific async runtime. Connections to unix sockets are
+//! established by a [`UnixConnector`], which can be supplied with
+//! [`ClientBuilder::unix_connector`](crate::ClientBuilder::unix_connector).
+//!
+//! The SCGI protocol does not support persistent connections: the server closes the connection
+//! after sending the response, so a new connection is opened for every method call, and
+//! connections are never pooled or reused. Restarting the server (i.e. rtorrent) therefore cannot
+//! leave the client with stale connections, and subsequent calls succeed as soon as the server is
+//! listening on its socket again.
+
+use std::fmt::Debug;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::{self, Either};
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::timings::Timer;
+
+/// trait for bidirectional byte streams that are returned by [`UnixConnector`]s
+///
+/// This trait is implemented for all types that implement the [`AsyncRead`] and [`AsyncWrite`]
+/// traits from the `futures` crate.
+pub trait UnixStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T> UnixStream for T where T: AsyncRead + AsyncWrite + Send + Unpin {}
+
+/// # Connector for unix sockets
+///
+/// This trait describes how connections to unix sockets are established, which makes it possible
+/// to use the SCGI transport with any async runtime. Implementations for [`tokio`] and [`async-io`]
+/// (for `async-std` and `smol`) are included with the `tokio` and `async-io` features.
+///
+/// [`tokio`]: https://docs.rs/tokio
+/// [`async-io`]: https://docs.rs/async-io
+#[async_trait::async_trait]
+pub trait UnixConnector: Debug + Send + Sync {
+    /// This method is called for opening a new connection to the unix socket at the given path.
+    async fn connect(&self, path: &Path) -> io::Result<Box<dyn UnixStream>>;
+
+    /// This method is called for waiting until a read or write timeout has elapsed (see
+    /// [`ClientBuilder::scgi_read_timeout`](crate::ClientBuilder::scgi_read_timeout) and
+    /// [`ClientBuilder::scgi_write_timeout`](crate::ClientBuilder::scgi_write_timeout)).
+    ///
+    /// Implementations need to use the timer of their async runtime, otherwise configured
+    /// timeouts cannot be enforced.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// connector for unix sockets based on the [`tokio`](https://docs.rs/tokio) runtime
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioConnector;
+
+#[cfg(feature = "tokio")]
+#[async_trait::async_trait]
+impl UnixConnector for TokioConnector {
+    async fn connect(&self, path: &Path) -> io::Result<Box<dyn UnixStream>> {
+        use tokio_util::compat::TokioAsyncReadCompatExt;
+
+        let stream = tokio::net::UnixStream::connect(path).await?;
+        Ok(Box::new(stream.compat()))
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+/// connector for unix sockets based on [`async-io`](https://docs.rs/async-io) (which is used by
+/// the `async-std` and `smol` runtimes)
+#[cfg(feature = "async-io")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsyncIoConnector;
+
+#[cfg(feature = "async-io")]
+#[async_trait::async_trait]
+impl UnixConnector for AsyncIoConnector {
+    async fn connect(&self, path: &Path) -> io::Result<Box<dyn UnixStream>> {
+        let stream = async_io::Async::<std::os::unix::net::UnixStream>::connect(path).await?;
+        Ok(Box::new(stream))
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        async_io::Timer::after(duration).await;
+    }
+}
+
+// placeholder for clients that were built without any runtime support for unix sockets
+#[cfg(not(any(feature = "tokio", feature = "async-io")))]
+#[derive(Debug)]
+struct MissingConnector;
+
+#[cfg(not(any(feature = "tokio", feature = "async-io")))]
+#[async_trait::async_trait]
+impl UnixConnector for MissingConnector {
+    async fn connect(&self, _path: &Path) -> io::Result<Box<dyn UnixStream>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "No connector for unix sockets is available (enable the tokio or async-io feature)",
+        ))
+    }
+
+    // connecting always fails, so there are never any pending reads or writes to time out
+    async fn sleep(&self, _duration: Duration) {
+        future::pending::<()>().await
+    }
+}
+
+fn default_connector() -> Arc<dyn UnixConnector> {
+    #[cfg(feature = "tokio")]
+    return Arc::new(TokioConnector);
+
+    #[cfg(all(feature = "async-io", not(feature = "tokio")))]
+    return Arc::new(AsyncIoConnector);
+
+    #[cfg(not(any(feature = "tokio", feature = "async-io")))]
+    return Arc::new(MissingConnector);
+}
+
+/// default request URI that is reported to SCGI servers
+pub const DEFAULT_SCGI_REQUEST_URI: &str = "/RPC";
+
+/// # Headers of SCGI requests
+///
+/// SCGI servers (and frontends) differ in which request headers they require. By default, only the
+/// `REQUEST_METHOD` (`POST`) and `REQUEST_URI` ([`/RPC`](DEFAULT_SCGI_REQUEST_URI)) headers are
+/// sent in addition to the mandatory `CONTENT_LENGTH` and `SCGI` headers. Other headers can be
+/// added (or the default headers replaced or removed), and the headers can be supplied with
+/// [`ClientBuilder::scgi_headers`](crate::ClientBuilder::scgi_headers):
+///
+/// ```
+/// use dxr_client::ScgiHeaders;
+///
+/// let headers = ScgiHeaders::new()
+///     .request_uri("/RPC2")
+///     .header("SERVER_PROTOCOL", "HTTP/1.1")
+///     .header("REMOTE_ADDR", "127.0.0.1");
+/// ```
+///
+/// The `CONTENT_LENGTH` and `SCGI` headers are always sent first (as required by the protocol),
+/// and cannot be overridden. Headers are sent in the order in which they were added.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScgiHeaders {
+    headers: Vec<(String, String)>,
+}
+
+impl Default for ScgiHeaders {
+    fn default() -> Self {
+        ScgiHeaders::new()
+    }
+}
+
+impl ScgiHeaders {
+    /// constructor for [`ScgiHeaders`] with the default headers
+    pub fn new() -> ScgiHeaders {
+        ScgiHeaders {
+            headers: vec![
+                (String::from("REQUEST_METHOD"), String::from("POST")),
+                (String::from("REQUEST_URI"), String::from(DEFAULT_SCGI_REQUEST_URI)),
+            ],
+        }
+    }
+
+    /// method for overriding the value of the `REQUEST_URI` header
+    pub fn request_uri(self, uri: impl Into<String>) -> Self {
+        self.header("REQUEST_URI", uri)
+    }
+
+    /// method for adding a header, or for replacing the value of a header that was already set
+    ///
+    /// The mandatory `CONTENT_LENGTH` and `SCGI` headers are ignored.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let value = value.into();
+
+        if is_mandatory(&name) {
+            return self;
+        }
+
+        match self.headers.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, old)) => *old = value,
+            None => self.headers.push((name, value)),
+        }
+        self
+    }
+
+    /// method for removing a header (i.e. one of the default headers)
+    pub fn remove(mut self, name: &str) -> Self {
+        self.headers.retain(|(key, _)| key != name);
+        self
+    }
+
+    // headers are encoded as a netstring, which must start with the CONTENT_LENGTH header
+    fn encode(&self, content_length: usize) -> io::Result<Vec<u8>> {
+        let mut headers = format!("CONTENT_LENGTH\0{content_length}\0SCGI\01\0");
+
+        for (name, value) in &self.headers {
+            if name.is_empty() || name.contains('\0') || value.contains('\0') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid SCGI header: {name:?}"),
+                ));
+            }
+            headers.push_str(name);
+            headers.push('\0');
+            headers.push_str(value);
+            headers.push('\0');
+        }
+
+        Ok(format!("{}:{headers},", headers.len()).into_bytes())
+    }
+}
+
+fn is_mandatory(name: &str) -> bool {
+    name == "CONTENT_LENGTH" || name == "SCGI"
+}
+
+// optional timeouts for sending requests and for receiving responses over SCGI connections
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Timeouts {
+    pub(crate) read: Option<Duration>,
+    pub(crate) write: Option<Duration>,
+}
+
+// configuration of the SCGI transport of a client
+#[derive(Clone, Debug)]
+pub(crate) struct ScgiTransport {
+    pub(crate) connector: Arc<dyn UnixConnector>,
+    pub(crate) headers: ScgiHeaders,
+    pub(crate) timeouts: Timeouts,
+}
+
+impl Default for ScgiTransport {
+    fn default() -> Self {
+        ScgiTransport {
+            connector: default_connector(),
+            headers: ScgiHeaders::new(),
+            timeouts: Timeouts::default(),
+        }
+    }
+}
+
+impl ScgiTransport {
+    /// connect to the unix socket at the given path, send a request body, and return the body of
+    /// the response
+    pub(crate) async fn request(&self, path: &Path, body: &[u8], timer: &mut Timer) -> io::Result<Vec<u8>> {
+        let mut stream = self.connector.connect(path).await?;
+        timer.connected();
+        request(
+            &*self.connector,
+            &mut *stream,
+            &self.headers,
+            self.timeouts,
+            body,
+            timer,
+        )
+        .await
+    }
+}
+
+/// send a request body over an SCGI connection and return the body of the response
+pub(crate) async fn request(
+    connector: &dyn UnixConnector,
+    stream: &mut dyn UnixStream,
+    headers: &ScgiHeaders,
+    timeouts: Timeouts,
+    body: &[u8],
+    timer: &mut Timer,
+) -> io::Result<Vec<u8>> {
+    let headers = headers.encode(body.len())?;
+
+    // the write timeout applies to sending the complete request
+    let send = async {
+        stream.write_all(&headers).await?;
+        stream.write_all(body).await?;
+        stream.flush().await
+    };
+    with_timeout(connector, timeouts.write, "writing the request", send).await?;
+    timer.sent();
+
+    // the server closes the connection after sending the response, and the read timeout applies
+    // to every read (i.e. it is the maximum time without receiving any data)
+    let mut response = Vec::new();
+    let mut buf = [0; 8192];
+    loop {
+        let n = with_timeout(connector, timeouts.read, "reading the response", stream.read(&mut buf)).await?;
+        if n == 0 {
+            break;
+        }
+        timer.first_byte();
+        response.extend_from_slice(&buf[..n]);
+    }
+
+    Ok(strip_headers(&response).to_vec())
+}
+
+async fn with_timeout<T, F>(
+    connector: &dyn UnixConnector,
+    timeout: Option<Duration>,
+    operation: &str,
+    future: F,
+) -> io::Result<T>
+where
+    F: std::future::Future<Output = io::Result<T>>,
+{
+    let Some(timeout) = timeout else {
+        return future.await;
+    };
+
+    futures::pin_mut!(future);
+    match future::select(future, connector.sleep(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("SCGI timeout while {operation} ({} ms)", timeout.as_millis()),
+        )),
+    }
+}
+
+fn strip_headers(response: &[u8]) -> &[u8] {
+    // XML documents start with an XML declaration, or with a byte order mark if they are encoded
+    // with UTF-16, which is the case if the declaration could not be found
+    if let Some(start) = response.windows(5).position(|window| window == b"<?xml") {
+        return &response[start..];
+    }
+
+    match response.windows(4).position(|window| window == b"\r\n\r\n") {
+        Some(end) => &response[end + 4..],
+        None => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use futures::io::Cursor;
+
+    // in-memory stream that records the request and replays a canned response
+    struct MockStream {
+        written: Vec<u8>,
+        response: Cursor<Vec<u8>>,
+    }
+
+    impl AsyncRead for MockStream {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut [u8],
+        ) -> std::task::Poll<io::Result<usize>> {
+            std::pin::Pin::new(&mut self.response).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for MockStream {
+        fn poll_write(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<io::Result<usize>> {
+            self.written.extend_from_slice(buf);
+            std::task::Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn headers() {
+        let expected = b"62:CONTENT_LENGTH\x0042\x00SCGI\x001\x00REQUEST_METHOD\x00POST\x00REQUEST_URI\x00/RPC\x00,";
+        assert_eq!(ScgiHeaders::new().encode(42).unwrap(), expected);
+    }
+
+    #[test]
+    fn custom_headers() {
+        let headers = ScgiHeaders::new()
+            .request_uri("/RPC2")
+            .header("SERVER_PROTOCOL", "HTTP/1.1")
+            .header("CONTENT_LENGTH", "0")
+            .remove("REQUEST_METHOD");
+
+        let expected =
+            b"68:CONTENT_LENGTH\x0042\x00SCGI\x001\x00REQUEST_URI\x00/RPC2\x00SERVER_PROTOCOL\x00HTTP/1.1\x00,";
+        assert_eq!(headers.encode(42).unwrap(), expected);
+
+        let headers = ScgiHeaders::new().header("REMOTE_ADDR", "\0");
+        assert_eq!(headers.encode(42).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut stream = MockStream {
+            written: Vec::new(),
+            response: Cursor::new(
+                b"Status: 200 OK\r\nContent-Type: text/xml\r\n\r\n<?xml version=\"1.0\"?><methodResponse/>".to_vec(),
+            ),
+        };
+
+        let response = futures::executor::block_on(request(
+            &*default_connector(),
+            &mut stream,
+            &ScgiHeaders::new(),
+            Timeouts::default(),
+            b"<methodCall/>",
+            &mut Timer::start(),
+        ))
+        .unwrap();
+
+        assert_eq!(response, b"<?xml version=\"1.0\"?><methodResponse/>");
+        assert!(stream.written.ends_with(b",<methodCall/>"));
+    }
+
+    #[test]
+    fn strip() {
+        assert_eq!(strip_headers(b"Content-Type: text/xml\r\n\r\n<a/>"), b"<a/>");
+        assert_eq!(strip_headers(b"<a/>"), b"<a/>");
+    }
+
+    // minimal SCGI server that answers a single request on a unix socket
+    #[cfg(any(feature = "tokio", feature = "async-io"))]
+    fn serve_once(path: &Path, response: &'static [u8]) -> std::thread::JoinHandle<Vec<u8>> {
+        use std::io::{Read, Write};
+
+        let _ = std::fs::remove_file(path);
+        let listener = std::os::unix::net::UnixListener::bind(path).unwrap();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            // read the netstring with the request headers and the request body
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if n == 0 || request.ends_with(b"</methodCall>\n") {
+                    break;
+                }
+            }
+
+            stream.write_all(response).unwrap();
+            request
+        })
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn tokio_client() {
+        let path = std::env::temp_dir().join(format!("dxr-client-scgi-{}.sock", std::process::id()));
+        let server = serve_once(
+            &path,
+            b"Status: 200 OK\r\nContent-Type: text/xml\r\n\r\n<?xml version=\"1.0\"?>\n<methodResponse><params><param><value><i4>3</i4></value></param></params></methodResponse>",
+        );
+
+        let timings: Arc<Mutex<Vec<crate::CallTimings>>> = Arc::default();
+        let collected = Arc::clone(&timings);
+
+        let url = format!("unix://{}", path.display()).parse().unwrap();
+        let client = crate::ClientBuilder::new(url)
+            .unix_connector(TokioConnector)
+            .scgi_headers(ScgiHeaders::new().request_uri("/RPC2"))
+            .on_timings(Arc::new(move |_, timings| collected.lock().unwrap().push(*timings)))
+            .build();
+        let result: i32 = client.call(crate::Call::new("add", (1, 2))).await.unwrap();
+        assert_eq!(result, 3);
+
+        // all phases of SCGI requests are measured
+        let timings = timings.lock().unwrap()[0];
+        let connect = timings.connect().unwrap();
+        let send = timings.send().unwrap();
+        let first_byte = timings.first_byte().unwrap();
+        assert!(timings.serialize() <= connect && connect <= send && send <= first_byte);
+        assert!(first_byte <= timings.total());
+
+        let request = server.join().unwrap();
+        assert!(request.windows(18).any(|window| window == b"<methodName>add</m"));
+        assert!(request.windows(18).any(|window| window == b"REQUEST_URI\0/RPC2\0"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn tokio_cancel() {
+        use std::io::Read;
+
+        let path = std::env::temp_dir().join(format!("dxr-client-scgi-cancel-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let (received, cancel) = futures::channel::oneshot::channel();
+
+        // server that reads the request, but never sends a response
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"</methodCall>\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            received.send(()).unwrap();
+
+            // the connection is closed by the client once the call is cancelled
+            stream.read(&mut buf).unwrap()
+        });
+
+        let url = format!("unix://{}", path.display()).parse().unwrap();
+        let client = crate::ClientBuilder::new(url).unix_connector(TokioConnector).build();
+        let call = client.call::<_, i32>(crate::Call::new("add", (1, 2)));
+
+        // cancel the call by dropping it while it is waiting for the response
+        match future::select(Box::pin(call), cancel).await {
+            Either::Left(_) => panic!("Call finished without a response."),
+            Either::Right((result, call)) => {
+                result.unwrap();
+                drop(call);
+            },
+        }
+
+        assert_eq!(server.join().unwrap(), 0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn tokio_read_timeout() {
+        use std::io::Read;
+
+        let path = std::env::temp_dir().join(format!("dxr-client-scgi-timeout-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+        // server that reads the request, but never sends a response
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+            let mut buf = [0; 1024];
+            while stream.read(&mut buf).unwrap() != 0 {}
+        });
+
+        let url = format!("unix://{}", path.display()).parse().unwrap();
+        let client = crate::ClientBuilder::new(url)
+            .unix_connector(TokioConnector)
+            .scgi_read_timeout(Some(Duration::from_millis(100)))
+            .build();
+
+        let error = client
+            .call::<_, i32>(crate::Call::new("add", (1, 2)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Timeout);
+        assert!(error.to_string().contains("reading the response"));
+
+        server.join().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "async-io")]
+    #[test]
+    fn async_io_client() {
+        let path = std::env::temp_dir().join(format!("dxr-client-scgi-async-io-{}.sock", std::process::id()));
+        let server = serve_once(
+            &path,
+            b"Status: 200 OK\r\n\r\n<?xml version=\"1.0\"?>\n<methodResponse><params><param><value><string>pong</string></value></param></params></methodResponse>",
+        );
+
+        let url = format!("unix://{}", path.display()).parse().unwrap();
+        let client = crate::ClientBuilder::new(url).unix_connector(AsyncIoConnector).build();
+        let result: String = futures::executor::block_on(client.call(crate::Call::new("ping", ()))).unwrap();
+        assert_eq!(result, "pong");
+
+        server.join().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+use std::io::Read;
+
+use ureq::Agent;
+use url::Url;
+
+use dxr::{DeserializeOptions, MethodCall, MethodResponse, SerializeOptions, TryFromValue, TryToParams};
+#[cfg(feature = "multicall")]
+use dxr::{Fault, Value};
+
+use crate::encoding::decode_response;
+use crate::transport::{check_status, request_to_body, response_to_result, return_value, WireHooks};
+#[cfg(feature = "multicall")]
+use crate::MulticallTypes;
+use crate::{Call, ClientError, DEFAULT_USER_AGENT};
+
+/// Builder that takes parameters for constructing a [`BlockingClient`] based on [`ureq::Agent`].
+#[derive(Debug)]
+pub struct BlockingClientBuilder {
+    url: Url,
+    headers: Vec<(String, String)>,
+    user_agent: Option<String>,
+    agent: Option<Agent>,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+}
+
+impl BlockingClientBuilder {
+    /// Constructor for [`BlockingClientBuilder`] from the URL of the XML-RPC server.
+    ///
+    /// This also sets up the default `Content-Type: text/xml` HTTP header for XML-RPC requests.
+    pub fn new(url: Url) -> Self {
+        BlockingClientBuilder {
+            url,
+            headers: vec![(String::from("Content-Type"), String::from("text/xml"))],
+            user_agent: None,
+            agent: None,
+            options: DeserializeOptions::default(),
+            output: SerializeOptions::default(),
+            hooks: WireHooks::default(),
+        }
+    }
+
+    /// Method for overriding the default User-Agent header.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Method for providing additional custom HTTP headers.
+    ///
+    /// Invalid header names or values are reported as errors of the method calls.
+    pub fn add_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        self.headers
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Method for supplying a custom [`ureq::Agent`] (i.e. with custom timeouts, proxy, or TLS
+    /// settings) instead of the default one.
+    pub fn agent(mut self, agent: Agent) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+
+    /// Method for overriding the options that are used for deserializing XML-RPC responses.
+    pub fn deserialize_options(mut self, options: DeserializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Method for overriding the options that are used for serializing XML-RPC method calls.
+    pub fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.output = options;
+        self
+    }
+
+    /// Method for registering a callback that is called with the serialized XML of every method
+    /// call before it is sent to the server.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_request(mut self, hook: fn(&str)) -> Self {
+        self.hooks.request = Some(hook);
+        self
+    }
+
+    /// Method for registering a callback that is called with the (decoded) XML of every response
+    /// before it is deserialized.
+    ///
+    /// This is intended for debugging interoperability problems with XML-RPC servers.
+    pub fn on_response(mut self, hook: fn(&str)) -> Self {
+        self.hooks.response = Some(hook);
+        self
+    }
+
+    /// Build the [`BlockingClient`] by setting up and initializing the internal [`ureq::Agent`].
+    ///
+    /// If no custom value was provided for `User-Agent`, the default value
+    /// ([`DEFAULT_USER_AGENT`]) will be used.
+    pub fn build(mut self) -> BlockingClient {
+        let user_agent = self
+            .user_agent
+            .take()
+            .unwrap_or_else(|| String::from(DEFAULT_USER_AGENT));
+        let builder = self.add_header("User-Agent", user_agent);
+
+        BlockingClient {
+            url: builder.url,
+            headers: builder.headers,
+            agent: builder.agent.unwrap_or_else(Agent::new),
+            options: builder.options,
+            output: builder.output,
+            hooks: builder.hooks,
+        }
+    }
+}
+
+/// # Blocking XML-RPC client implementation
+///
+/// This type provides a synchronous XML-RPC client implementation that is based on [`ureq`],
+/// for small tools that do not want to depend on an async runtime. Only plain HTTP connections
+/// are supported unless one of the TLS features is enabled as well.
+///
+/// Unlike the asynchronous clients, this client does not support servers that listen on unix
+/// sockets.
+#[derive(Debug)]
+pub struct BlockingClient {
+    url: Url,
+    headers: Vec<(String, String)>,
+    agent: Agent,
+    options: DeserializeOptions,
+    output: SerializeOptions,
+    hooks: WireHooks,
+}
+
+impl BlockingClient {
+    /// Blocking method for handling remote procedure calls with XML-RPC.
+    ///
+    /// Fault responses from the XML-RPC server are transparently converted into
+    /// [`Fault`](dxr::Fault) errors. Invalid XML-RPC responses or faults will result in an
+    /// appropriate [`DxrError`](dxr::DxrError).
+    pub fn call<P: TryToParams, R: TryFromValue>(&self, call: Call<'_, P, R>) -> Result<R, ClientError> {
+        let request = call.as_xml_rpc()?;
+        let method = request.name().to_owned();
+        let response = self.send(request)?;
+        Ok(return_value(response, Some(&method))?)
+    }
+
+    /// Blocking method for handling "system.multicall" calls.
+    ///
+    /// *Note*: This method does not check if the number of method calls matches the number of
+    /// returned results.
+    #[cfg(feature = "multicall")]
+    pub fn multicall<P: TryToParams>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Result<Value, Fault>>, ClientError> {
+        crate::transport::multicall_results(self.call(call)?)
+    }
+
+    /// Blocking method for handling "system.multicall" calls with the same return type.
+    ///
+    /// Each result is converted into the expected type, or it is an error if the method call failed
+    /// with a fault, or if the returned value had an unexpected type.
+    #[cfg(feature = "multicall")]
+    pub fn multicall_typed<P: TryToParams, T: TryFromValue>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<Vec<Result<T, ClientError>>, ClientError> {
+        Ok(crate::typed_results(self.multicall(call)?))
+    }
+
+    /// Blocking method for handling "system.multicall" calls with different return types.
+    ///
+    /// The results are paired with the expected return types of the method calls (see
+    /// [`MulticallTypes`] for details), so the number of results must match the number of calls.
+    #[cfg(feature = "multicall")]
+    pub fn multicall_zip<P: TryToParams, T: MulticallTypes>(
+        &self,
+        call: Call<'_, P, Vec<Value>>,
+    ) -> Result<T::Output, ClientError> {
+        T::from_results(self.multicall(call)?)
+    }
+
+    fn send(&self, call: MethodCall) -> Result<MethodResponse, ClientError> {
+        let body = request_to_body(&call, self.output)?;
+        self.hooks.on_request(&body);
+
+        let mut request = self.agent.post(self.url.as_str());
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+
+        let response = match request.send_string(&body) {
+            Ok(response) => response,
+            // fault responses can be sent with non-200 status codes by some servers
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(error)) => return Err(Box::new(error).into()),
+        };
+
+        let status = response.status();
+        let mut bytes = Vec::new();
+        response.into_reader().read_to_end(&mut bytes)?;
+        check_status(status, &bytes)?;
+
+        // deserialize XML-RPC method response
+        let contents = decode_response(&bytes)?;
+        self.hooks.on_response(&contents);
+        response_to_result(&contents, self.options)
+    }
+}
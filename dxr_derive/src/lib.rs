@@ -27,7 +27,10 @@ use proc_macro_crate::{crate_name, FoundCrate};
 use quote::{quote, quote_spanned, ToTokens};
 
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Ident, Type};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Field, Fields, GenericArgument, GenericParam, Ident,
+    LitStr, PathArguments, Type,
+};
 
 fn use_dxr() -> TokenStream2 {
     let krate = crate_name("dxr").ok().unwrap_or(FoundCrate::Itself);
@@ -41,18 +44,117 @@ fn use_dxr() -> TokenStream2 {
     }
 }
 
+/// Check for the `#[dxr(transparent)]` container attribute.
+fn is_transparent(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut transparent = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dxr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                transparent = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported dxr attribute."))
+            }
+        })?;
+    }
+
+    Ok(transparent)
+}
+
+/// Conversion of `None` values of `Option<T>` struct members into XML-RPC struct members.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OptionMode {
+    /// `None` is converted into a `<nil/>` value (the default)
+    Nil,
+    /// struct members with `None` values are omitted
+    Omit,
+}
+
+/// Check for the `#[dxr(option = "nil")]` and `#[dxr(option = "omit")]` field attributes.
+fn option_mode(field: &Field) -> syn::Result<OptionMode> {
+    let mut mode = OptionMode::Nil;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("dxr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("option") {
+                if option_type(&field.ty).is_none() {
+                    return Err(meta.error("The dxr(option) attribute is only supported for Option<T> fields."));
+                }
+
+                let value: LitStr = meta.value()?.parse()?;
+                mode = match value.value().as_str() {
+                    "nil" => OptionMode::Nil,
+                    "omit" => OptionMode::Omit,
+                    _ => {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            "Unsupported dxr(option) value, expected \"nil\" or \"omit\".",
+                        ))
+                    },
+                };
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported dxr attribute."))
+            }
+        })?;
+    }
+
+    Ok(mode)
+}
+
+/// Return the type parameter `T` if the type of a struct member is `Option<T>`.
+///
+/// This check is necessarily based on the name of the type only.
+fn option_type(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Procedural macro for deriving an implementation of the `TryFromValue` trait for structs.
 ///
 /// Deriving this trait for enums, unions, tuple structs, or unit structs is not supported,
 /// since there would be no canonical way of representing these types as XML-RPC values.
 ///
+/// The only exception are "newtype" tuple structs with exactly one field that are marked with the
+/// `#[dxr(transparent)]` attribute. These are converted from the XML-RPC value of their only field
+/// directly, instead of from an XML-RPC struct:
+///
+/// ```ignore
+/// #[derive(TryFromValue)]
+/// #[dxr(transparent)]
+/// struct TorrentHash(String);
+/// ```
+///
 /// Additionally, the following types of struct members are not supported:
 ///
 /// - non-fixed-size arrays `[T]` (i.e. the struct does not implement `Sized`): use a `Vec<T>` or a
 ///   fixed-size array `[T; N]` instead
 /// - borrowed values `&T` (i.e. borrowed value does not live long enough): use an owned type `T` or
 ///   a `Cow<T>` instead
-#[proc_macro_derive(TryFromValue)]
+///
+/// Struct members of type `Option<T>` (which requires the `nil` feature of `dxr`) are converted
+/// into `None` both from `<nil/>` values and from missing XML-RPC struct members.
+///
+/// Generic structs are supported: every type parameter `T` is required to implement
+/// `TryFromValue` in the generated implementation, and lifetime and const parameters (as well as
+/// existing `where` clauses) are carried over unchanged.
+#[proc_macro_derive(TryFromValue, attributes(dxr))]
 pub fn try_from_value(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
 
@@ -60,6 +162,11 @@ pub fn try_from_value(input: TokenStream) -> TokenStream {
     let name_str = name.to_string();
     let dxr = use_dxr();
 
+    let transparent = match is_transparent(&input.attrs) {
+        Ok(transparent) => transparent,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     for param in &mut input.generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
             type_param.bounds.push(parse_quote!(#dxr::TryFromValue));
@@ -74,6 +181,12 @@ pub fn try_from_value(input: TokenStream) -> TokenStream {
     match &input.data {
         Data::Struct(data) => {
             match &data.fields {
+                Fields::Named(_) if transparent => return quote_spanned! {
+                    name.span() => compile_error!(
+                        "Deriving TryFromValue with #[dxr(transparent)] requires a tuple struct with exactly one field."
+                    );
+                }
+                .into(),
                 Fields::Named(fields) => {
                     for field in &fields.named {
                         let ident = field.ident.as_ref().expect("Failed to get struct field identifier.");
@@ -106,16 +219,47 @@ pub fn try_from_value(input: TokenStream) -> TokenStream {
                             Some(s) => s,
                             None => ident_str.as_str(),
                         };
-                        field_impls.push(quote! {
-                            #ident: <#stype as TryFromValue>::try_from_value(map.get(#ident_str)
-                                .ok_or_else(|| #dxr::DxrError::missing_field(#name_str, #ident_str))?)?,
-                        });
+                        if let Err(error) = option_mode(field) {
+                            return error.to_compile_error().into();
+                        }
+                        if option_type(&field.ty).is_some() {
+                            // missing struct members are treated like <nil/> values
+                            field_impls.push(quote! {
+                                #ident: match get(#ident_str) {
+                                    ::std::option::Option::Some(value) => <#stype as #dxr::TryFromValue>::try_from_value(value)
+                                        .map_err(|error| error.in_member(#ident_str))?,
+                                    ::std::option::Option::None => ::std::option::Option::None,
+                                },
+                            });
+                        } else {
+                            field_impls.push(quote! {
+                                #ident: <#stype as #dxr::TryFromValue>::try_from_value(get(#ident_str)
+                                    .ok_or_else(|| #dxr::DxrError::missing_field(#name_str, #ident_str))?)
+                                    .map_err(|error| error.in_member(#ident_str))?,
+                            });
+                        }
                     }
                 },
+                Fields::Unnamed(fields) if transparent && fields.unnamed.len() == 1 => {
+                    let impl_block = quote! {
+                        impl #impl_generics #dxr::TryFromValue for #name #ty_generics #where_clause {
+                            fn try_from_value(value: &#dxr::Value) -> ::std::result::Result<#name #ty_generics, #dxr::DxrError> {
+                                ::std::result::Result::Ok(#name(#dxr::TryFromValue::try_from_value(value)?))
+                            }
+                        }
+                    };
+                    return TokenStream::from(impl_block);
+                },
+                Fields::Unnamed(_) if transparent => return quote_spanned! {
+                    name.span() => compile_error!(
+                        "Deriving TryFromValue with #[dxr(transparent)] requires a tuple struct with exactly one field."
+                    );
+                }
+                .into(),
                 Fields::Unnamed(_) => {
                     return quote_spanned! {
                         name.span() => compile_error!(
-                            "Deriving TryFromValue for tuple structs is not supported."
+                            "Deriving TryFromValue for tuple structs is only supported with #[dxr(transparent)]."
                         );
                     }
                     .into()
@@ -145,21 +289,29 @@ pub fn try_from_value(input: TokenStream) -> TokenStream {
 
     let impl_block = quote! {
         impl #impl_generics #dxr::TryFromValue for #name #ty_generics #where_clause {
-            fn try_from_value(value: &#dxr::Value) -> Result<#name #ty_generics, #dxr::DxrError> {
-                use ::std::collections::HashMap;
-                use ::std::string::String;
-                use #dxr::{Value, DxrError};
+            fn try_from_value(value: &#dxr::Value) -> ::std::result::Result<#name #ty_generics, #dxr::DxrError> {
+                // fully qualified paths: the field types must resolve in the scope of the struct
+                let members = value
+                    .as_struct()
+                    .ok_or_else(|| #dxr::DxrError::wrong_type(value.type_name(), "struct"))?;
 
-                let map: HashMap<String, Value> = HashMap::try_from_value(value)?;
+                // the last member with a given name wins (like for conversions into maps)
+                let get = |name: &str| {
+                    members
+                        .iter()
+                        .rev()
+                        .find(|member| member.name() == name)
+                        .map(#dxr::Member::inner)
+                };
 
-                Ok(#name {
+                ::std::result::Result::Ok(#name {
                     #fields
                 })
             }
         }
     };
 
-    proc_macro::TokenStream::from(impl_block)
+    TokenStream::from(impl_block)
 }
 
 /// Procedural macro for deriving an implementation of the `TryToValue` trait for structs.
@@ -167,15 +319,46 @@ pub fn try_from_value(input: TokenStream) -> TokenStream {
 /// Deriving this trait for enums, unions, tuple structs, or unit structs is not supported,
 /// since there would be no canonical way of translating these types to XML-RPC values.
 ///
+/// The only exception are "newtype" tuple structs with exactly one field that are marked with the
+/// `#[dxr(transparent)]` attribute. These are converted into the XML-RPC value of their only field
+/// directly, instead of into an XML-RPC struct.
+///
 /// Additionally, non-fixed-size arrays `[T]` (i.e. the struct does not implement `Sized`)
 /// are not supported.
-#[proc_macro_derive(TryToValue)]
+///
+/// Struct members are serialized in the order in which the fields are declared.
+///
+/// Struct members of type `Option<T>` with a `None` value are converted into `<nil/>` values by
+/// default (which requires the `nil` feature of `dxr`). Marking them with the
+/// `#[dxr(option = "omit")]` attribute omits them from the XML-RPC struct instead, which is
+/// useful for servers that do not support `<nil/>` values:
+///
+/// ```ignore
+/// #[derive(TryToValue)]
+/// struct Settings {
+///     name: String,
+///     #[dxr(option = "omit")]
+///     label: Option<String>,
+///     #[dxr(option = "nil")]
+///     comment: Option<String>,
+/// }
+/// ```
+///
+/// Generic structs are supported: every type parameter `T` is required to implement `TryToValue`
+/// in the generated implementation, and lifetime and const parameters (as well as existing `where`
+/// clauses) are carried over unchanged. Borrowed struct members `&'a T` are supported as well.
+#[proc_macro_derive(TryToValue, attributes(dxr))]
 pub fn try_to_value(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
 
     let name = input.ident;
     let dxr = use_dxr();
 
+    let transparent = match is_transparent(&input.attrs) {
+        Ok(transparent) => transparent,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     for param in &mut input.generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
             type_param.bounds.push(parse_quote!(#dxr::TryToValue));
@@ -188,11 +371,18 @@ pub fn try_to_value(input: TokenStream) -> TokenStream {
     let mut field_impls = Vec::new();
 
     match &input.data {
-        Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => {
-                for field in &fields.named {
-                    let ident = field.ident.as_ref().expect("Failed to get struct field identifier.");
-                    let stype =
+        Data::Struct(data) => {
+            match &data.fields {
+                Fields::Named(_) if transparent => return quote_spanned! {
+                    name.span() => compile_error!(
+                        "Deriving TryToValue with #[dxr(transparent)] requires a tuple struct with exactly one field."
+                    );
+                }
+                .into(),
+                Fields::Named(fields) => {
+                    for field in &fields.named {
+                        let ident = field.ident.as_ref().expect("Failed to get struct field identifier.");
+                        let stype =
                         match &field.ty {
                             Type::Path(t) => t.to_token_stream(),
                             Type::Tuple(t) => t.to_token_stream(),
@@ -216,32 +406,66 @@ pub fn try_to_value(input: TokenStream) -> TokenStream {
                                 .into()
                             },
                         };
-                    let ident_str = ident.to_string();
-                    let ident_str = match ident_str.strip_prefix("r#") {
-                        Some(s) => s,
-                        None => ident_str.as_str(),
+                        let ident_str = ident.to_string();
+                        let ident_str = match ident_str.strip_prefix("r#") {
+                            Some(s) => s,
+                            None => ident_str.as_str(),
+                        };
+                        let mode = match option_mode(field) {
+                            Ok(mode) => mode,
+                            Err(error) => return error.to_compile_error().into(),
+                        };
+                        match option_type(&field.ty) {
+                            Some(inner) if mode == OptionMode::Omit => field_impls.push(quote! {
+                                if let ::std::option::Option::Some(value) = &self.#ident {
+                                    members.push(#dxr::Member::new(
+                                        ::std::string::String::from(#ident_str),
+                                        <#inner as #dxr::TryToValue>::try_to_value(value)?,
+                                    ));
+                                }
+                            }),
+                            _ => field_impls.push(quote! {
+                                members.push(#dxr::Member::new(
+                                    ::std::string::String::from(#ident_str),
+                                    <#stype as #dxr::TryToValue>::try_to_value(&self.#ident)?,
+                                ));
+                            }),
+                        }
+                    }
+                },
+                Fields::Unnamed(fields) if transparent && fields.unnamed.len() == 1 => {
+                    let impl_block = quote! {
+                        impl #impl_generics #dxr::TryToValue for #name #ty_generics #where_clause {
+                            fn try_to_value(&self) -> ::std::result::Result<#dxr::Value, #dxr::DxrError> {
+                                #dxr::TryToValue::try_to_value(&self.0)
+                            }
+                        }
                     };
-                    field_impls.push(quote! {
-                        map.insert(String::from(#ident_str), <#stype as TryToValue>::try_to_value(&self.#ident)?);
-                    });
-                }
-            },
-            Fields::Unnamed(_) => {
-                return quote_spanned! {
-                    name.span() => compile_error!(
-                        "Deriving TryToValue for tuple structs is not supported."
-                    );
-                }
-                .into()
-            },
-            Fields::Unit => {
-                return quote_spanned! {
+                    return TokenStream::from(impl_block);
+                },
+                Fields::Unnamed(_) if transparent => return quote_spanned! {
                     name.span() => compile_error!(
-                        "Deriving TryToValue for unit structs is not supported."
+                        "Deriving TryToValue with #[dxr(transparent)] requires a tuple struct with exactly one field."
                     );
                 }
-                .into()
-            },
+                .into(),
+                Fields::Unnamed(_) => {
+                    return quote_spanned! {
+                        name.span() => compile_error!(
+                            "Deriving TryToValue for tuple structs is only supported with #[dxr(transparent)]."
+                        );
+                    }
+                    .into()
+                },
+                Fields::Unit => {
+                    return quote_spanned! {
+                        name.span() => compile_error!(
+                            "Deriving TryToValue for unit structs is not supported."
+                        );
+                    }
+                    .into()
+                },
+            }
         },
         Data::Enum(_) | Data::Union(_) => {
             return quote_spanned! {
@@ -258,19 +482,17 @@ pub fn try_to_value(input: TokenStream) -> TokenStream {
 
     let impl_block = quote! {
         impl #impl_generics #dxr::TryToValue for #name #ty_generics #where_clause {
-            fn try_to_value(&self) -> Result<#dxr::Value, #dxr::DxrError> {
-                use ::std::collections::HashMap;
-                use ::std::string::String;
-                use #dxr::Value;
-
-                let mut map: HashMap<String, Value> = HashMap::new();
+            fn try_to_value(&self) -> ::std::result::Result<#dxr::Value, #dxr::DxrError> {
+                // fully qualified paths: the field types must resolve in the scope of the struct
+                // struct members are serialized in the order of the field declarations
+                let mut members: ::std::vec::Vec<#dxr::Member> = ::std::vec::Vec::new();
 
                 #fields
 
-                HashMap::try_to_value(&map)
+                ::std::result::Result::Ok(#dxr::Value::structure(#dxr::Struct::new(members)))
             }
         }
     };
 
-    proc_macro::TokenStream::from(impl_block)
+    TokenStream::from(impl_block)
 }
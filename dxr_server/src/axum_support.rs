@@ -20,16 +20,51 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::net::{SocketAddr, TcpListener};
+#[cfg(feature = "uploads")]
+use std::path::Path;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+#[cfg(feature = "logging")]
+use std::time::Instant;
 
-use axum::http::HeaderMap;
-use axum::routing::post;
-use axum::Router;
+use axum::body::Body;
+#[cfg(feature = "logging")]
+use axum::extract::ConnectInfo;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue, Request, StatusCode};
+use axum::routing::{get, post};
+use axum::{RequestExt, Router};
+use hyper::server::accept::Accept;
+use hyper::server::conn::{AddrIncoming, AddrStream};
+use socket2::{Domain, Protocol, Socket, Type};
 
 use thiserror::Error;
 use tokio::sync::Notify;
 
-use crate::{server, Handler, DEFAULT_SERVER_ROUTE};
+use dxr::{DeserializeOptions, SerializeOptions, TryToValue};
+
+#[cfg(feature = "logging")]
+use crate::RequestLogger;
+use crate::{
+    dispatch, ApiDescription, CheckedHandler, FallbackHandler, Handler, MethodDescription, OutputOptions, RequestId,
+    ServerOptions, Signature, DEFAULT_SERVER_ROUTE, REQUEST_ID_HEADER,
+};
+#[cfg(feature = "uploads")]
+use crate::{UploadHandler, Uploads};
+
+/// route / path of the health check endpoint (see [`RouteBuilder::health_check`])
+pub const HEALTH_ROUTE: &str = "/health";
+
+/// route / path of the readiness check endpoint (see [`RouteBuilder::readiness_check`])
+pub const READY_ROUTE: &str = "/ready";
+
+/// route / path of the API description endpoint (see [`RouteBuilder::describe_endpoint`])
+pub const DESCRIBE_ROUTE: &str = "/describe";
+
+// type alias for user-supplied closures that determine the health or readiness of a server
+type Check = Arc<dyn Fn() -> bool + Send + Sync>;
 
 /// error type for XML-RPC servers
 #[derive(Debug, Error)]
@@ -44,7 +79,7 @@ pub enum ServerError {
     /// error variant for networking errors
     #[error("{}", error)]
     Net {
-        /// error returned by [`TcpListener::bind`]
+        /// error returned by [`TcpListener::bind`] or [`Server::bind`]
         #[from]
         error: std::io::Error,
     },
@@ -55,6 +90,18 @@ pub enum ServerError {
 pub struct RouteBuilder {
     path: Cow<'static, str>,
     handlers: HashMap<&'static str, Box<dyn Handler>>,
+    signatures: HashMap<&'static str, Signature>,
+    help: HashMap<&'static str, String>,
+    fallback: Option<Box<dyn FallbackHandler>>,
+    options: ServerOptions,
+    health: Option<Check>,
+    ready: Option<Check>,
+    description: bool,
+    request_ids: bool,
+    #[cfg(feature = "logging")]
+    logger: Option<RequestLogger>,
+    #[cfg(feature = "uploads")]
+    uploads: Uploads,
 }
 
 impl Debug for RouteBuilder {
@@ -62,10 +109,19 @@ impl Debug for RouteBuilder {
         let mut handler_list: Vec<&&str> = self.handlers.keys().collect();
         handler_list.sort();
 
-        f.debug_struct("ServerBuilder")
+        let mut debug = f.debug_struct("ServerBuilder");
+        debug
             .field("path", &self.path)
             .field("handlers", &handler_list)
-            .finish()
+            .field("fallback", &self.fallback.is_some())
+            .field("options", &self.options)
+            .field("health", &self.health.is_some())
+            .field("ready", &self.ready.is_some())
+            .field("description", &self.description)
+            .field("request_ids", &self.request_ids);
+        #[cfg(feature = "uploads")]
+        debug.field("uploads", &self.uploads);
+        debug.finish()
     }
 }
 
@@ -75,6 +131,18 @@ impl RouteBuilder {
         RouteBuilder {
             path: Cow::Borrowed(DEFAULT_SERVER_ROUTE),
             handlers: HashMap::new(),
+            signatures: HashMap::new(),
+            help: HashMap::new(),
+            fallback: None,
+            options: ServerOptions::default(),
+            health: None,
+            ready: None,
+            description: false,
+            request_ids: false,
+            #[cfg(feature = "logging")]
+            logger: None,
+            #[cfg(feature = "uploads")]
+            uploads: Uploads::default(),
         }
     }
 
@@ -90,33 +158,473 @@ impl RouteBuilder {
     /// method for adding a new method handler
     pub fn add_method(mut self, name: &'static str, handler: Box<dyn Handler>) -> Self {
         self.handlers.insert(name, handler);
+        self.signatures.remove(name);
+        #[cfg(feature = "uploads")]
+        self.uploads.handlers.remove(name);
+        self
+    }
+
+    /// method for adding a new method handler with a [`Signature`]
+    ///
+    /// Calls of the method with parameters that do not match the signature are rejected with an
+    /// "invalid params" fault, without calling the method handler (see [`CheckedHandler`]).
+    pub fn add_method_with_signature(
+        self,
+        name: &'static str,
+        handler: Box<dyn Handler>,
+        signature: Signature,
+    ) -> Self {
+        let mut builder = self.add_method(name, Box::new(CheckedHandler::new(handler, signature.clone())));
+        builder.signatures.insert(name, signature);
+        builder
+    }
+
+    /// method for adding a new upload handler
+    ///
+    /// For calls of this method, the contents of the first `<base64>` value in the request are
+    /// decoded into a temporary file while the request is received, instead of being buffered in
+    /// memory (see [`UploadHandler`] for details). This replaces any method handler with the same
+    /// name.
+    ///
+    /// Requests for routes with upload handlers are not subject to the body size limit of axum
+    /// (which would also apply to uploads). Instead, the size of the decoded upload is limited by
+    /// [`RouteBuilder::max_upload_size`], and the rest of the request (and requests for all other
+    /// methods) by the maximum document size of the [`DeserializeOptions`].
+    #[cfg(feature = "uploads")]
+    pub fn add_upload_method(mut self, name: &'static str, handler: Box<dyn UploadHandler>) -> Self {
+        self.uploads.handlers.insert(name, handler);
+        self.handlers.remove(name);
+        self.signatures.remove(name);
+        self
+    }
+
+    /// method for overriding the maximum size of decoded uploads (in bytes)
+    ///
+    /// Requests with larger uploads are rejected with HTTP status `413 Payload Too Large`. The
+    /// default limit is [`DEFAULT_MAX_UPLOAD_SIZE`](crate::DEFAULT_MAX_UPLOAD_SIZE).
+    #[cfg(feature = "uploads")]
+    pub fn max_upload_size(mut self, size: u64) -> Self {
+        self.uploads.max_size = size;
         self
     }
 
+    /// method for overriding the directory for temporary files of uploads
+    ///
+    /// By default, temporary files are created in the directory that is returned by
+    /// [`std::env::temp_dir`], which might be backed by memory on some systems.
+    #[cfg(feature = "uploads")]
+    pub fn upload_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.uploads.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// method for adding a help text for a method
+    ///
+    /// Help texts are only used for describing the API of the server (see
+    /// [`RouteBuilder::describe`]).
+    pub fn method_help(mut self, name: &'static str, help: &str) -> Self {
+        self.help.insert(name, help.to_owned());
+        self
+    }
+
+    /// method for setting a fallback handler for calls of methods without a registered handler
+    ///
+    /// See [`FallbackHandler`] for details, and [`Gateway`](crate::Gateway) (with the `gateway`
+    /// feature) for a fallback handler that forwards method calls to another XML-RPC server.
+    pub fn fallback(mut self, handler: Box<dyn FallbackHandler>) -> Self {
+        self.fallback = Some(handler);
+        self
+    }
+
+    /// method for overriding the options that are used for deserializing XML-RPC requests
+    ///
+    /// This can be used to adjust the limits for the nesting depth and size of requests (see
+    /// [`DeserializeOptions`] for details).
+    pub fn deserialize_options(mut self, options: DeserializeOptions) -> Self {
+        self.options = self.options.deserialize_options(options);
+        self
+    }
+
+    /// method for overriding the options that are used for serializing values in XML-RPC responses
+    ///
+    /// This can be used to select a different format for `dateTime.iso8601` values (see
+    /// [`SerializeOptions`] for details). This is a shortcut for
+    /// [`OutputOptions::serialize_options`], so calling [`RouteBuilder::output_options`]
+    /// afterwards resets these options.
+    pub fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.options = self.options.serialize_options(options);
+        self
+    }
+
+    /// method for overriding the options that are used for formatting XML-RPC responses
+    ///
+    /// This can be used to add an XML declaration to responses, or to pretty-print them (see
+    /// [`OutputOptions`] for details).
+    pub fn output_options(mut self, output: OutputOptions) -> Self {
+        self.options = self.options.output_options(output);
+        self
+    }
+
+    /// method for overriding all options that are used for parsing XML-RPC requests and formatting
+    /// responses at once (see [`ServerOptions`] for details)
+    pub fn server_options(mut self, options: ServerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// method for enabling logging of XML-RPC requests and responses
+    ///
+    /// See [`RequestLogger`] for details.
+    #[cfg(feature = "logging")]
+    pub fn logger(mut self, logger: RequestLogger) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    /// method for enabling correlation IDs for XML-RPC requests
+    ///
+    /// The request ID is taken from the [`X-Request-Id`](REQUEST_ID_HEADER) header of requests (or
+    /// generated, if there is none), passed on to method handlers, included in log messages, and
+    /// echoed in the `X-Request-Id` header of responses. See [`RequestId`] for details.
+    pub fn request_ids(mut self) -> Self {
+        self.request_ids = true;
+        self
+    }
+
+    /// method for exposing a health check endpoint at [`/health`](HEALTH_ROUTE)
+    ///
+    /// `GET` requests are answered with HTTP status `200 OK` if the supplied closure returns `true`,
+    /// and with `503 Service Unavailable` otherwise. This can be used for liveness probes of
+    /// container orchestrators, or for health checks of load balancers.
+    pub fn health_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.health = Some(Arc::new(check));
+        self
+    }
+
+    /// method for exposing a readiness check endpoint at [`/ready`](READY_ROUTE)
+    ///
+    /// `GET` requests are answered with HTTP status `200 OK` if the supplied closure returns `true`,
+    /// and with `503 Service Unavailable` otherwise. This can be used for readiness probes of
+    /// container orchestrators.
+    pub fn readiness_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.ready = Some(Arc::new(check));
+        self
+    }
+
+    /// method for exposing a description of the registered methods at [`/describe`](DESCRIBE_ROUTE)
+    ///
+    /// `GET` requests are answered with the description that is returned by
+    /// [`RouteBuilder::describe`], converted into an XML-RPC value, and serialized as an XML-RPC
+    /// method response (so it can be parsed with any XML-RPC library). This can be used by
+    /// documentation generators, without the need for calling introspection methods.
+    pub fn describe_endpoint(mut self) -> Self {
+        self.description = true;
+        self
+    }
+
+    /// method for generating a machine-readable description of the registered methods
+    ///
+    /// The description contains the names of all registered methods (but not of methods that are
+    /// handled by the fallback handler), the signatures of methods that were added with
+    /// [`RouteBuilder::add_method_with_signature`], and the help texts that were added with
+    /// [`RouteBuilder::method_help`].
+    pub fn describe(&self) -> ApiDescription {
+        let names = self.handlers.keys();
+        #[cfg(feature = "uploads")]
+        let names = names.chain(self.uploads.handlers.keys());
+
+        ApiDescription::new(
+            names
+                .map(|name| {
+                    MethodDescription::new(
+                        name,
+                        self.signatures.get(name).cloned(),
+                        self.help.get(name).map(String::as_str),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// getter method for the path / route of the XML-RPC endpoint
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
     /// build an [`axum::Router`] from the specified route and registered method handlers
+    ///
+    /// The router also contains the routes of the health and readiness check endpoints, if they
+    /// are enabled. Their paths must not overlap with the path of the XML-RPC endpoint.
+    ///
+    /// The router does not need to be served by [`Server`]. It can also be merged into (or nested
+    /// inside) an existing [`axum`] application that serves other routes, and it can be used as a
+    /// [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html) directly:
+    ///
+    /// ```
+    /// use dxr_server::axum::{routing::get, Router};
+    /// use dxr_server::RouteBuilder;
+    ///
+    /// let app: Router = Router::new()
+    ///     .route("/", get(|| async { "Hello, World!" }))
+    ///     .nest("/RPC2", RouteBuilder::new().build());
+    /// ```
     pub fn build(self) -> Router {
+        let description = self.description.then(|| self.describe());
         let handlers = Arc::new(self.handlers);
-        Router::new().route(
+        let fallback: Option<Arc<dyn FallbackHandler>> = self.fallback.map(Arc::from);
+        let options = self.options;
+        let request_ids = self.request_ids;
+        #[cfg(feature = "logging")]
+        let logger = self.logger.map(Arc::new);
+        #[cfg(feature = "uploads")]
+        let uploads = Arc::new(self.uploads);
+        let mut router = Router::new().route(
             self.path.as_ref(),
-            post(move |headers: HeaderMap, body: String| async move { server(handlers, &body, headers).await }),
-        )
+            post(move |request: Request<Body>| async move {
+                // routes with upload handlers decode uploads while the request is received
+                #[cfg(feature = "uploads")]
+                let (mut parts, body, upload) = if uploads.handlers.is_empty() {
+                    match read_request(request).await {
+                        Ok((parts, body)) => (parts, body, None),
+                        Err((status, message)) => return (status, HeaderMap::new(), message),
+                    }
+                } else {
+                    match uploads.read(request, options).await {
+                        Ok(request) => request,
+                        Err(response) => return response,
+                    }
+                };
+                #[cfg(not(feature = "uploads"))]
+                let (mut parts, body) = match read_request(request).await {
+                    Ok(request) => request,
+                    Err((status, message)) => return (status, HeaderMap::new(), message),
+                };
+
+                // request IDs that were assigned by middleware take precedence over request headers
+                let request_id = request_ids.then(|| {
+                    let id = parts
+                        .extensions
+                        .get::<RequestId>()
+                        .cloned()
+                        .unwrap_or_else(|| RequestId::from_headers(&parts.headers));
+                    parts.extensions.insert(id.clone());
+                    id
+                });
+
+                #[cfg(feature = "logging")]
+                let caller = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0);
+                #[cfg(feature = "logging")]
+                let start = Instant::now();
+
+                #[cfg(feature = "uploads")]
+                let mut response = match upload {
+                    Some((name, upload)) => {
+                        uploads
+                            .dispatch(name, upload, &body, parts.headers, parts.extensions, options)
+                            .await
+                    },
+                    None => {
+                        dispatch(
+                            handlers,
+                            fallback.as_deref(),
+                            &body,
+                            parts.headers,
+                            parts.extensions,
+                            options,
+                        )
+                        .await
+                    },
+                };
+                #[cfg(not(feature = "uploads"))]
+                let mut response = dispatch(
+                    handlers,
+                    fallback.as_deref(),
+                    &body,
+                    parts.headers,
+                    parts.extensions,
+                    options,
+                )
+                .await;
+                echo_request_id(&mut response.1, request_id.as_ref());
+
+                #[cfg(feature = "logging")]
+                if let Some(logger) = logger {
+                    logger.log(
+                        caller,
+                        request_id.as_ref(),
+                        &body,
+                        response.0,
+                        &response.2,
+                        start.elapsed(),
+                    );
+                }
+
+                response
+            }),
+        );
+
+        for (path, check) in [(HEALTH_ROUTE, self.health), (READY_ROUTE, self.ready)] {
+            if let Some(check) = check {
+                router = router.route(path, get(move || async move { check_response(check.as_ref()) }));
+            }
+        }
+
+        if let Some(description) = description {
+            let response = description_response(&description);
+            router = router.route(DESCRIBE_ROUTE, get(move || async move { response }));
+        }
+
+        router
+    }
+}
+
+impl From<RouteBuilder> for Router {
+    fn from(route: RouteBuilder) -> Self {
+        route.build()
     }
 }
 
+// request IDs are echoed in the headers of responses
+fn echo_request_id(headers: &mut HeaderMap, request_id: Option<&RequestId>) {
+    if let Some(value) = request_id.and_then(|id| HeaderValue::from_str(id.as_str()).ok()) {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
+}
+
+// health and readiness checks are answered with plain HTTP responses
+fn check_response(check: &(dyn Fn() -> bool + Send + Sync)) -> (StatusCode, &'static str) {
+    if check() {
+        (StatusCode::OK, "OK")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable")
+    }
+}
+
+// API descriptions are serialized once, when the router is built
+fn description_response(description: &ApiDescription) -> (StatusCode, HeaderMap, String) {
+    match description.try_to_value().and_then(dxr::serialize_response) {
+        Ok(body) => {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/xml"));
+            (StatusCode::OK, headers, body)
+        },
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, HeaderMap::new(), error.to_string()),
+    }
+}
+
+// reads the request body (respecting limits set with axum's DefaultBodyLimit), and splits off the
+// request headers and extensions, which are passed on to method handlers
+async fn read_request(request: Request<Body>) -> Result<(Parts, String), (StatusCode, String)> {
+    let (parts, bytes) = match request.with_limited_body() {
+        Ok(request) => {
+            let (parts, body) = request.into_parts();
+            let bytes = hyper::body::to_bytes(body).await.map_err(|error| {
+                if error.downcast_ref::<http_body::LengthLimitError>().is_some() {
+                    (StatusCode::PAYLOAD_TOO_LARGE, error.to_string())
+                } else {
+                    (StatusCode::BAD_REQUEST, error.to_string())
+                }
+            })?;
+            (parts, bytes)
+        },
+        Err(request) => {
+            let (parts, body) = request.into_parts();
+            let bytes = hyper::body::to_bytes(body)
+                .await
+                .map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+            (parts, bytes)
+        },
+    };
+
+    let body = String::from_utf8(Vec::from(bytes)).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            String::from("Request body is not valid UTF-8."),
+        )
+    })?;
+
+    Ok((parts, body))
+}
+
 /// # XML-RPC server implementation
 ///
 /// This type provides a very simple XML-RPC server implementation based on [`axum::Router`].
+///
+/// Servers can either listen at a single socket address (with [`Server::serve`] or
+/// [`Server::serve_listener`]), or at multiple socket addresses with the same route and method
+/// handlers. In the latter case, addresses are bound with [`Server::bind`] (or existing listeners
+/// are added with [`Server::add_listener`]) before the server is launched with
+/// [`Server::serve_bound`]:
+///
+/// ```no_run
+/// # async fn run() -> Result<(), dxr_server::ServerError> {
+/// use dxr_server::{RouteBuilder, Server};
+///
+/// let mut server = Server::from_route(RouteBuilder::new().build());
+/// server.bind("[::]:3000".parse().unwrap())?;
+/// server.bind("0.0.0.0:3000".parse().unwrap())?;
+/// server.serve_bound().await
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Server {
     route: Router,
     barrier: Option<Arc<Notify>>,
+    listeners: Vec<TcpListener>,
 }
 
 impl Server {
     /// This method can be used to construct a [`Server`] from a standalone [`axum::Router`], which
     /// will only handle requests at that one route.
     pub fn from_route(route: Router) -> Server {
-        Server { route, barrier: None }
+        Server {
+            route,
+            barrier: None,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// This method binds the specified socket address, which the server will listen on once it is
+    /// launched with [`Server::serve_bound`], and returns the address that was actually bound
+    /// (i.e. with the port that was assigned by the operating system for port 0).
+    ///
+    /// IPv6 addresses are bound in "IPv6 only" mode, so the unspecified IPv6 address (`[::]`) and
+    /// the unspecified IPv4 address (`0.0.0.0`) can be bound with the same port on all platforms.
+    pub fn bind(&mut self, addr: SocketAddr) -> Result<SocketAddr, ServerError> {
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+        if addr.is_ipv6() {
+            socket.set_only_v6(true)?;
+        }
+        // match the behaviour of TcpListener::bind, which allows reusing addresses except on Windows
+        #[cfg(not(windows))]
+        socket.set_reuse_address(true)?;
+        socket.bind(&addr.into())?;
+        socket.listen(128)?;
+
+        self.add_listener(socket.into())
+    }
+
+    /// This method adds an existing [`TcpListener`], which the server will accept connections from
+    /// once it is launched with [`Server::serve_bound`], and returns its local address.
+    pub fn add_listener(&mut self, listener: TcpListener) -> Result<SocketAddr, ServerError> {
+        let addr = listener.local_addr()?;
+        self.listeners.push(listener);
+        Ok(addr)
+    }
+
+    /// getter method for the addresses of all sockets that were bound with [`Server::bind`] or
+    /// added with [`Server::add_listener`]
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.listeners
+            .iter()
+            .filter_map(|listener| listener.local_addr().ok())
+            .collect()
     }
 
     /// This method adds a barrier / notifier to the server that will trigger graceful shutdown,
@@ -144,16 +652,74 @@ impl Server {
     ///
     /// Requests with invalid input, calls of unknown methods, and failed methods are converted
     /// into fault responses.
-    pub async fn serve_listener(self, listener: TcpListener) -> Result<(), ServerError> {
+    pub async fn serve_listener(mut self, listener: TcpListener) -> Result<(), ServerError> {
+        self.add_listener(listener)?;
+        self.serve_bound().await
+    }
+
+    /// This method launches an [`axum::Server`] that accepts connections at all socket addresses
+    /// that were bound with [`Server::bind`] or added with [`Server::add_listener`], with the
+    /// configured route of the XML-RPC endpoint as the only route that will accept requests.
+    ///
+    /// Requests with invalid input, calls of unknown methods, and failed methods are converted
+    /// into fault responses.
+    ///
+    /// An error is returned if no socket addresses were bound.
+    pub async fn serve_bound(self) -> Result<(), ServerError> {
+        if self.listeners.is_empty() {
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "No socket addresses were bound.").into(),
+            );
+        }
+
+        let incoming = self
+            .listeners
+            .into_iter()
+            .map(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(AddrIncoming::from_listener(tokio::net::TcpListener::from_std(
+                    listener,
+                )?)?)
+            })
+            .collect::<Result<Vec<AddrIncoming>, ServerError>>()?;
+        let incoming = MultiIncoming { incoming, next: 0 };
+
         if let Some(barrier) = &self.barrier {
-            Ok(axum::Server::from_tcp(listener)?
-                .serve(self.route.into_make_service())
+            Ok(axum::Server::builder(incoming)
+                .serve(self.route.into_make_service_with_connect_info::<SocketAddr>())
                 .with_graceful_shutdown(barrier.notified())
                 .await?)
         } else {
-            Ok(axum::Server::from_tcp(listener)?
-                .serve(self.route.into_make_service())
+            Ok(axum::Server::builder(incoming)
+                .serve(self.route.into_make_service_with_connect_info::<SocketAddr>())
                 .await?)
         }
     }
 }
+
+// accepts connections from multiple listeners, starting with the listener after the one that
+// accepted the last connection, so busy listeners cannot starve the others
+struct MultiIncoming {
+    incoming: Vec<AddrIncoming>,
+    next: usize,
+}
+
+impl Accept for MultiIncoming {
+    type Conn = AddrStream;
+    type Error = std::io::Error;
+
+    fn poll_accept(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+        let count = this.incoming.len();
+
+        for offset in 0..count {
+            let index = (this.next + offset) % count;
+            if let Poll::Ready(conn) = Pin::new(&mut this.incoming[index]).poll_accept(cx) {
+                this.next = (index + 1) % count;
+                return Poll::Ready(conn);
+            }
+        }
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,147 @@
+use dxr::{DxrError, Member, Struct, TryToValue, Value};
+
+use crate::Signature;
+
+/// # Machine-readable description of the methods of an XML-RPC server
+///
+/// This type lists the names of all registered methods, together with their [`Signature`] and a
+/// help text, if they are known. Descriptions can be converted into XML-RPC [`Value`]s with the
+/// [`TryToValue`] trait, which can then be converted into any other format (i.e. into JSON with
+/// the `json` feature of `dxr`). Converted descriptions have the following structure:
+///
+/// ```text
+/// {
+///   "methods": [
+///     {
+///       "name": "add",
+///       "signature": { "required": ["i4", "i4"], "optional": [], "variadic": "any" },
+///       "help": "Adds two integers."
+///     }
+///   ]
+/// }
+/// ```
+///
+/// The `signature` and `help` members are omitted for methods without a known signature or help
+/// text, and the `variadic` member is omitted for signatures without variadic parameters.
+///
+/// Descriptions of servers can be generated with `RouteBuilder::describe`, and they can be served
+/// at an HTTP endpoint with `RouteBuilder::describe_endpoint` (with the `axum` feature).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApiDescription {
+    methods: Vec<MethodDescription>,
+}
+
+impl ApiDescription {
+    /// constructor for an [`ApiDescription`] from a list of method descriptions
+    ///
+    /// Methods are sorted by name, so descriptions of the same server are always identical.
+    pub fn new(mut methods: Vec<MethodDescription>) -> ApiDescription {
+        methods.sort_by(|a, b| a.name.cmp(&b.name));
+        ApiDescription { methods }
+    }
+
+    /// getter method for the descriptions of all methods (sorted by name)
+    pub fn methods(&self) -> &[MethodDescription] {
+        &self.methods
+    }
+
+    /// getter method for the description of the method with the given name
+    pub fn method(&self, name: &str) -> Option<&MethodDescription> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+}
+
+impl TryToValue for ApiDescription {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        Ok(Value::structure(Struct::new(vec![Member::new(
+            String::from("methods"),
+            self.methods.try_to_value()?,
+        )])))
+    }
+}
+
+/// # Machine-readable description of a single XML-RPC method
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodDescription {
+    name: String,
+    signature: Option<Signature>,
+    help: Option<String>,
+}
+
+impl MethodDescription {
+    /// constructor for a [`MethodDescription`] from a method name, and its signature and help
+    /// text (if they are known)
+    pub fn new(name: &str, signature: Option<Signature>, help: Option<&str>) -> MethodDescription {
+        MethodDescription {
+            name: name.to_owned(),
+            signature,
+            help: help.map(ToOwned::to_owned),
+        }
+    }
+
+    /// getter method for the name of the method
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// getter method for the signature of the method
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    /// getter method for the help text of the method
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+}
+
+impl TryToValue for MethodDescription {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        let mut members = vec![Member::new(String::from("name"), Value::string(self.name.clone()))];
+
+        if let Some(signature) = &self.signature {
+            members.push(Member::new(String::from("signature"), signature.try_to_value()?));
+        }
+        if let Some(help) = &self.help {
+            members.push(Member::new(String::from("help"), Value::string(help.clone())));
+        }
+
+        Ok(Value::structure(Struct::new(members)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use crate::ParamType;
+
+    #[test]
+    fn to_value() {
+        let description = ApiDescription::new(vec![
+            MethodDescription::new("sub", None, None),
+            MethodDescription::new(
+                "add",
+                Some(Signature::new([ParamType::Int, ParamType::Int]).variadic(ParamType::Any)),
+                Some("Adds two integers."),
+            ),
+        ]);
+
+        assert_eq!(description.methods()[0].name(), "add");
+        assert_eq!(description.method("sub").unwrap().help(), None);
+
+        let expected = dxr::value!({
+            "methods": [
+                {
+                    "name": "add",
+                    "signature": { "required": ["i4", "i4"], "optional": [], "variadic": "any" },
+                    "help": "Adds two integers."
+                },
+                { "name": "sub" }
+            ]
+        });
+        assert_eq!(description.try_to_value().unwrap(), expected);
+    }
+}
@@ -0,0 +1,64 @@
+use dxr::{fault, Fault, Value};
+use dxr_client::{Client, ClientError};
+
+use crate::{FallbackHandler, HandlerResponse, RequestContext};
+
+/// # Gateway for forwarding method calls to another XML-RPC server
+///
+/// This type is a [`FallbackHandler`] that forwards calls of all methods without a registered
+/// method handler to an upstream XML-RPC server, and relays its results and faults. The upstream
+/// server can be accessed with any URL that is supported by the [`Client`] (including unix sockets
+/// with the SCGI protocol), so this can be used for exposing the SCGI interface of rtorrent as a
+/// plain HTTP endpoint:
+///
+/// ```no_run
+/// use dxr_server::dxr_client::{ClientBuilder, Url};
+/// use dxr_server::{Gateway, RouteBuilder, Server};
+///
+/// # async fn run() {
+/// let upstream = ClientBuilder::new(Url::parse("unix:///home/user/.rtorrent/rpc.socket").unwrap()).build();
+/// let route = RouteBuilder::new()
+///     .set_path("/RPC2")
+///     .fallback(Box::new(Gateway::new(upstream)))
+///     .build();
+///
+/// Server::from_route(route).serve("127.0.0.1:8000".parse().unwrap()).await.unwrap();
+/// # }
+/// ```
+///
+/// Request headers are not forwarded to the upstream server, so credentials of clients of the
+/// gateway (i.e. that are checked by middleware) are not leaked. Failures to reach the upstream
+/// server (or invalid responses) are reported as faults with the well-known
+/// [`TRANSPORT_ERROR`](fault::TRANSPORT_ERROR) code.
+#[derive(Debug)]
+pub struct Gateway {
+    client: Client,
+}
+
+impl Gateway {
+    /// constructor for a [`Gateway`] that forwards method calls with the given client
+    pub fn new(client: Client) -> Gateway {
+        Gateway { client }
+    }
+
+    /// getter method for the client that is used for forwarding method calls
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+#[async_trait::async_trait]
+impl FallbackHandler for Gateway {
+    async fn handle(&self, method: &str, params: &[Value], _context: &RequestContext<'_>) -> HandlerResponse {
+        let result = match self.client.call_raw(method, params.to_vec()).await {
+            Ok(value) => Ok(value),
+            Err(ClientError::Fault { fault }) => Err(fault),
+            Err(error) => Err(Fault::new(
+                fault::TRANSPORT_ERROR,
+                format!("Failed to forward method call to upstream server: {error}"),
+            )),
+        };
+
+        HandlerResponse::new(result)
+    }
+}
@@ -1,7 +1,15 @@
-use http::HeaderMap;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::header::{HeaderName, HeaderValue};
+use http::{Extensions, HeaderMap, StatusCode};
 
 use dxr::{Fault, Value};
 
+use crate::RequestId;
+
 /// type alias for the result type of method handlers
 pub type HandlerResult = Result<Value, Fault>;
 
@@ -17,6 +25,31 @@ pub trait Handler: Send + Sync {
     /// This method is called for handling incoming XML-RPC method requests with the method name
     /// registered for this [`Handler`], with the request's method parameters as its arguments.
     async fn handle(&self, params: &[Value], headers: HeaderMap) -> HandlerResult;
+
+    /// This method is called instead of [`Handler::handle`] by the server, and can be overridden
+    /// by handlers that need control over the HTTP response (i.e. for adding custom HTTP headers,
+    /// or for returning HTTP errors instead of XML-RPC faults).
+    ///
+    /// Handlers that override this method also have access to the [`RequestContext`], which
+    /// contains the request extensions (i.e. values that were inserted by middleware) and the raw
+    /// XML document of the request. The default implementation calls [`Handler::handle`] with
+    /// the request headers, and wraps its result in a plain [`HandlerResponse`].
+    async fn handle_http(&self, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+        self.handle(params, context.headers().clone()).await.into()
+    }
+}
+
+/// trait describing handlers for XML-RPC method calls without a registered method handler
+///
+/// A fallback handler can be registered with `RouteBuilder::fallback` (with the `axum` feature).
+/// Unlike a [`Handler`], it is called with the name of the called method, so it can handle calls of
+/// arbitrary methods (i.e. for forwarding them to another XML-RPC server). Without a fallback
+/// handler, calls of unknown methods result in a "method not found" fault.
+#[async_trait::async_trait]
+pub trait FallbackHandler: Send + Sync {
+    /// This method is called for handling incoming XML-RPC method requests for methods that do not
+    /// have a registered [`Handler`], with the name of the method and its parameters as arguments.
+    async fn handle(&self, method: &str, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse;
 }
 
 /// type alias for non-async handler functions without associated data
@@ -28,3 +61,176 @@ impl Handler for HandlerFn {
         self(params, headers)
     }
 }
+
+// Calls the method handler, and converts panics into internal error faults, so a buggy handler
+// cannot tear down the task that is serving the connection.
+pub(crate) async fn call_handler(
+    handler: &dyn Handler,
+    name: &str,
+    params: &[Value],
+    context: &RequestContext<'_>,
+) -> HandlerResponse {
+    catch_panics(name, handler.handle_http(params, context)).await
+}
+
+// Calls the fallback handler for methods without a registered handler, with the same protection
+// against panics as for method handlers.
+pub(crate) async fn call_fallback(
+    handler: &dyn FallbackHandler,
+    name: &str,
+    params: &[Value],
+    context: &RequestContext<'_>,
+) -> HandlerResponse {
+    catch_panics(name, handler.handle(name, params, context)).await
+}
+
+pub(crate) async fn catch_panics<F>(name: &str, future: F) -> HandlerResponse
+where
+    F: Future<Output = HandlerResponse> + Unpin,
+{
+    match CatchUnwind(future).await {
+        Ok(response) => response,
+        Err(_) => HandlerResponse::new(Err(Fault::internal_error(format!("Method handler panicked: {name}")))),
+    }
+}
+
+// wrapper for futures that catches panics while the inner future is polled
+struct CatchUnwind<F>(F);
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match catch_unwind(AssertUnwindSafe(|| Pin::new(&mut self.0).poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+/// # Context of XML-RPC requests
+///
+/// This type is passed to [`Handler::handle_http`]. It provides access to the HTTP headers and
+/// extensions of the request, and to the raw XML document of the request (i.e. for auditing or
+/// for verifying signatures).
+#[derive(Debug)]
+pub struct RequestContext<'a> {
+    headers: HeaderMap,
+    extensions: Extensions,
+    body: &'a str,
+}
+
+impl<'a> RequestContext<'a> {
+    /// constructor for a [`RequestContext`] from request headers, extensions, and body
+    pub fn new(headers: HeaderMap, extensions: Extensions, body: &'a str) -> Self {
+        RequestContext {
+            headers,
+            extensions,
+            body,
+        }
+    }
+
+    /// getter method for the HTTP headers of the request
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// getter method for the extensions of the request (i.e. values that were inserted by
+    /// middleware, like authenticated identities or request IDs)
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// getter method for the raw XML document of the request
+    pub fn body(&self) -> &'a str {
+        self.body
+    }
+
+    /// getter method for the correlation ID of the request
+    ///
+    /// This is only available if request IDs are enabled (see [`RequestId`]).
+    pub fn request_id(&self) -> Option<&str> {
+        self.extensions.get::<RequestId>().map(RequestId::as_str)
+    }
+}
+
+/// # HTTP response of method handlers
+///
+/// This type is returned by [`Handler::handle_http`]. In addition to the result of the method
+/// call, it can contain additional HTTP response headers, and a custom HTTP status code.
+///
+/// ```
+/// use dxr::Value;
+/// use dxr_server::HandlerResponse;
+/// use http::header::{HeaderValue, CACHE_CONTROL};
+///
+/// let response = HandlerResponse::new(Ok(Value::i4(42)))
+///     .header(CACHE_CONTROL, HeaderValue::from_static("max-age=60"));
+/// ```
+///
+/// Responses that are returned for calls within a `system.multicall` call can only contribute
+/// their HTTP headers, and HTTP errors are converted into faults (with the HTTP status code as
+/// fault code).
+#[derive(Debug)]
+pub struct HandlerResponse {
+    pub(crate) outcome: Outcome,
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+}
+
+#[derive(Debug)]
+pub(crate) enum Outcome {
+    Result(HandlerResult),
+    Error(String),
+}
+
+impl HandlerResponse {
+    /// constructor for a [`HandlerResponse`] from the result of a method call
+    pub fn new(result: HandlerResult) -> HandlerResponse {
+        HandlerResponse {
+            outcome: Outcome::Result(result),
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// constructor for a [`HandlerResponse`] that results in a plain HTTP error (i.e. `401` or
+    /// `429`) instead of an XML-RPC response
+    ///
+    /// The message is sent as the `text/plain` body of the HTTP response.
+    pub fn error(status: StatusCode, message: &str) -> HandlerResponse {
+        HandlerResponse {
+            outcome: Outcome::Error(message.to_owned()),
+            status,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// method for adding a custom HTTP response header
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// method for overriding the HTTP status code of the response (`200 OK` by default)
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    // fallback for calls within system.multicall, which can only return values or faults
+    #[cfg(feature = "multicall")]
+    pub(crate) fn into_result(self) -> HandlerResult {
+        match self.outcome {
+            Outcome::Result(result) => result,
+            Outcome::Error(message) => Err(Fault::new(i32::from(self.status.as_u16()), message)),
+        }
+    }
+}
+
+impl From<HandlerResult> for HandlerResponse {
+    fn from(result: HandlerResult) -> Self {
+        HandlerResponse::new(result)
+    }
+}
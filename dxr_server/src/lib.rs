@@ -19,22 +19,65 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
-use http::{HeaderMap, HeaderValue, StatusCode};
+use http::{Extensions, HeaderMap, HeaderValue, StatusCode};
 
-use dxr::{DxrError, Fault, FaultResponse, MethodCall, MethodResponse, Value};
+use dxr::{DxrError, ErrorContext, Fault, FaultResponse, MethodResponse, Value, WriteXml};
 
 mod handler;
 pub use handler::*;
+use handler::{call_fallback, call_handler, Outcome};
+
+mod describe;
+pub use describe::*;
+
+#[cfg(feature = "gateway")]
+mod gateway;
+#[cfg(feature = "gateway")]
+pub use gateway::*;
+
+#[cfg(feature = "logging")]
+mod logging;
+#[cfg(feature = "logging")]
+pub use logging::*;
+
+mod options;
+pub use options::*;
+
+mod output;
+pub use output::*;
+
+mod request_id;
+pub use request_id::*;
+
+mod signature;
+pub use signature::*;
 
 #[cfg(feature = "axum")]
 mod axum_support;
 #[cfg(feature = "axum")]
 pub use self::axum_support::*;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "timeout")]
+mod timeout;
+#[cfg(feature = "timeout")]
+pub use timeout::*;
+
+#[cfg(feature = "uploads")]
+mod upload;
+#[cfg(feature = "uploads")]
+pub use upload::*;
+
 // re-export axum, as it is exposed in the the public API
 #[cfg(feature = "axum")]
 pub use axum;
 
+// re-export dxr_client, as it is exposed in the the public API
+#[cfg(feature = "gateway")]
+pub use dxr_client;
+
 // re-export the async_trait macro, as it is exposed as part of the public API
 pub use async_trait::async_trait;
 
@@ -50,44 +93,74 @@ pub type HandlerMap = Arc<HashMap<&'static str, Box<dyn Handler>>>;
 /// as arguments, and returns a tuple of HTTP status code [`http::StatusCode`], request
 /// response headers, and response body.
 pub async fn server(handlers: HandlerMap, body: &str, headers: HeaderMap) -> (StatusCode, HeaderMap, String) {
+    server_with(handlers, body, headers, Extensions::new(), ServerOptions::default()).await
+}
+
+/// This function works like [`server`], but also passes the extensions of the HTTP request (i.e.
+/// values that were inserted by middleware) on to the method handlers, and uses custom
+/// [`ServerOptions`] for parsing requests and formatting responses.
+///
+/// Method handlers can access the request extensions in [`Handler::handle_http`].
+pub async fn server_with(
+    handlers: HandlerMap,
+    body: &str,
+    headers: HeaderMap,
+    extensions: Extensions,
+    options: ServerOptions,
+) -> (StatusCode, HeaderMap, String) {
+    dispatch(handlers, None, body, headers, extensions, options).await
+}
+
+// calls the method handlers (or the fallback handler, if there is one) and builds the response
+pub(crate) async fn dispatch(
+    handlers: HandlerMap,
+    fallback: Option<&dyn FallbackHandler>,
+    body: &str,
+    headers: HeaderMap,
+    extensions: Extensions,
+    options: ServerOptions,
+) -> (StatusCode, HeaderMap, String) {
+    let output = options.output();
+
     if headers.get(CONTENT_LENGTH).is_none() {
-        return fault_to_response(411, "Content-Length header missing.");
+        return fault_to_response(
+            output,
+            Fault::invalid_request(String::from("Content-Length header missing.")),
+        );
     }
 
-    let call: MethodCall = match dxr::deserialize_xml(body) {
+    let context = RequestContext::new(headers, extensions, body);
+
+    // errors include the position of syntax errors in malformed XML documents
+    let call = match dxr::parse_method_call_with(body, options.deserialize()) {
         Ok(call) => call,
-        Err(error) => {
-            let e = DxrError::invalid_data(error.to_string());
-            let f = Fault::from(e);
-            return fault_to_response(f.code(), f.string());
-        },
+        Err(error) => return fault_to_response(output, parse_fault(&error)),
     };
 
     #[cfg(feature = "multicall")]
     if call.name() == "system.multicall" {
         let calls = match dxr::from_multicall_params(call.params()) {
             Ok(calls) => calls,
-            Err(error) => {
-                let f = Fault::from(error);
-                return fault_to_response(f.code(), f.string());
-            },
+            Err(error) => return fault_to_response(output, Fault::from(error)),
         };
 
         let mut results = Vec::new();
+        let mut extra_headers = HeaderMap::new();
 
         for multi in calls {
             match multi {
                 Ok((name, params)) => {
-                    let handler = match handlers.get(name.as_str()) {
-                        Some(handler) => handler,
-                        None => {
-                            results.push(Err(Fault::new(404, String::from("Unknown method."))));
+                    let response = match (handlers.get(name.as_str()), fallback) {
+                        (Some(handler), _) => call_handler(handler.as_ref(), &name, &params, &context).await,
+                        (None, Some(fallback)) => call_fallback(fallback, &name, &params, &context).await,
+                        (None, None) => {
+                            results.push(Err(Fault::method_not_found(&name)));
                             continue;
                         },
                     };
 
-                    let result = handler.handle(&params, headers.clone()).await;
-                    results.push(result);
+                    extra_headers.extend(response.headers.clone());
+                    results.push(response.into_result());
                 },
                 Err(error) => {
                     results.push(Err(Fault::from(error)));
@@ -98,20 +171,52 @@ pub async fn server(handlers: HandlerMap, body: &str, headers: HeaderMap) -> (St
 
         let value = dxr::into_multicall_response(results);
 
-        return success_to_response(value);
+        let (status, mut headers, body) = success_to_response(output, value);
+        headers.extend(extra_headers);
+        return (status, headers, body);
+    }
+
+    let response = match (handlers.get_key_value(call.name()), fallback) {
+        (Some((name, handler)), _) => call_handler(handler.as_ref(), name, &call.params(), &context).await,
+        (None, Some(fallback)) => {
+            let name = call.name().to_owned();
+            call_fallback(fallback, &name, &call.params(), &context).await
+        },
+        (None, None) => return fault_to_response(output, Fault::method_not_found(call.name())),
+    };
+
+    handler_to_response(output, response)
+}
+
+// documents that are not well-formed XML are parse errors (their errors include the offset of the
+// syntax error), and well-formed documents that are not valid method calls are invalid requests
+pub(crate) fn parse_fault(error: &DxrError) -> Fault {
+    match error.context().and_then(ErrorContext::offset) {
+        Some(_) => Fault::parse_error(error.to_string()),
+        None => Fault::invalid_request(error.to_string()),
     }
+}
 
-    let handler = match handlers.get(call.name()) {
-        Some(handler) => handler,
-        None => return fault_to_response(404, "Unknown method."),
+fn handler_to_response(output: OutputOptions, response: HandlerResponse) -> (StatusCode, HeaderMap, String) {
+    let (status, mut headers, body) = match response.outcome {
+        Outcome::Result(Ok(value)) => success_to_response(output, value),
+        Outcome::Result(Err(fault)) => fault_to_response(output, fault),
+        Outcome::Error(message) => {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+            (response.status, headers, message)
+        },
     };
 
-    let response = match handler.handle(&call.params(), headers).await {
-        Ok(value) => success_to_response(value),
-        Err(fault) => fault_to_response(fault.code(), fault.string()),
+    // failures to serialize the response are not overridden by custom status codes
+    let status = if status == StatusCode::OK {
+        response.status
+    } else {
+        status
     };
+    headers.extend(response.headers);
 
-    response
+    (status, headers, body)
 }
 
 fn response_headers() -> HeaderMap {
@@ -120,21 +225,241 @@ fn response_headers() -> HeaderMap {
     headers
 }
 
-fn success_to_response(value: Value) -> (StatusCode, HeaderMap, String) {
+fn write_response<T: WriteXml>(output: OutputOptions, response: &T) -> (StatusCode, HeaderMap, String) {
+    match output.write(response) {
+        Ok(body) => (StatusCode::OK, response_headers(), body),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, response_headers(), error),
+    }
+}
+
+fn success_to_response(output: OutputOptions, value: Value) -> (StatusCode, HeaderMap, String) {
     let response = MethodResponse::new(value);
 
-    match dxr::serialize_xml(&response) {
-        Ok(success) => (StatusCode::OK, response_headers(), success),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, response_headers(), error.to_string()),
-    }
+    write_response(output, &response)
 }
 
-fn fault_to_response(code: i32, string: &str) -> (StatusCode, HeaderMap, String) {
-    let fault = Fault::new(code, string.to_owned());
+fn fault_to_response(output: OutputOptions, fault: Fault) -> (StatusCode, HeaderMap, String) {
     let response: FaultResponse = fault.into();
 
-    match dxr::serialize_xml(&response) {
-        Ok(fault) => (StatusCode::OK, response_headers(), fault),
-        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, response_headers(), error.to_string()),
+    write_response(output, &response)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use http::header::CACHE_CONTROL;
+
+    struct CachedHandler;
+
+    #[async_trait::async_trait]
+    impl Handler for CachedHandler {
+        async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+            Ok(Value::i4(42))
+        }
+
+        async fn handle_http(&self, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+            HandlerResponse::new(self.handle(params, context.headers().clone()).await)
+                .header(CACHE_CONTROL, HeaderValue::from_static("max-age=60"))
+        }
+    }
+
+    struct LimitedHandler;
+
+    #[async_trait::async_trait]
+    impl Handler for LimitedHandler {
+        async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+            Err(Fault::new(429, String::from("Too many requests.")))
+        }
+
+        async fn handle_http(&self, _params: &[Value], _context: &RequestContext<'_>) -> HandlerResponse {
+            HandlerResponse::error(StatusCode::TOO_MANY_REQUESTS, "Too many requests.")
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct RequestId(i32);
+
+    struct AuditHandler;
+
+    #[async_trait::async_trait]
+    impl Handler for AuditHandler {
+        async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+            Err(Fault::new(500, String::from("Missing request context.")))
+        }
+
+        async fn handle_http(&self, _params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+            let id = context.extensions().get::<RequestId>().map_or(0, |id| id.0);
+            let length = context.body().len() as i32;
+            HandlerResponse::new(Ok(Value::array(dxr::Array::new(vec![
+                Value::i4(id),
+                Value::i4(length),
+            ]))))
+        }
+    }
+
+    struct PanicHandler;
+
+    #[async_trait::async_trait]
+    impl Handler for PanicHandler {
+        async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+            panic!("oops");
+        }
+    }
+
+    fn handlers() -> HandlerMap {
+        let mut handlers: HashMap<&'static str, Box<dyn Handler>> = HashMap::new();
+        handlers.insert("audit", Box::new(AuditHandler));
+        handlers.insert("cached", Box::new(CachedHandler));
+        handlers.insert("limited", Box::new(LimitedHandler));
+        handlers.insert("panic", Box::new(PanicHandler));
+        Arc::new(handlers)
+    }
+
+    fn request(body: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+        headers
+    }
+
+    #[tokio::test]
+    async fn response_headers() {
+        let body = "<methodCall><methodName>cached</methodName></methodCall>";
+        let (status, headers, response) = server(handlers(), body, request(body)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers.get(CACHE_CONTROL).unwrap(), "max-age=60");
+        assert_eq!(headers.get(CONTENT_TYPE).unwrap(), "text/xml");
+        assert!(response.contains("<i4>42</i4>"));
+    }
+
+    #[tokio::test]
+    async fn http_error() {
+        let body = "<methodCall><methodName>limited</methodName></methodCall>";
+        let (status, headers, response) = server(handlers(), body, request(body)).await;
+
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers.get(CONTENT_TYPE).unwrap(), "text/plain");
+        assert_eq!(response, "Too many requests.");
+    }
+
+    #[tokio::test]
+    async fn standard_faults() {
+        let body = "<methodCall><methodName>unknown</methodName></methodCall>";
+        let (_, _, response) = server(handlers(), body, request(body)).await;
+        assert!(response.contains("<i4>-32601</i4>"));
+
+        let body = "<methodCall><methodName>unknown";
+        let (_, _, response) = server(handlers(), body, request(body)).await;
+        assert!(response.contains("<i4>-32700</i4>"));
+
+        let body = "<methodCall><methodName>unknown</name></methodCall>";
+        let (_, _, response) = server(handlers(), body, request(body)).await;
+        assert!(response.contains("<i4>-32700</i4>"));
+        assert!(response.contains("Error at byte offset 33"));
+
+        let body = "<methodResponse><params></params></methodResponse>";
+        let (_, _, response) = server(handlers(), body, request(body)).await;
+        assert!(response.contains("<i4>-32600</i4>"));
+
+        let body = "<methodCall><params></params></methodCall>";
+        let (_, _, response) = server(handlers(), body, request(body)).await;
+        assert!(response.contains("<i4>-32600</i4>"));
+
+        let body = "<methodCall><methodName>cached</methodName></methodCall>";
+        let (_, _, response) = server(handlers(), body, HeaderMap::new()).await;
+        assert!(response.contains("<i4>-32600</i4>"));
+    }
+
+    #[tokio::test]
+    async fn handler_panic() {
+        let body = "<methodCall><methodName>panic</methodName></methodCall>";
+        let (status, _, response) = server(handlers(), body, request(body)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(response.contains("<i4>-32603</i4>"));
+        assert!(response.contains("Method handler panicked: panic"));
+    }
+
+    struct EchoFallback;
+
+    #[async_trait::async_trait]
+    impl FallbackHandler for EchoFallback {
+        async fn handle(&self, method: &str, _params: &[Value], _context: &RequestContext<'_>) -> HandlerResponse {
+            HandlerResponse::new(Ok(Value::string(method.to_owned())))
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback() {
+        let body = "<methodCall><methodName>unknown</methodName></methodCall>";
+        let (status, _, response) = dispatch(
+            handlers(),
+            Some(&EchoFallback),
+            body,
+            request(body),
+            Extensions::new(),
+            ServerOptions::default(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(response.contains("<string>unknown</string>"));
+
+        // registered method handlers take precedence over the fallback handler
+        let body = "<methodCall><methodName>cached</methodName></methodCall>";
+        let (_, _, response) = dispatch(
+            handlers(),
+            Some(&EchoFallback),
+            body,
+            request(body),
+            Extensions::new(),
+            ServerOptions::default(),
+        )
+        .await;
+
+        assert!(response.contains("<i4>42</i4>"));
+    }
+
+    #[tokio::test]
+    async fn request_context() {
+        let body = "<methodCall><methodName>audit</methodName></methodCall>";
+        let mut extensions = Extensions::new();
+        extensions.insert(RequestId(7));
+
+        let (status, _, response) =
+            server_with(handlers(), body, request(body), extensions, ServerOptions::default()).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let expected = format!("<value><i4>7</i4></value><value><i4>{}</i4></value>", body.len());
+        assert!(response.contains(&expected));
+    }
+
+    #[tokio::test]
+    async fn output_options() {
+        let body = "<methodCall><methodName>unknown</methodName></methodCall>";
+        let options = ServerOptions::default().output_options(OutputOptions::default().encoding(true));
+
+        let (status, _, response) = server_with(handlers(), body, request(body), Extensions::new(), options).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(response.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<methodResponse><fault>"));
+    }
+
+    #[cfg(feature = "multicall")]
+    #[tokio::test]
+    async fn multicall() {
+        let body = "<methodCall><methodName>system.multicall</methodName><params><param><value><array><data>\
+<value><struct><member><name>methodName</name><value>cached</value></member><member><name>params</name><value><array><data/></array></value></member></struct></value>\
+<value><struct><member><name>methodName</name><value>limited</value></member><member><name>params</name><value><array><data/></array></value></member></struct></value>\
+</data></array></value></param></params></methodCall>";
+        let (status, headers, response) = server(handlers(), body, request(body)).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers.get(CACHE_CONTROL).unwrap(), "max-age=60");
+        assert!(response.contains("<i4>42</i4>"));
+        assert!(response.contains("<i4>429</i4>"));
     }
 }
@@ -0,0 +1,316 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use http::StatusCode;
+
+use dxr::{Array, DeserializeOptions, Member, MethodCall, MethodResponse, SerializeOptions, Struct, Value, WriteXml};
+
+use crate::RequestId;
+
+/// default maximum length (in bytes) of request and response bodies in log messages
+pub const DEFAULT_MAX_LOG_LENGTH: usize = 4096;
+
+/// # Logging of XML-RPC requests and responses
+///
+/// Logging is opt-in, and can be enabled with [`RouteBuilder::logger`](crate::RouteBuilder::logger).
+/// Every request is logged (with the [`log`] crate) at the `debug` level, including the name of
+/// the called method, the address of the caller, the HTTP status code, and the time it took to
+/// handle the request (and the request ID, if request IDs are enabled with
+/// [`RouteBuilder::request_ids`](crate::RouteBuilder::request_ids)). At the `trace` level, the raw
+/// XML documents of requests and responses are logged as well.
+///
+/// The caller address is only known if the server was started with [`Server`](crate::Server), or
+/// if the route is served with [`axum::extract::ConnectInfo`] otherwise.
+///
+/// Raw XML documents are truncated to a maximum length ([`DEFAULT_MAX_LOG_LENGTH`] bytes by
+/// default). Sensitive values (i.e. passwords) can be redacted, both in struct members with a
+/// given name and in positional parameters of a given method:
+///
+/// ```
+/// use dxr_server::RequestLogger;
+///
+/// let logger = RequestLogger::new()
+///     .max_length(1024)
+///     .redact("password")
+///     .redact_param("auth.login", 1);
+/// ```
+///
+/// If any values are redacted, documents are parsed and serialized again before they are logged,
+/// so the logged documents can differ from the raw documents in their formatting. Requests that
+/// cannot be parsed are not logged at all in this case, since they could contain sensitive values
+/// that cannot be located.
+#[derive(Clone, Debug)]
+pub struct RequestLogger {
+    max_length: usize,
+    redacted: Vec<String>,
+    redacted_params: Vec<(String, usize)>,
+}
+
+impl Default for RequestLogger {
+    fn default() -> Self {
+        RequestLogger::new()
+    }
+}
+
+impl RequestLogger {
+    /// constructor for a [`RequestLogger`] with default settings
+    pub fn new() -> RequestLogger {
+        RequestLogger {
+            max_length: DEFAULT_MAX_LOG_LENGTH,
+            redacted: Vec::new(),
+            redacted_params: Vec::new(),
+        }
+    }
+
+    /// method for overriding the maximum length (in bytes) of logged XML documents
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// method for adding the name of a struct member whose values are redacted in log messages
+    ///
+    /// Members with this name are redacted at any level of nesting, in both requests and
+    /// responses.
+    pub fn redact(mut self, member: &str) -> Self {
+        self.redacted.push(member.to_owned());
+        self
+    }
+
+    /// method for adding the position (starting at 0) of a parameter of the given method whose
+    /// values are redacted in log messages
+    pub fn redact_param(mut self, method: &str, index: usize) -> Self {
+        self.redacted_params.push((method.to_owned(), index));
+        self
+    }
+
+    pub(crate) fn log(
+        &self,
+        caller: Option<SocketAddr>,
+        request_id: Option<&RequestId>,
+        request: &str,
+        status: StatusCode,
+        response: &str,
+        latency: Duration,
+    ) {
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+
+        let caller = caller.map_or_else(|| String::from("unknown caller"), |addr| addr.to_string());
+        let method = method_name(request).unwrap_or("(invalid request)");
+
+        match request_id {
+            Some(request_id) => log::debug!(
+                "{} called {}: {} in {} ms (request ID {})",
+                caller,
+                method,
+                status.as_u16(),
+                latency.as_millis(),
+                request_id
+            ),
+            None => log::debug!(
+                "{} called {}: {} in {} ms",
+                caller,
+                method,
+                status.as_u16(),
+                latency.as_millis()
+            ),
+        }
+
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("Request from {}: {}", caller, self.format_request(request));
+            log::trace!("Response to {}: {}", caller, self.format_response(response));
+        }
+    }
+
+    fn redacting(&self) -> bool {
+        !self.redacted.is_empty() || !self.redacted_params.is_empty()
+    }
+
+    fn format_request(&self, xml: &str) -> String {
+        if !self.redacting() {
+            return truncate(xml, self.max_length);
+        }
+
+        let Ok(call) = dxr::parse_method_call_with(xml, DeserializeOptions::lenient()) else {
+            return String::from("(invalid request, not logged because values are redacted)");
+        };
+
+        let name = call.name().to_owned();
+        let params = call
+            .params()
+            .iter()
+            .enumerate()
+            .map(|(index, param)| {
+                if self
+                    .redacted_params
+                    .iter()
+                    .any(|(method, i)| *method == name && *i == index)
+                {
+                    redacted()
+                } else {
+                    self.redact_members(param)
+                }
+            })
+            .collect();
+
+        self.write(&MethodCall::new(name, params))
+    }
+
+    fn format_response(&self, xml: &str) -> String {
+        if self.redacted.is_empty() {
+            return truncate(xml, self.max_length);
+        }
+
+        // fault responses (and error messages) do not contain any struct members that are redacted
+        match dxr::deserialize_xml_with::<MethodResponse>(xml, DeserializeOptions::lenient()) {
+            Ok(response) => self.write(&MethodResponse::new(self.redact_members(&response.inner()))),
+            Err(_) => truncate(xml, self.max_length),
+        }
+    }
+
+    fn write<T: WriteXml>(&self, document: &T) -> String {
+        let mut xml = String::new();
+        match document.write_xml(&mut xml, SerializeOptions::default()) {
+            Ok(()) => truncate(&xml, self.max_length),
+            Err(_) => String::from("(document could not be serialized for logging)"),
+        }
+    }
+
+    // replaces the values of all struct members with redacted names (at any level of nesting)
+    fn redact_members(&self, value: &Value) -> Value {
+        if let Some(members) = value.as_struct() {
+            let members = members
+                .iter()
+                .map(|member| {
+                    if self.redacted.iter().any(|name| name == member.name()) {
+                        Member::new(member.name().to_owned(), redacted())
+                    } else {
+                        Member::new(member.name().to_owned(), self.redact_members(member.inner()))
+                    }
+                })
+                .collect();
+            Value::structure(Struct::new(members))
+        } else if let Some(values) = value.as_array() {
+            let values = values.iter().map(|value| self.redact_members(value)).collect();
+            Value::array(Array::new(values))
+        } else {
+            value.clone()
+        }
+    }
+}
+
+fn redacted() -> Value {
+    Value::string(String::from("***"))
+}
+
+// the method name is extracted without parsing the whole request
+fn method_name(request: &str) -> Option<&str> {
+    let start = request.find("<methodName>")? + "<methodName>".len();
+    let end = start + request[start..].find("</methodName>")?;
+    Some(request[start..end].trim())
+}
+
+fn truncate(xml: &str, max_length: usize) -> String {
+    if xml.len() <= max_length {
+        return xml.to_owned();
+    }
+
+    let mut end = max_length;
+    while !xml.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}... ({} bytes truncated)", &xml[..end], xml.len() - end)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn method_names() {
+        assert_eq!(
+            method_name("<methodCall><methodName>hello</methodName></methodCall>"),
+            Some("hello")
+        );
+        assert_eq!(method_name("<methodCall></methodCall>"), None);
+    }
+
+    #[test]
+    fn redacted_members() {
+        let xml = "<methodCall><methodName>login</methodName><params><param><value><struct>\
+<member><name>user</name><value>alice</value></member>\
+<member><name>password</name><value><string>secret</string></value></member>\
+<member><name>nested</name><value><struct><member><name>password</name><value>hunter2</value></member></struct></value></member>\
+</struct></value></param></params></methodCall>";
+
+        let logger = RequestLogger::new().redact("password");
+        let formatted = logger.format_request(xml);
+        assert!(!formatted.contains("secret") && !formatted.contains("hunter2"));
+        assert!(formatted.contains("<name>user</name><value><string>alice</string></value>"));
+        assert!(formatted.contains("<name>password</name><value><string>***</string></value>"));
+        assert_eq!(formatted.matches("***").count(), 2);
+
+        let logger = RequestLogger::new().redact("nested");
+        assert!(logger
+            .format_request(xml)
+            .contains("<name>nested</name><value><string>***</string></value>"));
+
+        // names that only occur in values are not redacted
+        let xml = "<methodCall><methodName>echo</methodName><params><param><value>\
+<string>&lt;name&gt;password&lt;/name&gt;</string></value></param></params></methodCall>";
+        let logger = RequestLogger::new().redact("password");
+        assert!(logger.format_request(xml).contains("&lt;name&gt;password&lt;/name&gt;"));
+    }
+
+    #[test]
+    fn redacted_params() {
+        let xml = "<methodCall><methodName>auth.login</methodName><params>\
+<param><value>alice</value></param><param><value>secret</value></param></params></methodCall>";
+
+        let logger = RequestLogger::new().redact_param("auth.login", 1);
+        assert_eq!(
+            logger.format_request(xml),
+            "<methodCall><methodName>auth.login</methodName><params>\
+<param><value><string>alice</string></value></param>\
+<param><value><string>***</string></value></param></params></methodCall>"
+        );
+
+        // parameters of other methods are not redacted
+        let logger = RequestLogger::new().redact_param("auth.logout", 1);
+        assert!(logger.format_request(xml).contains("secret"));
+
+        // invalid requests are not logged if values are redacted
+        assert!(!logger.format_request("<methodCall>secret").contains("secret"));
+        assert!(RequestLogger::new()
+            .format_request("<methodCall>")
+            .contains("<methodCall>"));
+    }
+
+    #[test]
+    fn redacted_response() {
+        let xml = "<methodResponse><params><param><value><struct>\
+<member><name>token</name><value>abc</value></member></struct></value></param></params></methodResponse>";
+
+        let logger = RequestLogger::new().redact("token");
+        assert_eq!(
+            logger.format_response(xml),
+            "<methodResponse><params><param><value><struct>\
+<member><name>token</name><value><string>***</string></value></member></struct></value></param></params></methodResponse>"
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let logger = RequestLogger::new().max_length(5);
+        assert_eq!(logger.format_request("<value>"), "<valu... (2 bytes truncated)");
+        assert_eq!(logger.format_response("<i4>"), "<i4>");
+
+        // truncation does not split characters
+        assert_eq!(truncate("äöü", 3), "ä... (4 bytes truncated)");
+    }
+}
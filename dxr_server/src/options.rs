@@ -0,0 +1,62 @@
+use dxr::{DeserializeOptions, SerializeOptions};
+
+use crate::OutputOptions;
+
+/// # Options for parsing XML-RPC requests and formatting responses
+///
+/// These options are used by [`server_with`](crate::server_with) for custom XML-RPC endpoints,
+/// and by the routes that are built with `RouteBuilder` (with the `axum` feature):
+///
+/// ```
+/// use dxr::DeserializeOptions;
+/// use dxr_server::{OutputOptions, ServerOptions};
+///
+/// let options = ServerOptions::default()
+///     .deserialize_options(DeserializeOptions::default().max_depth(32))
+///     .output_options(OutputOptions::default().xml_declaration(true));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ServerOptions {
+    deserialize: DeserializeOptions,
+    output: OutputOptions,
+}
+
+impl ServerOptions {
+    /// method for overriding the options that are used for deserializing XML-RPC requests
+    ///
+    /// This can be used to adjust the limits for the nesting depth and size of requests, which
+    /// protect servers from malicious requests that would exhaust their resources (see
+    /// [`DeserializeOptions`] for details).
+    pub const fn deserialize_options(mut self, options: DeserializeOptions) -> Self {
+        self.deserialize = options;
+        self
+    }
+
+    /// method for overriding the options that are used for serializing values in XML-RPC responses
+    ///
+    /// This can be used to select a different format for `dateTime.iso8601` values (see
+    /// [`SerializeOptions`] for details). This is a shortcut for
+    /// [`OutputOptions::serialize_options`], so calling [`ServerOptions::output_options`]
+    /// afterwards resets these options.
+    pub const fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.output = self.output.serialize_options(options);
+        self
+    }
+
+    /// method for overriding the options that are used for formatting XML-RPC responses
+    ///
+    /// This can be used to add an XML declaration to responses, or to pretty-print them (see
+    /// [`OutputOptions`] for details).
+    pub const fn output_options(mut self, output: OutputOptions) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub(crate) fn deserialize(&self) -> DeserializeOptions {
+        self.deserialize
+    }
+
+    pub(crate) fn output(&self) -> OutputOptions {
+        self.output
+    }
+}
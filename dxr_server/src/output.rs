@@ -0,0 +1,195 @@
+use dxr::{SerializeOptions, WriteXml};
+
+/// # Options for formatting XML-RPC responses
+///
+/// By default, responses are written as compact XML documents without an XML declaration. Some
+/// (older) clients require the declaration to be present, or even insist on an explicit
+/// `encoding="UTF-8"` attribute, which can be enabled with these options:
+///
+/// ```
+/// use dxr_server::OutputOptions;
+///
+/// // <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+/// let options = OutputOptions::default().encoding(true).standalone(Some(true));
+/// ```
+///
+/// Responses can also be pretty-printed (with the same formatting as
+/// [`dxr::serialize_xml_pretty`]), which can be useful for debugging clients. Note that pretty
+/// output contains whitespace next to the type elements of values, which is rejected by clients
+/// that strictly follow the XML-RPC specification.
+///
+/// The values in responses are serialized with the default [`SerializeOptions`] unless they are
+/// overridden (i.e. for emitting `dateTime.iso8601` values in a different format):
+///
+/// ```
+/// use dxr::{DateTimeFormat, SerializeOptions};
+/// use dxr_server::OutputOptions;
+///
+/// let options = OutputOptions::default()
+///     .serialize_options(SerializeOptions::default().datetime(DateTimeFormat::Rfc3339));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OutputOptions {
+    declaration: bool,
+    encoding: bool,
+    standalone: Option<bool>,
+    pretty: bool,
+    serialize: SerializeOptions,
+}
+
+impl OutputOptions {
+    /// method for enabling or disabling the XML declaration (`<?xml version="1.0"?>`)
+    ///
+    /// Disabling the declaration also removes the `encoding` and `standalone` attributes.
+    pub const fn xml_declaration(mut self, declaration: bool) -> Self {
+        self.declaration = declaration;
+        if !declaration {
+            self.encoding = false;
+            self.standalone = None;
+        }
+        self
+    }
+
+    /// method for enabling or disabling the `encoding="UTF-8"` attribute of the XML declaration
+    ///
+    /// Enabling the attribute also enables the XML declaration.
+    pub const fn encoding(mut self, encoding: bool) -> Self {
+        self.encoding = encoding;
+        self.declaration |= encoding;
+        self
+    }
+
+    /// method for setting the `standalone` attribute of the XML declaration (`"yes"` or `"no"`)
+    ///
+    /// Setting the attribute also enables the XML declaration. The attribute is omitted for `None`.
+    pub const fn standalone(mut self, standalone: Option<bool>) -> Self {
+        self.standalone = standalone;
+        self.declaration |= standalone.is_some();
+        self
+    }
+
+    /// method for enabling or disabling pretty-printed (indented, human-readable) responses
+    pub const fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// method for overriding the options that are used for serializing the values in responses
+    pub const fn serialize_options(mut self, options: SerializeOptions) -> Self {
+        self.serialize = options;
+        self
+    }
+
+    // serialization errors are only reported as error messages in the response body
+    pub(crate) fn write<T: WriteXml>(&self, response: &T) -> Result<String, String> {
+        let mut body = String::new();
+
+        if self.declaration {
+            body.push_str(r#"<?xml version="1.0""#);
+            if self.encoding {
+                body.push_str(r#" encoding="UTF-8""#);
+            }
+            if let Some(standalone) = self.standalone {
+                body.push_str(if standalone {
+                    r#" standalone="yes""#
+                } else {
+                    r#" standalone="no""#
+                });
+            }
+            body.push_str("?>\n");
+        }
+
+        if self.pretty {
+            let mut compact = String::new();
+            response
+                .write_xml(&mut compact, self.serialize)
+                .map_err(|error| error.to_string())?;
+            body.push_str(&dxr::pretty_print_xml(&compact).map_err(|error| error.to_string())?);
+            body.push('\n');
+        } else {
+            response
+                .write_xml(&mut body, self.serialize)
+                .map_err(|error| error.to_string())?;
+        }
+
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use dxr::{DateTimeFormat, MethodResponse, Value};
+
+    fn response() -> MethodResponse {
+        MethodResponse::new(Value::i4(42))
+    }
+
+    #[test]
+    fn default() {
+        let body = OutputOptions::default().write(&response()).unwrap();
+        assert_eq!(
+            body,
+            "<methodResponse><params><param><value><i4>42</i4></value></param></params></methodResponse>"
+        );
+    }
+
+    #[test]
+    fn declaration() {
+        let body = OutputOptions::default()
+            .xml_declaration(true)
+            .write(&response())
+            .unwrap();
+        assert!(body.starts_with("<?xml version=\"1.0\"?>\n<methodResponse>"));
+
+        let body = OutputOptions::default()
+            .encoding(true)
+            .standalone(Some(true))
+            .write(&response())
+            .unwrap();
+        assert!(body.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<methodResponse>"));
+
+        let body = OutputOptions::default()
+            .encoding(true)
+            .xml_declaration(false)
+            .write(&response())
+            .unwrap();
+        assert!(body.starts_with("<methodResponse>"));
+    }
+
+    #[test]
+    fn datetime_format() {
+        let value: Value =
+            dxr::deserialize_xml("<value><dateTime.iso8601>20230401T12:00:00</dateTime.iso8601></value>").unwrap();
+        let response = MethodResponse::new(value);
+
+        let body = OutputOptions::default()
+            .serialize_options(SerializeOptions::default().datetime(DateTimeFormat::Iso8601))
+            .write(&response)
+            .unwrap();
+        assert!(body.contains("<dateTime.iso8601>2023-04-01T12:00:00</dateTime.iso8601>"));
+    }
+
+    #[test]
+    fn pretty() {
+        let body = OutputOptions::default().pretty(true).write(&response()).unwrap();
+        let expected = "\
+<methodResponse>
+  <params>
+    <param>
+      <value>
+        <i4>42</i4>
+      </value>
+    </param>
+  </params>
+</methodResponse>
+";
+        assert_eq!(body, expected);
+
+        let value: MethodResponse = dxr::deserialize_xml(&body).unwrap();
+        assert_eq!(value.inner(), Value::i4(42));
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "axum")]
+use std::collections::hash_map::RandomState;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "axum")]
+use std::hash::{BuildHasher, Hasher};
+#[cfg(feature = "axum")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "axum")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "axum")]
+use http::HeaderMap;
+
+/// name of the HTTP header that contains the request IDs of XML-RPC requests and responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// request IDs that are longer than this are replaced with generated IDs
+#[cfg(feature = "axum")]
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// # Correlation ID of an XML-RPC request
+///
+/// If request IDs are enabled with `RouteBuilder::request_ids` (with the `axum` feature), the
+/// request ID is taken from the [`X-Request-Id`](REQUEST_ID_HEADER) header of the request, or a new
+/// ID is generated if the header is missing or invalid. It is added to the extensions of the
+/// request (where method handlers can access it with [`RequestContext::request_id`]), included in
+/// log messages, and echoed in the `X-Request-Id` header of the response.
+///
+/// Middleware that already assigns request IDs can insert a [`RequestId`] into the extensions of
+/// the request, which is then used instead.
+///
+/// [`RequestContext::request_id`]: crate::RequestContext::request_id
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// constructor for a [`RequestId`] from an existing ID
+    pub fn new(id: impl Into<String>) -> RequestId {
+        RequestId(id.into())
+    }
+
+    /// getter method for the request ID
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// constructor for a [`RequestId`] from the headers of a request, which falls back to a new
+    /// ID if the request does not contain a valid ID
+    #[cfg(feature = "axum")]
+    pub(crate) fn from_headers(headers: &HeaderMap) -> RequestId {
+        headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
+            .map_or_else(RequestId::generate, RequestId::new)
+    }
+
+    #[cfg(feature = "axum")]
+    fn generate() -> RequestId {
+        RequestId(unique_id())
+    }
+}
+
+// unique (but not cryptographically secure) identifiers with 32 hexadecimal digits, which are
+// derived from a per-process random seed, the current time, and a counter
+#[cfg(feature = "axum")]
+pub(crate) fn unique_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(nanos);
+    let first = hasher.finish();
+    hasher.write_u32(std::process::id());
+    let second = hasher.finish();
+
+    format!("{first:016x}{second:016x}")
+}
+
+impl Display for RequestId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(all(test, feature = "axum"))]
+mod tests {
+    use super::*;
+
+    use http::HeaderValue;
+
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("abc-123"));
+        assert_eq!(RequestId::from_headers(&headers).as_str(), "abc-123");
+
+        // missing and invalid IDs are replaced with generated IDs
+        let generated = RequestId::from_headers(&HeaderMap::new());
+        assert_eq!(generated.as_str().len(), 32);
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static(" "));
+        assert_ne!(RequestId::from_headers(&headers), generated);
+        assert_eq!(RequestId::from_headers(&headers).as_str().len(), 32);
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::{Debug, Formatter};
+
+use http::HeaderMap;
+
+use dxr::{DxrError, Fault, Member, Struct, TryToValue, Value};
+
+use crate::{Handler, HandlerResponse, HandlerResult, RequestContext};
+
+/// # Types of XML-RPC method parameters
+///
+/// This enum is used for declaring the expected types of method parameters in a [`Signature`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParamType {
+    /// `<i4>` or `<int>` values
+    Int,
+    /// `<i8>` values (requires the `i8` feature of `dxr`)
+    Long,
+    /// `<boolean>` values
+    Boolean,
+    /// `<string>` values
+    String,
+    /// `<double>` values
+    Double,
+    /// `<dateTime.iso8601>` values
+    DateTime,
+    /// `<base64>` values
+    Base64,
+    /// `<struct>` values
+    Struct,
+    /// `<array>` values
+    Array,
+    /// `<nil/>` values (requires the `nil` feature of `dxr`)
+    Nil,
+    /// values of any type
+    Any,
+}
+
+impl ParamType {
+    /// getter method for the name of the XML-RPC type (i.e. `i4` or `string`)
+    pub const fn name(self) -> &'static str {
+        match self {
+            ParamType::Int => "i4",
+            ParamType::Long => "i8",
+            ParamType::Boolean => "boolean",
+            ParamType::String => "string",
+            ParamType::Double => "double",
+            ParamType::DateTime => "dateTime.iso8601",
+            ParamType::Base64 => "base64",
+            ParamType::Struct => "struct",
+            ParamType::Array => "array",
+            ParamType::Nil => "nil",
+            ParamType::Any => "any",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        self == ParamType::Any || self.name() == value.type_name()
+    }
+}
+
+/// # Expected parameters of an XML-RPC method
+///
+/// Signatures declare the number and types of the parameters of an XML-RPC method, including
+/// optional trailing parameters, and an optional variable number of trailing parameters:
+///
+/// ```
+/// use dxr_server::{ParamType, Signature};
+///
+/// // two integers, followed by an optional string
+/// let signature = Signature::new([ParamType::Int, ParamType::Int]).optional(ParamType::String);
+///
+/// // a string, followed by any number of values of any type
+/// let signature = Signature::new([ParamType::String]).variadic(ParamType::Any);
+/// ```
+///
+/// Calls that do not match the signature of the method are rejected with an "invalid params" fault
+/// (see [`CheckedHandler`], and `RouteBuilder::add_method_with_signature` with the `axum` feature).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    required: Vec<ParamType>,
+    optional: Vec<ParamType>,
+    variadic: Option<ParamType>,
+}
+
+impl Signature {
+    /// constructor for a [`Signature`] with the types of the required parameters
+    pub fn new(params: impl IntoIterator<Item = ParamType>) -> Signature {
+        Signature {
+            required: params.into_iter().collect(),
+            optional: Vec::new(),
+            variadic: None,
+        }
+    }
+
+    /// method for adding an optional trailing parameter
+    pub fn optional(mut self, param: ParamType) -> Self {
+        self.optional.push(param);
+        self
+    }
+
+    /// method for allowing any number of additional trailing parameters of the given type
+    pub fn variadic(mut self, param: ParamType) -> Self {
+        self.variadic = Some(param);
+        self
+    }
+
+    /// method for checking whether method parameters match this [`Signature`]
+    ///
+    /// Mismatches are reported with the same errors as conversions with
+    /// [`TryFromParams`](dxr::TryFromParams), which can be converted into faults.
+    pub fn check(&self, params: &[Value]) -> Result<(), DxrError> {
+        let min = self.required.len();
+        let max = min + self.optional.len();
+
+        if params.len() < min {
+            return Err(DxrError::parameter_mismatch(params.len(), min));
+        }
+        if params.len() > max && self.variadic.is_none() {
+            return Err(DxrError::parameter_mismatch(params.len(), max));
+        }
+
+        let expected = self
+            .required
+            .iter()
+            .chain(&self.optional)
+            .copied()
+            .chain(self.variadic.into_iter().cycle());
+
+        for (index, (param, expected)) in params.iter().zip(expected).enumerate() {
+            if !expected.matches(param) {
+                return Err(DxrError::wrong_type(param.type_name(), expected.name()).in_index(index));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// signatures are described by the names of the parameter types (see `ApiDescription`)
+impl TryToValue for Signature {
+    fn try_to_value(&self) -> Result<Value, DxrError> {
+        let names = |params: &[ParamType]| params.iter().map(|param| param.name()).collect::<Vec<&str>>();
+
+        let mut members = vec![
+            Member::new(String::from("required"), names(&self.required).try_to_value()?),
+            Member::new(String::from("optional"), names(&self.optional).try_to_value()?),
+        ];
+        if let Some(variadic) = self.variadic {
+            members.push(Member::new(String::from("variadic"), variadic.name().try_to_value()?));
+        }
+
+        Ok(Value::structure(Struct::new(members)))
+    }
+}
+
+/// # Method handler with parameter validation
+///
+/// This type wraps another [`Handler`], and checks the parameters of method calls against a
+/// [`Signature`] before the wrapped handler is called. Calls with an unexpected number of
+/// parameters, or with parameters of unexpected types, are rejected with a standard "invalid
+/// params" fault.
+pub struct CheckedHandler {
+    handler: Box<dyn Handler>,
+    signature: Signature,
+}
+
+impl Debug for CheckedHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckedHandler")
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl CheckedHandler {
+    /// constructor for a [`CheckedHandler`] from a method handler and its signature
+    pub fn new(handler: Box<dyn Handler>, signature: Signature) -> CheckedHandler {
+        CheckedHandler { handler, signature }
+    }
+
+    /// getter method for the signature of the method
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for CheckedHandler {
+    async fn handle(&self, params: &[Value], headers: HeaderMap) -> HandlerResult {
+        self.signature.check(params).map_err(Fault::from)?;
+        self.handler.handle(params, headers).await
+    }
+
+    async fn handle_http(&self, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+        match self.signature.check(params) {
+            Ok(()) => self.handler.handle_http(params, context).await,
+            Err(error) => HandlerResponse::new(Err(Fault::from(error))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use crate::HandlerFn;
+
+    fn params(count: i32) -> Vec<Value> {
+        (0..count).map(Value::i4).collect()
+    }
+
+    #[test]
+    fn arity() {
+        let signature = Signature::new([ParamType::Int, ParamType::Int]).optional(ParamType::Int);
+
+        assert_eq!(
+            signature.check(&params(1)).unwrap_err().as_parameter_mismatch(),
+            Some((1, 2))
+        );
+        assert!(signature.check(&params(2)).is_ok());
+        assert!(signature.check(&params(3)).is_ok());
+        assert_eq!(
+            signature.check(&params(4)).unwrap_err().as_parameter_mismatch(),
+            Some((4, 3))
+        );
+
+        let signature = Signature::new([ParamType::Int]).variadic(ParamType::Int);
+        assert!(signature.check(&params(0)).is_err());
+        assert!(signature.check(&params(10)).is_ok());
+    }
+
+    #[test]
+    fn types() {
+        let signature = Signature::new([ParamType::String, ParamType::Any]).variadic(ParamType::Boolean);
+
+        let params = vec![Value::string(String::from("a")), Value::i4(1), Value::boolean(true)];
+        assert!(signature.check(&params).is_ok());
+
+        let params = vec![Value::string(String::from("a")), Value::i4(1), Value::i4(2)];
+        let error = signature.check(&params).unwrap_err();
+        assert_eq!(error.as_wrong_type(), Some(("i4", "boolean")));
+        assert_eq!(
+            error.to_string(),
+            "Error at [2]: Type mismatch: got i4, expected boolean"
+        );
+    }
+
+    fn adder(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+        let (a, b): (i32, i32) = dxr::TryFromParams::try_from_params(params)?;
+        Ok(Value::i4(a + b))
+    }
+
+    #[tokio::test]
+    async fn handler() {
+        let signature = Signature::new([ParamType::Int, ParamType::Int]);
+        let handler = CheckedHandler::new(Box::new(adder as HandlerFn), signature);
+
+        assert_eq!(
+            handler.handle(&params(2), HeaderMap::new()).await.unwrap(),
+            Value::i4(1)
+        );
+
+        let fault = handler.handle(&params(3), HeaderMap::new()).await.unwrap_err();
+        assert_eq!(fault.code(), dxr::fault::INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,128 @@
+//! # Ephemeral XML-RPC servers for integration tests
+//!
+//! This module provides the [`TestServer`] type, which launches an XML-RPC server in the
+//! background on a port that is assigned by the operating system, so tests can run in parallel
+//! without conflicting over ports, and without waiting for the server to start up.
+//!
+//! The harness is part of `dxr_server` (behind the `testing` feature) instead of being a
+//! `dxr::testing` module, since the core `dxr` crate does not depend on the server
+//! implementation or on `tokio`.
+//!
+//! ```no_run
+//! use dxr::{TryFromParams, TryToValue, Value};
+//! use dxr_server::axum::http::HeaderMap;
+//! use dxr_server::testing::TestServer;
+//! use dxr_server::{HandlerFn, HandlerResult, RouteBuilder};
+//!
+//! fn hello_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+//!     let (name,): (String,) = TryFromParams::try_from_params(params)?;
+//!     Ok(format!("Hello, {name}!").try_to_value()?)
+//! }
+//!
+//! #[tokio::test]
+//! async fn hello() {
+//!     let route = RouteBuilder::new().add_method("hello", Box::new(hello_handler as HandlerFn));
+//!     let server = TestServer::start(route).unwrap();
+//!
+//!     // ... submit method calls to server.url() with an XML-RPC client ...
+//!
+//!     server.shutdown().await.unwrap();
+//! }
+//! ```
+
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::{RouteBuilder, Server, ServerError};
+
+/// # XML-RPC server for integration tests
+///
+/// The server listens on `127.0.0.1` with a port that is assigned by the operating system. It is
+/// already accepting connections when [`TestServer::start`] returns. The server is shut down
+/// gracefully by [`TestServer::shutdown`], or aborted when the [`TestServer`] is dropped.
+#[derive(Debug)]
+pub struct TestServer {
+    addr: SocketAddr,
+    url: String,
+    trigger: Arc<Notify>,
+    handle: Option<JoinHandle<Result<(), ServerError>>>,
+}
+
+impl TestServer {
+    /// This method launches a new server in the background with the route and method handlers
+    /// from the given [`RouteBuilder`].
+    ///
+    /// It must be called from within a [`tokio`] runtime.
+    pub fn start(route: RouteBuilder) -> Result<TestServer, ServerError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let addr = listener.local_addr()?;
+
+        let url = format!("http://{addr}{}", route.path());
+
+        let mut server = Server::from_route(route.build());
+        let trigger = server.shutdown_trigger();
+        let handle = tokio::spawn(server.serve_listener(listener));
+
+        Ok(TestServer {
+            addr,
+            url,
+            trigger,
+            handle: Some(handle),
+        })
+    }
+
+    /// getter method for the socket address the server is listening on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// getter method for the URL of the XML-RPC endpoint of the server
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// This method shuts down the server gracefully and waits until it has stopped.
+    ///
+    /// Errors that caused the server to stop early are returned here.
+    pub async fn shutdown(mut self) -> Result<(), ServerError> {
+        self.trigger.notify_one();
+
+        match self.handle.take() {
+            Some(handle) => handle.await.unwrap_or(Ok(())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[tokio::test]
+    async fn ephemeral_ports() {
+        let first = TestServer::start(RouteBuilder::new()).unwrap();
+        let second = TestServer::start(RouteBuilder::new().set_path("/RPC2")).unwrap();
+
+        assert_ne!(first.addr().port(), 0);
+        assert_ne!(first.addr(), second.addr());
+
+        assert_eq!(first.url(), format!("http://{}/", first.addr()));
+        assert_eq!(second.url(), format!("http://{}/RPC2", second.addr()));
+
+        first.shutdown().await.unwrap();
+        second.shutdown().await.unwrap();
+    }
+}
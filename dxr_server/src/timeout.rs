@@ -0,0 +1,127 @@
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use http::HeaderMap;
+
+use dxr::{fault, Fault, Value};
+
+use crate::{Handler, HandlerResponse, HandlerResult, RequestContext};
+
+/// # Method handler with an execution timeout
+///
+/// This type wraps another [`Handler`], and returns a fault (with the well-known
+/// [`SYSTEM_ERROR`](fault::SYSTEM_ERROR) code) instead of the result of the wrapped handler if it
+/// does not finish within the configured timeout. The wrapped handler is cancelled in this case.
+///
+/// The timeout can only interrupt handlers at `.await` points. Synchronous handlers (i.e.
+/// [`HandlerFn`](crate::HandlerFn) functions) cannot be interrupted at all: they always run to
+/// completion, and their result is returned even if they take longer than the timeout. The same
+/// applies to asynchronous handlers while they block without awaiting. Handlers with long-running
+/// blocking work need to move that work to a blocking thread (i.e. with
+/// [`tokio::task::spawn_blocking`]) and await it, so the timeout can take effect while it runs.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use dxr_server::{HandlerFn, TimeoutHandler};
+/// # use dxr::{TryToValue, Value};
+/// # use dxr_server::HandlerResult;
+/// # use http::HeaderMap;
+///
+/// fn hello_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+///     Ok("Hello World!".try_to_value()?)
+/// }
+///
+/// let handler = TimeoutHandler::new(Box::new(hello_handler as HandlerFn), Duration::from_secs(5));
+/// ```
+pub struct TimeoutHandler {
+    handler: Box<dyn Handler>,
+    timeout: Duration,
+}
+
+impl Debug for TimeoutHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeoutHandler")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl TimeoutHandler {
+    /// constructor for a [`TimeoutHandler`] from a method handler and its execution timeout
+    pub fn new(handler: Box<dyn Handler>, timeout: Duration) -> TimeoutHandler {
+        TimeoutHandler { handler, timeout }
+    }
+
+    fn fault(&self) -> Fault {
+        Fault::new(
+            fault::SYSTEM_ERROR,
+            format!("Method call timed out after {} ms.", self.timeout.as_millis()),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for TimeoutHandler {
+    async fn handle(&self, params: &[Value], headers: HeaderMap) -> HandlerResult {
+        match tokio::time::timeout(self.timeout, self.handler.handle(params, headers)).await {
+            Ok(result) => result,
+            Err(_) => Err(self.fault()),
+        }
+    }
+
+    async fn handle_http(&self, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+        match tokio::time::timeout(self.timeout, self.handler.handle_http(params, context)).await {
+            Ok(response) => response,
+            Err(_) => HandlerResponse::new(Err(self.fault())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    struct SleepHandler;
+
+    #[async_trait::async_trait]
+    impl Handler for SleepHandler {
+        async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok(Value::i4(42))
+        }
+    }
+
+    #[tokio::test]
+    async fn finished() {
+        let handler = TimeoutHandler::new(Box::new(SleepHandler), Duration::from_secs(10));
+        let result = handler.handle(&[], HeaderMap::new()).await;
+        assert_eq!(result.unwrap(), Value::i4(42));
+    }
+
+    fn blocking_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+        std::thread::sleep(Duration::from_millis(50));
+        Ok(Value::i4(42))
+    }
+
+    #[tokio::test]
+    async fn blocking() {
+        // synchronous handlers cannot be interrupted
+        let handler = TimeoutHandler::new(
+            Box::new(blocking_handler as crate::HandlerFn),
+            Duration::from_millis(10),
+        );
+        let result = handler.handle(&[], HeaderMap::new()).await;
+        assert_eq!(result.unwrap(), Value::i4(42));
+    }
+
+    #[tokio::test]
+    async fn timed_out() {
+        let handler = TimeoutHandler::new(Box::new(SleepHandler), Duration::from_millis(10));
+        let fault = handler.handle(&[], HeaderMap::new()).await.unwrap_err();
+        assert_eq!(fault.code(), fault::SYSTEM_ERROR);
+        assert_eq!(fault.string(), "Method call timed out after 10 ms.");
+    }
+}
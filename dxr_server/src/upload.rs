@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use axum::body::{Body, HttpBody};
+use axum::http::header::CONTENT_LENGTH;
+use axum::http::request::Parts;
+use axum::http::{Extensions, HeaderMap, Request, StatusCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+use dxr::{Fault, Value};
+
+use crate::handler::catch_panics;
+use crate::{fault_to_response, handler_to_response, parse_fault, HandlerResponse, RequestContext, ServerOptions};
+
+/// default limit for the size of decoded uploads in bytes (see [`RouteBuilder::max_upload_size`])
+///
+/// [`RouteBuilder::max_upload_size`]: crate::RouteBuilder::max_upload_size
+pub const DEFAULT_MAX_UPLOAD_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+/// trait describing server methods that accept a large `<base64>` parameter
+///
+/// Upload handlers are registered with [`RouteBuilder::add_upload_method`]. For calls of these
+/// methods, the contents of the first `<base64>` value in the request are decoded into a temporary
+/// file while the request is received, instead of being buffered in memory. This makes it possible
+/// to accept uploads (i.e. firmware images) that are much larger than the available memory.
+///
+/// The rest of the request is buffered and parsed as usual. The method parameters that are passed
+/// to the handler contain an empty `<base64>` value in place of the value that was decoded into the
+/// [`Upload`].
+///
+/// [`RouteBuilder::add_upload_method`]: crate::RouteBuilder::add_upload_method
+#[async_trait::async_trait]
+pub trait UploadHandler: Send + Sync {
+    /// This method is called for handling incoming XML-RPC method requests with the method name
+    /// registered for this [`UploadHandler`], with the request's method parameters, the decoded
+    /// upload, and the [`RequestContext`] as its arguments.
+    async fn handle(&self, params: &[Value], upload: Upload, context: &RequestContext<'_>) -> HandlerResponse;
+}
+
+/// # Large `<base64>` value that was decoded into a temporary file
+///
+/// This type is passed to [`UploadHandler::handle`]. The temporary file is deleted when the
+/// [`Upload`] is dropped, unless it is moved to a permanent location with [`Upload::persist`].
+#[derive(Debug)]
+pub struct Upload {
+    file: NamedTempFile,
+    index: usize,
+    len: u64,
+}
+
+impl Upload {
+    /// getter method for the index of the method parameter that contained the `<base64>` value
+    ///
+    /// If the value was nested inside an array or struct, this is the index of the parameter that
+    /// contains the array or struct.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// getter method for the size of the decoded contents (in bytes)
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// method for checking whether the decoded contents are empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// getter method for the path of the temporary file
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// method for opening the temporary file for reading
+    ///
+    /// The returned [`tokio::fs::File`] starts at the beginning of the decoded contents, and
+    /// implements [`tokio::io::AsyncRead`].
+    pub fn open(&self) -> io::Result<tokio::fs::File> {
+        Ok(tokio::fs::File::from_std(self.file.reopen()?))
+    }
+
+    /// method for moving the temporary file to a permanent location
+    ///
+    /// This fails if the target path is on a different file system than the directory for
+    /// temporary files (see [`RouteBuilder::upload_dir`](crate::RouteBuilder::upload_dir)).
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> io::Result<()> {
+        self.file.persist(path).map(|_| ()).map_err(|error| error.error)
+    }
+}
+
+// upload handlers of a route, and settings for decoding uploads
+pub(crate) struct Uploads {
+    pub(crate) handlers: HashMap<&'static str, Box<dyn UploadHandler>>,
+    pub(crate) max_size: u64,
+    pub(crate) dir: Option<PathBuf>,
+}
+
+impl Default for Uploads {
+    fn default() -> Self {
+        Uploads {
+            handlers: HashMap::new(),
+            max_size: DEFAULT_MAX_UPLOAD_SIZE,
+            dir: None,
+        }
+    }
+}
+
+impl Debug for Uploads {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut handler_list: Vec<&&str> = self.handlers.keys().collect();
+        handler_list.sort();
+
+        f.debug_struct("Uploads")
+            .field("handlers", &handler_list)
+            .field("max_size", &self.max_size)
+            .field("dir", &self.dir)
+            .finish()
+    }
+}
+
+impl Uploads {
+    // Reads the request body, and decodes the contents of the first <base64> value into a
+    // temporary file for calls of upload methods. Everything else is buffered, up to the maximum
+    // document size of the deserialization options. Errors are returned as complete responses.
+    pub(crate) async fn read(
+        &self,
+        request: Request<Body>,
+        options: ServerOptions,
+    ) -> Result<(Parts, String, Option<(&'static str, Upload)>), (StatusCode, HeaderMap, String)> {
+        let (parts, mut body) = request.into_parts();
+        let output = options.output();
+        let max_size = options.deserialize().max_document_size();
+
+        let mut stream = UploadStream::new(&self.handlers);
+        let mut sink: Option<(NamedTempFile, tokio::fs::File)> = None;
+        let mut len: u64 = 0;
+
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|error| plain_response(StatusCode::BAD_REQUEST, error.to_string()))?;
+            let decoded = stream.push(&chunk).map_err(|fault| fault_to_response(output, fault))?;
+
+            if stream.buffered() > max_size {
+                return Err(plain_response(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    String::from("Request body is too large."),
+                ));
+            }
+
+            if !decoded.is_empty() {
+                len += decoded.len() as u64;
+                if len > self.max_size {
+                    return Err(plain_response(
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        String::from("Upload is too large."),
+                    ));
+                }
+
+                let (_, file) = match &mut sink {
+                    Some(sink) => sink,
+                    None => sink.insert(self.temp_file().map_err(internal_error)?),
+                };
+                file.write_all(&decoded).await.map_err(internal_error)?;
+            }
+        }
+
+        match stream.finish().map_err(|fault| fault_to_response(output, fault))? {
+            Finished::Buffered(bytes) => Ok((parts, String::from_utf8(bytes).map_err(|_| invalid_utf8())?, None)),
+            Finished::Upload {
+                skeleton,
+                method,
+                index,
+            } => {
+                let (file, mut writer) = match sink {
+                    Some(sink) => sink,
+                    None => self.temp_file().map_err(internal_error)?,
+                };
+                writer.flush().await.map_err(internal_error)?;
+
+                Ok((
+                    parts,
+                    String::from_utf8(skeleton).map_err(|_| invalid_utf8())?,
+                    Some((method, Upload { file, index, len })),
+                ))
+            },
+        }
+    }
+
+    // calls the upload handler and builds the response
+    pub(crate) async fn dispatch(
+        &self,
+        name: &'static str,
+        upload: Upload,
+        body: &str,
+        headers: HeaderMap,
+        extensions: Extensions,
+        options: ServerOptions,
+    ) -> (StatusCode, HeaderMap, String) {
+        let output = options.output();
+
+        let handler = match self.handlers.get(name) {
+            Some(handler) => handler,
+            None => return fault_to_response(output, Fault::method_not_found(name)),
+        };
+
+        if headers.get(CONTENT_LENGTH).is_none() {
+            return fault_to_response(
+                output,
+                Fault::invalid_request(String::from("Content-Length header missing.")),
+            );
+        }
+
+        let context = RequestContext::new(headers, extensions, body);
+
+        let call = match dxr::parse_method_call_with(body, options.deserialize()) {
+            Ok(call) => call,
+            Err(error) => return fault_to_response(output, parse_fault(&error)),
+        };
+
+        let response = catch_panics(name, handler.handle(&call.params(), upload, &context)).await;
+        handler_to_response(output, response)
+    }
+
+    fn temp_file(&self) -> io::Result<(NamedTempFile, tokio::fs::File)> {
+        let file = match &self.dir {
+            Some(dir) => NamedTempFile::new_in(dir)?,
+            None => NamedTempFile::new()?,
+        };
+        let writer = tokio::fs::File::from_std(file.reopen()?);
+        Ok((file, writer))
+    }
+}
+
+fn plain_response(status: StatusCode, message: String) -> (StatusCode, HeaderMap, String) {
+    (status, HeaderMap::new(), message)
+}
+
+fn internal_error(error: io::Error) -> (StatusCode, HeaderMap, String) {
+    plain_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+}
+
+fn invalid_utf8() -> (StatusCode, HeaderMap, String) {
+    plain_response(
+        StatusCode::BAD_REQUEST,
+        String::from("Request body is not valid UTF-8."),
+    )
+}
+
+const START_TAG: &[u8] = b"<base64>";
+
+enum State {
+    // looking for the start tag of the first base64 value (the document is buffered until it is found)
+    Head,
+    // decoding the contents of the base64 value
+    Contents,
+    // buffering the rest of the document after the contents of the base64 value
+    Tail,
+    // buffering the complete document (for calls of methods that are not upload methods)
+    Buffered,
+}
+
+// result of decoding a complete request
+enum Finished {
+    Buffered(Vec<u8>),
+    Upload {
+        skeleton: Vec<u8>,
+        method: &'static str,
+        index: usize,
+    },
+}
+
+// state machine that decodes the contents of the first `<base64>` value in calls of upload methods
+// while the request is received, without buffering them
+//
+// Everything except the contents of the base64 value is buffered. The buffered parts make up a
+// valid method call with an empty base64 value, which is parsed when the request is complete.
+struct UploadStream<'a> {
+    handlers: &'a HashMap<&'static str, Box<dyn UploadHandler>>,
+    state: State,
+    head: Vec<u8>,
+    tail: Vec<u8>,
+    pending: Vec<u8>,
+    padded: bool,
+    method: &'static str,
+    index: usize,
+}
+
+impl<'a> UploadStream<'a> {
+    fn new(handlers: &'a HashMap<&'static str, Box<dyn UploadHandler>>) -> Self {
+        UploadStream {
+            handlers,
+            state: State::Head,
+            head: Vec::new(),
+            tail: Vec::new(),
+            pending: Vec::new(),
+            padded: false,
+            method: "",
+            index: 0,
+        }
+    }
+
+    // number of bytes that are currently buffered
+    fn buffered(&self) -> usize {
+        self.head.len() + self.tail.len() + self.pending.len()
+    }
+
+    // process the next chunk of the request and return the bytes that were decoded from it
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<u8>, Fault> {
+        match self.state {
+            State::Head => {
+                // the start tag can be split across chunks, so the search starts a bit earlier
+                let from = self.head.len().saturating_sub(START_TAG.len() - 1);
+                self.head.extend_from_slice(chunk);
+
+                let start = self.head[from..]
+                    .windows(START_TAG.len())
+                    .position(|window| window == START_TAG);
+
+                match start {
+                    Some(start) => match self.upload_method() {
+                        Some(method) => {
+                            self.method = method;
+                            self.index = count(&self.head, b"<param>").saturating_sub(1);
+                            let contents = self.head.split_off(from + start + START_TAG.len());
+                            self.state = State::Contents;
+                            self.contents(&contents)
+                        },
+                        None => {
+                            self.state = State::Buffered;
+                            Ok(Vec::new())
+                        },
+                    },
+                    None => Ok(Vec::new()),
+                }
+            },
+            State::Contents => self.contents(chunk),
+            State::Tail => {
+                self.tail.extend_from_slice(chunk);
+                Ok(Vec::new())
+            },
+            State::Buffered => {
+                self.head.extend_from_slice(chunk);
+                Ok(Vec::new())
+            },
+        }
+    }
+
+    fn contents(&mut self, chunk: &[u8]) -> Result<Vec<u8>, Fault> {
+        let (contents, rest) = match chunk.iter().position(|b| *b == b'<') {
+            Some(end) => chunk.split_at(end),
+            None => (chunk, &[][..]),
+        };
+
+        for c in contents.iter().filter(|c| !c.is_ascii_whitespace()) {
+            // padding can only occur at the very end of base64 encoded data
+            if self.padded {
+                return Err(invalid_base64());
+            }
+            self.pending.push(*c);
+        }
+
+        let complete = self.pending.len() - self.pending.len() % 4;
+        let decoded = STANDARD
+            .decode(&self.pending[..complete])
+            .map_err(|_| invalid_base64())?;
+        self.padded = self.pending[..complete].ends_with(b"=");
+        self.pending.drain(..complete);
+
+        if !rest.is_empty() {
+            self.state = State::Tail;
+            self.tail.extend_from_slice(rest);
+        }
+
+        Ok(decoded)
+    }
+
+    // check the buffered parts of the complete request
+    fn finish(self) -> Result<Finished, Fault> {
+        match self.state {
+            State::Head => match self.upload_method() {
+                Some(method) => Err(Fault::invalid_params(format!(
+                    "Method {method} requires a <base64> parameter."
+                ))),
+                None => Ok(Finished::Buffered(self.head)),
+            },
+            State::Buffered => Ok(Finished::Buffered(self.head)),
+            State::Contents => Err(Fault::parse_error(String::from("Unterminated <base64> value."))),
+            State::Tail => {
+                if !self.pending.is_empty() {
+                    return Err(invalid_base64());
+                }
+
+                let mut skeleton = self.head;
+                skeleton.extend_from_slice(&self.tail);
+
+                Ok(Finished::Upload {
+                    skeleton,
+                    method: self.method,
+                    index: self.index,
+                })
+            },
+        }
+    }
+
+    // look up the name of the called method (from the buffered head of the request) in the map of
+    // upload handlers
+    fn upload_method(&self) -> Option<&'static str> {
+        let start = find(&self.head, b"<methodName>")? + b"<methodName>".len();
+        let end = start + find(&self.head[start..], b"</methodName>")?;
+        let name = std::str::from_utf8(&self.head[start..end]).ok()?.trim();
+
+        self.handlers.get_key_value(name).map(|(name, _)| *name)
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn count(haystack: &[u8], needle: &[u8]) -> usize {
+    haystack
+        .windows(needle.len())
+        .filter(|window| *window == needle)
+        .count()
+}
+
+fn invalid_base64() -> Fault {
+    Fault::parse_error(String::from("Invalid base64 encoded data."))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    use crate::HandlerResponse;
+
+    struct NoopHandler;
+
+    #[async_trait::async_trait]
+    impl UploadHandler for NoopHandler {
+        async fn handle(&self, _params: &[Value], _upload: Upload, _context: &RequestContext<'_>) -> HandlerResponse {
+            HandlerResponse::new(Ok(Value::i4(0)))
+        }
+    }
+
+    fn handlers() -> HashMap<&'static str, Box<dyn UploadHandler>> {
+        let mut handlers: HashMap<&'static str, Box<dyn UploadHandler>> = HashMap::new();
+        handlers.insert("upload", Box::new(NoopHandler));
+        handlers
+    }
+
+    fn decode(request: &[u8], chunk_size: usize) -> Result<(Vec<u8>, Finished), Fault> {
+        let handlers = handlers();
+        let mut stream = UploadStream::new(&handlers);
+        let mut bytes = Vec::new();
+
+        for chunk in request.chunks(chunk_size) {
+            bytes.extend(stream.push(chunk)?);
+        }
+
+        Ok((bytes, stream.finish()?))
+    }
+
+    fn request(name: &str, params: Vec<Value>) -> String {
+        dxr::serialize_xml(&dxr::MethodCall::new(name.to_owned(), params)).unwrap()
+    }
+
+    #[test]
+    fn chunked() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let request = request(
+            "upload",
+            vec![Value::string(String::from("image")), Value::base64(bytes.clone())],
+        );
+
+        for chunk_size in 1..=32 {
+            let (decoded, finished) = decode(request.as_bytes(), chunk_size).unwrap();
+            assert_eq!(decoded, bytes);
+
+            match finished {
+                Finished::Upload {
+                    skeleton,
+                    method,
+                    index,
+                } => {
+                    assert_eq!(method, "upload");
+                    assert_eq!(index, 1);
+
+                    let call = dxr::parse_method_call(std::str::from_utf8(&skeleton).unwrap()).unwrap();
+                    assert_eq!(
+                        call.params(),
+                        vec![Value::string(String::from("image")), Value::base64(Vec::new())]
+                    );
+                },
+                Finished::Buffered(_) => panic!("Upload was not decoded incrementally."),
+            }
+        }
+    }
+
+    #[test]
+    fn line_wrapped() {
+        let request = "<methodCall><methodName>upload</methodName><params><param><value><base64>\nSGVs\nbG8h\n</base64></value></param></params></methodCall>";
+        let (decoded, _) = decode(request.as_bytes(), 3).unwrap();
+        assert_eq!(decoded, b"Hello!");
+    }
+
+    #[test]
+    fn other_method() {
+        let request = request("download", vec![Value::base64(b"Hello!".to_vec())]);
+        let (decoded, finished) = decode(request.as_bytes(), 5).unwrap();
+
+        assert!(decoded.is_empty());
+        assert!(matches!(finished, Finished::Buffered(bytes) if bytes == request.as_bytes()));
+    }
+
+    #[test]
+    fn missing() {
+        let request = request("upload", vec![Value::i4(42)]);
+        let fault = decode(request.as_bytes(), 5).err().unwrap();
+        assert_eq!(fault.code(), -32602);
+    }
+
+    #[test]
+    fn invalid() {
+        let request = "<methodCall><methodName>upload</methodName><params><param><value><base64>SGVsbG8h";
+        assert!(decode(request.as_bytes(), 5).is_err());
+
+        let request = "<methodCall><methodName>upload</methodName><params><param><value><base64>SGV=SGVs</base64></value></param></params></methodCall>";
+        assert!(decode(request.as_bytes(), 5).is_err());
+
+        let request = "<methodCall><methodName>upload</methodName><params><param><value><base64>SGVsbG8</base64></value></param></params></methodCall>";
+        assert!(decode(request.as_bytes(), 5).is_err());
+    }
+}
@@ -3,7 +3,7 @@
 use std::time::Duration;
 
 use dxr::{Fault, TryFromParams, TryToValue, Value};
-use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_client::{Call, ClientBuilder, ClientError, MulticallChunks};
 use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder, Server};
 
 fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
@@ -53,6 +53,33 @@ async fn adder() {
         let r: i32 = client.call(call).await.unwrap();
         assert_eq!((a + b), r);
 
+        // statistics
+        let stats = client.stats();
+        assert_eq!(stats.calls(), 4);
+        assert_eq!(stats.faults(), 0);
+        assert!(stats.bytes_sent() > 0 && stats.bytes_received() > 0);
+
+        // concurrent calls
+        let calls: Vec<Call<_, i32>> = (0..10).map(|i| Call::new("add", (i, i))).collect();
+        let results = client.call_batch(calls, 3).await;
+        let sums: Vec<i32> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(sums, (0..10).map(|i| 2 * i).collect::<Vec<i32>>());
+
+        // failed calls in a batch do not affect other calls
+        let calls = vec![Call::new("add", (1, 2)), Call::new("sub", (1, 2))];
+        let results: Vec<Result<i32, ClientError>> = client.call_batch(calls, 0).await;
+        assert_eq!(results[0].as_ref().unwrap(), &3);
+        assert!(matches!(&results[1], Err(ClientError::Fault { fault }) if fault.code() == -32601));
+
+        // return values deserialized directly with serde
+        let call: Call<_, i32> = Call::new("add", (2i32, 3i32));
+        let r: i64 = client.call_deserialize(call).await.unwrap();
+        assert_eq!(r, 5);
+
+        let call: Call<_, i32> = Call::new("sub", (2i32, 3i32));
+        let error = client.call_deserialize::<_, _, i64>(call).await.unwrap_err();
+        assert!(matches!(error, ClientError::Fault { fault } if fault.code() == -32601));
+
         // multicall
         let call = Call::multicall(vec![
             (String::from("add"), (1, 2)),
@@ -68,10 +95,23 @@ async fn adder() {
                 Ok(Value::i4(3)),
                 Ok(Value::i4(-8)),
                 Ok(Value::i4(0)),
-                Err(Fault::new(404, String::from("Unknown method.")))
+                Err(Fault::method_not_found("sub"))
             ]
         );
 
+        // typed multicall
+        let call = Call::multicall(vec![(String::from("add"), (1, 2)), (String::from("sub"), (1, 2))]).unwrap();
+        let (sum, difference) = client.multicall_zip::<_, (i32, i32)>(call).await.unwrap();
+        assert_eq!(sum.unwrap(), 3);
+        assert!(matches!(difference.unwrap_err(), ClientError::Fault { fault } if fault.code() == -32601));
+
+        // chunked multicall
+        let calls: Vec<(String, (i32, i32))> = (0..10).map(|i| (String::from("add"), (i, i))).collect();
+        let chunks = MulticallChunks::new().max_calls(3).concurrency(2);
+        let values = client.multicall_chunked(calls, chunks).await.unwrap();
+        let expected: Vec<Result<Value, Fault>> = (0..10).map(|i| Ok(Value::i4(2 * i))).collect();
+        assert_eq!(values, expected);
+
         // argument number mismatch
         let (a, b, c) = (2i32, 3i32, 4i32);
         let call: Call<_, i32> = Call::new("add", (a, b, c));
@@ -87,6 +127,10 @@ async fn adder() {
             client.call(call).await.unwrap_err(),
             ClientError::Fault { .. }
         ));
+
+        // fault responses in multicalls are not counted
+        assert_eq!(client.stats().faults(), 4);
+        assert_eq!(client.stats().errors(), 0);
     };
 
     tokio::spawn(calls()).await.unwrap();
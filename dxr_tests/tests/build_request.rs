@@ -0,0 +1,49 @@
+//! This file implements a test for building requests that are sent by a custom executor.
+
+use dxr::{Fault, TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::axum::http::HeaderMap;
+use dxr_server::testing::TestServer;
+use dxr_server::{HandlerFn, HandlerResult, RouteBuilder};
+
+fn hello_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let name = String::try_from_params(params)?;
+    Ok(format!("Hello, {name}!").try_to_value()?)
+}
+
+#[tokio::test]
+async fn build_request() {
+    let route = RouteBuilder::new().add_method("hello", Box::new(hello_handler as HandlerFn));
+    let server = TestServer::start(route).unwrap();
+
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+    let executor = reqwest::Client::new();
+
+    let call: Call<_, String> = Call::new("hello", "DXR");
+    let request = client.build_request(&call).unwrap();
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.headers()["content-type"], "text/xml");
+
+    let contents = executor.execute(request).await.unwrap().text().await.unwrap();
+    let value: String = client.parse_response(&contents).unwrap();
+    assert_eq!(value, "Hello, DXR!");
+
+    let call: Call<_, String> = Call::new("goodbye", "DXR");
+    let request = client.build_request(&call).unwrap();
+
+    let contents = executor.execute(request).await.unwrap().text().await.unwrap();
+    match client.parse_response::<String>(&contents) {
+        Err(ClientError::Fault { fault }) => assert_eq!(fault, Fault::method_not_found("goodbye")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    server.shutdown().await.unwrap();
+}
+
+#[test]
+fn unix_socket() {
+    let client = ClientBuilder::new("unix:///run/rtorrent.sock".parse().unwrap()).build();
+
+    let call: Call<_, String> = Call::new("hello", "DXR");
+    assert!(matches!(client.build_request(&call), Err(ClientError::Io { .. })));
+}
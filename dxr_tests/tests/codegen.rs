@@ -0,0 +1,82 @@
+//! This file implements a test for client code that was generated with `dxr-cli codegen`.
+//!
+//! The generated code is checked in as `codegen/api.rs`, and it is compared with the output of the
+//! code generator by the tests of the `dxr_cli` crate.
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::ClientBuilder;
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+#[path = "codegen/api.rs"]
+mod api;
+use api::Api;
+
+fn add_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+fn new_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (name,): (String,) = TryFromParams::try_from_params(params)?;
+    Ok(Value::string(format!("new {name}")))
+}
+
+fn client_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(Value::boolean(true))
+}
+
+fn type_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (mut bytes,): (Vec<u8>,) = TryFromParams::try_from_params(params)?;
+    bytes.reverse();
+    Ok(Value::base64(bytes))
+}
+
+fn list_methods_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(vec!["add", "new", "client", "type", "system.listMethods", "echo", "e.cho"].try_to_value()?)
+}
+
+fn echo_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(params.try_to_value()?)
+}
+
+#[tokio::test]
+async fn codegen() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("add", Box::new(add_handler as HandlerFn))
+        .add_method("new", Box::new(new_handler as HandlerFn))
+        .add_method("client", Box::new(client_handler as HandlerFn))
+        .add_method("type", Box::new(type_handler as HandlerFn))
+        .add_method("system.listMethods", Box::new(list_methods_handler as HandlerFn))
+        .add_method("echo", Box::new(echo_handler as HandlerFn))
+        .add_method("e.cho", Box::new(echo_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let url = server.url().to_owned();
+
+    let calls = || async move {
+        let api = Api::new(ClientBuilder::new(url.parse().unwrap()).build());
+        assert_eq!(api.client().stats().calls(), 0);
+
+        assert_eq!(api.add(2, 3).await.unwrap(), 5);
+        assert_eq!(api.new_("client").await.unwrap(), "new client");
+        assert!(api.client_().await.unwrap());
+        assert_eq!(api.r#type(&[1, 2, 3]).await.unwrap(), vec![3, 2, 1]);
+
+        let methods: Vec<String> =
+            dxr::TryFromValue::try_from_value(&api.system_list_methods().await.unwrap()).unwrap();
+        assert_eq!(methods.len(), 7);
+
+        let params = vec![Value::i4(1), Value::string(String::from("two"))];
+        let expected = params.try_to_value().unwrap();
+        assert_eq!(api.echo(&params).await.unwrap(), expected);
+        assert_eq!(api.e_cho(&params).await.unwrap(), expected);
+
+        assert_eq!(api.client().stats().calls(), 7);
+    };
+
+    tokio::spawn(calls()).await.unwrap();
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,75 @@
+//! typed XML-RPC client for http://localhost/
+//!
+//! This file was generated by `dxr-cli codegen`.
+
+#![allow(dead_code)]
+
+use dxr::Value;
+use dxr_client::{Call, Client, ClientError};
+
+/// typed XML-RPC client for http://localhost/
+#[derive(Debug)]
+pub struct Api {
+    client: Client,
+}
+
+impl Api {
+    /// constructor for typed clients from an XML-RPC client
+    pub fn new(client: Client) -> Self {
+        Api { client }
+    }
+
+    /// getter method for the underlying XML-RPC client
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// `add`
+    ///
+    /// signature: `int (int, int)`
+    pub async fn add(&self, arg0: i32, arg1: i32) -> Result<i32, ClientError> {
+        self.client.call(Call::new("add", (arg0, arg1))).await
+    }
+
+    /// `new`
+    ///
+    /// signature: `string (string)`
+    pub async fn new_(&self, arg0: &str) -> Result<String, ClientError> {
+        self.client.call(Call::new("new", (arg0,))).await
+    }
+
+    /// `client`
+    ///
+    /// signature: `boolean ()`
+    pub async fn client_(&self) -> Result<bool, ClientError> {
+        self.client.call(Call::new("client", ())).await
+    }
+
+    /// `type`
+    ///
+    /// signature: `base64 (base64)`
+    pub async fn r#type(&self, arg0: &[u8]) -> Result<Vec<u8>, ClientError> {
+        self.client.call(Call::new("type", (arg0,))).await
+    }
+
+    /// `system.listMethods`
+    ///
+    /// signature: `array ()`
+    pub async fn system_list_methods(&self) -> Result<Value, ClientError> {
+        self.client.call(Call::new("system.listMethods", ())).await
+    }
+
+    /// `echo`
+    ///
+    /// The signature of this method is unknown.
+    pub async fn echo(&self, params: &[Value]) -> Result<Value, ClientError> {
+        self.client.call(Call::new("echo", params)).await
+    }
+
+    /// `e.cho`
+    ///
+    /// The signature of this method is unknown.
+    pub async fn e_cho(&self, params: &[Value]) -> Result<Value, ClientError> {
+        self.client.call(Call::new("e.cho", params)).await
+    }
+}
@@ -0,0 +1,47 @@
+//! This file implements a test for the API description endpoint of the server.
+
+use dxr::{TryToValue, Value};
+use dxr_client::ClientBuilder;
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, ParamType, RouteBuilder, Signature};
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = dxr::TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+fn version_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(Value::string(String::from("1.0")))
+}
+
+#[tokio::test]
+async fn describe() {
+    let route = RouteBuilder::new()
+        .set_path("/RPC2")
+        .add_method_with_signature(
+            "add",
+            Box::new(adder_handler as HandlerFn),
+            Signature::new([ParamType::Int, ParamType::Int]),
+        )
+        .add_method("version", Box::new(version_handler as HandlerFn))
+        .method_help("add", "Adds two integers.")
+        .describe_endpoint();
+
+    let description = route.describe();
+    assert_eq!(description.methods().len(), 2);
+    assert_eq!(description.method("add").unwrap().help(), Some("Adds two integers."));
+    assert!(description.method("version").unwrap().signature().is_none());
+
+    let server = TestServer::start(route).unwrap();
+    let base = format!("http://{}", server.addr());
+
+    let response = reqwest::get(format!("{base}/describe")).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // the description is served as an XML-RPC method response
+    let client = ClientBuilder::new(format!("{base}/RPC2").parse().unwrap()).build();
+    let value: Value = client.parse_response(&response.text().await.unwrap()).unwrap();
+    assert_eq!(value, description.try_to_value().unwrap());
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,71 @@
+//! This file implements a test for a server that requires HTTP Digest authentication.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::http::header::{HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use dxr_server::axum::http::{HeaderMap, StatusCode};
+use dxr_server::testing::TestServer;
+use dxr_server::{Handler, HandlerResponse, HandlerResult, RequestContext, RouteBuilder};
+
+// only checks whether a Digest response was sent, and counts the number of challenges
+struct DigestHandler {
+    challenges: Arc<AtomicUsize>,
+}
+
+#[dxr_server::async_trait]
+impl Handler for DigestHandler {
+    async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+        Ok("Hello, authenticated user!".try_to_value()?)
+    }
+
+    async fn handle_http(&self, params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+        let authorization = context
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+
+        match authorization {
+            Some(value) if value.starts_with("Digest username=\"user\"") && value.contains("uri=\"/RPC2\"") => {
+                self.handle(params, context.headers().clone()).await.into()
+            },
+            _ => {
+                self.challenges.fetch_add(1, Ordering::SeqCst);
+                HandlerResponse::error(StatusCode::UNAUTHORIZED, "Unauthorized").header(
+                    WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Digest realm=\"dxr\", nonce=\"abc\", qop=\"auth\""),
+                )
+            },
+        }
+    }
+}
+
+#[tokio::test]
+async fn digest_auth() {
+    let challenges = Arc::new(AtomicUsize::new(0));
+    let handler = DigestHandler {
+        challenges: challenges.clone(),
+    };
+
+    let route = RouteBuilder::new()
+        .set_path("/RPC2")
+        .add_method("hello", Box::new(handler));
+    let server = TestServer::start(route).unwrap();
+
+    let client = ClientBuilder::new(server.url().parse().unwrap())
+        .digest_auth("user", "secret")
+        .build();
+
+    // the first call is challenged and repeated, the second call reuses the challenge
+    for _ in 0..2 {
+        let call: Call<_, String> = Call::new("hello", ());
+        assert_eq!(client.call(call).await.unwrap(), "Hello, authenticated user!");
+    }
+    assert_eq!(challenges.load(Ordering::SeqCst), 1);
+    assert_eq!(client.stats().calls(), 2);
+    assert_eq!(client.stats().retries(), 1);
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,63 @@
+//! This file implements a test that downloads a large base64 value directly into a writer.
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn blob(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+fn blob_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let size: i32 = TryFromParams::try_from_params(params)?;
+    Ok(blob(size as usize).try_to_value()?)
+}
+
+fn number_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(42.try_to_value()?)
+}
+
+#[tokio::test]
+async fn download() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("blob", Box::new(blob_handler as HandlerFn))
+        .add_method("number", Box::new(number_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+
+    // large value that is received in multiple chunks
+    let mut buf = Vec::new();
+    let written = client
+        .call_into_writer(Call::new("blob", 1_000_000), &mut buf)
+        .await
+        .unwrap();
+    assert_eq!(written, 1_000_000);
+    assert_eq!(buf, blob(1_000_000));
+
+    // empty value
+    let mut buf = Vec::new();
+    let written = client.call_into_writer(Call::new("blob", 0), &mut buf).await.unwrap();
+    assert_eq!(written, 0);
+    assert!(buf.is_empty());
+
+    // value with a different type
+    let mut buf = Vec::new();
+    let error = client
+        .call_into_writer(Call::new("number", ()), &mut buf)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::RPC { .. }));
+
+    // fault response
+    let mut buf = Vec::new();
+    let error = client
+        .call_into_writer(Call::new("missing", ()), &mut buf)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Fault { .. }));
+
+    server.shutdown().await.unwrap();
+}
@@ -1,12 +1,11 @@
 //! This file implements a test that launches a simple echo server, which is then used for roundtrip
 //! tests with different types of values, including custom structs.
 
-use std::borrow::Cow;
 use std::collections::HashMap;
 use std::time::Duration;
 
 use dxr::chrono::{NaiveDateTime, SubsecRound, Utc};
-use dxr::{DxrError, TryFromValue, TryToValue, Value};
+use dxr::{TryFromValue, TryToValue, Value};
 use dxr_client::{Call, ClientBuilder, ClientError};
 use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder, Server};
 
@@ -177,12 +176,7 @@ async fn echo() {
             let call: Call<'_, _, (Response,)> = Call::new("echo", (value,));
             assert!(matches!(
                 client.call(call).await.unwrap_err(),
-                ClientError::RPC {
-                    error: DxrError::MissingField {
-                        name: Cow::Borrowed("Response"),
-                        field: Cow::Borrowed("baz")
-                    }
-                }
+                ClientError::RPC { error } if error.as_missing_field() == Some(("Response", "baz"))
             ));
         }
 
@@ -237,21 +231,19 @@ async fn echo() {
         // type mismatch
         let value = -12i32;
         let call: Call<(i32,), (String,)> = Call::new("echo", (value,));
-        assert!(matches!(
-            client.call(call).await.unwrap_err(),
-            ClientError::RPC {
-                error: DxrError::WrongType { .. }
-            }
-        ));
+        let error = client.call(call).await.unwrap_err();
+        assert!(matches!(&error, ClientError::RPC { error } if error.is_wrong_type()));
+        assert_eq!(
+            error.to_string(),
+            "Error in method call 'echo' at result[0]: Type mismatch: got i4, expected string"
+        );
 
         // parameter number mismatch
         let value = vec![2i32, 3i32];
         let call: Call<Vec<i32>, (i32, i32, i32)> = Call::new("echo", value);
         assert!(matches!(
             client.call(call).await.unwrap_err(),
-            ClientError::RPC {
-                error: DxrError::ParameterMismatch { .. }
-            }
+            ClientError::RPC { error } if error.is_parameter_mismatch()
         ));
     };
 
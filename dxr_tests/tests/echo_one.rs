@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use dxr::chrono::{NaiveDateTime, SubsecRound, Utc};
-use dxr::{DxrError, TryFromParams, TryFromValue, TryToValue, Value};
+use dxr::{TryFromParams, TryFromValue, TryToValue, Value};
 use dxr_client::{Call, ClientBuilder, ClientError};
 use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder, Server};
 
@@ -122,9 +122,7 @@ async fn echo_one() {
         let call: Call<i32, String> = Call::new("echo", value);
         assert!(matches!(
             client.call(call).await.unwrap_err(),
-            ClientError::RPC {
-                error: DxrError::WrongType { .. }
-            }
+            ClientError::RPC { error } if error.is_wrong_type()
         ));
 
         // server-side parameter number mismatch
@@ -0,0 +1,47 @@
+//! This file implements a test that sends calls to a different endpoint than the configured one.
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn primary_handler(_params: &[Value], headers: HeaderMap) -> HandlerResult {
+    let token = headers.get("x-token").and_then(|value| value.to_str().ok());
+    Ok(format!("primary: {}", token.unwrap_or_default()).try_to_value()?)
+}
+
+fn secondary_handler(_params: &[Value], headers: HeaderMap) -> HandlerResult {
+    let token = headers.get("x-token").and_then(|value| value.to_str().ok());
+    Ok(format!("secondary: {}", token.unwrap_or_default()).try_to_value()?)
+}
+
+#[tokio::test]
+async fn call_at() {
+    let primary = TestServer::start(
+        RouteBuilder::new()
+            .set_path("/")
+            .add_method("whoami", Box::new(primary_handler as HandlerFn)),
+    )
+    .unwrap();
+    let secondary = TestServer::start(
+        RouteBuilder::new()
+            .set_path("/")
+            .add_method("whoami", Box::new(secondary_handler as HandlerFn)),
+    )
+    .unwrap();
+
+    let client = ClientBuilder::new(primary.url().parse().unwrap())
+        .add_header("x-token", "secret")
+        .build();
+
+    let name: String = client.call(Call::new("whoami", ())).await.unwrap();
+    assert_eq!(name, "primary: secret");
+
+    // configured headers are sent to the other endpoint as well
+    let url = secondary.url().parse().unwrap();
+    let name: String = client.call_at(&url, Call::new("whoami", ())).await.unwrap();
+    assert_eq!(name, "secondary: secret");
+
+    primary.shutdown().await.unwrap();
+    secondary.shutdown().await.unwrap();
+}
@@ -0,0 +1,55 @@
+//! This file implements a test for a gateway that forwards method calls to an upstream server.
+
+use dxr::{fault, Fault, TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, Gateway, HandlerFn, HandlerResult, RouteBuilder};
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+fn hello_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok("Hello from the gateway!".try_to_value()?)
+}
+
+#[tokio::test]
+async fn gateway() {
+    let upstream =
+        TestServer::start(RouteBuilder::new().add_method("add", Box::new(adder_handler as HandlerFn))).unwrap();
+    let upstream_client = ClientBuilder::new(upstream.url().parse().unwrap()).build();
+
+    let route = RouteBuilder::new()
+        .add_method("hello", Box::new(hello_handler as HandlerFn))
+        .fallback(Box::new(Gateway::new(upstream_client)));
+    let gateway = TestServer::start(route).unwrap();
+
+    let client = ClientBuilder::new(gateway.url().parse().unwrap()).build();
+
+    // calls of unknown methods are forwarded to the upstream server
+    let call: Call<_, i32> = Call::new("add", (2, 3));
+    assert_eq!(client.call(call).await.unwrap(), 5);
+
+    // registered methods are handled by the gateway itself
+    let call: Call<_, String> = Call::new("hello", ());
+    assert_eq!(client.call(call).await.unwrap(), "Hello from the gateway!");
+
+    // faults of the upstream server are relayed
+    let call: Call<_, i32> = Call::new("sub", (2, 3));
+    match client.call(call).await {
+        Err(ClientError::Fault { fault }) => assert_eq!(fault, Fault::method_not_found("sub")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // failures to reach the upstream server result in transport errors
+    upstream.shutdown().await.unwrap();
+
+    let call: Call<_, i32> = Call::new("add", (2, 3));
+    match client.call(call).await {
+        Err(ClientError::Fault { fault }) => assert_eq!(fault.code(), fault::TRANSPORT_ERROR),
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    gateway.shutdown().await.unwrap();
+}
@@ -0,0 +1,67 @@
+//! This file implements a test for custom HTTP headers that are only known at runtime.
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError, HyperClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn headers_handler(_params: &[Value], headers: HeaderMap) -> HandlerResult {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_owned()
+    };
+    Ok((header("user-agent"), header("x-host")).try_to_value()?)
+}
+
+#[tokio::test]
+async fn headers() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("headers", Box::new(headers_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let url = server.url().parse().unwrap();
+
+    let host = String::from("localhost");
+    let user_agent = format!("dxr-tests/{} ({host})", env!("CARGO_PKG_VERSION"));
+    let expected = (user_agent.clone(), host.clone());
+
+    let client = ClientBuilder::new(server.url().parse().unwrap())
+        .user_agent(user_agent.clone())
+        .add_header("x-host", host.as_str())
+        .try_build()
+        .unwrap();
+    let headers: (String, String) = client.call(Call::new("headers", ())).await.unwrap();
+    assert_eq!(headers, expected);
+
+    let client = HyperClientBuilder::new(url)
+        .user_agent(user_agent)
+        .add_header("x-host", host)
+        .try_build()
+        .unwrap();
+    let headers: (String, String) = client.call(Call::new("headers", ())).await.unwrap();
+    assert_eq!(headers, expected);
+
+    server.shutdown().await.unwrap();
+}
+
+#[test]
+fn invalid_headers() {
+    let url: dxr_client::Url = "http://localhost/".parse().unwrap();
+
+    let result = ClientBuilder::new(url.clone())
+        .user_agent("invalid\nuser agent")
+        .try_build();
+    assert!(matches!(result, Err(ClientError::Header { .. })));
+
+    let result = ClientBuilder::new(url.clone())
+        .add_header("x header", "value")
+        .try_build();
+    assert!(matches!(result, Err(ClientError::Header { .. })));
+
+    let result = HyperClientBuilder::new(url).add_header("x-header", "\0").try_build();
+    assert!(matches!(result, Err(ClientError::Header { .. })));
+}
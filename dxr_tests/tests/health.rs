@@ -0,0 +1,45 @@
+//! This file implements a test for the health and readiness check endpoints of the server.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use dxr_server::testing::TestServer;
+use dxr_server::RouteBuilder;
+
+#[tokio::test]
+async fn health_checks() {
+    let ready = Arc::new(AtomicBool::new(false));
+    let readiness = ready.clone();
+
+    let route = RouteBuilder::new()
+        .set_path("/RPC2")
+        .health_check(|| true)
+        .readiness_check(move || readiness.load(Ordering::SeqCst));
+
+    let server = TestServer::start(route).unwrap();
+    let base = format!("http://{}", server.addr());
+
+    let response = reqwest::get(format!("{base}/health")).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "OK");
+
+    let response = reqwest::get(format!("{base}/ready")).await.unwrap();
+    assert_eq!(response.status(), 503);
+
+    ready.store(true, Ordering::SeqCst);
+    let response = reqwest::get(format!("{base}/ready")).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    server.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn disabled() {
+    let server = TestServer::start(RouteBuilder::new().set_path("/RPC2")).unwrap();
+    let base = format!("http://{}", server.addr());
+
+    let response = reqwest::get(format!("{base}/health")).await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    server.shutdown().await.unwrap();
+}
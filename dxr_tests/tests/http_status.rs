@@ -0,0 +1,72 @@
+//! This file implements a test for responses with HTTP error status codes.
+
+use std::net::{Ipv4Addr, TcpListener};
+
+use dxr_client::{BlockingClientBuilder, Call, ClientBuilder, ClientError, HyperClientBuilder};
+use dxr_server::axum::http::StatusCode;
+use dxr_server::axum::response::Html;
+use dxr_server::axum::{routing::post, Router};
+use dxr_server::Server;
+
+const FAULT: &str = "<?xml version=\"1.0\"?>\n<methodResponse><fault><value><struct>\
+    <member><name>faultCode</name><value><i4>42</i4></value></member>\
+    <member><name>faultString</name><value><string>Failure</string></value></member>\
+    </struct></value></fault></methodResponse>";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_status() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    let route = Router::new()
+        .route(
+            "/missing",
+            post(|| async { (StatusCode::NOT_FOUND, Html("<html><body>Not Found</body></html>")) }),
+        )
+        .route("/fault", post(|| async { (StatusCode::INTERNAL_SERVER_ERROR, FAULT) }));
+
+    let mut server = Server::from_route(route);
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_listener(listener));
+
+    let missing = format!("{base}/missing");
+    let fault = format!("{base}/fault");
+
+    // error pages are not parsed as XML-RPC responses
+    let client = ClientBuilder::new(missing.parse().unwrap()).build();
+    let error = client.call::<_, i32>(Call::new("add", (1, 2))).await.unwrap_err();
+    assert!(matches!(
+        &error,
+        ClientError::HttpStatus { status: 404, body_snippet } if body_snippet.contains("Not Found")
+    ));
+    assert!(!error.is_retryable());
+
+    let client = HyperClientBuilder::new(missing.parse().unwrap()).build();
+    let error = client.call::<_, i32>(Call::new("add", (1, 2))).await.unwrap_err();
+    assert!(matches!(error, ClientError::HttpStatus { status: 404, .. }));
+
+    // faults that are sent with error status codes are still parsed
+    let client = ClientBuilder::new(fault.parse().unwrap()).build();
+    let error = client.call::<_, i32>(Call::new("add", (1, 2))).await.unwrap_err();
+    assert!(matches!(error, ClientError::Fault { fault } if fault.code() == 42));
+
+    let client = HyperClientBuilder::new(fault.parse().unwrap()).build();
+    let error = client.call::<_, i32>(Call::new("add", (1, 2))).await.unwrap_err();
+    assert!(matches!(error, ClientError::Fault { fault } if fault.code() == 42));
+
+    // the blocking client must not be used from within the async runtime
+    tokio::task::spawn_blocking(move || {
+        let client = BlockingClientBuilder::new(missing.parse().unwrap()).build();
+        let error = client.call::<_, i32>(Call::new("add", (1, 2))).unwrap_err();
+        assert!(matches!(error, ClientError::HttpStatus { status: 404, .. }));
+
+        let client = BlockingClientBuilder::new(fault.parse().unwrap()).build();
+        let error = client.call::<_, i32>(Call::new("add", (1, 2))).unwrap_err();
+        assert!(matches!(error, ClientError::Fault { fault } if fault.code() == 42));
+    })
+    .await
+    .unwrap();
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
@@ -0,0 +1,53 @@
+//! This file implements a test that launches a server with custom limits for parsing requests.
+
+use std::time::Duration;
+
+use dxr::{DeserializeOptions, TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder, Server};
+
+fn count_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (values,): (Vec<Vec<i32>>,) = TryFromParams::try_from_params(params)?;
+    Ok((values.iter().map(Vec::len).sum::<usize>() as i32).try_to_value()?)
+}
+
+#[tokio::test]
+async fn limits() {
+    // <methodCall><params><param><value><array><data><value><array><data><value><i4>
+    let options = DeserializeOptions::default().max_depth(11);
+
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("count", Box::new(count_handler as HandlerFn))
+        .deserialize_options(options)
+        .build();
+
+    let mut server = Server::from_route(route);
+    let trigger = server.shutdown_trigger();
+
+    let serve = tokio::spawn(server.serve("0.0.0.0:3000".parse().unwrap()));
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let calls = || async {
+        let client = ClientBuilder::new("http://0.0.0.0:3000/".parse().unwrap())
+            .user_agent("limits-client")
+            .build();
+
+        // nesting depth within limits
+        let call = Call::new("count", (vec![vec![1, 2], vec![3]],));
+        let r: i32 = client.call(call).await.unwrap();
+        assert_eq!(r, 3);
+
+        // nesting depth exceeds limits
+        let call: Call<_, i32> = Call::new("count", (vec![vec![vec![1, 2]]],));
+        assert!(matches!(
+            client.call(call).await.unwrap_err(),
+            ClientError::Fault { .. }
+        ));
+    };
+
+    tokio::spawn(calls()).await.unwrap();
+
+    trigger.notify_one();
+    serve.await.unwrap().unwrap();
+}
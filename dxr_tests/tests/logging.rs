@@ -0,0 +1,72 @@
+//! This file implements a test for logging requests and responses on the server.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::http::HeaderMap;
+use dxr_server::testing::TestServer;
+use dxr_server::{HandlerFn, HandlerResult, RequestLogger, RouteBuilder};
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        MESSAGES.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+fn login_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (credentials,): (Value,) = TryFromParams::try_from_params(params)?;
+    Ok(credentials.try_to_value()?)
+}
+
+#[tokio::test]
+async fn logging() {
+    log::set_logger(&CaptureLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let route = RouteBuilder::new()
+        .add_method("login", Box::new(login_handler as HandlerFn))
+        .logger(RequestLogger::new().redact("password"));
+
+    let server = TestServer::start(route).unwrap();
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+
+    let mut credentials = HashMap::new();
+    credentials.insert("user", "alice");
+    credentials.insert("password", "hunter2");
+
+    let call: Call<_, Value> = Call::new("login", (credentials,));
+    client.call(call).await.unwrap();
+
+    server.shutdown().await.unwrap();
+
+    let messages = MESSAGES.lock().unwrap();
+
+    // the caller is the client socket (with an ephemeral port)
+    let summary = messages
+        .iter()
+        .find(|message| message.contains(" called login: 200 in "))
+        .unwrap();
+    assert!(summary.starts_with("127.0.0.1:"));
+
+    let requests: Vec<&String> = messages
+        .iter()
+        .filter(|message| message.starts_with("Request from 127.0.0.1:"))
+        .collect();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains("<name>password</name><value><string>***</string></value>"));
+    assert!(requests[0].contains("alice"));
+
+    assert!(messages.iter().all(|message| !message.contains("hunter2")));
+}
@@ -0,0 +1,67 @@
+//! This file implements a test for servers that listen at multiple socket addresses.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::http::HeaderMap;
+use dxr_server::{HandlerFn, HandlerResult, RouteBuilder, Server};
+
+fn hello_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok("Hello!".try_to_value()?)
+}
+
+#[tokio::test]
+async fn multi_bind() {
+    let route = RouteBuilder::new()
+        .add_method("hello", Box::new(hello_handler as HandlerFn))
+        .build();
+
+    let mut server = Server::from_route(route);
+    let first = server.bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let second = server.bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+
+    // IPv6 might not be available in all test environments
+    let mut addrs = vec![first, second];
+    if let Ok(addr) = server.bind(SocketAddr::from((Ipv6Addr::LOCALHOST, 0))) {
+        addrs.push(addr);
+    }
+
+    // actually bound addresses are returned when binding port 0
+    assert!(addrs.iter().all(|addr| addr.port() != 0));
+    assert_ne!(first, second);
+    assert_eq!(server.local_addrs(), addrs);
+
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_bound());
+
+    // requests are handled by the same route at all addresses
+    for addr in addrs {
+        let client = ClientBuilder::new(format!("http://{addr}/").parse().unwrap()).build();
+        let hello: String = client.call(Call::new("hello", ())).await.unwrap();
+        assert_eq!(hello, "Hello!");
+    }
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn no_addresses() {
+    let server = Server::from_route(RouteBuilder::new().build());
+    assert!(server.serve_bound().await.is_err());
+}
+
+#[tokio::test]
+async fn dual_stack() {
+    let mut server = Server::from_route(RouteBuilder::new().build());
+
+    // IPv6 might not be available in all test environments
+    if let Ok(addr) = server.bind(SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))) {
+        // the same port can be bound for IPv4 since IPv6 sockets do not accept IPv4 connections
+        let other = server
+            .bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, addr.port())))
+            .unwrap();
+        assert_eq!(addr.port(), other.port());
+    }
+}
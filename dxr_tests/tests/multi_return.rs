@@ -0,0 +1,42 @@
+//! This file implements a test for calling methods of a non-conformant server that returns more
+//! than one parameter in its responses.
+
+use std::net::{Ipv4Addr, TcpListener};
+
+use dxr::{DeserializeOptions, MultipleParams, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::{routing::post, Router};
+use dxr_server::Server;
+
+const RESPONSE: &str = "<?xml version=\"1.0\"?>\n<methodResponse><params>\
+<param><value><i4>1</i4></value></param>\
+<param><value><string>two</string></value></param>\
+</params></methodResponse>";
+
+#[tokio::test]
+async fn multi_return() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let mut server = Server::from_route(Router::new().route("/", post(|| async { RESPONSE })));
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_listener(listener));
+
+    // multiple parameters are rejected by default
+    let client = ClientBuilder::new(url.parse().unwrap()).build();
+    assert!(client.call::<_, i32>(Call::new("pair", ())).await.is_err());
+
+    // all parameters are returned independent of the policy
+    let values = client.call_multi_return(Call::new("pair", ())).await.unwrap();
+    assert_eq!(values, vec![Value::i4(1), Value::string(String::from("two"))]);
+
+    // the first parameter is returned with the "first" policy
+    let client = ClientBuilder::new(url.parse().unwrap())
+        .deserialize_options(DeserializeOptions::default().multiple_params(MultipleParams::First))
+        .build();
+    let value: i32 = client.call(Call::new("pair", ())).await.unwrap();
+    assert_eq!(value, 1);
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
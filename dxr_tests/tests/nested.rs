@@ -0,0 +1,38 @@
+//! This file implements a test for nesting the XML-RPC endpoint inside a larger axum application.
+
+use std::net::{Ipv4Addr, TcpListener};
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::{http::HeaderMap, routing::get, Router};
+use dxr_server::{HandlerFn, HandlerResult, RouteBuilder, Server};
+
+fn hello_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let name = String::try_from_params(params)?;
+    Ok(format!("Hello, {name}!").try_to_value()?)
+}
+
+#[tokio::test]
+async fn nested() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    let route = RouteBuilder::new().add_method("hello", Box::new(hello_handler as HandlerFn));
+    let app = Router::new()
+        .route("/", get(|| async { "Hello, World!" }))
+        .nest("/RPC2", Router::from(route));
+
+    let mut server = Server::from_route(app);
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_listener(listener));
+
+    let response = reqwest::get(format!("{base}/")).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "Hello, World!");
+
+    let client = ClientBuilder::new(format!("{base}/RPC2").parse().unwrap()).build();
+    let call: Call<_, String> = Call::new("hello", "DXR");
+    assert_eq!(client.call(call).await.unwrap(), "Hello, DXR!");
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
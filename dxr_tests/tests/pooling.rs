@@ -0,0 +1,47 @@
+//! This file implements a test that calls methods of a simple server with custom settings for the
+//! connection pool of the client.
+
+use std::time::Duration;
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+#[tokio::test]
+async fn pooling() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("add", Box::new(adder_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+
+    let pooled = ClientBuilder::new(server.url().parse().unwrap())
+        .pool_idle_timeout(Some(Duration::from_secs(10)))
+        .pool_max_idle_per_host(1)
+        .tcp_keepalive(Some(Duration::from_secs(30)))
+        .build();
+
+    // connections are reused across calls
+    for i in 0..3 {
+        let r: i32 = pooled.call(Call::new("add", (i, 1))).await.unwrap();
+        assert_eq!(r, i + 1);
+    }
+
+    let unpooled = ClientBuilder::new(server.url().parse().unwrap())
+        .pool_max_idle_per_host(0)
+        .build();
+
+    // a new connection is established for every call
+    for i in 0..3 {
+        let r: i32 = unpooled.call(Call::new("add", (i, 2))).await.unwrap();
+        assert_eq!(r, i + 2);
+    }
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,53 @@
+//! This file implements a test that calls methods of a simple server with the low-level raw API.
+
+use dxr::{Fault, TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+#[tokio::test]
+async fn raw_calls() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("add", Box::new(adder_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+
+    // typed method call with untyped return value
+    let call: Call<_, i32> = Call::new("add", (2, 3));
+    let value = client.call_value(call).await.unwrap();
+    assert_eq!(value, Value::i4(5));
+
+    // untyped method call
+    let value = client.call_raw("add", vec![Value::i4(2), Value::i4(3)]).await.unwrap();
+    assert_eq!(value, Value::i4(5));
+
+    // untyped method call that results in a fault
+    let error = client
+        .call_raw("sub", vec![Value::i4(2), Value::i4(3)])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::Fault { fault } if fault == Fault::method_not_found("sub")
+    ));
+
+    // raw XML documents
+    let body = "<?xml version=\"1.0\"?>\n<methodCall><methodName>add</methodName><params><param><value><i4>1</i4></value></param><param><value><i4>2</i4></value></param></params></methodCall>";
+    let response = client.send_xml(body).await.unwrap();
+    assert!(response.contains("<i4>3</i4>"));
+
+    // fault responses are returned as-is
+    let body = "<?xml version=\"1.0\"?>\n<methodCall><methodName>sub</methodName></methodCall>";
+    let response = client.send_xml(body).await.unwrap();
+    assert!(response.contains("<fault>"));
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,43 @@
+//! This file implements a test for the redirect policy of the client.
+
+use std::net::{Ipv4Addr, TcpListener};
+
+use dxr::{TryToValue, Value};
+use dxr_client::{redirect, Call, ClientBuilder, ClientError};
+use dxr_server::axum::{http::HeaderMap, response::Redirect, routing::post};
+use dxr_server::{HandlerFn, HandlerResult, RouteBuilder, Server};
+
+fn hello_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok("Hello!".try_to_value()?)
+}
+
+#[tokio::test]
+async fn redirects() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let url = format!("http://{}/old", listener.local_addr().unwrap());
+
+    let route = RouteBuilder::new()
+        .set_path("/RPC2")
+        .add_method("hello", Box::new(hello_handler as HandlerFn))
+        .build()
+        .route("/old", post(|| async { Redirect::temporary("/RPC2") }));
+
+    let mut server = Server::from_route(route);
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_listener(listener));
+
+    // 307 redirects are followed by default
+    let client = ClientBuilder::new(url.parse().unwrap()).build();
+    let hello: String = client.call(Call::new("hello", ())).await.unwrap();
+    assert_eq!(hello, "Hello!");
+
+    // redirects are not followed if they are disabled
+    let client = ClientBuilder::new(url.parse().unwrap())
+        .redirect(redirect::Policy::none())
+        .build();
+    let error = client.call::<_, String>(Call::new("hello", ())).await.unwrap_err();
+    assert!(matches!(error, ClientError::HttpStatus { status: 307, .. }));
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
@@ -0,0 +1,75 @@
+//! This file implements a test for correlation IDs of requests and responses.
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::http::HeaderMap;
+use dxr_server::testing::TestServer;
+use dxr_server::{Handler, HandlerResponse, HandlerResult, RequestContext, RouteBuilder, REQUEST_ID_HEADER};
+
+// returns the request ID of the request (or an empty string, if there is none)
+struct RequestIdHandler;
+
+#[dxr_server::async_trait]
+impl Handler for RequestIdHandler {
+    async fn handle(&self, _params: &[Value], _headers: HeaderMap) -> HandlerResult {
+        Ok("".try_to_value()?)
+    }
+
+    async fn handle_http(&self, _params: &[Value], context: &RequestContext<'_>) -> HandlerResponse {
+        HandlerResponse::new(Ok(Value::string(context.request_id().unwrap_or_default().to_owned())))
+    }
+}
+
+const CALL: &str = "<?xml version=\"1.0\"?><methodCall><methodName>id</methodName><params></params></methodCall>";
+
+#[tokio::test]
+async fn request_ids() {
+    let route = RouteBuilder::new()
+        .add_method("id", Box::new(RequestIdHandler))
+        .request_ids();
+    let server = TestServer::start(route).unwrap();
+
+    // request IDs of clients are passed on to method handlers
+    let client = ClientBuilder::new(server.url().parse().unwrap())
+        .request_id_generator(|| String::from("client-id"))
+        .build();
+    let id: String = client.call(Call::new("id", ())).await.unwrap();
+    assert_eq!(id, "client-id");
+
+    // generated request IDs are different for every call
+    let client = ClientBuilder::new(server.url().parse().unwrap()).request_ids().build();
+    let first: String = client.call(Call::new("id", ())).await.unwrap();
+    let second: String = client.call(Call::new("id", ())).await.unwrap();
+    assert!(!first.is_empty());
+    assert_ne!(first, second);
+
+    // request IDs are echoed in responses
+    let http = reqwest::Client::new();
+    let response = http
+        .post(server.url())
+        .header(REQUEST_ID_HEADER, "abc-123")
+        .body(CALL)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], "abc-123");
+
+    // servers generate request IDs for requests without one
+    let response = http.post(server.url()).body(CALL).send().await.unwrap();
+    let generated = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_owned();
+    assert!(response.text().await.unwrap().contains(&generated));
+
+    server.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn disabled() {
+    let route = RouteBuilder::new().add_method("id", Box::new(RequestIdHandler));
+    let server = TestServer::start(route).unwrap();
+
+    let client = ClientBuilder::new(server.url().parse().unwrap()).request_ids().build();
+    let id: String = client.call(Call::new("id", ())).await.unwrap();
+    assert_eq!(id, "");
+
+    server.shutdown().await.unwrap();
+}
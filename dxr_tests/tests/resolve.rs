@@ -0,0 +1,35 @@
+//! This file implements a test that pins the host name of the XML-RPC server to a fixed address.
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn host_handler(_params: &[Value], headers: HeaderMap) -> HandlerResult {
+    let host = headers
+        .get("host")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    Ok(host.try_to_value()?)
+}
+
+#[tokio::test]
+async fn resolve() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("host", Box::new(host_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let addr = server.addr();
+
+    let url = format!("http://xml-rpc.invalid:{}/", addr.port());
+    let client = ClientBuilder::new(url.parse().unwrap())
+        .resolve("xml-rpc.invalid", addr)
+        .build();
+
+    // the request is sent to the pinned address, but with the original host name
+    let host: String = client.call(Call::new("host", ())).await.unwrap();
+    assert_eq!(host, format!("xml-rpc.invalid:{}", addr.port()));
+
+    server.shutdown().await.unwrap();
+}
@@ -0,0 +1,51 @@
+//! This file implements a test that checks that responses for read-mostly methods are cached.
+
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ResponseCache};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+static COUNTER: AtomicI32 = AtomicI32::new(0);
+
+fn counter_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok((COUNTER.fetch_add(1, Ordering::SeqCst) + 1).try_to_value()?)
+}
+
+#[tokio::test]
+async fn response_cache() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("cached", Box::new(counter_handler as HandlerFn))
+        .add_method("uncached", Box::new(counter_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+
+    let cache = ResponseCache::new(Duration::from_secs(60), 16).method("cached");
+    let client = ClientBuilder::new(server.url().parse().unwrap())
+        .response_cache(cache)
+        .build();
+
+    // the second call is answered from the cache
+    let first: i32 = client.call(Call::new("cached", ())).await.unwrap();
+    let second: i32 = client.call(Call::new("cached", ())).await.unwrap();
+    assert_eq!(first, second);
+
+    // calls with different parameters are cached separately
+    let other: i32 = client.call(Call::new("cached", (1,))).await.unwrap();
+    assert_ne!(first, other);
+
+    // methods that were not added to the cache are always sent to the server
+    let a: i32 = client.call(Call::new("uncached", ())).await.unwrap();
+    let b: i32 = client.call(Call::new("uncached", ())).await.unwrap();
+    assert_ne!(a, b);
+
+    // clearing the cache forces a new call
+    client.clear_cache();
+    let third: i32 = client.call(Call::new("cached", ())).await.unwrap();
+    assert_ne!(first, third);
+
+    server.shutdown().await.unwrap();
+}
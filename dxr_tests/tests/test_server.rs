@@ -0,0 +1,46 @@
+//! This file implements a test that launches several servers with the `TestServer` harness, which
+//! assigns ports dynamically, so the servers can run in parallel.
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn hello_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (name,): (String,) = TryFromParams::try_from_params(params)?;
+    Ok(format!("Hello, {name}!").try_to_value()?)
+}
+
+fn goodbye_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (name,): (String,) = TryFromParams::try_from_params(params)?;
+    Ok(format!("Goodbye, {name}!").try_to_value()?)
+}
+
+#[tokio::test]
+async fn test_server() {
+    let hello = TestServer::start(
+        RouteBuilder::new()
+            .set_path("/")
+            .add_method("greet", Box::new(hello_handler as HandlerFn)),
+    )
+    .unwrap();
+    let goodbye = TestServer::start(
+        RouteBuilder::new()
+            .set_path("/RPC2")
+            .add_method("greet", Box::new(goodbye_handler as HandlerFn)),
+    )
+    .unwrap();
+
+    assert_ne!(hello.addr(), goodbye.addr());
+
+    let client = ClientBuilder::new(hello.url().parse().unwrap()).build();
+    let r: String = client.call(Call::new("greet", "Alice")).await.unwrap();
+    assert_eq!(r, "Hello, Alice!");
+
+    let client = ClientBuilder::new(goodbye.url().parse().unwrap()).build();
+    let r: String = client.call(Call::new("greet", "Bob")).await.unwrap();
+    assert_eq!(r, "Goodbye, Bob!");
+
+    hello.shutdown().await.unwrap();
+    goodbye.shutdown().await.unwrap();
+}
@@ -0,0 +1,51 @@
+//! This file implements a test for calling methods that do not return anything useful.
+
+use std::net::{Ipv4Addr, TcpListener};
+
+use dxr::{TryToValue, Value};
+use dxr_client::{Call, ClientBuilder};
+use dxr_server::axum::{http::HeaderMap, routing::post, Router};
+use dxr_server::testing::TestServer;
+use dxr_server::{HandlerFn, HandlerResult, RouteBuilder, Server};
+
+fn set_handler(_params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    Ok(().try_to_value()?)
+}
+
+#[tokio::test]
+async fn unit_handler() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("set", Box::new(set_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+
+    let call: Call<_, ()> = Call::new("set", ("throttle", 100));
+    client.call(call).await.unwrap();
+
+    server.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn empty_response() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let response = "<?xml version=\"1.0\"?>\n<methodResponse><params></params></methodResponse>";
+    let mut server = Server::from_route(Router::new().route("/", post(move || async move { response })));
+    let trigger = server.shutdown_trigger();
+    let handle = tokio::spawn(server.serve_listener(listener));
+
+    let client = ClientBuilder::new(url.parse().unwrap()).build();
+
+    let call: Call<_, ()> = Call::new("set", ("throttle", 100));
+    client.call(call).await.unwrap();
+
+    // empty responses cannot be converted into other types
+    let call: Call<_, i32> = Call::new("get", ("throttle",));
+    assert!(client.call(call).await.is_err());
+
+    trigger.notify_one();
+    handle.await.unwrap().unwrap();
+}
@@ -0,0 +1,67 @@
+//! This file implements a test that uploads a large base64 value, which is decoded into a
+//! temporary file by the server.
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, ClientBuilder, ClientError};
+use dxr_server::testing::TestServer;
+use dxr_server::{async_trait, HandlerResponse, RequestContext, RouteBuilder, Upload, UploadHandler};
+use tokio::io::AsyncReadExt;
+
+fn blob(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+struct ImageHandler;
+
+#[async_trait]
+impl UploadHandler for ImageHandler {
+    async fn handle(&self, params: &[Value], upload: Upload, _context: &RequestContext<'_>) -> HandlerResponse {
+        let (name, image): (String, Vec<u8>) = match TryFromParams::try_from_params(params) {
+            Ok(params) => params,
+            Err(error) => return HandlerResponse::new(Err(error.into())),
+        };
+
+        let mut contents = Vec::new();
+        upload.open().unwrap().read_to_end(&mut contents).await.unwrap();
+
+        let valid = image.is_empty() && contents == blob(upload.len() as usize);
+        HandlerResponse::new(
+            (name, upload.index() as i32, upload.len() as i32, valid)
+                .try_to_value()
+                .map_err(Into::into),
+        )
+    }
+}
+
+#[tokio::test]
+async fn upload() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_upload_method("image", Box::new(ImageHandler))
+        .max_upload_size(2_000_000);
+
+    let server = TestServer::start(route).unwrap();
+    let client = ClientBuilder::new(server.url().parse().unwrap()).build();
+
+    // large value that is received in multiple chunks
+    let call = Call::new("image", (String::from("firmware"), blob(1_000_000)));
+    let result: (String, i32, i32, bool) = client.call(call).await.unwrap();
+    assert_eq!(result, (String::from("firmware"), 1, 1_000_000, true));
+
+    // empty value
+    let call = Call::new("image", (String::from("empty"), Vec::<u8>::new()));
+    let result: (String, i32, i32, bool) = client.call(call).await.unwrap();
+    assert_eq!(result, (String::from("empty"), 1, 0, true));
+
+    // missing value
+    let call: Call<_, (String, i32, i32, bool)> = Call::new("image", (String::from("missing"),));
+    let error = client.call(call).await.unwrap_err();
+    assert!(matches!(error, ClientError::Fault { .. }));
+
+    // value that exceeds the size limit
+    let call: Call<_, (String, i32, i32, bool)> = Call::new("image", (String::from("huge"), blob(3_000_000)));
+    let error = client.call(call).await.unwrap_err();
+    assert!(matches!(error, ClientError::HttpStatus { status: 413, .. }));
+
+    server.shutdown().await.unwrap();
+}
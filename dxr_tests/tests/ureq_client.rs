@@ -0,0 +1,45 @@
+//! This file implements a test that calls methods of a simple server with the blocking client.
+
+use dxr::{Fault, TryFromParams, TryToValue, Value};
+use dxr_client::{BlockingClientBuilder, Call, ClientError};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ureq_client() {
+    let route = RouteBuilder::new()
+        .set_path("/RPC2")
+        .add_method("add", Box::new(adder_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+    let url = server.url().to_owned();
+
+    // the blocking client must not be used from within the async runtime
+    let calls = move || {
+        let client = BlockingClientBuilder::new(url.parse().unwrap())
+            .user_agent("ureq-client")
+            .build();
+
+        // successful method call
+        let r: i32 = client.call(Call::new("add", (2, 3))).unwrap();
+        assert_eq!(r, 5);
+
+        // multicall
+        let call = Call::multicall(vec![(String::from("add"), (1, 2)), (String::from("sub"), (1, 2))]).unwrap();
+        let values = client.multicall(call).unwrap();
+        assert_eq!(values, vec![Ok(Value::i4(3)), Err(Fault::method_not_found("sub"))]);
+
+        // argument number mismatch
+        let call: Call<_, i32> = Call::new("add", (1, 2, 3));
+        assert!(matches!(client.call(call).unwrap_err(), ClientError::Fault { .. }));
+    };
+
+    tokio::task::spawn_blocking(calls).await.unwrap();
+
+    server.shutdown().await.unwrap();
+}
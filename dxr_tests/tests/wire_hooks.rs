@@ -0,0 +1,60 @@
+//! This file implements a test that inspects the raw XML of requests and responses (and the
+//! timings of method calls) with hooks.
+
+use std::sync::{Arc, Mutex};
+
+use dxr::{TryFromParams, TryToValue, Value};
+use dxr_client::{Call, CallTimings, ClientBuilder};
+use dxr_server::testing::TestServer;
+use dxr_server::{axum::http::HeaderMap, HandlerFn, HandlerResult, RouteBuilder};
+
+static REQUESTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static RESPONSES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn adder_handler(params: &[Value], _headers: HeaderMap) -> HandlerResult {
+    let (a, b): (i32, i32) = TryFromParams::try_from_params(params)?;
+    Ok((a + b).try_to_value()?)
+}
+
+#[tokio::test]
+async fn wire_hooks() {
+    let route = RouteBuilder::new()
+        .set_path("/")
+        .add_method("add", Box::new(adder_handler as HandlerFn));
+
+    let server = TestServer::start(route).unwrap();
+
+    let timings: Arc<Mutex<Vec<(String, CallTimings)>>> = Arc::default();
+    let collected = Arc::clone(&timings);
+
+    let client = ClientBuilder::new(server.url().parse().unwrap())
+        .on_request(|body| REQUESTS.lock().unwrap().push(body.to_owned()))
+        .on_response(|body| RESPONSES.lock().unwrap().push(body.to_owned()))
+        .on_timings(Arc::new(move |method, timings| {
+            collected.lock().unwrap().push((method.to_owned(), *timings));
+        }))
+        .build();
+
+    let r: i32 = client.call(Call::new("add", (2, 3))).await.unwrap();
+    assert_eq!(r, 5);
+
+    server.shutdown().await.unwrap();
+
+    let requests = REQUESTS.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains("<methodName>add</methodName>"));
+
+    let responses = RESPONSES.lock().unwrap();
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].contains("<i4>5</i4>"));
+
+    // connections are handled by reqwest, so only the first byte of HTTP responses is measured
+    let timings = timings.lock().unwrap();
+    assert_eq!(timings.len(), 1);
+    let (method, timings) = &timings[0];
+    assert_eq!(method, "add");
+    assert_eq!(timings.connect(), None);
+    assert_eq!(timings.send(), None);
+    let first_byte = timings.first_byte().unwrap();
+    assert!(timings.serialize() <= first_byte && first_byte <= timings.total());
+}
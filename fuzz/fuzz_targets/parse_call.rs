@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // method calls that were parsed successfully must survive a round trip
+    if let Ok(call) = dxr::fuzz::parse_call(data) {
+        let xml = dxr::serialize_xml(&call).expect("failed to serialize parsed method call");
+        let again = dxr::fuzz::parse_call(xml.as_bytes()).expect("failed to parse serialized method call");
+        assert_eq!(call, again);
+    }
+});
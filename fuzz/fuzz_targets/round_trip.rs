@@ -0,0 +1,18 @@
+#![no_main]
+
+use dxr::{SerializeOptions, Value, WriteXml};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|value: Value| {
+    let xml = dxr::serialize_xml(&value).expect("failed to serialize value");
+
+    // the fast writer must produce exactly the same output as the serde-based serializer
+    let mut written = String::new();
+    value
+        .write_xml(&mut written, SerializeOptions::default())
+        .expect("failed to write value");
+    assert_eq!(xml, written);
+
+    let parsed = dxr::fuzz::parse_value(xml.as_bytes()).expect("failed to parse serialized value");
+    assert_eq!(value, parsed);
+});